@@ -13,6 +13,8 @@
 // limitations under the License.
 //
 
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs::File;
 use std::io::{self, Read};
 use std::result;
@@ -30,7 +32,14 @@ pub type Result<T> = result::Result<T, Error>;
 #[derive(Debug)]
 pub enum Error {
     IOError(io::Error),
-    TOMLParserError(Vec<toml::ParserError>),
+    TOMLParserError(Vec<SyntaxError>),
+}
+
+#[derive(Debug)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub description: String,
 }
 
 impl Configuration {
@@ -43,7 +52,18 @@ impl Configuration {
         let value = match parser.parse() {
             Some(value) => toml::Value::Table(value),
             None => {
-                return Err(Error::TOMLParserError(parser.errors));
+                return Err(Error::TOMLParserError(parser.errors
+                                                        .iter()
+                                                        .map(|error| {
+                                                            let (line, column) =
+                                                                parser.to_linecol(error.lo);
+                                                            SyntaxError {
+                                                                line: line + 1,
+                                                                column: column + 1,
+                                                                description: error.desc.clone(),
+                                                            }
+                                                        })
+                                                        .collect()));
             }
         };
 
@@ -58,6 +78,16 @@ impl Configuration {
         self.root.lookup(path).and_then(|value| value.as_integer())
     }
 
+    pub fn f64_at(&self, path: &str) -> Option<f64> {
+        self.root
+            .lookup(path)
+            .and_then(|value| value.as_float().or(value.as_integer().map(|value| value as f64)))
+    }
+
+    pub fn bool_at(&self, path: &str) -> Option<bool> {
+        self.root.lookup(path).and_then(|value| value.as_bool())
+    }
+
     pub fn string_at(&self, path: &str) -> Option<String> {
         self.root.lookup(path).and_then(|value| value.as_str()).map(|value| value.to_string())
     }
@@ -74,10 +104,31 @@ impl Configuration {
             })
     }
 
+    pub fn string_map_at(&self, path: &str) -> Option<BTreeMap<String, String>> {
+        self.root
+            .lookup(path)
+            .and_then(|value| value.as_table())
+            .map(|table| {
+                table.iter()
+                     .filter_map(|(key, value)| {
+                         value.as_str().map(|value| (key.to_string(), value.to_string()))
+                     })
+                     .collect()
+            })
+    }
+
     pub fn bytes_at(&self, path: &str) -> Option<Vec<u8>> {
         self.string_at(path).and_then(|value| value.from_hex().ok())
     }
 
+    pub fn bytes_vec_at(&self, path: &str) -> Option<Vec<Vec<u8>>> {
+        self.strings_at(path).and_then(|values| {
+            values.iter()
+                  .map(|value| value.from_hex().ok())
+                  .collect()
+        })
+    }
+
     pub fn configurations_at(&self, path: &str) -> Option<Vec<Configuration>> {
         self.root.lookup(path).and_then(|value| {
             value.as_slice().map(|slice| {
@@ -94,3 +145,30 @@ impl From<io::Error> for Error {
         Error::IOError(error)
     }
 }
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::IOError(ref error) => write!(f, "{}", error),
+            Error::TOMLParserError(ref errors) => {
+                for (index, error) in errors.iter().enumerate() {
+                    if index > 0 {
+                        try!(write!(f, "; "));
+                    }
+                    try!(write!(f, "{}", error));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "line {}, column {}: {}",
+               self.line,
+               self.column,
+               self.description)
+    }
+}
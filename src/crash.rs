@@ -0,0 +1,100 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::any::Any;
+use std::fs;
+use std::io::Write;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use backtrace::Backtrace;
+use delix::node::ID;
+use time;
+
+/// Fills in the node id of the crash reports once it's known.
+pub struct Handle {
+    node_id: Arc<RwLock<Option<ID>>>,
+}
+
+impl Handle {
+    pub fn set_node_id(&self, node_id: ID) {
+        *self.node_id.write().unwrap() = Some(node_id);
+    }
+}
+
+/// Installs a panic hook that logs the panic with a backtrace and writes a crash report to the
+/// report directory. Panics before the node id is set are reported without it.
+pub fn install(report_directory: Option<String>) -> Handle {
+    let node_id = Arc::new(RwLock::new(None));
+    let node_id_clone = node_id.clone();
+    panic::set_hook(Box::new(move |info| {
+        let node_id = match node_id_clone.read().ok().and_then(|node_id| *node_id) {
+            Some(node_id) => node_id.to_string(),
+            None => "unknown".to_string(),
+        };
+        let thread = thread::current();
+        let thread_name = thread.name().unwrap_or("<unnamed>");
+        let message = payload_message(info.payload());
+        let location = info.location()
+                           .map(|location| format!("{}:{}", location.file(), location.line()))
+                           .unwrap_or("<unknown>".to_string());
+        let backtrace = Backtrace::new();
+
+        error!("{}: thread '{}' panicked at '{}', {}\n{:?}",
+               node_id,
+               thread_name,
+               message,
+               location,
+               backtrace);
+
+        if let Some(ref report_directory) = report_directory {
+            let now = time::now_utc();
+            let path = PathBuf::from(report_directory)
+                           .join(format!("delix-crash-{}-{}.txt",
+                                         node_id,
+                                         time::strftime("%Y%m%d%H%M%S", &now).unwrap()));
+            let report = format!("time: {}\nnode: {}\nthread: {}\nmessage: {}\nlocation: \
+                                  {}\n\n{:?}\n",
+                                 now.rfc3339(),
+                                 node_id,
+                                 thread_name,
+                                 message,
+                                 location,
+                                 backtrace);
+            match fs::File::create(&path).and_then(|mut file| file.write_all(report.as_bytes())) {
+                Ok(()) => error!("{}: wrote crash report to {}", node_id, path.display()),
+                Err(error) => {
+                    error!("{}: failed to write crash report to {}: {:?}",
+                           node_id,
+                           path.display(),
+                           error)
+                }
+            }
+        }
+    }));
+    Handle { node_id: node_id }
+}
+
+fn payload_message(payload: &(Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<Any>".to_string()
+    }
+}
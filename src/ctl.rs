@@ -0,0 +1,294 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+#[cfg(not(test))]
+extern crate getopts;
+#[cfg(not(test))]
+extern crate hyper;
+#[cfg(not(test))]
+extern crate rustc_serialize;
+
+#[cfg(not(test))]
+use std::env;
+#[cfg(not(test))]
+use std::io::Read;
+#[cfg(not(test))]
+use std::process;
+#[cfg(not(test))]
+use std::thread;
+#[cfg(not(test))]
+use std::time::Duration;
+
+#[cfg(not(test))]
+use hyper::client::Client;
+#[cfg(not(test))]
+use hyper::status::StatusCode;
+#[cfg(not(test))]
+use rustc_serialize::json::Json;
+
+#[cfg(not(test))]
+const DEFAULT_API_ADDRESS: &'static str = "localhost:4200";
+#[cfg(not(test))]
+const DRAIN_POLL_INTERVAL_SECS: u64 = 1;
+
+#[cfg(not(test))]
+fn main() {
+    let arguments: Vec<String> = env::args().collect();
+    let program = arguments[0].clone();
+
+    let mut options = getopts::Options::new();
+    options.optopt("a", "api", "address of the relay api", "ADDRESS");
+    options.optflag("h", "help", "print help");
+
+    let matches = match options.parse(&arguments[1..]) {
+        Ok(matches) => matches,
+        Err(error) => fail(&format!("error while parsing arguments: {}", error)),
+    };
+
+    if matches.opt_present("h") || matches.free.is_empty() {
+        print_usage(&program, options);
+        process::exit(1);
+    }
+
+    let api_address = matches.opt_str("a").unwrap_or(DEFAULT_API_ADDRESS.to_string());
+
+    match (&*matches.free[0], matches.free.get(1)) {
+        ("debug-peer", Some(peer_node_id)) => debug_peer(&api_address, peer_node_id),
+        ("drain", None) => drain(&api_address),
+        ("release", None) => release(&api_address),
+        ("usage", None) => usage(&api_address),
+        ("reconnects", None) => reconnects(&api_address),
+        ("trace", Some(trace_id)) => trace(&api_address, trace_id),
+        _ => {
+            print_usage(&program, options);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn debug_peer(api_address: &str, peer_node_id: &str) {
+    let url = format!("http://{}/peers/{}/stats", api_address, peer_node_id);
+    let mut response = match Client::new().get(&url).send() {
+        Ok(response) => response,
+        Err(error) => fail(&format!("error while requesting {}: {}", url, error)),
+    };
+
+    let mut body = String::new();
+    if let Err(error) = response.read_to_string(&mut body) {
+        fail(&format!("error while reading response: {}", error));
+    }
+    if response.status != StatusCode::Ok {
+        fail(&format!("{}: {}", response.status, body));
+    }
+
+    let stats = match Json::from_str(&body) {
+        Ok(stats) => stats,
+        Err(error) => fail(&format!("error while parsing response: {}", error)),
+    };
+    let (local, remote) = match (stats.find("local"), stats.find("remote")) {
+        (Some(local), Some(remote)) => (local, remote),
+        _ => fail("incomplete response"),
+    };
+
+    println!("{:<18} {:>20} {:>20}", "", "local", "remote");
+    for &(label, key) in &[("bytes sent", "bytes_sent"),
+                           ("bytes received", "bytes_received"),
+                           ("frames sent", "frames_sent"),
+                           ("frames received", "frames_received"),
+                           ("uptime ms", "uptime_ms")] {
+        println!("{:<18} {:>20} {:>20}",
+                 label,
+                 format_value(local.find(key)),
+                 format_value(remote.find(key)));
+    }
+    println!("{:<18} {:>20} {:>20}",
+             "last error",
+             format_value(local.find("last_error")),
+             format_value(remote.find("last_error")));
+
+    if let (Some(local_clock), Some(remote_clock)) =
+           (local.find("clock_ms").and_then(Json::as_i64),
+            remote.find("clock_ms").and_then(Json::as_i64)) {
+        println!("{:<18} {:>41}", "clock offset ms", remote_clock - local_clock);
+    }
+}
+
+#[cfg(not(test))]
+fn drain(api_address: &str) {
+    let url = format!("http://{}/drain", api_address);
+    loop {
+        let mut response = match Client::new().post(&url).send() {
+            Ok(response) => response,
+            Err(error) => fail(&format!("error while requesting {}: {}", url, error)),
+        };
+        match response.status {
+            StatusCode::Ok => {
+                println!("drain permit granted");
+                return;
+            }
+            StatusCode::Conflict => {
+                thread::sleep(Duration::from_secs(DRAIN_POLL_INTERVAL_SECS));
+            }
+            status => {
+                let mut body = String::new();
+                let _ = response.read_to_string(&mut body);
+                fail(&format!("{}: {}", status, body));
+            }
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn release(api_address: &str) {
+    let url = format!("http://{}/drain", api_address);
+    let mut response = match Client::new().delete(&url).send() {
+        Ok(response) => response,
+        Err(error) => fail(&format!("error while requesting {}: {}", url, error)),
+    };
+    if response.status != StatusCode::Ok {
+        let mut body = String::new();
+        let _ = response.read_to_string(&mut body);
+        fail(&format!("{}: {}", response.status, body));
+    }
+    println!("drain permit released");
+}
+
+#[cfg(not(test))]
+fn usage(api_address: &str) {
+    let url = format!("http://{}/usage", api_address);
+    let mut response = match Client::new().get(&url).send() {
+        Ok(response) => response,
+        Err(error) => fail(&format!("error while requesting {}: {}", url, error)),
+    };
+
+    let mut body = String::new();
+    if let Err(error) = response.read_to_string(&mut body) {
+        fail(&format!("error while reading response: {}", error));
+    }
+    if response.status != StatusCode::Ok {
+        fail(&format!("{}: {}", response.status, body));
+    }
+
+    let usage = match Json::from_str(&body) {
+        Ok(Json::Object(usage)) => usage,
+        Ok(_) => fail("unexpected response"),
+        Err(error) => fail(&format!("error while parsing response: {}", error)),
+    };
+
+    println!("{:<24} {:<36} {:>12}", "service", "caller", "requests");
+    for (name, callers) in &usage {
+        if let Some(callers) = callers.as_object() {
+            for (caller_node_id, count) in callers {
+                println!("{:<24} {:<36} {:>12}", name, caller_node_id, format_value(Some(count)));
+            }
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn reconnects(api_address: &str) {
+    let url = format!("http://{}/reconnects", api_address);
+    let mut response = match Client::new().get(&url).send() {
+        Ok(response) => response,
+        Err(error) => fail(&format!("error while requesting {}: {}", url, error)),
+    };
+
+    let mut body = String::new();
+    if let Err(error) = response.read_to_string(&mut body) {
+        fail(&format!("error while reading response: {}", error));
+    }
+    if response.status != StatusCode::Ok {
+        fail(&format!("{}: {}", response.status, body));
+    }
+
+    let states = match Json::from_str(&body) {
+        Ok(Json::Array(states)) => states,
+        Ok(_) => fail("unexpected response"),
+        Err(error) => fail(&format!("error while parsing response: {}", error)),
+    };
+
+    println!("{:<36} {:<22} {:>9} {:>10} {:>10}  {}",
+             "peer",
+             "address",
+             "attempts",
+             "reconnects",
+             "backoff ms",
+             "last error");
+    for state in &states {
+        println!("{:<36} {:<22} {:>9} {:>10} {:>10}  {}",
+                 format_value(state.find("peer")),
+                 format_value(state.find("address")),
+                 format_value(state.find("attempts")),
+                 format_value(state.find("reconnects")),
+                 format_value(state.find("backoff_ms")),
+                 format_value(state.find("last_error")));
+    }
+}
+
+#[cfg(not(test))]
+fn trace(api_address: &str, trace_id: &str) {
+    let url = format!("http://{}/traces/{}", api_address, trace_id);
+    let mut response = match Client::new().get(&url).send() {
+        Ok(response) => response,
+        Err(error) => fail(&format!("error while requesting {}: {}", url, error)),
+    };
+
+    let mut body = String::new();
+    if let Err(error) = response.read_to_string(&mut body) {
+        fail(&format!("error while reading response: {}", error));
+    }
+    if response.status != StatusCode::Ok {
+        fail(&format!("{}: {}", response.status, body));
+    }
+
+    let records = match Json::from_str(&body) {
+        Ok(Json::Array(records)) => records,
+        Ok(_) => fail("unexpected response"),
+        Err(error) => fail(&format!("error while parsing response: {}", error)),
+    };
+
+    for record in &records {
+        println!("{} {:<36} {:>5} {}: {}",
+                 format_value(record.find("at")),
+                 format_value(record.find("node")),
+                 format_value(record.find("level")),
+                 format_value(record.find("target")),
+                 format_value(record.find("text")));
+    }
+}
+
+#[cfg(not(test))]
+fn format_value(value: Option<&Json>) -> String {
+    match value {
+        Some(&Json::String(ref value)) => value.to_string(),
+        Some(&Json::Null) | None => "-".to_string(),
+        Some(value) => value.to_string(),
+    }
+}
+
+#[cfg(not(test))]
+fn fail(message: &str) -> ! {
+    println!("{}", message);
+    process::exit(1);
+}
+
+#[cfg(not(test))]
+fn print_usage(program: &str, options: getopts::Options) {
+    let brief = format!("Usage: {} [options] (debug-peer ID | drain | release | usage | \
+                          reconnects | trace ID)",
+                        program);
+    print!("{}", options.usage(&brief));
+}
@@ -0,0 +1,67 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::net::SocketAddr;
+use std::sync::RwLock;
+
+use super::PeerLostHandler;
+
+pub fn replace(addresses: &RwLock<Vec<SocketAddr>>,
+               new_addresses: Vec<SocketAddr>,
+               peer_lost_handler: &RwLock<Option<PeerLostHandler>>) {
+    let lost = {
+        let mut addresses = addresses.write().unwrap();
+        let lost = addresses.iter()
+                            .filter(|address| !new_addresses.contains(address))
+                            .cloned()
+                            .collect::<Vec<SocketAddr>>();
+        *addresses = new_addresses;
+        lost
+    };
+
+    if let Some(ref handler) = *peer_lost_handler.read().unwrap() {
+        for address in lost {
+            handler(address);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex, RwLock};
+    use super::replace;
+    use super::super::PeerLostHandler;
+
+    #[test]
+    fn replace_notifies_lost_addresses() {
+        let address_one = "127.0.0.1:3001".parse::<SocketAddr>().unwrap();
+        let address_two = "127.0.0.1:3002".parse::<SocketAddr>().unwrap();
+        let address_three = "127.0.0.1:3003".parse::<SocketAddr>().unwrap();
+
+        let addresses = RwLock::new(vec![address_one, address_two]);
+        let lost = Arc::new(Mutex::new(Vec::new()));
+        let lost_clone = lost.clone();
+        let handler = RwLock::new(Some(Box::new(move |address| {
+            lost_clone.lock().unwrap().push(address);
+        }) as PeerLostHandler));
+
+        replace(&addresses, vec![address_two, address_three], &handler);
+
+        assert_eq!(vec![address_two, address_three], *addresses.read().unwrap());
+        assert_eq!(vec![address_one], *lost.lock().unwrap());
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+pub fn encode_address(bytes: &mut Vec<u8>, address: SocketAddr) {
+    match address {
+        SocketAddr::V4(address) => {
+            bytes.push(4);
+            bytes.extend_from_slice(&address.ip().octets());
+            encode_u16(bytes, address.port());
+        }
+        SocketAddr::V6(address) => {
+            bytes.push(6);
+            for segment in address.ip().segments().iter() {
+                encode_u16(bytes, *segment);
+            }
+            encode_u16(bytes, address.port());
+        }
+    }
+}
+
+pub fn encode_u32(bytes: &mut Vec<u8>, value: u32) {
+    encode_u16(bytes, (value >> 16) as u16);
+    encode_u16(bytes, value as u16);
+}
+
+pub fn encode_u16(bytes: &mut Vec<u8>, value: u16) {
+    bytes.push((value >> 8) as u8);
+    bytes.push(value as u8);
+}
+
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Reader {
+            bytes: bytes,
+            position: 0,
+        }
+    }
+
+    pub fn u8(&mut self) -> io::Result<u8> {
+        let result = try!(self.bytes
+                              .get(self.position)
+                              .cloned()
+                              .ok_or(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                                    "packet too short")));
+        self.position += 1;
+        Ok(result)
+    }
+
+    pub fn u16(&mut self) -> io::Result<u16> {
+        let high = try!(self.u8()) as u16;
+        let low = try!(self.u8()) as u16;
+        Ok((high << 8) | low)
+    }
+
+    pub fn u32(&mut self) -> io::Result<u32> {
+        let high = try!(self.u16()) as u32;
+        let low = try!(self.u16()) as u32;
+        Ok((high << 16) | low)
+    }
+
+    pub fn address(&mut self) -> io::Result<SocketAddr> {
+        let ip = match try!(self.u8()) {
+            4 => {
+                IpAddr::V4(Ipv4Addr::new(try!(self.u8()),
+                                         try!(self.u8()),
+                                         try!(self.u8()),
+                                         try!(self.u8())))
+            }
+            6 => {
+                let mut segments = [0u16; 8];
+                for segment in segments.iter_mut() {
+                    *segment = try!(self.u16());
+                }
+                IpAddr::V6(Ipv6Addr::new(segments[0],
+                                         segments[1],
+                                         segments[2],
+                                         segments[3],
+                                         segments[4],
+                                         segments[5],
+                                         segments[6],
+                                         segments[7]))
+            }
+            _ => return Err(invalid_data("invalid address type")),
+        };
+        let port = try!(self.u16());
+        Ok(match ip {
+            IpAddr::V4(ip) => SocketAddr::V4(SocketAddrV4::new(ip, port)),
+            IpAddr::V6(ip) => SocketAddr::V6(SocketAddrV6::new(ip, port, 0, 0)),
+        })
+    }
+
+    pub fn bytes(&mut self, count: usize) -> io::Result<Vec<u8>> {
+        let mut result = Vec::with_capacity(count);
+        for _ in 0..count {
+            result.push(try!(self.u8()));
+        }
+        Ok(result)
+    }
+}
+
+pub fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::SocketAddr;
+    use super::{Reader, encode_address, encode_u32};
+
+    #[test]
+    fn address_round_trip() {
+        for address in vec!["127.0.0.1:3001", "[::1]:3001"] {
+            let address = address.parse::<SocketAddr>().unwrap();
+            let mut bytes = Vec::new();
+            encode_address(&mut bytes, address);
+
+            assert_eq!(address, Reader::new(&bytes).address().unwrap());
+        }
+    }
+
+    #[test]
+    fn short_input() {
+        let mut bytes = Vec::new();
+        encode_u32(&mut bytes, 70000);
+
+        assert_eq!(70000, Reader::new(&bytes).u32().unwrap());
+        assert!(Reader::new(&bytes[..3]).u32().is_err());
+    }
+}
@@ -13,35 +13,188 @@
 // limitations under the License.
 //
 
+use std::cmp;
 use std::net::SocketAddr;
-use std::sync::RwLock;
+use std::sync::{Arc, RwLock};
+
+use time::Duration;
 
 use super::Discovery;
+use util::clock::{self, Clock};
+use util::resolve::Pending;
+
+const DEFAULT_INITIAL_BACKOFF_MS: i64 = 500;
+const DEFAULT_MAX_BACKOFF_MS: i64 = 30000;
 
 pub struct Constant {
     addresses: RwLock<Vec<SocketAddr>>,
     current_index: RwLock<usize>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    current_backoff: RwLock<Duration>,
+    clock: Arc<Clock>,
+    unresolved: Pending,
 }
 
 impl Constant {
     pub fn new(addresses: Vec<SocketAddr>) -> Constant {
+        Self::with_backoff(addresses,
+                           Duration::milliseconds(DEFAULT_INITIAL_BACKOFF_MS),
+                           Duration::milliseconds(DEFAULT_MAX_BACKOFF_MS))
+    }
+
+    pub fn with_backoff(addresses: Vec<SocketAddr>,
+                        initial_backoff: Duration,
+                        max_backoff: Duration)
+                        -> Constant {
+        Self::with_clock(addresses,
+                         initial_backoff,
+                         max_backoff,
+                         Arc::new(clock::System))
+    }
+
+    pub fn with_clock(addresses: Vec<SocketAddr>,
+                      initial_backoff: Duration,
+                      max_backoff: Duration,
+                      clock: Arc<Clock>)
+                      -> Constant {
         Constant {
             addresses: RwLock::new(addresses),
             current_index: RwLock::new(0),
+            initial_backoff: initial_backoff,
+            max_backoff: cmp::max(initial_backoff, max_backoff),
+            current_backoff: RwLock::new(initial_backoff),
+            clock: clock,
+            unresolved: Pending::new(),
+        }
+    }
+
+    /// Adds host names that couldn't be resolved yet. They are retried while the discovery is
+    /// asked for addresses and join the list once they resolve.
+    pub fn add_unresolved(&self, names: Vec<String>) {
+        self.unresolved.add(names);
+    }
+
+    fn resolve_pending(&self) {
+        let resolved = self.unresolved.resolve();
+        if resolved.is_empty() {
+            return;
+        }
+        let mut addresses = self.addresses.write().unwrap();
+        for address in resolved {
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
         }
     }
 }
 
 impl Discovery for Constant {
     fn next(&self) -> Option<SocketAddr> {
+        self.resolve_pending();
+        while self.addresses.read().unwrap().is_empty() && !self.unresolved.is_empty() {
+            let mut current_backoff = self.current_backoff.write().unwrap();
+            debug!("no address resolved yet - retrying in {} ms",
+                   current_backoff.num_milliseconds());
+            self.clock.sleep(*current_backoff);
+            *current_backoff = cmp::min(*current_backoff * 2, self.max_backoff);
+            drop(current_backoff);
+            self.resolve_pending();
+        }
+
         let addresses = self.addresses.read().unwrap();
         let mut current_index = self.current_index.write().unwrap();
 
-        let result = addresses.get(*current_index);
-        *current_index += 1;
+        if addresses.is_empty() {
+            return None;
+        }
+
         if *current_index >= addresses.len() {
             *current_index = 0;
+
+            let mut current_backoff = self.current_backoff.write().unwrap();
+            debug!("all {} addresses tried - retrying in {} ms",
+                   addresses.len(),
+                   current_backoff.num_milliseconds());
+            self.clock.sleep(*current_backoff);
+            *current_backoff = cmp::min(*current_backoff * 2, self.max_backoff);
         }
+
+        let result = addresses.get(*current_index);
+        *current_index += 1;
         result.map(|address| *address)
     }
+
+    fn reset(&self) {
+        *self.current_index.write().unwrap() = 0;
+        *self.current_backoff.write().unwrap() = self.initial_backoff;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::thread;
+    use time::{Duration, SteadyTime};
+    use util::clock::Mock;
+    use super::Constant;
+    use super::super::Discovery;
+
+    #[test]
+    fn retry_with_backoff() {
+        let address_one = "127.0.0.1:3001".parse::<SocketAddr>().unwrap();
+        let address_two = "127.0.0.1:3002".parse::<SocketAddr>().unwrap();
+        let constant = Constant::with_backoff(vec![address_one, address_two],
+                                              Duration::milliseconds(20),
+                                              Duration::milliseconds(30));
+
+        assert_eq!(Some(address_one), constant.next());
+        assert_eq!(Some(address_two), constant.next());
+
+        let start = SteadyTime::now();
+        assert_eq!(Some(address_one), constant.next());
+        assert_eq!(Some(address_two), constant.next());
+        assert_eq!(Some(address_one), constant.next());
+        assert!(SteadyTime::now() - start >= Duration::milliseconds(50));
+
+        constant.reset();
+        let start = SteadyTime::now();
+        assert_eq!(Some(address_one), constant.next());
+        assert_eq!(Some(address_two), constant.next());
+        assert!(SteadyTime::now() - start < Duration::milliseconds(20));
+    }
+
+    #[test]
+    fn retry_with_backoff_on_mock_clock() {
+        let address = "127.0.0.1:3001".parse::<SocketAddr>().unwrap();
+        let clock = Arc::new(Mock::new());
+        let constant = Arc::new(Constant::with_clock(vec![address],
+                                                     Duration::milliseconds(20),
+                                                     Duration::milliseconds(30),
+                                                     clock.clone()));
+
+        assert_eq!(Some(address), constant.next());
+
+        let constant_clone = constant.clone();
+        let handle = thread::spawn(move || constant_clone.next());
+        clock.wait_for_sleepers(1);
+        clock.advance(Duration::milliseconds(20));
+        assert_eq!(Some(address), handle.join().unwrap());
+
+        let constant_clone = constant.clone();
+        let handle = thread::spawn(move || constant_clone.next());
+        clock.wait_for_sleepers(1);
+        clock.advance(Duration::milliseconds(20));
+        assert_eq!(1, clock.sleepers());
+        clock.advance(Duration::milliseconds(10));
+        assert_eq!(Some(address), handle.join().unwrap());
+    }
+
+    #[test]
+    fn no_addresses() {
+        let constant = Constant::new(Vec::new());
+        assert_eq!(None, constant.next());
+    }
 }
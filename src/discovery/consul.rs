@@ -0,0 +1,371 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+extern crate hyper;
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::net::{IpAddr, SocketAddr};
+use std::result;
+use std::str;
+use std::sync::Mutex;
+
+use rustc_serialize::json::{self, Json};
+use time::Duration;
+
+use self::hyper::status::StatusCode;
+
+use super::{Discovery, PeerLostHandler};
+use super::registry::{self, Registry};
+use util::time::to_std_duration;
+
+pub struct Consul {
+    registry: Registry<Catalog>,
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    Hyper(hyper::Error),
+    Io(io::Error),
+    Json(json::ParserError),
+    UnexpectedStatus(StatusCode),
+}
+
+struct Catalog {
+    endpoint: String,
+    service_name: String,
+    service_id: String,
+    public_address: SocketAddr,
+    ttl: Duration,
+    index: Mutex<u64>,
+}
+
+impl Consul {
+    pub fn new(endpoint: &str,
+               service_name: &str,
+               public_address: SocketAddr,
+               ttl: Duration)
+               -> Result<Self> {
+        let catalog = Catalog {
+            endpoint: endpoint.trim_right_matches('/').to_string(),
+            service_name: service_name.to_string(),
+            service_id: format!("{}-{}", service_name, public_address),
+            public_address: public_address,
+            ttl: ttl,
+            index: Mutex::new(0),
+        };
+        Ok(Consul { registry: try!(Registry::start("consul", catalog, ttl)) })
+    }
+}
+
+impl Discovery for Consul {
+    fn next(&self) -> Option<SocketAddr> {
+        self.registry.next()
+    }
+
+    fn set_peer_lost_handler(&self, handler: PeerLostHandler) {
+        self.registry.set_peer_lost_handler(handler);
+    }
+}
+
+impl Catalog {
+    fn pass(&self) -> Result<()> {
+        let response = try!(hyper::Client::new()
+                                .put(&format!("{}/v1/agent/check/pass/service:{}",
+                                              self.endpoint,
+                                              self.service_id))
+                                .send());
+        match response.status {
+            StatusCode::Ok => Ok(()),
+            status => Err(Error::UnexpectedStatus(status)),
+        }
+    }
+}
+
+impl registry::Backend for Catalog {
+    type Error = Error;
+
+    fn register(&self) -> Result<()> {
+        // the check is unknown, if the agent has been restarted in the meantime. in that case,
+        // the service is registered again.
+        if self.pass().is_ok() {
+            return Ok(());
+        }
+
+        let body = build_registration(&self.service_name,
+                                      &self.service_id,
+                                      self.public_address,
+                                      self.ttl)
+                       .to_string();
+        let response = try!(hyper::Client::new()
+                                .put(&format!("{}/v1/agent/service/register", self.endpoint))
+                                .body(&body[..])
+                                .send());
+        match response.status {
+            StatusCode::Ok => self.pass(),
+            status => Err(Error::UnexpectedStatus(status)),
+        }
+    }
+
+    fn deregister(&self) -> Result<()> {
+        let response = try!(hyper::Client::new()
+                                .put(&format!("{}/v1/agent/service/deregister/{}",
+                                              self.endpoint,
+                                              self.service_id))
+                                .send());
+        match response.status {
+            StatusCode::Ok => Ok(()),
+            status => Err(Error::UnexpectedStatus(status)),
+        }
+    }
+
+    fn fetch(&self) -> Result<Vec<SocketAddr>> {
+        let mut response = try!(hyper::Client::new()
+                                    .get(&format!("{}/v1/health/service/{}?passing",
+                                                  self.endpoint,
+                                                  self.service_name))
+                                    .send());
+        match response.status {
+            StatusCode::Ok => {}
+            status => return Err(Error::UnexpectedStatus(status)),
+        }
+
+        let mut content = String::new();
+        try!(response.read_to_string(&mut content));
+        Ok(try!(parse_addresses(&content))
+               .into_iter()
+               .filter(|address| *address != self.public_address)
+               .collect())
+    }
+
+    fn watch(&self, timeout: Duration) -> Result<()> {
+        let index = *self.index.lock().unwrap();
+
+        // consul adds a jitter of up to a sixteenth of the wait time, so the read timeout is
+        // doubled.
+        let mut client = hyper::Client::new();
+        client.set_read_timeout(Some(to_std_duration(timeout * 2)));
+        let mut response = try!(client.get(&format!("{}/v1/health/service/{}?passing&index={}\
+                                                     &wait={}ms",
+                                                    self.endpoint,
+                                                    self.service_name,
+                                                    index,
+                                                    timeout.num_milliseconds()))
+                                      .send());
+        try!(io::copy(&mut response, &mut io::sink()));
+        match response.status {
+            StatusCode::Ok => {}
+            status => return Err(Error::UnexpectedStatus(status)),
+        }
+
+        if let Some(index) = response.headers
+                                     .get_raw("X-Consul-Index")
+                                     .and_then(|values| values.first())
+                                     .and_then(|value| str::from_utf8(value).ok())
+                                     .and_then(|value| value.parse::<u64>().ok()) {
+            *self.index.lock().unwrap() = index;
+        }
+        Ok(())
+    }
+}
+
+impl From<hyper::Error> for Error {
+    fn from(error: hyper::Error) -> Self {
+        Error::Hyper(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<json::ParserError> for Error {
+    fn from(error: json::ParserError) -> Self {
+        Error::Json(error)
+    }
+}
+
+fn build_registration(service_name: &str,
+                      service_id: &str,
+                      public_address: SocketAddr,
+                      ttl: Duration)
+                      -> Json {
+    let mut check = BTreeMap::new();
+    check.insert("TTL".to_string(),
+                 Json::String(format!("{}s", cmp::max(1, ttl.num_seconds()))));
+
+    let mut registration = BTreeMap::new();
+    registration.insert("ID".to_string(), Json::String(service_id.to_string()));
+    registration.insert("Name".to_string(), Json::String(service_name.to_string()));
+    registration.insert("Address".to_string(),
+                        Json::String(format!("{}", public_address.ip())));
+    registration.insert("Port".to_string(), Json::U64(public_address.port() as u64));
+    registration.insert("Check".to_string(), Json::Object(check));
+    Json::Object(registration)
+}
+
+fn parse_addresses(content: &str) -> Result<Vec<SocketAddr>> {
+    let json = try!(Json::from_str(content));
+    let entries = match json.as_array() {
+        Some(entries) => entries,
+        None => return Ok(Vec::new()),
+    };
+
+    Ok(entries.iter()
+              .filter_map(|entry| {
+                  let service = match entry.find("Service") {
+                      Some(service) => service,
+                      None => return None,
+                  };
+                  let port = match service.find("Port").and_then(|port| port.as_u64()) {
+                      Some(port) => port as u16,
+                      None => return None,
+                  };
+                  let address = service.find("Address")
+                                       .and_then(|address| address.as_string())
+                                       .and_then(|address| address.parse::<IpAddr>().ok())
+                                       .or(entry.find_path(&["Node", "Address"])
+                                                .and_then(|address| address.as_string())
+                                                .and_then(|address| {
+                                                    address.parse::<IpAddr>().ok()
+                                                }));
+                  address.map(|address| SocketAddr::new(address, port))
+              })
+              .collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Read;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::thread;
+    use time::{Duration, SteadyTime};
+    use discovery::Discovery;
+    use util::time::to_std_duration;
+    use super::{Consul, build_registration, hyper, parse_addresses};
+    use super::hyper::status::StatusCode;
+
+    #[test]
+    fn watch_service_and_register_again() {
+        let peers = Arc::new(Mutex::new(Vec::<SocketAddr>::new()));
+        let registered = Arc::new(AtomicBool::new(false));
+        let registrations = Arc::new(AtomicUsize::new(0));
+        let deregistered = Arc::new(AtomicBool::new(false));
+
+        let peers_clone = peers.clone();
+        let registered_clone = registered.clone();
+        let registrations_clone = registrations.clone();
+        let deregistered_clone = deregistered.clone();
+        let mut listening =
+            hyper::Server::http("127.0.0.1:0")
+                .unwrap()
+                .handle(move |mut request: hyper::server::Request,
+                              mut response: hyper::server::Response| {
+                    let mut body = String::new();
+                    request.read_to_string(&mut body).unwrap();
+                    let target = format!("{} {}", request.method, request.uri);
+                    let pass = target == "PUT /v1/agent/check/pass/service:delix-127.0.0.1:3001";
+
+                    if target == "PUT /v1/agent/service/register" {
+                        registered_clone.store(true, Ordering::SeqCst);
+                        registrations_clone.fetch_add(1, Ordering::SeqCst);
+                    } else if pass && !registered_clone.load(Ordering::SeqCst) {
+                        *response.status_mut() = StatusCode::InternalServerError;
+                    } else if target == "PUT /v1/agent/service/deregister/delix-127.0.0.1:3001" {
+                        deregistered_clone.store(true, Ordering::SeqCst);
+                    } else if target.starts_with("GET /v1/health/service/delix?passing") {
+                        if target.contains("index=") {
+                            thread::sleep(to_std_duration(Duration::milliseconds(20)));
+                        }
+                        let entries = peers_clone.lock()
+                                                 .unwrap()
+                                                 .iter()
+                                                 .map(|peer| {
+                                                     format!("{{\"Service\":{{\"Address\":\
+                                                              \"{}\",\"Port\":{}}}}}",
+                                                             peer.ip(),
+                                                             peer.port())
+                                                 })
+                                                 .collect::<Vec<_>>();
+                        response.headers_mut().set_raw("X-Consul-Index", vec![b"7".to_vec()]);
+                        response.send(format!("[{}]", entries.join(",")).as_bytes()).unwrap();
+                        return;
+                    }
+                    response.send(b"").unwrap();
+                })
+                .unwrap();
+
+        let peer = "127.0.0.1:3002".parse::<SocketAddr>().unwrap();
+        let consul = Consul::new(&format!("http://{}", listening.socket),
+                                 "delix",
+                                 "127.0.0.1:3001".parse::<SocketAddr>().unwrap(),
+                                 Duration::milliseconds(100))
+                         .unwrap();
+        assert_eq!(1, registrations.load(Ordering::SeqCst));
+        assert_eq!(None, consul.next());
+
+        peers.lock().unwrap().push(peer);
+        wait_until(|| consul.next() == Some(peer));
+
+        // the agent forgets the service, e.g. after a restart.
+        registered.store(false, Ordering::SeqCst);
+        wait_until(|| registrations.load(Ordering::SeqCst) == 2);
+
+        drop(consul);
+        assert!(deregistered.load(Ordering::SeqCst));
+
+        listening.close().unwrap();
+    }
+
+    #[test]
+    fn registration() {
+        let registration = build_registration("delix",
+                                              "delix-127.0.0.1:3001",
+                                              "127.0.0.1:3001".parse::<SocketAddr>().unwrap(),
+                                              Duration::milliseconds(30000));
+
+        assert_eq!("{\"Address\":\"127.0.0.1\",\"Check\":{\"TTL\":\"30s\"},\
+                    \"ID\":\"delix-127.0.0.1:3001\",\"Name\":\"delix\",\"Port\":3001}",
+                   registration.to_string());
+    }
+
+    #[test]
+    fn parse_addresses_of_service() {
+        let content = r#"[
+            {"Node":{"Address":"10.0.0.1"},"Service":{"Address":"127.0.0.1","Port":3001}},
+            {"Node":{"Address":"10.0.0.2"},"Service":{"Address":"","Port":3002}},
+            {"Node":{"Address":"10.0.0.3"}}]"#;
+
+        assert_eq!(vec!["127.0.0.1:3001".parse::<SocketAddr>().unwrap(),
+                        "10.0.0.2:3002".parse::<SocketAddr>().unwrap()],
+                   parse_addresses(content).unwrap());
+    }
+
+    fn wait_until<F: Fn() -> bool>(f: F) {
+        let deadline = SteadyTime::now() + Duration::seconds(5);
+        while !f() {
+            assert!(SteadyTime::now() < deadline, "condition not met in time");
+            thread::sleep(to_std_duration(Duration::milliseconds(10)));
+        }
+    }
+}
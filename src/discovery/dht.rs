@@ -0,0 +1,719 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::net::{self, SocketAddr};
+use std::sync::{Arc, Mutex, RwLock, mpsc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use time::{Duration, SteadyTime};
+
+use node::ID;
+use super::{Discovery, PeerLostHandler};
+use super::codec::{Reader, encode_address, encode_u32, invalid_data};
+use util::clock::{self, Clock};
+use util::resolve::Pending;
+use util::thread::supervisor;
+use util::time::to_std_duration;
+
+const PACKET_SIZE: usize = 1400;
+const BUCKET_SIZE: usize = 8;
+const PARALLELISM: usize = 3;
+
+pub struct Dht {
+    inner: Arc<Inner>,
+    running: Arc<AtomicBool>,
+    current_index: RwLock<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Contact {
+    id: ID,
+    address: SocketAddr,
+    public_address: SocketAddr,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Kind {
+    Ping,
+    Pong,
+    FindNode(ID),
+    Nodes(Vec<Contact>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Packet {
+    kind: Kind,
+    sequence: u32,
+    sender: Contact,
+}
+
+struct Entry {
+    contact: Contact,
+    last_seen: SteadyTime,
+}
+
+struct RoutingTable {
+    id: ID,
+    buckets: Vec<Vec<Entry>>,
+    replacements: Vec<Vec<Contact>>,
+    clock: Arc<Clock>,
+}
+
+struct Inner {
+    contact: Contact,
+    udp_socket: net::UdpSocket,
+    table: RwLock<RoutingTable>,
+    pending: Mutex<HashMap<u32, mpsc::Sender<Packet>>>,
+    checks: Mutex<Vec<Contact>>,
+    next_sequence: Mutex<u32>,
+    request_timeout: Duration,
+    peer_lost_handler: RwLock<Option<PeerLostHandler>>,
+    unresolved_seeds: Pending,
+}
+
+impl Dht {
+    pub fn new(id: ID,
+               address: SocketAddr,
+               public_address: SocketAddr,
+               seeds: Vec<SocketAddr>,
+               request_timeout: Duration,
+               refresh_interval: Duration)
+               -> io::Result<Self> {
+        Self::with_clock(id,
+                         address,
+                         public_address,
+                         seeds,
+                         request_timeout,
+                         refresh_interval,
+                         Arc::new(clock::System))
+    }
+
+    pub fn with_clock(id: ID,
+                      address: SocketAddr,
+                      public_address: SocketAddr,
+                      seeds: Vec<SocketAddr>,
+                      request_timeout: Duration,
+                      refresh_interval: Duration,
+                      clock: Arc<Clock>)
+                      -> io::Result<Self> {
+        let udp_socket = try!(net::UdpSocket::bind(address));
+        let inner = Arc::new(Inner {
+            contact: Contact {
+                id: id,
+                address: address,
+                public_address: public_address,
+            },
+            udp_socket: udp_socket,
+            table: RwLock::new(RoutingTable::new(id, clock.clone())),
+            pending: Mutex::new(HashMap::new()),
+            checks: Mutex::new(Vec::new()),
+            next_sequence: Mutex::new(0),
+            request_timeout: request_timeout,
+            peer_lost_handler: RwLock::new(None),
+            unresolved_seeds: Pending::new(),
+        });
+        let running = Arc::new(AtomicBool::new(true));
+
+        let inner_clone = inner.clone();
+        let running_clone = running.clone();
+        supervisor().spawn("dht receiver", move || {
+            let mut buffer = [0u8; PACKET_SIZE];
+            while running_clone.load(Ordering::SeqCst) {
+                match inner_clone.udp_socket.recv_from(&mut buffer) {
+                    Ok((size, sender_address)) => {
+                        match decode(&buffer[..size]) {
+                            Ok(packet) => inner_clone.handle(packet),
+                            Err(error) => {
+                                debug!("{}: dropped packet from {}: {:?}",
+                                       inner_clone.contact.address,
+                                       sender_address,
+                                       error)
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        error!("{}: receive error: {:?}", inner_clone.contact.address, error)
+                    }
+                }
+            }
+        });
+
+        for seed in seeds {
+            if seed == address {
+                continue;
+            }
+            match inner.request(seed, Kind::Ping) {
+                Some(_) => {}
+                None => warn!("{}: seed {} did not answer", address, seed),
+            }
+        }
+        inner.lookup(&id);
+
+        let inner_clone = inner.clone();
+        let running_clone = running.clone();
+        supervisor().spawn_restartable("dht maintenance",
+                                       Duration::seconds(1),
+                                       move || {
+            while running_clone.load(Ordering::SeqCst) {
+                clock.sleep(refresh_interval);
+                if !running_clone.load(Ordering::SeqCst) {
+                    break;
+                }
+                inner_clone.maintain(refresh_interval);
+
+                let seeds = inner_clone.unresolved_seeds.resolve();
+                for &seed in &seeds {
+                    if seed != inner_clone.contact.address &&
+                       inner_clone.request(seed, Kind::Ping).is_none() {
+                        warn!("{}: seed {} did not answer", inner_clone.contact.address, seed);
+                    }
+                }
+                if !seeds.is_empty() {
+                    inner_clone.lookup(&inner_clone.contact.id);
+                }
+            }
+        });
+
+        Ok(Dht {
+            inner: inner,
+            running: running,
+            current_index: RwLock::new(0),
+        })
+    }
+
+    pub fn id(&self) -> ID {
+        self.inner.contact.id
+    }
+
+    /// Adds seeds that couldn't be resolved yet. They are contacted once they resolve.
+    pub fn add_unresolved_seeds(&self, names: Vec<String>) {
+        self.inner.unresolved_seeds.add(names);
+    }
+
+    pub fn find(&self, id: &ID) -> Option<SocketAddr> {
+        self.inner
+            .lookup(id)
+            .into_iter()
+            .find(|contact| contact.id == *id)
+            .map(|contact| contact.public_address)
+    }
+}
+
+impl Discovery for Dht {
+    fn next(&self) -> Option<SocketAddr> {
+        let contacts = self.inner.table.read().unwrap().contacts();
+
+        let mut current_index = self.current_index.write().unwrap();
+        if *current_index >= contacts.len() {
+            *current_index = 0;
+        }
+        let result = contacts.get(*current_index).map(|contact| contact.public_address);
+        *current_index += 1;
+        result
+    }
+
+    fn set_peer_lost_handler(&self, handler: PeerLostHandler) {
+        *self.inner.peer_lost_handler.write().unwrap() = Some(handler);
+    }
+}
+
+impl Drop for Dht {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        *self.inner.peer_lost_handler.write().unwrap() = None;
+        // unblock the receiving thread
+        let _ = self.inner
+                    .udp_socket
+                    .send_to(&[], self.inner.udp_socket.local_addr().unwrap());
+    }
+}
+
+impl Inner {
+    fn send(&self, address: SocketAddr, kind: Kind, sequence: u32) -> io::Result<()> {
+        let packet = Packet {
+            kind: kind,
+            sequence: sequence,
+            sender: self.contact,
+        };
+        try!(self.udp_socket.send_to(&encode(&packet), address));
+        Ok(())
+    }
+
+    fn start_request(&self, address: SocketAddr, kind: Kind) -> (u32, mpsc::Receiver<Packet>) {
+        let sequence = {
+            let mut next_sequence = self.next_sequence.lock().unwrap();
+            *next_sequence = next_sequence.wrapping_add(1);
+            *next_sequence
+        };
+
+        let (tx, rx) = mpsc::channel();
+        self.pending.lock().unwrap().insert(sequence, tx);
+
+        if let Err(error) = self.send(address, kind, sequence) {
+            error!("{}: failed to send request to {}: {:?}",
+                   self.contact.address,
+                   address,
+                   error);
+        }
+        (sequence, rx)
+    }
+
+    /// Waits for the response to a request until the deadline, which is shared by all requests
+    /// that have been started together.
+    fn finish_request(&self,
+                      sequence: u32,
+                      rx: mpsc::Receiver<Packet>,
+                      deadline: SteadyTime)
+                      -> Option<Packet> {
+        let remaining = deadline - SteadyTime::now();
+        let result = if remaining > Duration::zero() {
+            rx.recv_timeout(to_std_duration(remaining)).ok()
+        } else {
+            rx.try_recv().ok()
+        };
+        self.pending.lock().unwrap().remove(&sequence);
+        result
+    }
+
+    fn request(&self, address: SocketAddr, kind: Kind) -> Option<Packet> {
+        let deadline = SteadyTime::now() + self.request_timeout;
+        let (sequence, rx) = self.start_request(address, kind);
+        self.finish_request(sequence, rx, deadline)
+    }
+
+    fn handle(&self, packet: Packet) {
+        if packet.sender.id == self.contact.id {
+            return;
+        }
+        let oldest = self.table.write().unwrap().update(packet.sender);
+        if let Some(oldest) = oldest {
+            let mut checks = self.checks.lock().unwrap();
+            if !checks.iter().any(|contact| contact.id == oldest.id) {
+                checks.push(oldest);
+            }
+        }
+
+        let result = match packet.kind {
+            Kind::Ping => self.send(packet.sender.address, Kind::Pong, packet.sequence),
+            Kind::FindNode(ref target) => {
+                let mut contacts = self.table.read().unwrap().closest(target, BUCKET_SIZE + 1);
+                contacts.retain(|contact| contact.id != packet.sender.id);
+                contacts.truncate(BUCKET_SIZE);
+                self.send(packet.sender.address, Kind::Nodes(contacts), packet.sequence)
+            }
+            Kind::Pong | Kind::Nodes(_) => {
+                if let Some(tx) = self.pending.lock().unwrap().remove(&packet.sequence) {
+                    let _ = tx.send(packet.clone());
+                }
+                Ok(())
+            }
+        };
+        if let Err(error) = result {
+            error!("{}: failed to reply to {}: {:?}",
+                   self.contact.address,
+                   packet.sender.address,
+                   error);
+        }
+    }
+
+    fn lookup(&self, target: &ID) -> Vec<Contact> {
+        let mut shortlist = self.table.read().unwrap().closest(target, BUCKET_SIZE);
+        let mut queried = HashSet::new();
+
+        loop {
+            let candidates = shortlist.iter()
+                                      .filter(|contact| !queried.contains(&contact.id))
+                                      .take(PARALLELISM)
+                                      .cloned()
+                                      .collect::<Vec<_>>();
+            if candidates.is_empty() {
+                break;
+            }
+
+            let deadline = SteadyTime::now() + self.request_timeout;
+            let requests = candidates.iter()
+                                     .map(|candidate| {
+                                         queried.insert(candidate.id);
+                                         self.start_request(candidate.address,
+                                                            Kind::FindNode(*target))
+                                     })
+                                     .collect::<Vec<_>>();
+
+            for (candidate, (sequence, rx)) in candidates.into_iter().zip(requests) {
+                match self.finish_request(sequence, rx, deadline) {
+                    Some(Packet { kind: Kind::Nodes(contacts), .. }) => {
+                        for contact in contacts {
+                            if contact.id != self.contact.id &&
+                               !shortlist.iter().any(|known| known.id == contact.id) {
+                                shortlist.push(contact);
+                            }
+                        }
+                    }
+                    _ => {
+                        shortlist.retain(|contact| contact.id != candidate.id);
+                    }
+                }
+            }
+
+            shortlist.sort_by(|a, b| distance(&a.id, target).cmp(&distance(&b.id, target)));
+            shortlist.truncate(BUCKET_SIZE);
+        }
+
+        shortlist
+    }
+
+    fn maintain(&self, refresh_interval: Duration) {
+        // the least-recently seen contacts of full buckets are only replaced if they don't answer
+        let checks = self.checks.lock().unwrap().drain(..).collect::<Vec<_>>();
+        let stale = self.table.read().unwrap().stale(refresh_interval);
+        for contact in checks.into_iter().chain(stale) {
+            if self.request(contact.address, Kind::Ping).is_none() {
+                self.evict(contact);
+            }
+        }
+
+        self.lookup(&ID::new_random());
+        self.lookup(&self.contact.id);
+    }
+
+    fn evict(&self, contact: Contact) {
+        if !self.table.write().unwrap().remove(&contact.id) {
+            return;
+        }
+        debug!("{}: contact {} is gone", self.contact.address, contact.id.short());
+        if let Some(ref handler) = *self.peer_lost_handler.read().unwrap() {
+            handler(contact.public_address);
+        }
+    }
+}
+
+impl RoutingTable {
+    fn new(id: ID, clock: Arc<Clock>) -> Self {
+        let bits = id.to_vec().len() * 8;
+        RoutingTable {
+            id: id,
+            buckets: (0..bits).map(|_| Vec::new()).collect(),
+            replacements: (0..bits).map(|_| Vec::new()).collect(),
+            clock: clock,
+        }
+    }
+
+    fn bucket_index(&self, id: &ID) -> Option<usize> {
+        let distance = distance(&self.id, id);
+        let bits = distance.len() * 8;
+        distance.iter()
+                .position(|byte| *byte != 0)
+                .map(|index| bits - 1 - (index * 8 + distance[index].leading_zeros() as usize))
+    }
+
+    /// Moves the contact to the tail of its bucket. If the bucket is full, the contact is kept
+    /// as a replacement and the least-recently seen contact of the bucket is returned, so it can
+    /// be pinged and removed if it doesn't answer.
+    fn update(&mut self, contact: Contact) -> Option<Contact> {
+        let index = match self.bucket_index(&contact.id) {
+            Some(index) => index,
+            None => return None,
+        };
+
+        let bucket = &mut self.buckets[index];
+        if let Some(position) = bucket.iter().position(|entry| entry.contact.id == contact.id) {
+            bucket.remove(position);
+        } else if bucket.len() >= BUCKET_SIZE {
+            let replacements = &mut self.replacements[index];
+            replacements.retain(|replacement| replacement.id != contact.id);
+            replacements.push(contact);
+            if replacements.len() > BUCKET_SIZE {
+                replacements.remove(0);
+            }
+            return bucket.first().map(|entry| entry.contact);
+        }
+        bucket.push(Entry {
+            contact: contact,
+            last_seen: self.clock.now(),
+        });
+        None
+    }
+
+    /// Removes the contact and fills the gap with the most recent replacement. Returns `false`
+    /// if the contact wasn't in the table.
+    fn remove(&mut self, id: &ID) -> bool {
+        let index = match self.bucket_index(id) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        let now = self.clock.now();
+        let bucket = &mut self.buckets[index];
+        let length = bucket.len();
+        bucket.retain(|entry| entry.contact.id != *id);
+        if bucket.len() == length {
+            return false;
+        }
+        if let Some(replacement) = self.replacements[index].pop() {
+            bucket.push(Entry {
+                contact: replacement,
+                last_seen: now,
+            });
+        }
+        true
+    }
+
+    fn contacts(&self) -> Vec<Contact> {
+        self.closest(&self.id, usize::max_value())
+    }
+
+    fn closest(&self, target: &ID, count: usize) -> Vec<Contact> {
+        let mut contacts = self.buckets
+                               .iter()
+                               .flat_map(|bucket| bucket.iter().map(|entry| entry.contact))
+                               .collect::<Vec<_>>();
+        contacts.sort_by(|a, b| distance(&a.id, target).cmp(&distance(&b.id, target)));
+        contacts.truncate(count);
+        contacts
+    }
+
+    fn stale(&self, max_age: Duration) -> Vec<Contact> {
+        let now = self.clock.now();
+        self.buckets
+            .iter()
+            .flat_map(|bucket| bucket.iter())
+            .filter(|entry| now - entry.last_seen > max_age)
+            .map(|entry| entry.contact)
+            .collect()
+    }
+}
+
+fn distance(a: &ID, b: &ID) -> Vec<u8> {
+    a.to_vec().iter().zip(b.to_vec().iter()).map(|(a, b)| a ^ b).collect()
+}
+
+fn encode(packet: &Packet) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(PACKET_SIZE);
+    match packet.kind {
+        Kind::Ping => bytes.push(0),
+        Kind::Pong => bytes.push(1),
+        Kind::FindNode(ref target) => {
+            bytes.push(2);
+            encode_id(&mut bytes, target);
+        }
+        Kind::Nodes(ref contacts) => {
+            bytes.push(3);
+            bytes.push(contacts.len() as u8);
+            for contact in contacts {
+                encode_contact(&mut bytes, contact);
+            }
+        }
+    }
+    encode_u32(&mut bytes, packet.sequence);
+    encode_contact(&mut bytes, &packet.sender);
+    bytes
+}
+
+fn encode_contact(bytes: &mut Vec<u8>, contact: &Contact) {
+    encode_id(bytes, &contact.id);
+    encode_address(bytes, contact.address);
+    encode_address(bytes, contact.public_address);
+}
+
+fn encode_id(bytes: &mut Vec<u8>, id: &ID) {
+    let id = id.to_vec();
+    bytes.push(id.len() as u8);
+    bytes.extend_from_slice(&id);
+}
+
+fn decode(bytes: &[u8]) -> io::Result<Packet> {
+    let mut reader = Reader::new(bytes);
+
+    let kind = match try!(reader.u8()) {
+        0 => Kind::Ping,
+        1 => Kind::Pong,
+        2 => Kind::FindNode(try!(decode_id(&mut reader))),
+        3 => {
+            let count = try!(reader.u8());
+            let mut contacts = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                contacts.push(try!(decode_contact(&mut reader)));
+            }
+            Kind::Nodes(contacts)
+        }
+        _ => return Err(invalid_data("invalid packet kind")),
+    };
+    let sequence = try!(reader.u32());
+    let sender = try!(decode_contact(&mut reader));
+
+    Ok(Packet {
+        kind: kind,
+        sequence: sequence,
+        sender: sender,
+    })
+}
+
+fn decode_contact(reader: &mut Reader) -> io::Result<Contact> {
+    let id = try!(decode_id(reader));
+    let address = try!(reader.address());
+    let public_address = try!(reader.address());
+    Ok(Contact {
+        id: id,
+        address: address,
+        public_address: public_address,
+    })
+}
+
+fn decode_id(reader: &mut Reader) -> io::Result<ID> {
+    let length = try!(reader.u8());
+    let bytes = try!(reader.bytes(length as usize));
+    ID::from_vec(bytes).map_err(|_| invalid_data("invalid node id"))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex, mpsc};
+    use time::Duration;
+    use node::ID;
+    use util::clock::{self, Clock, Mock};
+    use super::{BUCKET_SIZE, Contact, Dht, Kind, Packet, RoutingTable, decode, encode};
+    use super::super::Discovery;
+
+    #[test]
+    fn packet_encoding() {
+        let packet = Packet {
+            kind: Kind::Nodes(vec![contact("127.0.0.1:4201", "127.0.0.1:3201")]),
+            sequence: 70000,
+            sender: contact("[::1]:4200", "[::1]:3200"),
+        };
+
+        assert_eq!(packet, decode(&encode(&packet)).unwrap());
+        assert!(decode(&encode(&packet)[..10]).is_err());
+    }
+
+    #[test]
+    fn routing_table_bucket_index() {
+        let table = RoutingTable::new("0000000000".parse::<ID>().unwrap(),
+                                      Arc::new(clock::System));
+
+        assert_eq!(None, table.bucket_index(&"0000000000".parse::<ID>().unwrap()));
+        assert_eq!(Some(0), table.bucket_index(&"0000000001".parse::<ID>().unwrap()));
+        assert_eq!(Some(39), table.bucket_index(&"8000000000".parse::<ID>().unwrap()));
+    }
+
+    #[test]
+    fn routing_table_keeps_replacements_for_full_buckets() {
+        let mut table = RoutingTable::new("0000000000".parse::<ID>().unwrap(),
+                                          Arc::new(clock::System));
+        let contacts = (0..BUCKET_SIZE + 1)
+                           .map(|index| {
+                               let mut contact = contact("127.0.0.1:4200", "127.0.0.1:3200");
+                               contact.id = format!("80000000{:02x}", index).parse::<ID>().unwrap();
+                               contact
+                           })
+                           .collect::<Vec<_>>();
+        for contact in contacts[..BUCKET_SIZE].iter() {
+            assert_eq!(None, table.update(*contact));
+        }
+        assert_eq!(Some(contacts[0]), table.update(contacts[BUCKET_SIZE]));
+
+        assert_eq!(BUCKET_SIZE, table.contacts().len());
+        assert!(!table.contacts().contains(&contacts[BUCKET_SIZE]));
+
+        assert!(table.remove(&contacts[0].id));
+        assert!(!table.remove(&contacts[0].id));
+
+        assert_eq!(BUCKET_SIZE, table.contacts().len());
+        assert!(table.contacts().contains(&contacts[BUCKET_SIZE]));
+    }
+
+    #[test]
+    fn routing_table_reports_stale_contacts() {
+        let clock = Arc::new(Mock::new());
+        let mut table = RoutingTable::new("0000000000".parse::<ID>().unwrap(), clock.clone());
+        table.update(contact("127.0.0.1:4200", "127.0.0.1:3200"));
+
+        assert!(table.stale(Duration::seconds(10)).is_empty());
+
+        clock.advance(Duration::seconds(11));
+        assert_eq!(1, table.stale(Duration::seconds(10)).len());
+    }
+
+    #[test]
+    fn iterative_lookup_with_three_nodes() {
+        let dht_one = build("127.0.0.1:4211", "127.0.0.1:3211", vec![]);
+        let dht_two = build("127.0.0.1:4212", "127.0.0.1:3212", vec!["127.0.0.1:4211"]);
+        let dht_three = build("127.0.0.1:4213", "127.0.0.1:3213", vec!["127.0.0.1:4212"]);
+
+        assert_eq!(Some("127.0.0.1:3211".parse::<SocketAddr>().unwrap()),
+                   dht_three.find(&dht_one.id()));
+        assert_eq!(Some("127.0.0.1:3213".parse::<SocketAddr>().unwrap()),
+                   dht_one.find(&dht_three.id()));
+        assert!(dht_two.next().is_some());
+    }
+
+    #[test]
+    fn lose_unresponsive_contact_on_refresh() {
+        let clock = Arc::new(Mock::new());
+        let dht_one = build_with_clock("127.0.0.1:4221", "127.0.0.1:3221", vec![], &clock);
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        dht_one.set_peer_lost_handler(Box::new(move |address| {
+            tx.lock().unwrap().send(address).unwrap();
+        }));
+
+        {
+            let dht_two = build_with_clock("127.0.0.1:4222",
+                                           "127.0.0.1:3222",
+                                           vec!["127.0.0.1:4221"],
+                                           &clock);
+            assert_eq!(Some("127.0.0.1:3222".parse::<SocketAddr>().unwrap()),
+                       dht_one.next());
+            clock.wait_for_sleepers(2);
+            drop(dht_two);
+        }
+
+        clock.advance(Duration::milliseconds(10001));
+
+        assert_eq!("127.0.0.1:3222".parse::<SocketAddr>().unwrap(),
+                   rx.recv().unwrap());
+        assert_eq!(None, dht_one.next());
+    }
+
+    fn build(address: &str, public_address: &str, seeds: Vec<&str>) -> Dht {
+        build_with_clock(address, public_address, seeds, &Arc::new(clock::System))
+    }
+
+    fn build_with_clock<C: Clock + 'static>(address: &str,
+                                            public_address: &str,
+                                            seeds: Vec<&str>,
+                                            clock: &Arc<C>)
+                                            -> Dht {
+        Dht::with_clock(ID::new_random(),
+                        address.parse::<SocketAddr>().unwrap(),
+                        public_address.parse::<SocketAddr>().unwrap(),
+                        seeds.iter().map(|seed| seed.parse::<SocketAddr>().unwrap()).collect(),
+                        Duration::milliseconds(200),
+                        Duration::milliseconds(10000),
+                        clock.clone())
+            .unwrap()
+    }
+
+    fn contact(address: &str, public_address: &str) -> Contact {
+        Contact {
+            id: ID::new_random(),
+            address: address.parse::<SocketAddr>().unwrap(),
+            public_address: public_address.parse::<SocketAddr>().unwrap(),
+        }
+    }
+}
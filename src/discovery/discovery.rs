@@ -15,6 +15,12 @@
 
 use std::net::SocketAddr;
 
+pub type PeerLostHandler = Box<Fn(SocketAddr) + Send + Sync>;
+
 pub trait Discovery : Send + Sync {
     fn next(&self) -> Option<SocketAddr>;
+
+    fn reset(&self) {}
+
+    fn set_peer_lost_handler(&self, _: PeerLostHandler) {}
 }
@@ -0,0 +1,302 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+extern crate hyper;
+
+use std::cmp;
+use std::io::{self, Read};
+use std::net::SocketAddr;
+use std::result;
+
+use rustc_serialize::json::{self, Json};
+use time::Duration;
+
+use self::hyper::header::Headers;
+use self::hyper::status::StatusCode;
+
+use super::{Discovery, PeerLostHandler};
+use super::registry::{self, Registry};
+use util::time::to_std_duration;
+
+pub struct Etcd {
+    registry: Registry<Store>,
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    Hyper(hyper::Error),
+    Io(io::Error),
+    Json(json::ParserError),
+    UnexpectedStatus(StatusCode),
+}
+
+struct Store {
+    endpoint: String,
+    prefix: String,
+    key: String,
+    public_address: SocketAddr,
+    ttl: Duration,
+}
+
+impl Etcd {
+    pub fn new(endpoint: &str,
+               prefix: &str,
+               public_address: SocketAddr,
+               ttl: Duration)
+               -> Result<Self> {
+        let endpoint = endpoint.trim_right_matches('/').to_string();
+        let prefix = format!("/{}", prefix.trim_matches('/'));
+        let key = format!("{}/{}", prefix, public_address);
+
+        let store = Store {
+            endpoint: endpoint,
+            prefix: prefix,
+            key: key,
+            public_address: public_address,
+            ttl: ttl,
+        };
+        Ok(Etcd { registry: try!(Registry::start("etcd", store, ttl)) })
+    }
+}
+
+impl Discovery for Etcd {
+    fn next(&self) -> Option<SocketAddr> {
+        self.registry.next()
+    }
+
+    fn set_peer_lost_handler(&self, handler: PeerLostHandler) {
+        self.registry.set_peer_lost_handler(handler);
+    }
+}
+
+impl registry::Backend for Store {
+    type Error = Error;
+
+    fn register(&self) -> Result<()> {
+        let mut headers = Headers::new();
+        headers.set_raw("Content-Type",
+                        vec![b"application/x-www-form-urlencoded".to_vec()]);
+        let body = format!("value={}&ttl={}",
+                           self.public_address,
+                           cmp::max(1, self.ttl.num_seconds()));
+
+        let response = try!(hyper::Client::new()
+                                .put(&format!("{}/v2/keys{}", self.endpoint, self.key))
+                                .headers(headers)
+                                .body(&body[..])
+                                .send());
+        match response.status {
+            StatusCode::Ok | StatusCode::Created => Ok(()),
+            status => Err(Error::UnexpectedStatus(status)),
+        }
+    }
+
+    fn deregister(&self) -> Result<()> {
+        let response = try!(hyper::Client::new()
+                                .delete(&format!("{}/v2/keys{}", self.endpoint, self.key))
+                                .send());
+        match response.status {
+            StatusCode::Ok | StatusCode::NotFound => Ok(()),
+            status => Err(Error::UnexpectedStatus(status)),
+        }
+    }
+
+    fn fetch(&self) -> Result<Vec<SocketAddr>> {
+        let mut response = try!(hyper::Client::new()
+                                    .get(&format!("{}/v2/keys{}?recursive=true",
+                                                  self.endpoint,
+                                                  self.prefix))
+                                    .send());
+        match response.status {
+            StatusCode::Ok => {}
+            StatusCode::NotFound => return Ok(Vec::new()),
+            status => return Err(Error::UnexpectedStatus(status)),
+        }
+
+        let mut content = String::new();
+        try!(response.read_to_string(&mut content));
+        Ok(try!(parse_addresses(&content))
+               .into_iter()
+               .filter(|address| *address != self.public_address)
+               .collect())
+    }
+
+    fn watch(&self, timeout: Duration) -> Result<()> {
+        let mut client = hyper::Client::new();
+        client.set_read_timeout(Some(to_std_duration(timeout)));
+        let result = client.get(&format!("{}/v2/keys{}?wait=true&recursive=true",
+                                         self.endpoint,
+                                         self.prefix))
+                           .send();
+        let mut response = match result {
+            Ok(response) => response,
+            Err(hyper::Error::Io(ref error)) if is_timeout(error) => return Ok(()),
+            Err(error) => return Err(Error::from(error)),
+        };
+        let mut content = String::new();
+        match response.read_to_string(&mut content) {
+            Err(ref error) if is_timeout(error) => return Ok(()),
+            result => try!(result),
+        };
+        match response.status {
+            StatusCode::Ok => Ok(()),
+            status => Err(Error::UnexpectedStatus(status)),
+        }
+    }
+}
+
+impl From<hyper::Error> for Error {
+    fn from(error: hyper::Error) -> Self {
+        Error::Hyper(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<json::ParserError> for Error {
+    fn from(error: json::ParserError) -> Self {
+        Error::Json(error)
+    }
+}
+
+fn is_timeout(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::WouldBlock || error.kind() == io::ErrorKind::TimedOut
+}
+
+fn parse_addresses(content: &str) -> Result<Vec<SocketAddr>> {
+    let json = try!(Json::from_str(content));
+    let nodes = match json.find_path(&["node", "nodes"]).and_then(|nodes| nodes.as_array()) {
+        Some(nodes) => nodes,
+        None => return Ok(Vec::new()),
+    };
+
+    Ok(nodes.iter()
+            .filter_map(|node| node.find("value").and_then(|value| value.as_string()))
+            .filter_map(|value| value.parse::<SocketAddr>().ok())
+            .collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Read;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use time::{Duration, SteadyTime};
+    use discovery::Discovery;
+    use util::time::to_std_duration;
+    use super::{Etcd, hyper, parse_addresses};
+
+    #[test]
+    fn watch_prefix_and_refresh_registration() {
+        let values = Arc::new(Mutex::new(Vec::<String>::new()));
+        let requests = Arc::new(Mutex::new(Vec::<String>::new()));
+
+        let values_clone = values.clone();
+        let requests_clone = requests.clone();
+        let mut listening = hyper::Server::http("127.0.0.1:0")
+                                .unwrap()
+                                .handle(move |mut request: hyper::server::Request,
+                                              response: hyper::server::Response| {
+                                    let mut body = String::new();
+                                    request.read_to_string(&mut body).unwrap();
+                                    let target = format!("{} {}", request.method, request.uri);
+                                    let watch = target.contains("wait=true");
+                                    requests_clone.lock().unwrap().push(target);
+                                    if watch {
+                                        thread::sleep(to_std_duration(Duration::milliseconds(20)));
+                                    }
+
+                                    let nodes = values_clone.lock()
+                                                            .unwrap()
+                                                            .iter()
+                                                            .map(|value| {
+                                                                format!("{{\"value\":\"{}\"}}",
+                                                                        value)
+                                                            })
+                                                            .collect::<Vec<_>>();
+                                    let content = format!("{{\"node\":{{\"nodes\":[{}]}}}}",
+                                                          nodes.join(","));
+                                    response.send(content.as_bytes()).unwrap();
+                                })
+                                .unwrap();
+
+        let peer = "127.0.0.1:3002".parse::<SocketAddr>().unwrap();
+        let etcd = Etcd::new(&format!("http://{}", listening.socket),
+                             "delix",
+                             "127.0.0.1:3001".parse::<SocketAddr>().unwrap(),
+                             Duration::milliseconds(100))
+                       .unwrap();
+        assert_eq!(None, etcd.next());
+
+        let lost = Arc::new(Mutex::new(Vec::new()));
+        let lost_clone = lost.clone();
+        etcd.set_peer_lost_handler(Box::new(move |address| {
+            lost_clone.lock().unwrap().push(address);
+        }));
+
+        values.lock().unwrap().push(peer.to_string());
+        wait_until(|| etcd.next() == Some(peer));
+        values.lock().unwrap().clear();
+        wait_until(|| *lost.lock().unwrap() == vec![peer]);
+        wait_until(|| {
+            requests.lock()
+                    .unwrap()
+                    .iter()
+                    .filter(|request| *request == "PUT /v2/keys/delix/127.0.0.1:3001")
+                    .count() > 1
+        });
+
+        drop(etcd);
+        assert_eq!(Some("DELETE /v2/keys/delix/127.0.0.1:3001"),
+                   requests.lock().unwrap().last().map(String::as_str));
+
+        listening.close().unwrap();
+    }
+
+    #[test]
+    fn parse_addresses_of_prefix() {
+        let content = r#"{"action":"get","node":{"key":"/delix/nodes","dir":true,"nodes":[
+            {"key":"/delix/nodes/127.0.0.1:3001","value":"127.0.0.1:3001"},
+            {"key":"/delix/nodes/127.0.0.1:3002","value":"127.0.0.1:3002"},
+            {"key":"/delix/nodes/invalid","value":"invalid"}]}}"#;
+
+        assert_eq!(vec!["127.0.0.1:3001".parse::<SocketAddr>().unwrap(),
+                        "127.0.0.1:3002".parse::<SocketAddr>().unwrap()],
+                   parse_addresses(content).unwrap());
+    }
+
+    #[test]
+    fn parse_addresses_of_empty_prefix() {
+        let content = r#"{"action":"get","node":{"key":"/delix/nodes","dir":true}}"#;
+
+        assert!(parse_addresses(content).unwrap().is_empty());
+    }
+
+    fn wait_until<F: Fn() -> bool>(f: F) {
+        let deadline = SteadyTime::now() + Duration::seconds(5);
+        while !f() {
+            assert!(SteadyTime::now() < deadline, "condition not met in time");
+            thread::sleep(to_std_duration(Duration::milliseconds(10)));
+        }
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::fs;
+use std::io::{self, Read};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use time::Duration;
+
+use super::{Discovery, PeerLostHandler};
+use super::addresses;
+use util::resolve;
+use util::thread::supervisor;
+use util::time::to_std_duration;
+
+pub struct File {
+    inner: Arc<Inner>,
+    current_index: RwLock<usize>,
+    running: Arc<AtomicBool>,
+}
+
+struct Inner {
+    path: PathBuf,
+    public_address: SocketAddr,
+    content: RwLock<String>,
+    addresses: RwLock<Vec<SocketAddr>>,
+    peer_lost_handler: RwLock<Option<PeerLostHandler>>,
+}
+
+impl File {
+    pub fn new<P: AsRef<Path>>(path: P,
+                               public_address: SocketAddr,
+                               poll_interval: Duration)
+                               -> io::Result<Self> {
+        let inner = Arc::new(Inner {
+            path: path.as_ref().to_path_buf(),
+            public_address: public_address,
+            content: RwLock::new(String::new()),
+            addresses: RwLock::new(Vec::new()),
+            peer_lost_handler: RwLock::new(None),
+        });
+        try!(inner.reload());
+        let running = Arc::new(AtomicBool::new(true));
+
+        {
+            let inner = inner.clone();
+            let running = running.clone();
+            supervisor().spawn_restartable("file discovery",
+                                           Duration::seconds(1),
+                                           move || {
+                while running.load(Ordering::SeqCst) {
+                    thread::sleep(to_std_duration(poll_interval));
+                    if !running.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Err(error) = inner.reload() {
+                        error!("{}: failed to reload {}: {:?}",
+                               inner.public_address,
+                               inner.path.display(),
+                               error);
+                    }
+                }
+            });
+        }
+
+        Ok(File {
+            inner: inner,
+            current_index: RwLock::new(0),
+            running: running,
+        })
+    }
+}
+
+impl Discovery for File {
+    fn next(&self) -> Option<SocketAddr> {
+        let addresses = self.inner.addresses.read().unwrap();
+        let mut current_index = self.current_index.write().unwrap();
+
+        if *current_index >= addresses.len() {
+            *current_index = 0;
+        }
+        let result = addresses.get(*current_index);
+        *current_index += 1;
+        result.map(|address| *address)
+    }
+
+    fn set_peer_lost_handler(&self, handler: PeerLostHandler) {
+        *self.inner.peer_lost_handler.write().unwrap() = Some(handler);
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        *self.inner.peer_lost_handler.write().unwrap() = None;
+    }
+}
+
+impl Inner {
+    fn reload(&self) -> io::Result<()> {
+        let mut content = String::new();
+        try!(try!(fs::File::open(&self.path)).read_to_string(&mut content));
+
+        if *self.content.read().unwrap() == content {
+            return Ok(());
+        }
+
+        let addresses = parse_addresses(&content)
+                            .into_iter()
+                            .filter(|address| *address != self.public_address)
+                            .collect::<Vec<SocketAddr>>();
+        info!("{}: loaded {} addresses from {}",
+              self.public_address,
+              addresses.len(),
+              self.path.display());
+
+        addresses::replace(&self.addresses, addresses, &self.peer_lost_handler);
+        *self.content.write().unwrap() = content;
+        Ok(())
+    }
+}
+
+fn parse_addresses(content: &str) -> Vec<SocketAddr> {
+    content.lines()
+           .map(|line| line.split('#').next().unwrap_or("").trim())
+           .filter(|line| !line.is_empty())
+           .filter_map(|line| {
+               match resolve::socket_address(line) {
+                   Ok(address) => Some(address),
+                   Err(error) => {
+                       error!("skipping address [{}]: {:?}", line, error);
+                       None
+                   }
+               }
+           })
+           .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env;
+    use std::fs;
+    use std::io::Write;
+    use std::net::SocketAddr;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use rand;
+    use time::Duration;
+    use super::{File, parse_addresses};
+    use super::super::Discovery;
+
+    #[test]
+    fn parse_addresses_with_comments() {
+        let content = "# peers\n127.0.0.1:3001\n\n  127.0.0.1:3002  # second\n";
+
+        assert_eq!(vec!["127.0.0.1:3001".parse::<SocketAddr>().unwrap(),
+                        "127.0.0.1:3002".parse::<SocketAddr>().unwrap()],
+                   parse_addresses(content));
+    }
+
+    #[test]
+    fn reload_on_change() {
+        let path = env::temp_dir().join(format!("delix-file-discovery-test-{}",
+                                                rand::random::<u64>()));
+        write(&path, "127.0.0.1:3001\n127.0.0.1:3002\n");
+
+        let file = File::new(&path,
+                             "127.0.0.1:3000".parse::<SocketAddr>().unwrap(),
+                             Duration::hours(1))
+                       .unwrap();
+        let lost = Arc::new(Mutex::new(Vec::new()));
+        {
+            let lost = lost.clone();
+            file.set_peer_lost_handler(Box::new(move |address| {
+                lost.lock().unwrap().push(address);
+            }));
+        }
+        assert_eq!(Some("127.0.0.1:3001".parse::<SocketAddr>().unwrap()), file.next());
+
+        write(&path, "127.0.0.1:3003\n127.0.0.1:3000\n");
+        file.inner.reload().unwrap();
+
+        assert_eq!(Some("127.0.0.1:3003".parse::<SocketAddr>().unwrap()), file.next());
+        assert_eq!(Some("127.0.0.1:3003".parse::<SocketAddr>().unwrap()), file.next());
+        assert_eq!(vec!["127.0.0.1:3001".parse::<SocketAddr>().unwrap(),
+                        "127.0.0.1:3002".parse::<SocketAddr>().unwrap()],
+                   *lost.lock().unwrap());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    fn write(path: &Path, content: &str) {
+        fs::File::create(path).unwrap().write_all(content.as_bytes()).unwrap();
+    }
+}
@@ -15,9 +15,22 @@ limitations under the License.
 */
 
 pub mod discovery;
+mod addresses;
+mod codec;
+pub mod consul;
+pub mod etcd;
 mod constant;
+mod dht;
+mod file;
 mod multicast;
+mod registry;
+pub mod swim;
 
-pub use self::discovery::Discovery;
+pub use self::discovery::{Discovery, PeerLostHandler};
 pub use self::constant::Constant;
+pub use self::consul::Consul;
+pub use self::dht::Dht;
+pub use self::etcd::Etcd;
+pub use self::file::File;
 pub use self::multicast::Multicast;
+pub use self::swim::Swim;
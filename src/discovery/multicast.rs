@@ -13,20 +13,25 @@
 // limitations under the License.
 //
 
+extern crate libc;
 extern crate net2;
 
-use std::net::{self, Ipv4Addr, SocketAddr};
+use std::cmp;
+use std::ffi::CString;
+use std::net::{self, Ipv4Addr, SocketAddr, SocketAddrV6};
 use std::io;
-use std::thread;
-use std::sync::{Mutex, mpsc};
+use std::sync::{Arc, Mutex, mpsc};
 
 use time::Duration;
 
 use self::net2::UdpSocketExt;
 
 use super::Discovery;
+use super::codec::{self, Reader};
+use util::clock::{self, Clock};
+use util::thread::supervisor;
 
-const PACKET_SIZE: usize = 16;
+const PACKET_SIZE: usize = 32;
 
 type Packet = [u8; PACKET_SIZE];
 
@@ -36,37 +41,85 @@ enum Kind {
     Tell,
 }
 
+enum Event {
+    Reply(SocketAddr),
+    Resend,
+    Timeout,
+}
+
 pub struct Multicast {
     udp_socket: net::UdpSocket,
     multicast_address: SocketAddr,
     public_address: SocketAddr,
     reply_timeout: Duration,
-    tx: Mutex<mpsc::Sender<mpsc::Sender<SocketAddr>>>,
+    probe_interval: Option<Duration>,
+    clock: Arc<Clock>,
+    tx: Mutex<mpsc::Sender<mpsc::Sender<Event>>>,
 }
 
 impl Multicast {
     pub fn new(interface_address: SocketAddr,
                multicast_address: SocketAddr,
+               interface_name: Option<&str>,
                public_address: SocketAddr,
                reply_timeout: Duration)
                -> io::Result<Self> {
-        let any_ip = Ipv4Addr::new(0, 0, 0, 0);
+        Self::with_clock(interface_address,
+                         multicast_address,
+                         interface_name,
+                         public_address,
+                         reply_timeout,
+                         None,
+                         Arc::new(clock::System))
+    }
+
+    /// Builds a discovery that waits for replies on the given clock. With a probe interval, the
+    /// question is repeated in that interval until a reply arrives or the reply timeout is over.
+    pub fn with_clock(interface_address: SocketAddr,
+                      multicast_address: SocketAddr,
+                      interface_name: Option<&str>,
+                      public_address: SocketAddr,
+                      reply_timeout: Duration,
+                      probe_interval: Option<Duration>,
+                      clock: Arc<Clock>)
+                      -> io::Result<Self> {
+        let interface_index = match interface_name {
+            Some(name) => try!(interface_index(name)),
+            None => 0,
+        };
 
         let udp_socket = try!(net::UdpSocket::bind(interface_address));
 
-        match multicast_address {
-            SocketAddr::V4(ref address) => {
+        let multicast_address = match multicast_address {
+            SocketAddr::V4(address) => {
+                let interface_ip = match interface_address {
+                    SocketAddr::V4(interface_address) => *interface_address.ip(),
+                    SocketAddr::V6(_) => Ipv4Addr::new(0, 0, 0, 0),
+                };
                 try!(udp_socket.set_multicast_loop_v4(true));
-                try!(udp_socket.join_multicast_v4(address.ip(), &any_ip));
+                try!(udp_socket.join_multicast_v4(address.ip(), &interface_ip));
+                SocketAddr::V4(address)
             }
-            SocketAddr::V6(_) => panic!("ip v6 is not implemented yet"),
-        }
+            SocketAddr::V6(address) => {
+                if !address.ip().is_multicast() {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                              format!("{} is not a multicast address",
+                                                      address.ip())));
+                }
+                try!(udp_socket.set_multicast_loop_v6(true));
+                try!(udp_socket.join_multicast_v6(address.ip(), interface_index));
+                SocketAddr::V6(SocketAddrV6::new(*address.ip(),
+                                                 address.port(),
+                                                 0,
+                                                 interface_index))
+            }
+        };
 
         let udp_socket_clone = udp_socket.try_clone().unwrap();
         let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
+        supervisor().spawn("multicast discovery", move || {
             loop {
-                match receive_packet(&udp_socket_clone) {
+                match receive_packet(&udp_socket_clone, interface_index) {
                     Ok(tuple) => {
                         match tuple {
                             (Kind::Ask, address, sender_address) if address != public_address => {
@@ -77,11 +130,18 @@ impl Multicast {
                                     .unwrap();
                             }
                             (Kind::Tell, address, _) => {
-                                match rx.try_recv() as Result<mpsc::Sender<SocketAddr>,
-                                                              mpsc::TryRecvError> {
-                                    Ok(tx) => tx.send(address).unwrap(),
-                                    Err(mpsc::TryRecvError::Empty) => {}
-                                    Err(error) => panic!(error),
+                                // skip the probes that are already over.
+                                loop {
+                                    match rx.try_recv() as Result<mpsc::Sender<Event>,
+                                                                  mpsc::TryRecvError> {
+                                        Ok(tx) => {
+                                            if tx.send(Event::Reply(address)).is_ok() {
+                                                break;
+                                            }
+                                        }
+                                        Err(mpsc::TryRecvError::Empty) => break,
+                                        Err(error) => panic!(error),
+                                    }
                                 }
                             }
                             (kind, address, sender_address) => {
@@ -105,28 +165,62 @@ impl Multicast {
             multicast_address: multicast_address,
             public_address: public_address,
             reply_timeout: reply_timeout,
+            probe_interval: probe_interval,
+            clock: clock,
             tx: Mutex::new(tx),
         })
     }
+
+    /// Asks the group once and returns the first reply, or `None` if none arrived within the
+    /// reply timeout.
+    pub fn probe(&self) -> Option<SocketAddr> {
+        let (tx, rx) = mpsc::channel();
+        self.tx.lock().unwrap().send(tx.clone()).unwrap();
+
+        let reply_timeout = self.reply_timeout;
+        let probe_interval = self.probe_interval;
+        let clock = self.clock.clone();
+        supervisor().spawn("multicast reply timeout", move || {
+            let mut waited = Duration::zero();
+            loop {
+                let step = match probe_interval {
+                    Some(probe_interval) if probe_interval > Duration::zero() => {
+                        cmp::min(probe_interval, reply_timeout - waited)
+                    }
+                    _ => reply_timeout - waited,
+                };
+                clock.sleep(step);
+                waited = waited + step;
+                if waited >= reply_timeout {
+                    let _ = tx.send(Event::Timeout);
+                    return;
+                }
+                // the probe is over, if the receiver is gone.
+                if tx.send(Event::Resend).is_err() {
+                    return;
+                }
+            }
+        });
+
+        loop {
+            send_packet(&self.udp_socket,
+                        self.multicast_address,
+                        Kind::Ask,
+                        self.public_address)
+                .unwrap();
+
+            match rx.recv().unwrap() {
+                Event::Reply(address) => return Some(address),
+                Event::Resend => continue,
+                Event::Timeout => return None,
+            }
+        }
+    }
 }
 
 impl Discovery for Multicast {
     fn next(&self) -> Option<SocketAddr> {
-        let (tx, rx) = mpsc::channel();
-        self.tx.lock().unwrap().send(tx).unwrap();
-
-        send_packet(&self.udp_socket,
-                    self.multicast_address,
-                    Kind::Ask,
-                    self.public_address)
-            .unwrap();
-
-        thread::sleep(::std::time::Duration::from_millis(self.reply_timeout.num_milliseconds() as u64));
-        match rx.try_recv() {
-            Ok(address) => Some(address),
-            Err(mpsc::TryRecvError::Empty) => None,
-            Err(error) => panic!(error),
-        }
+        self.probe()
     }
 }
 
@@ -140,60 +234,96 @@ fn send_packet(udp_socket: &net::UdpSocket,
     Ok(())
 }
 
-fn receive_packet(udp_socket: &net::UdpSocket) -> io::Result<(Kind, SocketAddr, SocketAddr)> {
+fn receive_packet(udp_socket: &net::UdpSocket,
+                  interface_index: u32)
+                  -> io::Result<(Kind, SocketAddr, SocketAddr)> {
     let mut packet: Packet = [0; PACKET_SIZE];
     let (_, sender_address) = try!(udp_socket.recv_from(&mut packet));
-    let (kind, address) = unpack(&packet);
-    Ok((kind, address, sender_address))
+    let (kind, address) = try!(unpack(&packet));
+    Ok((kind, with_scope(address, interface_index), sender_address))
 }
 
 fn pack(kind: Kind, address: SocketAddr) -> Packet {
-    let mut p: Packet = [0; PACKET_SIZE];
-
-    p[0] = match kind {
+    let mut bytes = Vec::with_capacity(PACKET_SIZE);
+    bytes.push(match kind {
         Kind::Ask => 0,
         Kind::Tell => 1,
-    };
+    });
+    codec::encode_address(&mut bytes, address);
 
-    if let SocketAddr::V4(address_v4) = address {
-        let ip_bytes = address_v4.ip().octets();
-        let port = address_v4.port();
-        p[1] = ip_bytes[0];
-        p[2] = ip_bytes[1];
-        p[3] = ip_bytes[2];
-        p[4] = ip_bytes[3];
-        p[5] = ((port & 0xff00) >> 8) as u8;
-        p[6] = ((port & 0x00ff) >> 0) as u8;
+    let mut p: Packet = [0; PACKET_SIZE];
+    for (index, byte) in bytes.into_iter().enumerate() {
+        p[index] = byte;
     }
-
     p
 }
 
-fn unpack(p: &Packet) -> (Kind, SocketAddr) {
-    let kind = match p[0] {
+fn unpack(p: &Packet) -> io::Result<(Kind, SocketAddr)> {
+    let mut reader = Reader::new(p);
+    let kind = match try!(reader.u8()) {
         0 => Kind::Ask,
         1 => Kind::Tell,
-        _ => unreachable!(),
+        _ => return Err(codec::invalid_data("invalid packet kind")),
     };
-    let ip_address = Ipv4Addr::new(p[1], p[2], p[3], p[4]);
-    let port = ((p[5] as u16) << 8) | ((p[6] as u16) << 0);
-    (kind,
-     SocketAddr::V4(net::SocketAddrV4::new(ip_address, port)))
+    Ok((kind, try!(reader.address())))
+}
+
+fn with_scope(address: SocketAddr, interface_index: u32) -> SocketAddr {
+    match address {
+        SocketAddr::V6(address) if address.scope_id() == 0 &&
+                                   (address.ip().segments()[0] & 0xffc0) == 0xfe80 => {
+            SocketAddr::V6(SocketAddrV6::new(*address.ip(),
+                                             address.port(),
+                                             address.flowinfo(),
+                                             interface_index))
+        }
+        address => address,
+    }
+}
+
+fn interface_index(name: &str) -> io::Result<u32> {
+    let name = try!(CString::new(name).map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidInput, "invalid interface name")
+    }));
+    match unsafe { libc::if_nametoindex(name.as_ptr()) } {
+        0 => Err(io::Error::last_os_error()),
+        index => Ok(index),
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::thread;
     use time::Duration;
-    use super::Multicast;
+    use super::{Kind, Multicast, pack, unpack, with_scope};
     use super::super::Discovery;
+    use util::clock::Mock;
+
+    #[test]
+    fn packet_with_ip_v6_address() {
+        let address = "[fe80::1]:3001".parse::<SocketAddr>().unwrap();
+        let (kind, unpacked_address) = unpack(&pack(Kind::Tell, address)).unwrap();
+
+        match kind {
+            Kind::Tell => {}
+            kind => panic!("unexpected kind {:?}", kind),
+        }
+        assert_eq!(address, unpacked_address);
+        match with_scope(unpacked_address, 2) {
+            SocketAddr::V6(address) => assert_eq!(2, address.scope_id()),
+            address => panic!("unexpected address {}", address),
+        }
+    }
 
     #[test]
     fn discovery_with_one_nodes() {
         let address = "127.0.0.1:3001".parse::<SocketAddr>().unwrap();
         let discovery = Multicast::new("0.0.0.0:4001".parse::<SocketAddr>().unwrap(),
                                        "224.0.0.1:4002".parse::<SocketAddr>().unwrap(),
+                                       None,
                                        address,
                                        Duration::milliseconds(500))
                             .unwrap();
@@ -206,6 +336,7 @@ mod tests {
         let address_one = "127.0.0.1:3011".parse::<SocketAddr>().unwrap();
         let discovery_one = Multicast::new("0.0.0.0:4011".parse::<SocketAddr>().unwrap(),
                                            "224.0.0.1:4012".parse::<SocketAddr>().unwrap(),
+                                           None,
                                            address_one,
                                            Duration::milliseconds(500))
                                 .unwrap();
@@ -213,6 +344,7 @@ mod tests {
         let address_two = "127.0.0.1:3012".parse::<SocketAddr>().unwrap();
         let discovery_two = Multicast::new("0.0.0.0:4012".parse::<SocketAddr>().unwrap(),
                                            "224.0.0.1:4011".parse::<SocketAddr>().unwrap(),
+                                           None,
                                            address_two,
                                            Duration::milliseconds(500))
                                 .unwrap();
@@ -226,6 +358,7 @@ mod tests {
         let address_one = "127.0.0.1:3021".parse::<SocketAddr>().unwrap();
         let discovery_one = Multicast::new("0.0.0.0:4021".parse::<SocketAddr>().unwrap(),
                                            "224.0.0.2:4022".parse::<SocketAddr>().unwrap(),
+                                           None,
                                            address_one,
                                            Duration::milliseconds(500))
                                 .unwrap();
@@ -233,6 +366,7 @@ mod tests {
         let address_two = "127.0.0.1:3022".parse::<SocketAddr>().unwrap();
         let discovery_two = Multicast::new("0.0.0.0:4022".parse::<SocketAddr>().unwrap(),
                                            "224.0.0.2:4021".parse::<SocketAddr>().unwrap(),
+                                           None,
                                            address_two,
                                            Duration::milliseconds(500))
                                 .unwrap();
@@ -240,6 +374,7 @@ mod tests {
         let address_three = "127.0.0.1:3023".parse::<SocketAddr>().unwrap();
         let discovery_three = Multicast::new("0.0.0.0:4023".parse::<SocketAddr>().unwrap(),
                                              "224.0.0.2:4021".parse::<SocketAddr>().unwrap(),
+                                             None,
                                              address_three,
                                              Duration::milliseconds(500))
                                   .unwrap();
@@ -249,4 +384,59 @@ mod tests {
         assert_eq!(Some(address_one), discovery_three.next());
     }
 
+    #[test]
+    fn probe_times_out_on_clock() {
+        let clock = Arc::new(Mock::new());
+        let discovery = Arc::new(Multicast::with_clock("0.0.0.0:4031"
+                                                           .parse::<SocketAddr>()
+                                                           .unwrap(),
+                                                       "224.0.0.3:4032"
+                                                           .parse::<SocketAddr>()
+                                                           .unwrap(),
+                                                       None,
+                                                       "127.0.0.1:3031"
+                                                           .parse::<SocketAddr>()
+                                                           .unwrap(),
+                                                       Duration::milliseconds(500),
+                                                       None,
+                                                       clock.clone())
+                                     .unwrap());
+
+        let discovery_clone = discovery.clone();
+        let join_handle = thread::spawn(move || discovery_clone.probe());
+
+        clock.wait_for_sleepers(1);
+        clock.advance(Duration::milliseconds(500));
+        assert_eq!(None, join_handle.join().unwrap());
+    }
+
+    #[test]
+    fn probe_returns_first_reply() {
+        let clock = Arc::new(Mock::new());
+
+        let address_one = "127.0.0.1:3041".parse::<SocketAddr>().unwrap();
+        let discovery_one = Multicast::with_clock("0.0.0.0:4041".parse::<SocketAddr>().unwrap(),
+                                                  "224.0.0.3:4042".parse::<SocketAddr>().unwrap(),
+                                                  None,
+                                                  address_one,
+                                                  Duration::seconds(60),
+                                                  Some(Duration::milliseconds(10)),
+                                                  clock.clone())
+                                .unwrap();
+
+        let address_two = "127.0.0.1:3042".parse::<SocketAddr>().unwrap();
+        let discovery_two = Multicast::with_clock("0.0.0.0:4042".parse::<SocketAddr>().unwrap(),
+                                                  "224.0.0.3:4041".parse::<SocketAddr>().unwrap(),
+                                                  None,
+                                                  address_two,
+                                                  Duration::seconds(60),
+                                                  Some(Duration::milliseconds(10)),
+                                                  clock.clone())
+                                .unwrap();
+
+        // the clock never moves, so the probes can only end with a reply.
+        assert_eq!(Some(address_two), discovery_one.probe());
+        assert_eq!(Some(address_one), discovery_two.probe());
+    }
+
 }
@@ -0,0 +1,169 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::cmp;
+use std::fmt;
+use std::net::SocketAddr;
+use std::result;
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::thread;
+
+use time::Duration;
+
+use super::{Discovery, PeerLostHandler};
+use super::addresses;
+use util::thread::supervisor;
+use util::time::to_std_duration;
+
+const MAX_WATCH_TIMEOUT_S: i64 = 5;
+
+/// A store in which the nodes register their public addresses and look up the ones of the
+/// others.
+pub trait Backend: Send + Sync + 'static {
+    type Error: fmt::Debug;
+
+    /// Registers the node or renews its registration.
+    fn register(&self) -> result::Result<(), Self::Error>;
+
+    /// Removes the registration of the node.
+    fn deregister(&self) -> result::Result<(), Self::Error>;
+
+    /// Returns the addresses of all other registered nodes.
+    fn fetch(&self) -> result::Result<Vec<SocketAddr>, Self::Error>;
+
+    /// Blocks until the registrations change or the timeout expires.
+    fn watch(&self, timeout: Duration) -> result::Result<(), Self::Error>;
+}
+
+/// Renews the registration of the node every half ttl and keeps the addresses of the other nodes
+/// up to date. Dropping the registry stops and joins both threads and removes the registration.
+pub struct Registry<B: Backend> {
+    name: &'static str,
+    backend: Arc<B>,
+    addresses: Arc<RwLock<Vec<SocketAddr>>>,
+    current_index: RwLock<usize>,
+    peer_lost_handler: Arc<RwLock<Option<PeerLostHandler>>>,
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    threads: Mutex<Vec<thread::JoinHandle<()>>>,
+}
+
+impl<B: Backend> Registry<B> {
+    pub fn start(name: &'static str,
+                 backend: B,
+                 ttl: Duration)
+                 -> result::Result<Registry<B>, B::Error> {
+        try!(backend.register());
+        let backend = Arc::new(backend);
+        let addresses = Arc::new(RwLock::new(try!(backend.fetch())));
+        let peer_lost_handler = Arc::new(RwLock::new(None));
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let watch_timeout = cmp::min(ttl / 2, Duration::seconds(MAX_WATCH_TIMEOUT_S));
+
+        let mut threads = Vec::new();
+        {
+            let backend = backend.clone();
+            let stop = stop.clone();
+            threads.push(supervisor().spawn_restartable(&format!("{} registration", name),
+                                                        Duration::seconds(1),
+                                                        move || {
+                while !wait_for_stop(&stop, ttl / 2) {
+                    if let Err(error) = backend.register() {
+                        error!("failed to refresh {} registration: {:?}", name, error);
+                    }
+                }
+            }));
+        }
+        {
+            let backend = backend.clone();
+            let addresses = addresses.clone();
+            let peer_lost_handler = peer_lost_handler.clone();
+            let stop = stop.clone();
+            threads.push(supervisor().spawn_restartable(&format!("{} watch", name),
+                                                        Duration::seconds(1),
+                                                        move || {
+                loop {
+                    if let Err(error) = backend.watch(watch_timeout) {
+                        error!("failed to watch {} registrations: {:?}", name, error);
+                        if wait_for_stop(&stop, watch_timeout) {
+                            return;
+                        }
+                    }
+                    if *stop.0.lock().unwrap() {
+                        return;
+                    }
+                    match backend.fetch() {
+                        Ok(fetched) => addresses::replace(&addresses, fetched, &peer_lost_handler),
+                        Err(error) => {
+                            error!("failed to fetch {} registrations: {:?}", name, error)
+                        }
+                    }
+                }
+            }));
+        }
+
+        Ok(Registry {
+            name: name,
+            backend: backend,
+            addresses: addresses,
+            current_index: RwLock::new(0),
+            peer_lost_handler: peer_lost_handler,
+            stop: stop,
+            threads: Mutex::new(threads),
+        })
+    }
+}
+
+impl<B: Backend> Discovery for Registry<B> {
+    fn next(&self) -> Option<SocketAddr> {
+        let addresses = self.addresses.read().unwrap();
+        let mut current_index = self.current_index.write().unwrap();
+
+        if *current_index >= addresses.len() {
+            *current_index = 0;
+        }
+        let result = addresses.get(*current_index);
+        *current_index += 1;
+        result.map(|address| *address)
+    }
+
+    fn set_peer_lost_handler(&self, handler: PeerLostHandler) {
+        *self.peer_lost_handler.write().unwrap() = Some(handler);
+    }
+}
+
+impl<B: Backend> Drop for Registry<B> {
+    fn drop(&mut self) {
+        *self.stop.0.lock().unwrap() = true;
+        self.stop.1.notify_all();
+        *self.peer_lost_handler.write().unwrap() = None;
+
+        // the threads are joined first, so a late refresh can't re-create the registration.
+        for thread in self.threads.lock().unwrap().drain(..) {
+            thread.join().unwrap();
+        }
+        if let Err(error) = self.backend.deregister() {
+            error!("failed to remove {} registration: {:?}", self.name, error);
+        }
+    }
+}
+
+fn wait_for_stop(stop: &(Mutex<bool>, Condvar), timeout: Duration) -> bool {
+    let &(ref stopped, ref condvar) = stop;
+    let stopped = stopped.lock().unwrap();
+    if *stopped {
+        return true;
+    }
+    *condvar.wait_timeout(stopped, to_std_duration(timeout)).unwrap().0
+}
@@ -0,0 +1,683 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{self, SocketAddr};
+use std::sync::{Arc, Mutex, RwLock, mpsc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use rand::{self, Rng};
+use time::{self, Duration};
+
+use super::{Discovery, PeerLostHandler};
+use super::codec::{Reader, encode_address, encode_u32, invalid_data};
+use util::resolve::Pending;
+use util::thread::supervisor;
+use util::time::to_std_duration;
+
+const PACKET_SIZE: usize = 1400;
+const GOSSIP_TRANSMISSIONS: usize = 6;
+const MAXIMAL_UPDATES_PER_PACKET: usize = 16;
+
+pub struct Swim {
+    inner: Arc<Inner>,
+    running: Arc<AtomicBool>,
+    current_index: RwLock<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum State {
+    Alive,
+    Suspect,
+    Dead,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Update {
+    address: SocketAddr,
+    public_address: SocketAddr,
+    state: State,
+    incarnation: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Ping,
+    Ack,
+    PingReq(SocketAddr),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Packet {
+    kind: Kind,
+    sequence: u32,
+    sender: Update,
+    updates: Vec<Update>,
+}
+
+struct Member {
+    public_address: SocketAddr,
+    state: State,
+    incarnation: u32,
+    changed_at: time::Tm,
+}
+
+struct Inner {
+    address: SocketAddr,
+    public_address: SocketAddr,
+    udp_socket: net::UdpSocket,
+    incarnation: Mutex<u32>,
+    members: RwLock<HashMap<SocketAddr, Member>>,
+    gossip: Mutex<Vec<(Update, usize)>>,
+    pending: Mutex<HashMap<u32, mpsc::Sender<()>>>,
+    relays: Mutex<HashMap<u32, (SocketAddr, u32)>>,
+    next_sequence: Mutex<u32>,
+    peer_lost_handler: RwLock<Option<PeerLostHandler>>,
+    unresolved_seeds: Pending,
+}
+
+impl Swim {
+    pub fn new(address: SocketAddr,
+               public_address: SocketAddr,
+               seeds: Vec<SocketAddr>,
+               protocol_period: Duration,
+               ping_timeout: Duration,
+               suspicion_timeout: Duration,
+               indirect_checks: usize)
+               -> io::Result<Self> {
+        let ping_timeout = if ping_timeout < protocol_period {
+            ping_timeout
+        } else {
+            protocol_period / 2
+        };
+
+        let udp_socket = try!(net::UdpSocket::bind(address));
+        let inner = Arc::new(Inner {
+            address: address,
+            public_address: public_address,
+            udp_socket: udp_socket,
+            incarnation: Mutex::new(0),
+            members: RwLock::new(HashMap::new()),
+            gossip: Mutex::new(Vec::new()),
+            pending: Mutex::new(HashMap::new()),
+            relays: Mutex::new(HashMap::new()),
+            next_sequence: Mutex::new(0),
+            peer_lost_handler: RwLock::new(None),
+            unresolved_seeds: Pending::new(),
+        });
+        let running = Arc::new(AtomicBool::new(true));
+
+        inner.enqueue(inner.own_update());
+
+        let inner_clone = inner.clone();
+        let running_clone = running.clone();
+        supervisor().spawn("swim receiver", move || {
+            let mut buffer = [0u8; PACKET_SIZE];
+            while running_clone.load(Ordering::SeqCst) {
+                match inner_clone.udp_socket.recv_from(&mut buffer) {
+                    Ok((size, sender_address)) => {
+                        match decode(&buffer[..size]) {
+                            Ok(packet) => inner_clone.handle(packet),
+                            Err(error) => {
+                                debug!("{}: dropped packet from {}: {:?}",
+                                       inner_clone.address,
+                                       sender_address,
+                                       error)
+                            }
+                        }
+                    }
+                    Err(error) => error!("{}: receive error: {:?}", inner_clone.address, error),
+                }
+            }
+        });
+
+        for seed in seeds {
+            if seed != address {
+                let sequence = inner.next_sequence();
+                try!(inner.send(seed, Kind::Ping, sequence));
+            }
+        }
+
+        let inner_clone = inner.clone();
+        let running_clone = running.clone();
+        supervisor().spawn_restartable("swim protocol",
+                                       Duration::seconds(1),
+                                       move || {
+            let mut targets = Vec::new();
+            while running_clone.load(Ordering::SeqCst) {
+                let started_at = time::now_utc();
+
+                for seed in inner_clone.unresolved_seeds.resolve() {
+                    if seed != inner_clone.address {
+                        let sequence = inner_clone.next_sequence();
+                        if let Err(error) = inner_clone.send(seed, Kind::Ping, sequence) {
+                            debug!("{}: ping to seed {} failed: {:?}",
+                                   inner_clone.address,
+                                   seed,
+                                   error);
+                        }
+                    }
+                }
+
+                if targets.is_empty() {
+                    targets = inner_clone.probe_targets();
+                }
+                if let Some(target) = targets.pop() {
+                    inner_clone.probe(target, protocol_period, ping_timeout, indirect_checks);
+                }
+                inner_clone.expire_members(suspicion_timeout);
+
+                let elapsed = time::now_utc() - started_at;
+                if elapsed < protocol_period {
+                    thread::sleep(to_std_duration(protocol_period - elapsed));
+                }
+            }
+        });
+
+        Ok(Swim {
+            inner: inner,
+            running: running,
+            current_index: RwLock::new(0),
+        })
+    }
+
+    /// Adds seeds that couldn't be resolved yet. They are pinged once they resolve.
+    pub fn add_unresolved_seeds(&self, names: Vec<String>) {
+        self.inner.unresolved_seeds.add(names);
+    }
+
+    pub fn members(&self) -> Vec<(SocketAddr, State)> {
+        self.inner
+            .members
+            .read()
+            .unwrap()
+            .values()
+            .map(|member| (member.public_address, member.state))
+            .collect()
+    }
+}
+
+impl Discovery for Swim {
+    fn next(&self) -> Option<SocketAddr> {
+        let mut addresses = self.members()
+                                .into_iter()
+                                .filter(|&(_, state)| state == State::Alive)
+                                .map(|(public_address, _)| public_address)
+                                .collect::<Vec<_>>();
+        addresses.sort_by(|a, b| format!("{}", a).cmp(&format!("{}", b)));
+
+        let mut current_index = self.current_index.write().unwrap();
+        if *current_index >= addresses.len() {
+            *current_index = 0;
+        }
+        let result = addresses.get(*current_index).cloned();
+        *current_index += 1;
+        result
+    }
+
+    fn set_peer_lost_handler(&self, handler: PeerLostHandler) {
+        *self.inner.peer_lost_handler.write().unwrap() = Some(handler);
+    }
+}
+
+impl Drop for Swim {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        *self.inner.peer_lost_handler.write().unwrap() = None;
+        self.inner.leave();
+    }
+}
+
+impl Inner {
+    fn own_update(&self) -> Update {
+        Update {
+            address: self.address,
+            public_address: self.public_address,
+            state: State::Alive,
+            incarnation: *self.incarnation.lock().unwrap(),
+        }
+    }
+
+    fn next_sequence(&self) -> u32 {
+        let mut next_sequence = self.next_sequence.lock().unwrap();
+        *next_sequence = next_sequence.wrapping_add(1);
+        *next_sequence
+    }
+
+    fn send(&self, address: SocketAddr, kind: Kind, sequence: u32) -> io::Result<()> {
+        let packet = Packet {
+            kind: kind,
+            sequence: sequence,
+            sender: self.own_update(),
+            updates: self.take_gossip(),
+        };
+        try!(self.udp_socket.send_to(&encode(&packet), address));
+        Ok(())
+    }
+
+    fn enqueue(&self, update: Update) {
+        let mut gossip = self.gossip.lock().unwrap();
+        gossip.retain(|&(ref queued, _)| queued.address != update.address);
+        gossip.push((update, GOSSIP_TRANSMISSIONS));
+    }
+
+    fn take_gossip(&self) -> Vec<Update> {
+        let mut gossip = self.gossip.lock().unwrap();
+        let mut updates = Vec::new();
+        for &mut (ref update, ref mut transmissions) in gossip.iter_mut()
+                                                              .take(MAXIMAL_UPDATES_PER_PACKET) {
+            updates.push(*update);
+            *transmissions -= 1;
+        }
+        gossip.retain(|&(_, transmissions)| transmissions > 0);
+        updates
+    }
+
+    fn handle(&self, packet: Packet) {
+        let sender_address = packet.sender.address;
+        self.apply(packet.sender, true);
+        for update in packet.updates {
+            self.apply(update, false);
+        }
+
+        let result = match packet.kind {
+            Kind::Ping => self.send(sender_address, Kind::Ack, packet.sequence),
+            Kind::PingReq(target) => {
+                let sequence = self.next_sequence();
+                self.relays.lock().unwrap().insert(sequence, (sender_address, packet.sequence));
+                self.send(target, Kind::Ping, sequence)
+            }
+            Kind::Ack => {
+                let relay = self.relays.lock().unwrap().remove(&packet.sequence);
+                match relay {
+                    Some((requester, sequence)) => self.send(requester, Kind::Ack, sequence),
+                    None => {
+                        if let Some(tx) = self.pending.lock().unwrap().remove(&packet.sequence) {
+                            let _ = tx.send(());
+                        }
+                        Ok(())
+                    }
+                }
+            }
+        };
+        if let Err(error) = result {
+            error!("{}: failed to reply to {}: {:?}",
+                   self.address,
+                   sender_address,
+                   error);
+        }
+    }
+
+    /// Applies a membership update. A `direct` update comes from the member itself, which proves
+    /// that it's alive again even if its incarnation has been reset by a restart.
+    fn apply(&self, update: Update, direct: bool) {
+        if update.address == self.address {
+            if update.state != State::Alive {
+                // refute the suspicion by raising the own incarnation
+                {
+                    let mut incarnation = self.incarnation.lock().unwrap();
+                    if update.incarnation >= *incarnation {
+                        *incarnation = update.incarnation.wrapping_add(1);
+                    }
+                }
+                self.enqueue(self.own_update());
+            }
+            return;
+        }
+
+        let changed = {
+            let mut members = self.members.write().unwrap();
+            if !members.contains_key(&update.address) {
+                if update.state == State::Dead {
+                    false
+                } else {
+                    members.insert(update.address,
+                                   Member {
+                                       public_address: update.public_address,
+                                       state: update.state,
+                                       incarnation: update.incarnation,
+                                       changed_at: time::now_utc(),
+                                   });
+                    true
+                }
+            } else {
+                let member = members.get_mut(&update.address).unwrap();
+                let newer = update.incarnation > member.incarnation;
+                let apply = match update.state {
+                    State::Alive => newer || (direct && member.state == State::Dead),
+                    State::Suspect => {
+                        newer ||
+                        (update.incarnation == member.incarnation && member.state == State::Alive)
+                    }
+                    State::Dead => {
+                        member.state != State::Dead && update.incarnation >= member.incarnation
+                    }
+                };
+                if apply {
+                    member.public_address = update.public_address;
+                    member.state = update.state;
+                    member.incarnation = update.incarnation;
+                    member.changed_at = time::now_utc();
+                }
+                apply
+            }
+        };
+
+        if changed {
+            debug!("{}: member {} is {:?}",
+                   self.address,
+                   update.address,
+                   update.state);
+            self.enqueue(update);
+            if update.state == State::Dead {
+                if let Some(ref handler) = *self.peer_lost_handler.read().unwrap() {
+                    handler(update.public_address);
+                }
+            }
+        }
+    }
+
+    fn probe_targets(&self) -> Vec<SocketAddr> {
+        let mut targets = self.members
+                              .read()
+                              .unwrap()
+                              .iter()
+                              .filter(|&(_, member)| member.state != State::Dead)
+                              .map(|(address, _)| *address)
+                              .collect::<Vec<_>>();
+        rand::thread_rng().shuffle(&mut targets);
+        targets
+    }
+
+    fn probe(&self,
+             target: SocketAddr,
+             protocol_period: Duration,
+             ping_timeout: Duration,
+             indirect_checks: usize) {
+        let sequence = self.next_sequence();
+
+        let rx = self.expect_ack(sequence);
+        if let Err(error) = self.send(target, Kind::Ping, sequence) {
+            error!("{}: failed to ping {}: {:?}", self.address, target, error);
+        }
+        if rx.recv_timeout(to_std_duration(ping_timeout)).is_ok() {
+            return;
+        }
+
+        let mut helpers = self.probe_targets();
+        helpers.retain(|address| *address != target);
+        helpers.truncate(indirect_checks);
+
+        for helper in helpers {
+            if let Err(error) = self.send(helper, Kind::PingReq(target), sequence) {
+                error!("{}: failed to send ping request to {}: {:?}",
+                       self.address,
+                       helper,
+                       error);
+            }
+        }
+        let acked = rx.recv_timeout(to_std_duration(protocol_period - ping_timeout)).is_ok();
+        self.pending.lock().unwrap().remove(&sequence);
+        if acked {
+            return;
+        }
+
+        let update = self.members.read().unwrap().get(&target).map(|member| {
+            Update {
+                address: target,
+                public_address: member.public_address,
+                state: State::Suspect,
+                incarnation: member.incarnation,
+            }
+        });
+        if let Some(update) = update {
+            self.apply(update, false);
+        }
+    }
+
+    fn expect_ack(&self, sequence: u32) -> mpsc::Receiver<()> {
+        let (tx, rx) = mpsc::channel();
+        self.pending.lock().unwrap().insert(sequence, tx);
+        rx
+    }
+
+    /// Declares the suspects dead whose suspicion timed out and forgets the dead members after
+    /// another timeout, so a node that restarts on the same address is admitted again.
+    fn expire_members(&self, suspicion_timeout: Duration) {
+        let now = time::now_utc();
+        self.members.write().unwrap().retain(|_, member| {
+            member.state != State::Dead || now - member.changed_at <= suspicion_timeout
+        });
+
+        let updates = self.members
+                          .read()
+                          .unwrap()
+                          .iter()
+                          .filter(|&(_, member)| {
+                              member.state == State::Suspect &&
+                              now - member.changed_at > suspicion_timeout
+                          })
+                          .map(|(address, member)| {
+                              Update {
+                                  address: *address,
+                                  public_address: member.public_address,
+                                  state: State::Dead,
+                                  incarnation: member.incarnation,
+                              }
+                          })
+                          .collect::<Vec<_>>();
+        for update in updates {
+            self.apply(update, false);
+        }
+    }
+
+    fn leave(&self) {
+        let mut update = self.own_update();
+        update.state = State::Dead;
+        let packet = encode(&Packet {
+            kind: Kind::Ack,
+            sequence: 0,
+            sender: update,
+            updates: Vec::new(),
+        });
+
+        let members = self.members.read().unwrap();
+        for (address, _) in members.iter().filter(|&(_, member)| member.state != State::Dead) {
+            let _ = self.udp_socket.send_to(&packet, *address);
+        }
+        // unblock the receiving thread
+        let _ = self.udp_socket.send_to(&[], self.udp_socket.local_addr().unwrap());
+    }
+}
+
+fn encode(packet: &Packet) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(PACKET_SIZE);
+    match packet.kind {
+        Kind::Ping => bytes.push(0),
+        Kind::Ack => bytes.push(1),
+        Kind::PingReq(target) => {
+            bytes.push(2);
+            encode_address(&mut bytes, target);
+        }
+    }
+    encode_u32(&mut bytes, packet.sequence);
+    encode_update(&mut bytes, &packet.sender);
+    bytes.push(packet.updates.len() as u8);
+    for update in &packet.updates {
+        encode_update(&mut bytes, update);
+    }
+    bytes
+}
+
+fn encode_update(bytes: &mut Vec<u8>, update: &Update) {
+    bytes.push(match update.state {
+        State::Alive => 0,
+        State::Suspect => 1,
+        State::Dead => 2,
+    });
+    encode_u32(bytes, update.incarnation);
+    encode_address(bytes, update.address);
+    encode_address(bytes, update.public_address);
+}
+
+fn decode(bytes: &[u8]) -> io::Result<Packet> {
+    let mut reader = Reader::new(bytes);
+
+    let kind = match try!(reader.u8()) {
+        0 => Kind::Ping,
+        1 => Kind::Ack,
+        2 => Kind::PingReq(try!(reader.address())),
+        _ => return Err(invalid_data("invalid packet kind")),
+    };
+    let sequence = try!(reader.u32());
+    let sender = try!(decode_update(&mut reader));
+    let count = try!(reader.u8());
+    let mut updates = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        updates.push(try!(decode_update(&mut reader)));
+    }
+
+    Ok(Packet {
+        kind: kind,
+        sequence: sequence,
+        sender: sender,
+        updates: updates,
+    })
+}
+
+fn decode_update(reader: &mut Reader) -> io::Result<Update> {
+    let state = match try!(reader.u8()) {
+        0 => State::Alive,
+        1 => State::Suspect,
+        2 => State::Dead,
+        _ => return Err(invalid_data("invalid member state")),
+    };
+    let incarnation = try!(reader.u32());
+    let address = try!(reader.address());
+    let public_address = try!(reader.address());
+    Ok(Update {
+        address: address,
+        public_address: public_address,
+        state: state,
+        incarnation: incarnation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use time::Duration;
+    use super::{Kind, Packet, State, Swim, Update, decode, encode};
+    use super::super::Discovery;
+
+    #[test]
+    fn packet_encoding() {
+        let packet = Packet {
+            kind: Kind::PingReq("[::1]:4101".parse::<SocketAddr>().unwrap()),
+            sequence: 70000,
+            sender: Update {
+                address: "127.0.0.1:4100".parse::<SocketAddr>().unwrap(),
+                public_address: "127.0.0.1:3100".parse::<SocketAddr>().unwrap(),
+                state: State::Alive,
+                incarnation: 2,
+            },
+            updates: vec![Update {
+                              address: "127.0.0.1:4102".parse::<SocketAddr>().unwrap(),
+                              public_address: "127.0.0.1:3102".parse::<SocketAddr>().unwrap(),
+                              state: State::Suspect,
+                              incarnation: 1,
+                          }],
+        };
+
+        assert_eq!(packet, decode(&encode(&packet)).unwrap());
+        assert!(decode(&encode(&packet)[..10]).is_err());
+    }
+
+    #[test]
+    fn membership_with_two_nodes() {
+        let public_address_one = "127.0.0.1:3111".parse::<SocketAddr>().unwrap();
+        let public_address_two = "127.0.0.1:3112".parse::<SocketAddr>().unwrap();
+        let discovery_one = build("127.0.0.1:4111", public_address_one, vec![]);
+        let discovery_two = build("127.0.0.1:4112", public_address_two, vec!["127.0.0.1:4111"]);
+
+        thread::sleep(::std::time::Duration::from_millis(200));
+
+        assert_eq!(Some(public_address_two), discovery_one.next());
+        assert_eq!(Some(public_address_one), discovery_two.next());
+    }
+
+    #[test]
+    fn peer_lost_after_leave() {
+        let public_address_one = "127.0.0.1:3121".parse::<SocketAddr>().unwrap();
+        let public_address_two = "127.0.0.1:3122".parse::<SocketAddr>().unwrap();
+        let discovery_one = build("127.0.0.1:4121", public_address_one, vec![]);
+        let lost = Arc::new(Mutex::new(Vec::new()));
+        let lost_clone = lost.clone();
+        discovery_one.set_peer_lost_handler(Box::new(move |address| {
+            lost_clone.lock().unwrap().push(address);
+        }));
+
+        {
+            let discovery_two = build("127.0.0.1:4122",
+                                      public_address_two,
+                                      vec!["127.0.0.1:4121"]);
+            thread::sleep(::std::time::Duration::from_millis(200));
+            assert_eq!(Some(public_address_one), discovery_two.next());
+        }
+        thread::sleep(::std::time::Duration::from_millis(200));
+
+        assert_eq!(vec![public_address_two], *lost.lock().unwrap());
+        assert_eq!(None, discovery_one.next());
+    }
+
+    #[test]
+    fn rejoin_after_restart() {
+        let public_address_one = "127.0.0.1:3131".parse::<SocketAddr>().unwrap();
+        let public_address_two = "127.0.0.1:3132".parse::<SocketAddr>().unwrap();
+        let discovery_one = build("127.0.0.1:4131", public_address_one, vec![]);
+
+        {
+            let _discovery_two = build("127.0.0.1:4132",
+                                       public_address_two,
+                                       vec!["127.0.0.1:4131"]);
+            thread::sleep(::std::time::Duration::from_millis(200));
+            assert_eq!(Some(public_address_two), discovery_one.next());
+        }
+        thread::sleep(::std::time::Duration::from_millis(200));
+        assert_eq!(None, discovery_one.next());
+
+        let discovery_two = build("127.0.0.1:4132", public_address_two, vec!["127.0.0.1:4131"]);
+        thread::sleep(::std::time::Duration::from_millis(200));
+
+        assert_eq!(Some(public_address_two), discovery_one.next());
+        assert_eq!(Some(public_address_one), discovery_two.next());
+    }
+
+    fn build(address: &str, public_address: SocketAddr, seeds: Vec<&str>) -> Swim {
+        Swim::new(address.parse::<SocketAddr>().unwrap(),
+                  public_address,
+                  seeds.iter().map(|seed| seed.parse::<SocketAddr>().unwrap()).collect(),
+                  Duration::milliseconds(50),
+                  Duration::milliseconds(20),
+                  Duration::milliseconds(500),
+                  2)
+            .unwrap()
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+#[cfg(not(test))]
+extern crate delix;
+#[cfg(not(test))]
+extern crate getopts;
+#[cfg(not(test))]
+extern crate rustc_serialize;
+
+#[cfg(not(test))]
+use std::collections::HashMap;
+#[cfg(not(test))]
+use std::env;
+#[cfg(not(test))]
+use std::fs;
+#[cfg(not(test))]
+use std::io::{self, Write};
+#[cfg(not(test))]
+use std::net;
+#[cfg(not(test))]
+use std::process;
+#[cfg(not(test))]
+use std::thread;
+#[cfg(not(test))]
+use std::time::Duration;
+
+#[cfg(not(test))]
+use delix::transport::capture::{self, Direction};
+#[cfg(not(test))]
+use delix::transport::cipher::{self, Cipher};
+#[cfg(not(test))]
+use delix::transport::direct::container::Container;
+#[cfg(not(test))]
+use delix::util::reader;
+#[cfg(not(test))]
+use rustc_serialize::hex::FromHex;
+
+#[cfg(not(test))]
+const DEFAULT_WAIT_MS: u64 = 1000;
+#[cfg(not(test))]
+const SIZE_PREFIX_LENGTH: usize = 8;
+
+/// Reassembles the containers of each stream and direction from the recorded frames.
+#[cfg(not(test))]
+struct Assembler {
+    buffers: HashMap<(u64, Direction), Vec<u8>>,
+}
+
+#[cfg(not(test))]
+impl Assembler {
+    fn new() -> Assembler {
+        Assembler { buffers: HashMap::new() }
+    }
+
+    fn push(&mut self, record: capture::Record) -> io::Result<Vec<(Container, usize)>> {
+        let buffer = self.buffers
+                         .entry((record.stream_id, record.direction))
+                         .or_insert(Vec::new());
+        buffer.extend_from_slice(&record.bytes);
+
+        let mut containers = Vec::new();
+        while buffer.len() >= SIZE_PREFIX_LENGTH {
+            let size = SIZE_PREFIX_LENGTH +
+                       try!(reader::read_size(&mut io::Cursor::new(&buffer[..SIZE_PREFIX_LENGTH])));
+            if buffer.len() < size {
+                break;
+            }
+            let container = try!(Container::read(&mut io::Cursor::new(&buffer[..size])));
+            containers.push((container, size));
+            buffer.drain(..size);
+        }
+        Ok(containers)
+    }
+}
+
+#[cfg(not(test))]
+fn main() {
+    let arguments: Vec<String> = env::args().collect();
+    let program = arguments[0].clone();
+
+    let mut options = getopts::Options::new();
+    options.optopt("k", "key", "hex key of the symmetric cipher (plain if omitted)", "KEY");
+    options.optopt("s", "stream", "id of the recorded stream to replay", "ID");
+    options.optopt("w",
+                   "wait",
+                   "milliseconds to wait for responses after the replay",
+                   "MS");
+    options.optflag("r", "realtime", "keep the recorded delays during the replay");
+    options.optflag("h", "help", "print help");
+
+    let matches = match options.parse(&arguments[1..]) {
+        Ok(matches) => matches,
+        Err(error) => fail(&format!("error while parsing arguments: {}", error)),
+    };
+
+    if matches.opt_present("h") || matches.free.is_empty() {
+        print_usage(&program, options);
+        process::exit(1);
+    }
+
+    match (&*matches.free[0], matches.free.get(1), matches.free.get(2)) {
+        ("show", Some(path), None) => show(path),
+        ("replay", Some(path), Some(address)) => {
+            let cipher: Box<Cipher> = match matches.opt_str("k") {
+                Some(key) => {
+                    let key = match key.from_hex() {
+                        Ok(key) => key,
+                        Err(error) => fail(&format!("invalid key: {}", error)),
+                    };
+                    match cipher::Symmetric::new(&key, None) {
+                        Ok(cipher) => Box::new(cipher),
+                        Err(error) => fail(&format!("invalid key: {:?}", error)),
+                    }
+                }
+                None => Box::new(cipher::Plain::new()),
+            };
+            let stream_id = matches.opt_str("s").map(|value| {
+                value.parse::<u64>().unwrap_or_else(|_| fail("invalid stream id"))
+            });
+            let wait_ms = matches.opt_str("w")
+                                 .map(|value| {
+                                     value.parse::<u64>().unwrap_or_else(|_| fail("invalid wait"))
+                                 })
+                                 .unwrap_or(DEFAULT_WAIT_MS);
+            replay(path,
+                   address,
+                   cipher,
+                   stream_id,
+                   matches.opt_present("r"),
+                   wait_ms)
+        }
+        _ => {
+            print_usage(&program, options);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn show(path: &str) {
+    let mut assembler = Assembler::new();
+    for_each_record(path, |record| {
+        let (offset_ms, stream_id, direction) = (record.offset_ms,
+                                                 record.stream_id,
+                                                 record.direction);
+        for (container, size) in assemble(&mut assembler, record) {
+            println!("{:>10} {:>6} {:<3} {:<24} {:>10}",
+                     offset_ms,
+                     stream_id,
+                     format_direction(direction),
+                     format!("{:?}", container.get_kind()),
+                     size);
+        }
+    });
+}
+
+#[cfg(not(test))]
+fn replay(path: &str,
+          address: &str,
+          cipher: Box<Cipher>,
+          stream_id: Option<u64>,
+          realtime: bool,
+          wait_ms: u64) {
+    let mut tcp_stream = match net::TcpStream::connect(address) {
+        Ok(tcp_stream) => tcp_stream,
+        Err(error) => fail(&format!("error while connecting to {}: {}", address, error)),
+    };
+    let session = match cipher.handshake(&mut tcp_stream, cipher::Role::Initiator) {
+        Ok(session) => session,
+        Err(error) => fail(&format!("error during handshake: {:?}", error)),
+    };
+    let mut stream = cipher::Stream::new(tcp_stream, session);
+
+    let mut rx_stream = stream.try_clone().unwrap_or_else(|error| fail(&format!("{}", error)));
+    let join_handle = thread::spawn(move || {
+        while let Ok(container) = Container::read(&mut rx_stream) {
+            println!("<- {:?}", container.get_kind());
+        }
+    });
+
+    let mut assembler = Assembler::new();
+    let mut replayed_stream_id = stream_id;
+    let mut last_offset_ms = None;
+    for_each_record(path, |record| {
+        if record.direction != Direction::Inbound {
+            return;
+        }
+        match replayed_stream_id {
+            Some(stream_id) if stream_id != record.stream_id => return,
+            Some(_) => {}
+            None => replayed_stream_id = Some(record.stream_id),
+        }
+        if realtime {
+            if let Some(last_offset_ms) = last_offset_ms {
+                let delay_ms = record.offset_ms.saturating_sub(last_offset_ms);
+                thread::sleep(Duration::from_millis(delay_ms));
+            }
+            last_offset_ms = Some(record.offset_ms);
+        }
+        for (container, _) in assemble(&mut assembler, record) {
+            println!("-> {:?}", container.get_kind());
+            if let Err(error) = container.write(&mut stream).and_then(|_| stream.flush()) {
+                fail(&format!("error while replaying: {}", error));
+            }
+        }
+    });
+
+    thread::sleep(Duration::from_millis(wait_ms));
+    let _ = stream.get_ref().shutdown(net::Shutdown::Both);
+    let _ = join_handle.join();
+}
+
+#[cfg(not(test))]
+fn for_each_record<F>(path: &str, mut f: F)
+    where F: FnMut(capture::Record)
+{
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(error) => fail(&format!("error while opening {}: {}", path, error)),
+    };
+    let mut reader = io::BufReader::new(file);
+    loop {
+        match capture::read_record(&mut reader) {
+            Ok(Some(record)) => f(record),
+            Ok(None) => return,
+            Err(error) => fail(&format!("error while reading {}: {}", path, error)),
+        }
+    }
+}
+
+#[cfg(not(test))]
+fn assemble(assembler: &mut Assembler, record: capture::Record) -> Vec<(Container, usize)> {
+    let stream_id = record.stream_id;
+    assembler.push(record).unwrap_or_else(|error| {
+        fail(&format!("error while parsing stream {}: {}", stream_id, error))
+    })
+}
+
+#[cfg(not(test))]
+fn format_direction(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Inbound => "in",
+        Direction::Outbound => "out",
+    }
+}
+
+#[cfg(not(test))]
+fn fail(message: &str) -> ! {
+    println!("{}", message);
+    process::exit(1);
+}
+
+#[cfg(not(test))]
+fn print_usage(program: &str, options: getopts::Options) {
+    let brief = format!("Usage: {} [options] (show FILE | replay FILE ADDRESS)", program);
+    print!("{}", options.usage(&brief));
+}
@@ -16,6 +16,9 @@ limitations under the License.
 
 extern crate byteorder;
 extern crate crypto;
+extern crate flate2;
+extern crate hyper;
+#[macro_use] extern crate lazy_static;
 #[macro_use] extern crate log;
 extern crate protobuf;
 extern crate rand;
@@ -28,5 +31,7 @@ pub mod message;
 pub mod metric;
 pub mod node;
 pub mod relay;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod transport;
 pub mod util;
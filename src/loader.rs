@@ -13,27 +13,91 @@
 // limitations under the License.
 //
 
+use std::collections::BTreeMap;
+use std::fmt;
 use std::net::SocketAddr;
 use std::io;
 use std::result;
-use std::sync::Arc;
-use time::Duration;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::thread;
+use time::{self, Duration};
 use log;
+use rustc_serialize::hex::ToHex;
 
 use delix::logger;
+use delix::message::Encrypted_CipherType;
 use delix::metric::{self, Metric};
-use delix::node::{self, Node};
+use delix::node::{self, IdFile, Node};
 use delix::discovery::{self, Discovery};
 use delix::relay::{self, Relay};
 use delix::transport::{self, Transport};
 use delix::transport::cipher::{self, Cipher};
-use delix::transport::direct::balancer;
+use delix::transport::direct::{balancer, retry};
+use delix::util::clock;
+use delix::util::memory::Watermark;
+use delix::util::net::Proxy;
 use delix::util::resolve;
+use delix::util::thread::supervisor;
+use delix::util::time::to_std_duration;
 use configuration::Configuration;
 
+const DEFAULT_JOIN_PARALLELISM: i64 = 8;
+const DEFAULT_COALESCE_SIZE: i64 = 1024;
+const DEFAULT_HEARTBEAT_TIMEOUT_FACTOR: i32 = 3;
+const DEFAULT_RECONNECT_MAX_BACKOFF_MS: i64 = 30000;
+const DEFAULT_FLOW_CONTROL_WINDOW: i64 = 16;
+const DEFAULT_PACKET_BUFFER_SIZE: i64 = 64 * 1024;
+const DEFAULT_READINESS_TIMEOUT_MS: i64 = 30000;
+const READINESS_POLL_INTERVAL_MS: i64 = 100;
+const DEFAULT_KEY_RELOAD_INTERVAL_MS: i64 = 10000;
+const DEFAULT_DRAIN_LIMIT: i64 = 1;
+const DEFAULT_DRAIN_LEASE_TTL_MS: i64 = 300000;
+const DEFAULT_MAX_MESSAGE_SIZE: i64 = 64 * 1024 * 1024;
+const DEFAULT_RELAY_RETRY_INTERVAL_MS: i64 = 10000;
+const DEFAULT_MEMORY_CHECK_INTERVAL_MS: i64 = 1000;
+const DEFAULT_OUTLIER_EJECTION_DURATION_MS: i64 = 30000;
+const DEFAULT_BREAKER_COOL_DOWN_MS: i64 = 10000;
+const DEFAULT_RETRY_BACKOFF_MS: i64 = 50;
+const DEFAULT_AUDIT_SAMPLE_RATE: f64 = 0.01;
+const DEFAULT_LOG_COLLECT_CAPACITY: i64 = 10000;
+
+const METRIC_TYPES: &'static [&'static str] = &["console", "terminal"];
+const LOG_TYPES: &'static [&'static str] = &["console"];
+const CIPHER_TYPES: &'static [&'static str] = &["symmetric", "noise", "none"];
+const CIPHER_ALGORITHMS: &'static [&'static str] = &["aes_gcm", "chacha20_poly1305"];
+const DISCOVERY_TYPES: &'static [&'static str] = &["constant", "multicast", "etcd", "consul",
+                                                   "file", "swim", "dht"];
+const TRANSPORT_TYPES: &'static [&'static str] = &["direct"];
+const BALANCER_TYPES: &'static [&'static str] = &["dynamic_round_robin",
+                                                  "weighted_round_robin",
+                                                  "consistent_hash"];
+const REQUEST_ID_BITS: &'static [&'static str] = &["32", "64"];
+const READINESS_STATES: &'static [&'static str] = &["joined"];
+const RELAY_TYPES: &'static [&'static str] = &["http"];
+const RELAY_FAILURE_POLICIES: &'static [&'static str] = &["abort", "retry"];
+const RETRY_ERRORS: &'static [&'static str] = &["unavailable", "timeout", "internal"];
+const SUMMARY_KEYS: &'static [&'static str] = &["node",
+                                                "bind",
+                                                "public_address",
+                                                "discovery",
+                                                "cipher",
+                                                "balancer",
+                                                "proxy",
+                                                "relay"];
+
 #[derive(Debug)]
 pub struct Loader {
     configuration: Configuration,
+    keyring: RwLock<Option<cipher::Keyring>>,
+    forwarder: RwLock<Option<Arc<logger::Forwarder>>>,
+    summary: Summary,
+}
+
+/// Collects what has actually been loaded, so the startup summary can't drift from the
+/// configuration's interpretation.
+#[derive(Debug)]
+struct Summary {
+    entries: Mutex<Vec<(&'static str, String)>>,
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -46,11 +110,137 @@ pub enum Error {
     Cipher(cipher::Error),
     Relay(relay::Error),
     Resolve(io::Error),
+    IdFile(io::Error),
+    Etcd(discovery::etcd::Error),
+    Consul(discovery::consul::Error),
+    Script(String),
+    InsecureCipher,
 }
 
 impl Loader {
     pub fn new(configuration: Configuration) -> Loader {
-        Loader { configuration: configuration }
+        Loader {
+            configuration: configuration,
+            keyring: RwLock::new(None),
+            forwarder: RwLock::new(None),
+            summary: Summary::new(),
+        }
+    }
+
+    pub fn validate(&self) -> result::Result<(), Vec<Error>> {
+        let configuration = &self.configuration;
+        let mut errors = Vec::new();
+
+        check_choice(configuration.string_at("metric.type"),
+                     "metric.type",
+                     METRIC_TYPES,
+                     &mut errors);
+        check_choice(configuration.string_at("log.type"),
+                     "log.type",
+                     LOG_TYPES,
+                     &mut errors);
+
+        if let Some(cipher_type) = check_choice(configuration.string_at("cipher.type"),
+                                                "cipher.type",
+                                                CIPHER_TYPES,
+                                                &mut errors) {
+            if cipher_type == "none" {
+                if !configuration.bool_at("cipher.allow_insecure").unwrap_or(false) {
+                    errors.push(Error::InsecureCipher);
+                }
+            } else if cipher_type == "noise" {
+                check_present(configuration.bytes_at("cipher.private_key"),
+                              "cipher.private_key",
+                              &mut errors);
+                check_present(configuration.bytes_vec_at("cipher.peer_keys"),
+                              "cipher.peer_keys",
+                              &mut errors);
+            } else if let Some(keys) = configuration.configurations_at("cipher.keys") {
+                for key in keys {
+                    check_present(key.i64_at("id"), "cipher.keys.id", &mut errors);
+                    check_present(key.bytes_at("key"), "cipher.keys.key", &mut errors);
+                }
+            } else {
+                check_present(configuration.bytes_at("cipher.key"), "cipher.key", &mut errors);
+            }
+            if cipher_type == "symmetric" {
+                for algorithm in configuration.strings_at("cipher.algorithms")
+                                              .unwrap_or(Vec::new()) {
+                    check_choice(Some(algorithm),
+                                 "cipher.algorithms",
+                                 CIPHER_ALGORITHMS,
+                                 &mut errors);
+                }
+            }
+        }
+
+        if let Some(discovery_type) = check_choice(configuration.string_at("discovery.type"),
+                                                   "discovery.type",
+                                                   DISCOVERY_TYPES,
+                                                   &mut errors) {
+            let required_fields: &[&'static str] = match discovery_type.as_ref() {
+                "constant" => &["discovery.addresses"],
+                "multicast" => &["discovery.interface_address", "discovery.multicast_address"],
+                "etcd" | "consul" => &["discovery.endpoint"],
+                "file" => &["discovery.path"],
+                _ => &["discovery.address"],
+            };
+            for path in required_fields {
+                if configuration.string_at(path).is_none() &&
+                   configuration.strings_at(path).is_none() {
+                    errors.push(Error::MissingField(*path));
+                }
+            }
+        }
+
+        if check_choice(configuration.string_at("transport.type"),
+                        "transport.type",
+                        TRANSPORT_TYPES,
+                        &mut errors)
+               .is_some() {
+            if configuration.string_at("transport.local_address").is_none() &&
+               configuration.strings_at("transport.local_address")
+                            .map(|values| values.is_empty())
+                            .unwrap_or(true) {
+                errors.push(Error::MissingField("transport.local_address"));
+            }
+            match configuration.string_at("transport.balancer.type") {
+                Some(ref balancer_type) if balancer::registry().contains(balancer_type) => {}
+                balancer_type => {
+                    check_choice(balancer_type,
+                                 "transport.balancer.type",
+                                 BALANCER_TYPES,
+                                 &mut errors);
+                }
+            }
+            if let Some(value) = configuration.i64_at("transport.request_id_bits") {
+                check_choice(Some(value.to_string()),
+                             "transport.request_id_bits",
+                             REQUEST_ID_BITS,
+                             &mut errors);
+            }
+            if let Some(value) = configuration.i64_at("transport.packet_buffer_size") {
+                if value <= 0 {
+                    errors.push(Error::InvalidValue("transport.packet_buffer_size",
+                                                    value.to_string(),
+                                                    vec!["positive number of bytes"]));
+                }
+            }
+        }
+
+        if let Some(state) = configuration.string_at("readiness.state") {
+            check_choice(Some(state), "readiness.state", READINESS_STATES, &mut errors);
+        }
+
+        for relay in configuration.configurations_at("relay").unwrap_or(Vec::new()) {
+            check_choice(relay.string_at("type"), "relay.type", RELAY_TYPES, &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
 
     pub fn load_metric(&self) -> Result<Arc<metric::Metric>> {
@@ -58,22 +248,27 @@ impl Loader {
                                    .string_at("metric.type")
                                    .ok_or(Error::MissingField("metric.type")));
 
+        let naming = metric::Naming::new(self.configuration.string_at("metric.prefix"),
+                                         self.configuration
+                                             .string_map_at("metric.labels")
+                                             .unwrap_or(BTreeMap::new()));
+
         match metric_type.as_ref() {
             "console" => {
                 info!("loaded console metric");
-                Ok(Arc::new(metric::Memory::new()))
+                Ok(Arc::new(metric::Memory::with_naming(naming)))
             }
             "terminal" => {
                 info!("loaded terminal metric");
                 let refresh_interval_ms = self.configuration
                                               .i64_at("metric.refresh_interval_ms")
                                               .unwrap_or(100);
-                Ok(Arc::new(metric::Terminal::new(refresh_interval_ms as u64)))
+                Ok(Arc::new(metric::Terminal::new(refresh_interval_ms as u64, naming)))
             }
             _ => {
                 Err(Error::InvalidValue("metric.type",
                                         metric_type.to_string(),
-                                        vec!["console", "terminal"]))
+                                        METRIC_TYPES.to_vec()))
             }
         }
     }
@@ -83,35 +278,73 @@ impl Loader {
                                 .string_at("log.type")
                                 .ok_or(Error::MissingField("log.type")));
 
-        let log_level_filter = match self.configuration
-                                         .string_at("log.level")
-                                         .unwrap_or("off".to_string())
-                                         .as_ref() {
-            "off" => log::LogLevelFilter::Off,
-            "error" => log::LogLevelFilter::Error,
-            "warn" => log::LogLevelFilter::Warn,
-            "info" => log::LogLevelFilter::Info,
-            "debug" => log::LogLevelFilter::Debug,
-            "trace" => log::LogLevelFilter::Trace,
-            _ => log::LogLevelFilter::Off,
-        };
+        let log_level_filter = parse_log_level(self.configuration.string_at("log.level"));
+        let forwarder = self.configuration
+                            .string_at("log.forward_level")
+                            .map(|level| parse_log_level(Some(level)))
+                            .map(|level_filter| Arc::new(logger::Forwarder::new(level_filter)));
 
         match log_type.as_ref() {
             "console" => {
-                logger::Console::init(log_level_filter, "delix", metric).unwrap();
+                logger::Console::init_with_forwarder(log_level_filter,
+                                                     "delix",
+                                                     metric,
+                                                     forwarder.clone())
+                    .unwrap();
                 info!("loaded console log");
+                *self.forwarder.write().unwrap() = forwarder;
                 Ok(())
             }
-            _ => Err(Error::InvalidValue("log.type", log_type.to_string(), vec!["console"])),
+            _ => Err(Error::InvalidValue("log.type", log_type.to_string(), LOG_TYPES.to_vec())),
         }
     }
 
+    pub fn load_crash_report_directory(&self) -> Option<String> {
+        self.configuration.string_at("log.crash_report_directory")
+    }
+
     pub fn load_node(&self, metric: &Arc<metric::Metric>) -> Result<Arc<Node>> {
         let cipher = try!(self.load_cipher());
         let transport = try!(self.load_transport(cipher, metric.clone()));
-        let discovery = try!(self.load_discovery(transport.public_address()));
+        let id_file = match self.configuration.string_at("node.id_file") {
+            Some(path) => {
+                let id_file = try!(IdFile::open(&path).map_err(Error::IdFile));
+                info!("loaded node id {} from {}", id_file.id(), path);
+                Some(id_file)
+            }
+            None => None,
+        };
+        let node_id = id_file.as_ref()
+                             .map(|id_file| id_file.id())
+                             .unwrap_or_else(node::ID::new_random);
 
-        Ok(Arc::new(try!(Node::new(discovery, transport, metric.clone()))))
+        let discovery = try!(self.load_discovery(node_id, transport.public_address()));
+        self.summary.add("public_address", transport.public_address().to_string());
+
+        let node = match id_file {
+            Some(id_file) => {
+                try!(Node::with_id_file(id_file, discovery, transport, metric.clone()))
+            }
+            None => try!(Node::with_id(node_id, discovery, transport, metric.clone())),
+        };
+        if let Some(exposed_services) = self.configuration.strings_at("node.exposed_services") {
+            info!("announcing only the services {}", exposed_services.join(", "));
+            node.set_exposed_services(&exposed_services);
+        }
+        if self.configuration.bool_at("log.collect").unwrap_or(false) {
+            let capacity = self.configuration
+                               .i64_at("log.collect_capacity")
+                               .unwrap_or(DEFAULT_LOG_COLLECT_CAPACITY);
+            try!(node.set_log_collector(Arc::new(logger::Collector::new(capacity as usize))));
+            info!("collecting up to {} log records of traced requests", capacity);
+        }
+        self.summary.add("node", node.id.to_string());
+
+        let node = Arc::new(node);
+        if let Some(ref forwarder) = *self.forwarder.read().unwrap() {
+            forwarder.connect(Arc::downgrade(&node));
+        }
+        Ok(node)
     }
 
     fn load_cipher(&self) -> Result<Box<Cipher>> {
@@ -121,20 +354,68 @@ impl Loader {
 
         match cipher_type.as_ref() {
             "symmetric" => {
-                let key = try!(self.configuration
-                                   .bytes_at("cipher.key")
-                                   .ok_or(Error::MissingField("cipher.key")));
-                let cipher = try!(cipher::Symmetric::new(&key, None));
-                info!("loaded symmetric cipher");
+                let keyring = try!(cipher::Keyring::new(&try!(load_keys(&self.configuration))));
+                let cipher_types = match self.configuration.strings_at("cipher.algorithms") {
+                    Some(algorithms) => {
+                        let mut cipher_types = Vec::new();
+                        for algorithm in algorithms {
+                            cipher_types.push(match algorithm.as_ref() {
+                                "aes_gcm" => Encrypted_CipherType::AESGCM,
+                                "chacha20_poly1305" => Encrypted_CipherType::CHACHA20POLY1305,
+                                _ => {
+                                    return Err(Error::InvalidValue("cipher.algorithms",
+                                                                   algorithm.to_string(),
+                                                                   CIPHER_ALGORITHMS.to_vec()))
+                                }
+                            });
+                        }
+                        cipher_types
+                    }
+                    None => keyring.cipher_types(),
+                };
+                let nonce_source = Box::new(cipher::RandomNonceSource::new());
+                let cipher = try!(cipher::Symmetric::with_keyring(keyring.clone(),
+                                                                  nonce_source,
+                                                                  &cipher_types));
+                info!("loaded symmetric cipher with keys {:?}", keyring.ids());
+                self.summary.add("cipher", "symmetric".to_string());
+                *self.keyring.write().unwrap() = Some(keyring);
+                Ok(Box::new(cipher))
+            }
+            "noise" => {
+                let private_key = try!(self.configuration
+                                           .bytes_at("cipher.private_key")
+                                           .ok_or(Error::MissingField("cipher.private_key")));
+                let peer_keys = try!(self.configuration
+                                         .bytes_vec_at("cipher.peer_keys")
+                                         .ok_or(Error::MissingField("cipher.peer_keys")));
+                let cipher = try!(cipher::Noise::new(&private_key, &peer_keys));
+                self.summary.add("cipher", "noise".to_string());
+                info!("loaded noise cipher with public key {} and {} peer keys",
+                      cipher.public_key().to_hex(),
+                      peer_keys.len());
                 Ok(Box::new(cipher))
             }
+            "none" => {
+                if !self.configuration.bool_at("cipher.allow_insecure").unwrap_or(false) {
+                    return Err(Error::InsecureCipher);
+                }
+                warn!("loaded plain cipher - traffic is neither encrypted nor authenticated");
+                self.summary.add("cipher", "none".to_string());
+                Ok(Box::new(cipher::Plain::new()))
+            }
             _ => {
-                Err(Error::InvalidValue("cipher.type", cipher_type.to_string(), vec!["symmetric"]))
+                Err(Error::InvalidValue("cipher.type",
+                                        cipher_type.to_string(),
+                                        CIPHER_TYPES.to_vec()))
             }
         }
     }
 
-    fn load_discovery(&self, public_address: SocketAddr) -> Result<Box<Discovery>> {
+    fn load_discovery(&self,
+                      node_id: node::ID,
+                      public_address: SocketAddr)
+                      -> Result<Box<Discovery>> {
         let discovery_type = try!(self.configuration
                                       .string_at("discovery.type")
                                       .ok_or(Error::MissingField("discovery.type")));
@@ -144,9 +425,22 @@ impl Loader {
                 let addresses = try!(self.configuration
                                          .strings_at("discovery.addresses")
                                          .ok_or(Error::MissingField("discovery.addresses")));
-                let addresses = try!(resolve::socket_addresses(&addresses));
-                let discovery = discovery::Constant::new(addresses);
+                let (addresses, unresolved) = try!(resolve::partition(&addresses));
+                warn_unresolved(&unresolved);
+                let initial_backoff = Duration::milliseconds(self.configuration
+                                                                 .i64_at("discovery.\
+                                                                          initial_backoff_ms")
+                                                                 .unwrap_or(500));
+                let max_backoff = Duration::milliseconds(self.configuration
+                                                             .i64_at("discovery.max_backoff_ms")
+                                                             .unwrap_or(30000));
+                let discovery = discovery::Constant::with_backoff(addresses.clone(),
+                                                                  initial_backoff,
+                                                                  max_backoff);
+                let description = format!("constant({})", join_seeds(&addresses, &unresolved));
+                discovery.add_unresolved(unresolved);
                 info!("loaded constant discovery");
+                self.summary.add("discovery", description);
                 Ok(Box::new(discovery))
             }
             "multicast" => {
@@ -161,23 +455,157 @@ impl Loader {
                                                                              multicast_address")));
                 let multicast_address = try!(resolve::socket_address(&multicast_address));
 
+                // the reply timeout used to be read from the transport section.
                 let reply_timeout = Duration::milliseconds(self.configuration
-                                                               .i64_at("transport.\
+                                                               .i64_at("discovery.\
                                                                         reply_timeout_ms")
+                                                               .or(self.configuration
+                                                                       .i64_at("transport.\
+                                                                                reply_timeout_ms"))
                                                                .unwrap_or(500));
+                let probe_interval = self.configuration
+                                         .i64_at("discovery.probe_interval_ms")
+                                         .map(|value| Duration::milliseconds(value));
+
+                let interface_name = self.configuration.string_at("discovery.interface");
 
-                let discovery = try!(discovery::Multicast::new(interface_address,
-                                                               multicast_address,
-                                                               public_address,
-                                                               reply_timeout));
+                let discovery = try!(discovery::Multicast::with_clock(interface_address,
+                                                                      multicast_address,
+                                                                      interface_name.as_ref()
+                                                                                    .map(|name| {
+                                                                                        &name[..]
+                                                                                    }),
+                                                                      public_address,
+                                                                      reply_timeout,
+                                                                      probe_interval,
+                                                                      Arc::new(clock::System)));
                 info!("loaded multicast discovery");
+                self.summary.add("discovery", format!("multicast({})", multicast_address));
+                Ok(Box::new(discovery))
+
+            }
+            "etcd" => {
+                let endpoint = try!(self.configuration
+                                        .string_at("discovery.endpoint")
+                                        .ok_or(Error::MissingField("discovery.endpoint")));
+                let prefix = self.configuration
+                                 .string_at("discovery.prefix")
+                                 .unwrap_or("/delix/nodes".to_string());
+                let ttl = Duration::milliseconds(self.configuration
+                                                     .i64_at("discovery.ttl_ms")
+                                                     .unwrap_or(30000));
+
+                let discovery = try!(discovery::Etcd::new(&endpoint, &prefix, public_address, ttl));
+                info!("loaded etcd discovery");
+                self.summary.add("discovery", format!("etcd({})", endpoint));
+                Ok(Box::new(discovery))
+            }
+            "file" => {
+                let path = try!(self.configuration
+                                    .string_at("discovery.path")
+                                    .ok_or(Error::MissingField("discovery.path")));
+                let poll_interval = Duration::milliseconds(self.configuration
+                                                               .i64_at("discovery.\
+                                                                        poll_interval_ms")
+                                                               .unwrap_or(1000));
+
+                let discovery = try!(discovery::File::new(&path, public_address, poll_interval));
+                info!("loaded file discovery");
+                self.summary.add("discovery", format!("file({})", path));
                 Ok(Box::new(discovery))
+            }
+            "swim" => {
+                let address = try!(self.configuration
+                                       .string_at("discovery.address")
+                                       .ok_or(Error::MissingField("discovery.address")));
+                let address = try!(resolve::socket_address(&address));
+                let (seeds, unresolved) =
+                    try!(resolve::partition(&self.configuration
+                                                 .strings_at("discovery.seeds")
+                                                 .unwrap_or(Vec::new())));
+                warn_unresolved(&unresolved);
+                let protocol_period = Duration::milliseconds(self.configuration
+                                                                 .i64_at("discovery.\
+                                                                          protocol_period_ms")
+                                                                 .unwrap_or(1000));
+                let ping_timeout = Duration::milliseconds(self.configuration
+                                                              .i64_at("discovery.ping_timeout_ms")
+                                                              .unwrap_or(200));
+                let suspicion_timeout = Duration::milliseconds(self.configuration
+                                                                   .i64_at("discovery.\
+                                                                            suspicion_timeout_ms")
+                                                                   .unwrap_or(5000));
+                let indirect_checks = self.configuration
+                                          .i64_at("discovery.indirect_checks")
+                                          .unwrap_or(3);
+
+                let description = format!("swim({})", join_seeds(&seeds, &unresolved));
+                let discovery = try!(discovery::Swim::new(address,
+                                                          public_address,
+                                                          seeds,
+                                                          protocol_period,
+                                                          ping_timeout,
+                                                          suspicion_timeout,
+                                                          indirect_checks as usize));
+                discovery.add_unresolved_seeds(unresolved);
+                info!("loaded swim discovery");
+                self.summary.add("discovery", description);
+                Ok(Box::new(discovery))
+            }
+            "dht" => {
+                let address = try!(self.configuration
+                                       .string_at("discovery.address")
+                                       .ok_or(Error::MissingField("discovery.address")));
+                let address = try!(resolve::socket_address(&address));
+                let (seeds, unresolved) =
+                    try!(resolve::partition(&self.configuration
+                                                 .strings_at("discovery.seeds")
+                                                 .unwrap_or(Vec::new())));
+                warn_unresolved(&unresolved);
+                let request_timeout = Duration::milliseconds(self.configuration
+                                                                 .i64_at("discovery.\
+                                                                          request_timeout_ms")
+                                                                 .unwrap_or(500));
+                let refresh_interval = Duration::milliseconds(self.configuration
+                                                                  .i64_at("discovery.\
+                                                                           refresh_interval_ms")
+                                                                  .unwrap_or(10000));
+
+                let description = format!("dht({})", join_seeds(&seeds, &unresolved));
+                let discovery = try!(discovery::Dht::new(node_id,
+                                                         address,
+                                                         public_address,
+                                                         seeds,
+                                                         request_timeout,
+                                                         refresh_interval));
+                discovery.add_unresolved_seeds(unresolved);
+                info!("loaded dht discovery");
+                self.summary.add("discovery", description);
+                Ok(Box::new(discovery))
+            }
+            "consul" => {
+                let endpoint = try!(self.configuration
+                                        .string_at("discovery.endpoint")
+                                        .ok_or(Error::MissingField("discovery.endpoint")));
+                let service_name = self.configuration
+                                       .string_at("discovery.service_name")
+                                       .unwrap_or("delix".to_string());
+                let ttl = Duration::milliseconds(self.configuration
+                                                     .i64_at("discovery.ttl_ms")
+                                                     .unwrap_or(30000));
 
+                let discovery = try!(discovery::Consul::new(&endpoint,
+                                                            &service_name,
+                                                            public_address,
+                                                            ttl));
+                info!("loaded consul discovery");
+                self.summary.add("discovery", format!("consul({})", endpoint));
+                Ok(Box::new(discovery))
             }
             _ => {
                 Err(Error::InvalidValue("discovery.type",
                                         discovery_type.to_string(),
-                                        vec!["constant"]))
+                                        DISCOVERY_TYPES.to_vec()))
             }
         }
     }
@@ -189,11 +617,22 @@ impl Loader {
 
         match transport_type.as_ref() {
             "direct" => {
-                let local_address = try!(self.configuration
-                                             .string_at("transport.local_address")
-                                             .ok_or(Error::MissingField("transport.\
-                                                                         local_address")));
-                let local_address = try!(resolve::socket_address(&local_address));
+                let local_address_values = match self.configuration
+                                                     .strings_at("transport.local_address") {
+                    Some(values) => values,
+                    None => {
+                        vec![try!(self.configuration
+                                      .string_at("transport.local_address")
+                                      .ok_or(Error::MissingField("transport.local_address")))]
+                    }
+                };
+                if local_address_values.is_empty() {
+                    return Err(Error::MissingField("transport.local_address"));
+                }
+                let mut local_addresses = Vec::new();
+                for value in &local_address_values {
+                    local_addresses.push(try!(resolve::socket_address(value)));
+                }
 
                 let public_address = match self.configuration
                                                .string_at("transport.public_address") {
@@ -205,45 +644,490 @@ impl Loader {
                                           .i64_at("transport.request_timeout_ms")
                                           .map(|value| Duration::milliseconds(value));
 
+                let service_grace_period = self.configuration
+                                               .i64_at("transport.service_grace_period_ms")
+                                               .map(|value| Duration::milliseconds(value));
+
+                let max_services_per_peer = self.configuration
+                                                .i64_at("transport.max_services_per_peer")
+                                                .map(|value| value as usize);
+                let max_service_name_length = self.configuration
+                                                  .i64_at("transport.max_service_name_length")
+                                                  .map(|value| value as usize);
+                let strict_service_names = self.configuration
+                                               .bool_at("transport.strict_service_names")
+                                               .unwrap_or(false);
+
+                let aknowledge_timeout = self.configuration
+                                             .i64_at("transport.aknowledge_timeout_ms")
+                                             .map(|value| Duration::milliseconds(value));
+
+                let join_parallelism = self.configuration
+                                           .i64_at("transport.join_parallelism")
+                                           .unwrap_or(DEFAULT_JOIN_PARALLELISM);
+                let join_dial_timeout = self.configuration
+                                            .i64_at("transport.join_dial_timeout_ms")
+                                            .map(|value| Duration::milliseconds(value));
+
+                let wide_request_ids = match self.configuration
+                                                 .i64_at("transport.request_id_bits")
+                                                 .unwrap_or(32) {
+                    32 => false,
+                    64 => true,
+                    value => {
+                        return Err(Error::InvalidValue("transport.request_id_bits",
+                                                       value.to_string(),
+                                                       REQUEST_ID_BITS.to_vec()))
+                    }
+                };
+
+                let coalesce_delay = self.configuration
+                                         .i64_at("transport.coalesce_delay_ms")
+                                         .map(|value| Duration::milliseconds(value));
+                let coalesce_size = self.configuration
+                                        .i64_at("transport.coalesce_size")
+                                        .unwrap_or(DEFAULT_COALESCE_SIZE);
+
+                let heartbeat_interval = self.configuration
+                                             .i64_at("transport.heartbeat_interval_ms")
+                                             .map(|value| Duration::milliseconds(value));
+                let heartbeat_timeout = self.configuration
+                                            .i64_at("transport.heartbeat_timeout_ms")
+                                            .map(|value| Duration::milliseconds(value))
+                                            .or(heartbeat_interval.map(|value| {
+                                                value * DEFAULT_HEARTBEAT_TIMEOUT_FACTOR
+                                            }));
+
+                let reconnect_backoff = self.configuration
+                                            .i64_at("transport.reconnect_initial_backoff_ms")
+                                            .map(|value| Duration::milliseconds(value));
+                let reconnect_max_backoff =
+                    Duration::milliseconds(self.configuration
+                                               .i64_at("transport.reconnect_max_backoff_ms")
+                                               .unwrap_or(DEFAULT_RECONNECT_MAX_BACKOFF_MS));
+
+                let compression_threshold = self.configuration
+                                                .i64_at("transport.compression_threshold")
+                                                .map(|value| value as usize);
+
+                let flow_control_window = self.configuration
+                                              .i64_at("transport.flow_control_window")
+                                              .unwrap_or(DEFAULT_FLOW_CONTROL_WINDOW);
+
+                let packet_buffer_size = self.configuration
+                                             .i64_at("transport.packet_buffer_size")
+                                             .unwrap_or(DEFAULT_PACKET_BUFFER_SIZE);
+                if packet_buffer_size <= 0 {
+                    return Err(Error::InvalidValue("transport.packet_buffer_size",
+                                                   packet_buffer_size.to_string(),
+                                                   vec!["positive number of bytes"]));
+                }
+
+                let max_message_size = self.configuration
+                                           .i64_at("transport.max_message_size")
+                                           .unwrap_or(DEFAULT_MAX_MESSAGE_SIZE);
+                if max_message_size <= packet_buffer_size {
+                    return Err(Error::InvalidValue("transport.max_message_size",
+                                                   max_message_size.to_string(),
+                                                   vec!["bytes above packet_buffer_size"]));
+                }
+
+                let idle_timeout = self.configuration
+                                       .i64_at("transport.idle_timeout_ms")
+                                       .map(|value| Duration::milliseconds(value));
+
+                let zone = self.configuration.string_at("transport.zone");
+
+                let drain_limit = self.configuration
+                                      .i64_at("transport.drain_limit")
+                                      .unwrap_or(DEFAULT_DRAIN_LIMIT);
+                let drain_lease_ttl =
+                    Duration::milliseconds(self.configuration
+                                               .i64_at("transport.drain_lease_ttl_ms")
+                                               .unwrap_or(DEFAULT_DRAIN_LEASE_TTL_MS));
+
+                let upload_limit = try!(load_rate_limit(&self.configuration,
+                                                        "transport.upload_limit"));
+                let download_limit = try!(load_rate_limit(&self.configuration,
+                                                          "transport.download_limit"));
+
+                let max_hops = self.configuration.i64_at("transport.max_hops").unwrap_or(0);
+                if max_hops < 0 {
+                    return Err(Error::InvalidValue("transport.max_hops",
+                                                   max_hops.to_string(),
+                                                   vec!["zero or positive number of hops"]));
+                }
+
+                let capture = match self.configuration.string_at("transport.capture_path") {
+                    Some(capture_path) => {
+                        warn!("capturing decrypted traffic to {}", capture_path);
+                        Some(try!(transport::Capture::create(&capture_path)))
+                    }
+                    None => None,
+                };
+
+                let proxy = match self.configuration.string_at("transport.proxy.url") {
+                    Some(url) => Some(try!(Proxy::parse(&url))),
+                    None => None,
+                };
+
+                let memory = match self.configuration.i64_at("transport.memory_limit") {
+                    Some(memory_limit) if memory_limit <= 0 => {
+                        return Err(Error::InvalidValue("transport.memory_limit",
+                                                       memory_limit.to_string(),
+                                                       vec!["positive number of bytes"]))
+                    }
+                    Some(memory_limit) => {
+                        let interval = self.configuration
+                                           .i64_at("transport.memory_check_interval_ms")
+                                           .unwrap_or(DEFAULT_MEMORY_CHECK_INTERVAL_MS);
+                        Some(Watermark::start(memory_limit as usize,
+                                              Duration::milliseconds(interval)))
+                    }
+                    None => None,
+                };
+
+                let outlier_ejection = match self.configuration
+                                                 .i64_at("transport.outlier_ejection.\
+                                                          consecutive_failures") {
+                    Some(consecutive_failures) if consecutive_failures <= 0 => {
+                        return Err(Error::InvalidValue("transport.outlier_ejection.\
+                                                        consecutive_failures",
+                                                       consecutive_failures.to_string(),
+                                                       vec!["positive number"]))
+                    }
+                    Some(consecutive_failures) => {
+                        let duration = self.configuration
+                                           .i64_at("transport.outlier_ejection.duration_ms")
+                                           .unwrap_or(DEFAULT_OUTLIER_EJECTION_DURATION_MS);
+                        Some((consecutive_failures as usize, Duration::milliseconds(duration)))
+                    }
+                    None => None,
+                };
+
+                let breaker = match self.configuration
+                                        .i64_at("transport.breaker.consecutive_failures") {
+                    Some(consecutive_failures) if consecutive_failures <= 0 => {
+                        return Err(Error::InvalidValue("transport.breaker.consecutive_failures",
+                                                       consecutive_failures.to_string(),
+                                                       vec!["positive number"]))
+                    }
+                    Some(consecutive_failures) => {
+                        let cool_down = self.configuration
+                                            .i64_at("transport.breaker.cool_down_ms")
+                                            .unwrap_or(DEFAULT_BREAKER_COOL_DOWN_MS);
+                        Some((consecutive_failures as usize, Duration::milliseconds(cool_down)))
+                    }
+                    None => None,
+                };
+
+                let retry_policies = try!(self.load_retry_policies());
+
                 let balancer_type = try!(self.configuration
                                              .string_at("transport.balancer.type")
                                              .ok_or(Error::MissingField("transport.balancer.\
                                                                          type")));
 
-                let balancer_factory = match balancer_type.as_ref() {
-                    "dynamic_round_robin" => Box::new(balancer::DynamicRoundRobinFactory::new()),
-                    _ => {
+                // besides the built-in balancers, library users may have registered their own.
+                let balancer_factory = match balancer::registry().build(&balancer_type) {
+                    Some(balancer_factory) => balancer_factory,
+                    None => {
                         return Err(Error::InvalidValue("transport.balancer.type",
                                                        balancer_type.to_string(),
-                                                       vec!["dynamic_round_robin"]))
+                                                       BALANCER_TYPES.to_vec()))
                     }
                 };
 
-                info!("loaded direct transport - listening at {}", local_address);
+                info!("loaded direct transport - listening at {}",
+                      local_addresses.iter()
+                                     .map(|address| address.to_string())
+                                     .collect::<Vec<_>>()
+                                     .join(", "));
+
+                self.summary.add("bind", join(&local_addresses));
+                self.summary.add("balancer", balancer_type.to_string());
+                if let Some(ref proxy) = proxy {
+                    self.summary.add("proxy", proxy.to_string());
+                }
 
-                Ok(Box::new(transport::Direct::new(cipher,
-                                                   balancer_factory,
-                                                   metric,
-                                                   local_address,
-                                                   public_address,
-                                                   request_timeout)))
+                let mut config = transport::direct::Config::new(local_addresses);
+                config.public_address = public_address;
+                config.request_timeout = request_timeout;
+                config.service_grace_period = service_grace_period;
+                config.max_services_per_peer = max_services_per_peer;
+                config.max_service_name_length = max_service_name_length;
+                config.strict_service_names = strict_service_names;
+                config.aknowledge_timeout = aknowledge_timeout;
+                config.join_parallelism = join_parallelism as usize;
+                config.join_dial_timeout = join_dial_timeout;
+                config.wide_request_ids = wide_request_ids;
+                config.coalesce_delay = coalesce_delay;
+                config.coalesce_size = coalesce_size as usize;
+                config.heartbeat_interval = heartbeat_interval;
+                config.heartbeat_timeout = heartbeat_timeout;
+                config.reconnect_backoff = reconnect_backoff;
+                config.reconnect_max_backoff = reconnect_max_backoff;
+                config.compression_threshold = compression_threshold;
+                config.flow_control_window = flow_control_window as usize;
+                config.packet_buffer_size = packet_buffer_size as usize;
+                config.idle_timeout = idle_timeout;
+                config.zone = zone;
+                config.drain_limit = drain_limit as usize;
+                config.drain_lease_ttl = drain_lease_ttl;
+                config.upload_limit = upload_limit;
+                config.download_limit = download_limit;
+                config.max_message_size = max_message_size as usize;
+                config.capture = capture;
+                config.max_hops = max_hops as usize;
+                config.proxy = proxy;
+                config.memory = memory;
+                config.outlier_ejection = outlier_ejection;
+                config.breaker = breaker;
+                config.retry_policies = retry_policies;
+
+                Ok(Box::new(transport::Direct::new(cipher, balancer_factory, metric, config)))
             }
             _ => {
                 Err(Error::InvalidValue("transport.type",
                                         transport_type.to_string(),
-                                        vec!["direct"]))
+                                        TRANSPORT_TYPES.to_vec()))
+            }
+        }
+    }
+
+    pub fn watch_cipher_keys(&self, path: &str) {
+        let keyring = match *self.keyring.read().unwrap() {
+            Some(ref keyring) => keyring.clone(),
+            None => return,
+        };
+        let interval = Duration::milliseconds(self.configuration
+                                                  .i64_at("cipher.reload_interval_ms")
+                                                  .unwrap_or(DEFAULT_KEY_RELOAD_INTERVAL_MS));
+        let path = path.to_string();
+        let mut keys = load_keys(&self.configuration).ok();
+
+        supervisor().spawn("key reload", move || {
+            loop {
+                thread::sleep(to_std_duration(interval));
+                let loaded = match Configuration::read_file(&path) {
+                    Ok(configuration) => load_keys(&configuration),
+                    Err(error) => {
+                        error!("error while reloading cipher keys from {}: {}", path, error);
+                        continue;
+                    }
+                };
+                match loaded {
+                    Ok(ref loaded) if keys.as_ref() == Some(loaded) => {}
+                    Ok(loaded) => {
+                        match keyring.replace(&loaded) {
+                            Ok(()) => {
+                                info!("reloaded cipher keys {:?}", keyring.ids());
+                                keys = Some(loaded);
+                            }
+                            Err(error) => error!("error while reloading cipher keys: {:?}", error),
+                        }
+                    }
+                    Err(error) => error!("error while reloading cipher keys: {:?}", error),
+                }
+            }
+        });
+    }
+
+    pub fn wait_for_readiness(&self, node: &Arc<Node>) -> Result<()> {
+        let wait_for_joined = match self.configuration.string_at("readiness.state") {
+            Some(ref state) if state == "joined" => true,
+            Some(state) => {
+                return Err(Error::InvalidValue("readiness.state",
+                                               state,
+                                               READINESS_STATES.to_vec()))
+            }
+            None => false,
+        };
+        let services = self.configuration.strings_at("readiness.services").unwrap_or(Vec::new());
+        if !wait_for_joined && services.is_empty() {
+            return Ok(());
+        }
+
+        let timeout = Duration::milliseconds(self.configuration
+                                                 .i64_at("readiness.timeout_ms")
+                                                 .unwrap_or(DEFAULT_READINESS_TIMEOUT_MS));
+        let deadline = time::now_utc() + timeout;
+
+        info!("waiting up to {} ms for readiness (joined: {}, services: {:?}) before binding \
+               relays",
+              timeout.num_milliseconds(),
+              wait_for_joined,
+              services);
+
+        loop {
+            let state = node.state();
+            let missing = services.iter()
+                                  .filter(|name| !node.has_service(name))
+                                  .collect::<Vec<_>>();
+
+            if (!wait_for_joined || state == node::State::Joined) && missing.is_empty() {
+                info!("node is ready - binding relays");
+                return Ok(());
+            }
+
+            if time::now_utc() >= deadline {
+                warn!("readiness not reached after {} ms (state: {}, missing services: {:?}) - \
+                       binding relays anyway",
+                      timeout.num_milliseconds(),
+                      state,
+                      missing);
+                return Ok(());
             }
+
+            thread::sleep(to_std_duration(Duration::milliseconds(READINESS_POLL_INTERVAL_MS)));
         }
     }
 
-    pub fn load_relays(&self, node: &Arc<Node>) -> Result<Vec<Box<Relay>>> {
-        let mut relays = Vec::new();
+    fn load_retry_policies(&self) -> Result<retry::Policies> {
+        let mut policies = retry::Policies::new();
+        for configuration in self.configuration
+                                 .configurations_at("transport.retry")
+                                 .unwrap_or(Vec::new()) {
+            let max_attempts = try!(configuration.i64_at("max_attempts")
+                                                 .ok_or(Error::MissingField("transport.retry.\
+                                                                             max_attempts")));
+            if max_attempts <= 0 {
+                return Err(Error::InvalidValue("transport.retry.max_attempts",
+                                               max_attempts.to_string(),
+                                               vec!["positive number"]));
+            }
+            let backoff = configuration.i64_at("backoff_ms").unwrap_or(DEFAULT_RETRY_BACKOFF_MS);
+
+            let mut retry_on = Vec::new();
+            for name in configuration.strings_at("retry_on")
+                                     .unwrap_or(vec!["unavailable".to_string()]) {
+                match retry::ErrorKind::parse(&name) {
+                    Some(kind) => retry_on.push(kind),
+                    None => {
+                        return Err(Error::InvalidValue("transport.retry.retry_on",
+                                                       name,
+                                                       RETRY_ERRORS.to_vec()))
+                    }
+                }
+            }
+
+            let policy = retry::Policy::new(max_attempts as usize,
+                                            Duration::milliseconds(backoff),
+                                            retry_on);
+            match configuration.string_at("service") {
+                Some(service) => {
+                    policies.services.insert(service, policy);
+                }
+                None => policies.default = Some(policy),
+            }
+        }
+        Ok(policies)
+    }
+
+    /// Registers the services whose handlers run in child processes.
+    pub fn load_processes(&self, node: &Arc<Node>) -> Result<()> {
+        for configuration in self.configuration
+                                 .configurations_at("process")
+                                 .unwrap_or(Vec::new()) {
+            let service = try!(configuration.string_at("service")
+                                            .ok_or(Error::MissingField("process.service")));
+            let command = try!(configuration.string_at("command")
+                                            .ok_or(Error::MissingField("process.command")));
+            let arguments = configuration.strings_at("arguments").unwrap_or(Vec::new());
+            let process = node::Process::new(&command, &arguments);
+            try!(node.register_handler(&service, Arc::new(process)));
+            info!("loaded service {} running in process {}", service, command);
+        }
+        Ok(())
+    }
+
+    pub fn load_relays(&self, node: &Arc<Node>) -> Result<()> {
         if let Some(configurations) = self.configuration.configurations_at("relay") {
             for configuration in configurations {
-                relays.push(try!(load_relay(&configuration, node)));
+                let retry = match configuration.string_at("on_failure") {
+                    Some(ref policy) if policy == "abort" => false,
+                    Some(ref policy) if policy == "retry" => true,
+                    Some(policy) => {
+                        return Err(Error::InvalidValue("relay.on_failure",
+                                                       policy,
+                                                       RELAY_FAILURE_POLICIES.to_vec()))
+                    }
+                    None => false,
+                };
+
+                match load_relay(&configuration, node, &self.summary) {
+                    Ok(relay) => node.add_relay(relay),
+                    Err(error) => {
+                        if !retry {
+                            return Err(error);
+                        }
+                        warn!("error while loading relay: {:?} - retrying in the background",
+                              error);
+                        self.summary.add("relay", "retrying".to_string());
+                        retry_relay(configuration, Arc::downgrade(node));
+                    }
+                }
             }
         }
-        Ok(relays)
+        Ok(())
+    }
+
+    /// Returns a single line with the effective settings of everything loaded so far.
+    pub fn summary(&self) -> String {
+        let features = [("scripting", cfg!(feature = "scripting")),
+                        ("ssl", cfg!(feature = "ssl"))];
+        format!("{} features={}",
+                self.summary,
+                features.iter()
+                        .filter(|&&(_, enabled)| enabled)
+                        .map(|&(name, _)| name)
+                        .collect::<Vec<_>>()
+                        .join(","))
+    }
+}
+
+impl Summary {
+    fn new() -> Summary {
+        Summary { entries: Mutex::new(Vec::new()) }
+    }
+
+    fn add(&self, key: &'static str, value: String) {
+        self.entries.lock().unwrap().push((key, value));
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut entries = self.entries.lock().unwrap().clone();
+        entries.sort_by_key(|&(key, _)| SUMMARY_KEYS.iter().position(|value| *value == key));
+        let entries = entries.iter()
+                             .map(|&(key, ref value)| format!("{}={}", key, value))
+                             .collect::<Vec<_>>();
+        write!(f, "{}", entries.join(" "))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::MissingField(path) => write!(f, "missing field {}", path),
+            Error::InvalidValue(path, ref value, ref choices) => {
+                write!(f,
+                       "invalid value '{}' for {} (expected {})",
+                       value,
+                       path,
+                       choices.join(", "))
+            }
+            Error::NodeError(ref error) => write!(f, "node: {:?}", error),
+            Error::Cipher(ref error) => write!(f, "cipher: {:?}", error),
+            Error::Relay(ref error) => write!(f, "relay: {:?}", error),
+            Error::Resolve(ref error) => write!(f, "resolve: {}", error),
+            Error::IdFile(ref error) => write!(f, "id file: {}", error),
+            Error::Etcd(ref error) => write!(f, "etcd: {:?}", error),
+            Error::Consul(ref error) => write!(f, "consul: {:?}", error),
+            Error::Script(ref message) => write!(f, "script: {}", message),
+            Error::InsecureCipher => write!(f, "cipher type none requires allow_insecure = true"),
+        }
     }
 }
 
@@ -265,13 +1149,57 @@ impl From<relay::Error> for Error {
     }
 }
 
+impl From<discovery::etcd::Error> for Error {
+    fn from(error: discovery::etcd::Error) -> Self {
+        Error::Etcd(error)
+    }
+}
+
+impl From<discovery::consul::Error> for Error {
+    fn from(error: discovery::consul::Error) -> Self {
+        Error::Consul(error)
+    }
+}
+
 impl From<io::Error> for Error {
     fn from(error: io::Error) -> Self {
         Error::Resolve(error)
     }
 }
 
-fn load_relay(configuration: &Configuration, node: &Arc<Node>) -> Result<Box<Relay>> {
+fn load_keys(configuration: &Configuration) -> Result<Vec<(u32, Vec<u8>)>> {
+    match configuration.configurations_at("cipher.keys") {
+        Some(keys) => {
+            keys.iter()
+                .map(|key| {
+                    let id = try!(key.i64_at("id").ok_or(Error::MissingField("cipher.keys.id")));
+                    let bytes = try!(key.bytes_at("key")
+                                        .ok_or(Error::MissingField("cipher.keys.key")));
+                    Ok((id as u32, bytes))
+                })
+                .collect()
+        }
+        None => {
+            let key = try!(configuration.bytes_at("cipher.key")
+                                        .ok_or(Error::MissingField("cipher.key")));
+            Ok(vec![(0, key)])
+        }
+    }
+}
+
+fn load_rate_limit(configuration: &Configuration, path: &'static str) -> Result<Option<usize>> {
+    match configuration.i64_at(path) {
+        Some(value) if value <= 0 => {
+            Err(Error::InvalidValue(path, value.to_string(), vec!["positive bytes per second"]))
+        }
+        value => Ok(value.map(|value| value as usize)),
+    }
+}
+
+fn load_relay(configuration: &Configuration,
+              node: &Arc<Node>,
+              summary: &Summary)
+              -> Result<Arc<Relay>> {
     let relay_type = try!(configuration.string_at("type")
                                        .ok_or(Error::MissingField("relay.type")));
 
@@ -283,11 +1211,41 @@ fn load_relay(configuration: &Configuration, node: &Arc<Node>) -> Result<Box<Rel
             };
             let header_field = configuration.string_at("header_field")
                                             .unwrap_or("X-Delix-Service".to_string());
+            let key_header_field = configuration.string_at("key_header_field");
+            let trace_header_field = configuration.string_at("trace_header_field")
+                                                  .unwrap_or("X-Delix-Trace-Id".to_string());
             let read_timeout = configuration.i64_at("read_timeout_ms")
                                             .map(|value| Duration::milliseconds(value));
             let write_timeout = configuration.i64_at("write_timeout_ms")
                                              .map(|value| Duration::milliseconds(value));
             let services_path = configuration.string_at("services_path");
+            let filter = match configuration.string_at("script_path") {
+                Some(script_path) => Some(try!(load_script(&script_path))),
+                None => None,
+            };
+
+            let audit = match configuration.string_at("audit.path") {
+                Some(audit_path) => {
+                    let sample_rate = configuration.f64_at("audit.sample_rate")
+                                                   .unwrap_or(DEFAULT_AUDIT_SAMPLE_RATE);
+                    if sample_rate < 0.0 || sample_rate > 1.0 {
+                        return Err(Error::InvalidValue("relay.audit.sample_rate",
+                                                       sample_rate.to_string(),
+                                                       vec!["number between 0 and 1"]));
+                    }
+                    let body_limit = configuration.i64_at("audit.body_limit").unwrap_or(0);
+                    if body_limit < 0 {
+                        return Err(Error::InvalidValue("relay.audit.body_limit",
+                                                       body_limit.to_string(),
+                                                       vec!["positive number of bytes"]));
+                    }
+                    info!("sampling {}% of the relayed requests into {}",
+                          sample_rate * 100.0,
+                          audit_path);
+                    Some(try!(relay::Audit::create(&audit_path, sample_rate, body_limit as usize)))
+                }
+                None => None,
+            };
 
             let api_address = match configuration.string_at("api.address") {
                 Some(address) => Some(try!(resolve::socket_address(&address))),
@@ -298,16 +1256,129 @@ fn load_relay(configuration: &Configuration, node: &Arc<Node>) -> Result<Box<Rel
                                               address,
                                               api_address,
                                               &header_field,
+                                              key_header_field.as_ref().map(String::as_str),
+                                              Some(&trace_header_field),
                                               read_timeout,
                                               write_timeout,
-                                              services_path));
+                                              services_path,
+                                              filter,
+                                              audit));
 
             try!(http.load());
 
             info!("loaded http relay");
+            summary.add("relay",
+                        format!("http(address={}, api={})",
+                                format_address(address),
+                                format_address(api_address)));
+
+            Ok(Arc::new(http))
+        }
+        _ => Err(Error::InvalidValue("relay.type", relay_type.to_string(), RELAY_TYPES.to_vec())),
+    }
+}
 
-            Ok(Box::new(http))
+/// Retries to load the relay until it succeeds or the node is gone.
+fn retry_relay(configuration: Configuration, node: Weak<Node>) {
+    let interval = Duration::milliseconds(configuration.i64_at("retry_interval_ms")
+                                                       .unwrap_or(DEFAULT_RELAY_RETRY_INTERVAL_MS));
+    supervisor().spawn("relay retry", move || {
+        loop {
+            thread::sleep(to_std_duration(interval));
+            let node = match node.upgrade() {
+                Some(node) => node,
+                None => return,
+            };
+            match load_relay(&configuration, &node, &Summary::new()) {
+                Ok(relay) => {
+                    node.add_relay(relay);
+                    return;
+                }
+                Err(error) => warn!("error while retrying to load relay: {:?}", error),
+            }
         }
-        _ => Err(Error::InvalidValue("relay.type", relay_type.to_string(), vec!["http"])),
+    });
+}
+
+#[cfg(feature = "scripting")]
+fn load_script(path: &str) -> Result<Arc<relay::Filter>> {
+    use std::fs;
+    use std::io::Read;
+
+    let mut source = String::new();
+    try!(fs::File::open(path)
+             .and_then(|mut file| file.read_to_string(&mut source))
+             .map_err(|error| Error::Script(format!("{}: {}", path, error))));
+    let script = try!(relay::Script::parse(&source)
+                          .map_err(|error| Error::Script(format!("{}: {}", path, error))));
+    info!("loaded relay script {}", path);
+    Ok(Arc::new(move |head: &mut relay::Head| script.apply(head)))
+}
+
+#[cfg(not(feature = "scripting"))]
+fn load_script(path: &str) -> Result<Arc<relay::Filter>> {
+    Err(Error::Script(format!("{}: scripting support is not enabled in this build", path)))
+}
+
+fn join(addresses: &[SocketAddr]) -> String {
+    addresses.iter()
+             .map(|address| address.to_string())
+             .collect::<Vec<_>>()
+             .join(",")
+}
+
+fn join_seeds(addresses: &[SocketAddr], unresolved: &[String]) -> String {
+    addresses.iter()
+             .map(|address| address.to_string())
+             .chain(unresolved.iter().map(|name| format!("{}?", name)))
+             .collect::<Vec<_>>()
+             .join(",")
+}
+
+fn warn_unresolved(names: &[String]) {
+    for name in names {
+        warn!("could not resolve {} - retrying in the background", name);
+    }
+}
+
+fn format_address(address: Option<SocketAddr>) -> String {
+    address.map(|address| address.to_string()).unwrap_or("-".to_string())
+}
+
+fn parse_log_level(value: Option<String>) -> log::LogLevelFilter {
+    match value.as_ref().map(String::as_str) {
+        Some("error") => log::LogLevelFilter::Error,
+        Some("warn") => log::LogLevelFilter::Warn,
+        Some("info") => log::LogLevelFilter::Info,
+        Some("debug") => log::LogLevelFilter::Debug,
+        Some("trace") => log::LogLevelFilter::Trace,
+        _ => log::LogLevelFilter::Off,
+    }
+}
+
+fn check_choice(value: Option<String>,
+                path: &'static str,
+                choices: &[&'static str],
+                errors: &mut Vec<Error>)
+                -> Option<String> {
+    match value {
+        Some(value) => {
+            if choices.iter().any(|choice| *choice == value) {
+                Some(value)
+            } else {
+                errors.push(Error::InvalidValue(path, value, choices.to_vec()));
+                None
+            }
+        }
+        None => {
+            errors.push(Error::MissingField(path));
+            None
+        }
+    }
+}
+
+fn check_present<T>(value: Option<T>, path: &'static str, errors: &mut Vec<Error>) {
+    if value.is_none() {
+        errors.push(Error::MissingField(path));
     }
 }
@@ -0,0 +1,203 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::VecDeque;
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex, Weak, mpsc};
+
+use log;
+use rustc_serialize::json;
+use time::{self, Duration};
+
+use logger::trace;
+use node::{Node, Service, request, service};
+use util::thread::supervisor;
+
+/// The name of the built-in service that collects the log records of traced requests.
+pub const SERVICE_NAME: &'static str = "delix.logs";
+
+const BATCH_SIZE: usize = 100;
+const FORWARD_TIMEOUT_SECS: i64 = 5;
+
+#[derive(Clone, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Record {
+    pub node: String,
+    pub at: String,
+    pub level: String,
+    pub target: String,
+    pub text: String,
+    pub trace_id: String,
+}
+
+/// Keeps the most recent log records that have been sent to the collector service.
+#[derive(Debug)]
+pub struct Collector {
+    capacity: usize,
+    records: Mutex<VecDeque<Record>>,
+}
+
+/// Sends the log records that are written while a trace id is set to the collector service.
+/// Records are dropped until the forwarder is connected to a node.
+#[derive(Debug)]
+pub struct Forwarder {
+    level_filter: log::LogLevelFilter,
+    tx: Mutex<Option<mpsc::Sender<Record>>>,
+}
+
+impl Collector {
+    pub fn new(capacity: usize) -> Collector {
+        Collector {
+            capacity: capacity,
+            records: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn push(&self, records: Vec<Record>) {
+        let mut buffer = self.records.lock().unwrap();
+        for record in records {
+            if buffer.len() >= self.capacity {
+                buffer.pop_front();
+            }
+            buffer.push_back(record);
+        }
+    }
+
+    /// Returns the records of the trace in the order they have been received.
+    pub fn trace(&self, trace_id: &str) -> Vec<Record> {
+        self.records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|record| record.trace_id == trace_id)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Builds the collector service, which takes a json encoded list of records.
+pub fn service(collector: Arc<Collector>) -> Box<Service> {
+    Box::new(move |mut request: Box<request::Reader>| {
+        let mut content = String::new();
+        if let Err(error) = request.read_to_string(&mut content) {
+            return Err(service::Error::InvalidInput(format!("{}", error)));
+        }
+        let records = match json::decode::<Vec<Record>>(&content) {
+            Ok(records) => records,
+            Err(error) => return Err(service::Error::InvalidInput(format!("{}", error))),
+        };
+        collector.push(records);
+        Ok(Box::new(io::Cursor::new(Vec::new())))
+    })
+}
+
+impl Forwarder {
+    pub fn new(level_filter: log::LogLevelFilter) -> Forwarder {
+        Forwarder {
+            level_filter: level_filter,
+            tx: Mutex::new(None),
+        }
+    }
+
+    pub fn connect(&self, node: Weak<Node>) {
+        let (tx, rx) = mpsc::channel();
+        *self.tx.lock().unwrap() = Some(tx);
+
+        supervisor().spawn("log forwarder", move || {
+            while let Ok(record) = rx.recv() {
+                let mut records = vec![record];
+                while records.len() < BATCH_SIZE {
+                    match rx.try_recv() {
+                        Ok(record) => records.push(record),
+                        Err(_) => break,
+                    }
+                }
+
+                let node = match node.upgrade() {
+                    Some(node) => node,
+                    None => return,
+                };
+                for record in &mut records {
+                    record.node = node.id.to_string();
+                }
+                let content = json::encode(&records).unwrap();
+                let timeout = Duration::seconds(FORWARD_TIMEOUT_SECS);
+                if let Err(error) = node.request_bytes(SERVICE_NAME,
+                                                       content.as_bytes(),
+                                                       Some(timeout)) {
+                    debug!("could not forward {} log records: {}", records.len(), error);
+                }
+            }
+        });
+    }
+
+    pub fn forward(&self, level: log::LogLevel, target: &str, text: &str) {
+        if level > self.level_filter {
+            return;
+        }
+        let trace_id = match trace::trace_id() {
+            Some(trace_id) => trace_id,
+            None => return,
+        };
+        if let Some(ref tx) = *self.tx.lock().unwrap() {
+            let _ = tx.send(Record {
+                node: String::new(),
+                at: format!("{}", time::now_utc().rfc3339()),
+                level: level.to_string(),
+                target: target.to_string(),
+                text: text.to_string(),
+                trace_id: trace_id,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io;
+    use std::sync::Arc;
+    use rustc_serialize::json;
+
+    use super::{Collector, Record, service};
+
+    fn build_record(trace_id: &str, text: &str) -> Record {
+        Record {
+            node: "node".to_string(),
+            at: "2016-01-01T00:00:00Z".to_string(),
+            level: "INFO".to_string(),
+            target: "delix".to_string(),
+            text: text.to_string(),
+            trace_id: trace_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn collect_records_of_a_trace() {
+        let collector = Arc::new(Collector::new(2));
+        let service = service(collector.clone());
+
+        let records = vec![build_record("one", "first"),
+                           build_record("two", "second"),
+                           build_record("one", "third")];
+        let request = json::encode(&records).unwrap().into_bytes();
+        assert!(service(Box::new(io::Cursor::new(request))).is_ok());
+
+        // the capacity only leaves room for the last two records.
+        assert_eq!(vec![build_record("one", "third")], collector.trace("one"));
+        assert_eq!(vec![build_record("two", "second")], collector.trace("two"));
+        assert!(service(Box::new(io::Cursor::new(b"invalid".to_vec()))).is_err());
+    }
+
+}
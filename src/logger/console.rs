@@ -17,12 +17,14 @@ use std::sync::Arc;
 
 use log;
 
+use logger::{Forwarder, trace};
 use metric::Metric;
 
 pub struct Console {
     metric: Arc<Metric>,
     level_filter: log::LogLevelFilter,
     target_prefix: String,
+    forwarder: Option<Arc<Forwarder>>,
 }
 
 impl Console {
@@ -30,9 +32,20 @@ impl Console {
                 target_prefix: &str,
                 metric: &Arc<Metric>)
                 -> Result<(), log::SetLoggerError> {
+        Self::init_with_forwarder(level_filter, target_prefix, metric, None)
+    }
+
+    /// Installs the console logger. Records of traced requests are also handed to the forwarder.
+    pub fn init_with_forwarder(level_filter: log::LogLevelFilter,
+                               target_prefix: &str,
+                               metric: &Arc<Metric>,
+                               forwarder: Option<Arc<Forwarder>>)
+                               -> Result<(), log::SetLoggerError> {
         let result = log::set_logger(|maximal_log_level| {
             maximal_log_level.set(level_filter);
-            Box::new(Console::new(level_filter, target_prefix, metric))
+            let mut console = Console::new(level_filter, target_prefix, metric);
+            console.forwarder = forwarder;
+            Box::new(console)
         });
         result
     }
@@ -47,6 +60,7 @@ impl Console {
             metric: metric.clone(),
             level_filter: level_filter,
             target_prefix: target_prefix.to_string(),
+            forwarder: None,
         }
     }
 }
@@ -76,6 +90,14 @@ impl log::Log for Console {
         };
         let text = format!("{}", record.args());
 
-        self.metric.log(&tag.to_string(), &target, &text);
+        match trace::trace_id() {
+            Some(trace_id) => {
+                self.metric.log(&tag.to_string(), &target, &format!("[{}] {}", trace_id, text))
+            }
+            None => self.metric.log(&tag.to_string(), &target, &text),
+        }
+        if let Some(ref forwarder) = self.forwarder {
+            forwarder.forward(record.level(), &target, &text);
+        }
     }
 }
@@ -13,6 +13,9 @@
 // limitations under the License.
 //
 
+pub mod collector;
 pub mod console;
+pub mod trace;
 
+pub use logger::collector::{Collector, Forwarder};
 pub use logger::console::Console;
@@ -0,0 +1,35 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::cell::RefCell;
+
+use rand;
+
+thread_local!(static TRACE_ID: RefCell<Option<String>> = RefCell::new(None));
+
+/// Sets the trace id of the current thread. Log records that are written while it's set are
+/// tagged with it.
+pub fn set_trace_id(trace_id: Option<String>) {
+    TRACE_ID.with(|current| *current.borrow_mut() = trace_id);
+}
+
+pub fn trace_id() -> Option<String> {
+    TRACE_ID.with(|current| current.borrow().clone())
+}
+
+/// Returns a new random trace id.
+pub fn generate() -> String {
+    format!("{:016x}", rand::random::<u64>())
+}
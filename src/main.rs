@@ -13,6 +13,8 @@
 // limitations under the License.
 //
 
+#[cfg(not(test))]
+extern crate backtrace;
 #[cfg(not(test))]
 extern crate delix;
 
@@ -33,6 +35,8 @@ mod arguments;
 #[cfg(not(test))]
 mod configuration;
 #[cfg(not(test))]
+mod crash;
+#[cfg(not(test))]
 mod loader;
 
 #[cfg(not(test))]
@@ -49,12 +53,18 @@ fn main() {
     let configuration = match ::configuration::Configuration::read_file(&arguments.configuration_path) {
         Ok(configuration) => configuration,
         Err(error) => {
-            error!("error while reading configuration: {:?}", error);
+            error!("error while reading configuration: {}", error);
             return;
         },
     };
 
     let loader = ::loader::Loader::new(configuration);
+    if let Err(errors) = loader.validate() {
+        for error in errors {
+            error!("invalid configuration: {}", error);
+        }
+        return;
+    }
 
     let metric = match loader.load_metric() {
         Ok(metric) => metric,
@@ -68,6 +78,7 @@ fn main() {
         error!("error while loading log: {:?}", error);
         return;
     }
+    let crash_handle = ::crash::install(loader.load_crash_report_directory());
 
     let node = match loader.load_node(&metric) {
         Ok(node) => node,
@@ -76,16 +87,33 @@ fn main() {
             return;
         }
     };
+    crash_handle.set_node_id(node.id);
 
-    node.join();
+    if let Err(error) = loader.load_processes(&node) {
+        error!("error while loading processes: {:?}", error);
+        return;
+    }
 
-    let relays = match loader.load_relays(&node) {
-        Ok(relays) => relays,
-        Err(error) => {
-            error!("error while loading relays: {:?}", error);
-            return;
-        }
-    };
+    loader.watch_cipher_keys(&arguments.configuration_path);
+
+    if let Some(report) = node.join_with_progress(&|event| info!("join: {}", event)) {
+        info!("joined network - {}", report);
+    }
+
+    if let Err(error) = loader.wait_for_readiness(&node) {
+        error!("error while waiting for readiness: {:?}", error);
+        return;
+    }
+
+    if let Err(error) = loader.load_relays(&node) {
+        error!("error while loading relays: {:?}", error);
+        node.leave();
+        return;
+    }
+
+    info!("started - {}", loader.summary());
 
     metric.display();
+
+    node.shutdown();
 }
@@ -16,6 +16,7 @@ pub struct Container {
     // message fields
     kind: ::std::option::Option<Kind>,
     payload: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    compressed: ::std::option::Option<bool>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::std::cell::Cell<u32>,
@@ -36,6 +37,7 @@ impl Container {
                 Container {
                     kind: ::std::option::Option::None,
                     payload: ::protobuf::SingularField::none(),
+                    compressed: ::std::option::Option::None,
                     unknown_fields: ::protobuf::UnknownFields::new(),
                     cached_size: ::std::cell::Cell::new(0),
                 }
@@ -97,6 +99,25 @@ impl Container {
             None => &[],
         }
     }
+
+    // optional bool compressed = 3;
+
+    pub fn clear_compressed(&mut self) {
+        self.compressed = ::std::option::Option::None;
+    }
+
+    pub fn has_compressed(&self) -> bool {
+        self.compressed.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_compressed(&mut self, v: bool) {
+        self.compressed = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_compressed<'a>(&self) -> bool {
+        self.compressed.unwrap_or(false)
+    }
 }
 
 impl ::protobuf::Message for Container {
@@ -122,6 +143,13 @@ impl ::protobuf::Message for Container {
                     let tmp = self.payload.set_default();
                     try!(is.read_bytes_into(tmp))
                 },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_bool());
+                    self.compressed = ::std::option::Option::Some(tmp);
+                },
                 _ => {
                     let unknown = try!(is.read_unknown(wire_type));
                     self.mut_unknown_fields().add_value(field_number, unknown);
@@ -141,6 +169,9 @@ impl ::protobuf::Message for Container {
         for value in self.payload.iter() {
             my_size += ::protobuf::rt::bytes_size(2, &value);
         };
+        if self.compressed.is_some() {
+            my_size += 2;
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -153,6 +184,9 @@ impl ::protobuf::Message for Container {
         if let Some(v) = self.payload.as_ref() {
             try!(os.write_bytes(2, &v));
         };
+        if let Some(v) = self.compressed {
+            try!(os.write_bool(3, v));
+        };
         try!(os.write_unknown_fields(self.get_unknown_fields()));
         ::std::result::Result::Ok(())
     }
@@ -205,6 +239,11 @@ impl ::protobuf::MessageStatic for Container {
                     Container::has_payload,
                     Container::get_payload,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_bool_accessor(
+                    "compressed",
+                    Container::has_compressed,
+                    Container::get_compressed,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Container>(
                     "Container",
                     fields,
@@ -219,6 +258,7 @@ impl ::protobuf::Clear for Container {
     fn clear(&mut self) {
         self.clear_kind();
         self.clear_payload();
+        self.clear_compressed();
         self.unknown_fields.clear();
     }
 }
@@ -227,6 +267,7 @@ impl ::std::cmp::PartialEq for Container {
     fn eq(&self, other: &Container) -> bool {
         self.kind == other.kind &&
         self.payload == other.payload &&
+        self.compressed == other.compressed &&
         self.unknown_fields == other.unknown_fields
     }
 }
@@ -240,23 +281,28 @@ impl ::std::fmt::Debug for Container {
 static file_descriptor_proto_data: &'static [u8] = &[
     0x0a, 0x0f, 0x63, 0x6f, 0x6e, 0x74, 0x61, 0x69, 0x6e, 0x65, 0x72, 0x2e, 0x70, 0x72, 0x6f, 0x74,
     0x6f, 0x12, 0x07, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x1a, 0x0a, 0x6b, 0x69, 0x6e, 0x64,
-    0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x22, 0x39, 0x0a, 0x09, 0x43, 0x6f, 0x6e, 0x74, 0x61, 0x69,
+    0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x22, 0x4d, 0x0a, 0x09, 0x43, 0x6f, 0x6e, 0x74, 0x61, 0x69,
     0x6e, 0x65, 0x72, 0x12, 0x1b, 0x0a, 0x04, 0x6b, 0x69, 0x6e, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28,
     0x0e, 0x32, 0x0d, 0x2e, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x2e, 0x4b, 0x69, 0x6e, 0x64,
     0x12, 0x0f, 0x0a, 0x07, 0x70, 0x61, 0x79, 0x6c, 0x6f, 0x61, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28,
-    0x0c, 0x4a, 0xbf, 0x01, 0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x07, 0x01, 0x0a, 0x08, 0x0a, 0x01,
-    0x02, 0x12, 0x03, 0x00, 0x08, 0x0f, 0x0a, 0x09, 0x0a, 0x02, 0x03, 0x00, 0x12, 0x03, 0x02, 0x07,
-    0x13, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x04, 0x00, 0x07, 0x01, 0x0a, 0x0a, 0x0a,
-    0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x04, 0x08, 0x11, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02,
-    0x00, 0x12, 0x03, 0x05, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x04, 0x12,
-    0x03, 0x05, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x06, 0x12, 0x03, 0x05,
-    0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x05, 0x10, 0x14,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12, 0x03, 0x05, 0x17, 0x18, 0x0a, 0x0b,
-    0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x06, 0x02, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x00, 0x02, 0x01, 0x04, 0x12, 0x03, 0x06, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
-    0x01, 0x05, 0x12, 0x03, 0x06, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01,
-    0x12, 0x03, 0x06, 0x11, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03,
-    0x06, 0x1b, 0x1c,
+    0x0c, 0x12, 0x12, 0x0a, 0x0a, 0x63, 0x6f, 0x6d, 0x70, 0x72, 0x65, 0x73, 0x73, 0x65, 0x64, 0x18,
+    0x03, 0x20, 0x01, 0x28, 0x08, 0x4a, 0x84, 0x02, 0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x08, 0x01,
+    0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12, 0x03, 0x00, 0x08, 0x0f, 0x0a, 0x09, 0x0a, 0x02, 0x03, 0x00,
+    0x12, 0x03, 0x02, 0x07, 0x13, 0x0a, 0x0a, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x04, 0x00, 0x08,
+    0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x04, 0x08, 0x11, 0x0a, 0x0b, 0x0a,
+    0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x05, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
+    0x02, 0x00, 0x04, 0x12, 0x03, 0x05, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00,
+    0x06, 0x12, 0x03, 0x05, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12,
+    0x03, 0x05, 0x10, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12, 0x03, 0x05,
+    0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x06, 0x02, 0x1d, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x04, 0x12, 0x03, 0x06, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x00, 0x02, 0x01, 0x05, 0x12, 0x03, 0x06, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x06, 0x11, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
+    0x01, 0x03, 0x12, 0x03, 0x06, 0x1b, 0x1c, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x02, 0x12,
+    0x03, 0x07, 0x02, 0x1f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x04, 0x12, 0x03, 0x07,
+    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x05, 0x12, 0x03, 0x07, 0x0b, 0x0f,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03, 0x07, 0x10, 0x1a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x03, 0x12, 0x03, 0x07, 0x1d, 0x1e,
 ];
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -0,0 +1,539 @@
+// This file is generated. Do not edit
+// @generated
+
+#![allow(dead_code)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(unused_imports)]
+
+use protobuf::Message as Message_imported_for_functions;
+use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
+
+#[derive(Clone,Default)]
+pub struct DrainRequest {
+    // message fields
+    acquire: ::std::option::Option<bool>,
+    request_id: ::std::option::Option<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::std::cell::Cell<u32>,
+}
+
+impl DrainRequest {
+    pub fn new() -> DrainRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static DrainRequest {
+        static mut instance: ::protobuf::lazy::Lazy<DrainRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DrainRequest,
+        };
+        unsafe {
+            instance.get(|| {
+                DrainRequest {
+                    acquire: ::std::option::Option::None,
+                    request_id: ::std::option::Option::None,
+                    unknown_fields: ::protobuf::UnknownFields::new(),
+                    cached_size: ::std::cell::Cell::new(0),
+                }
+            })
+        }
+    }
+
+    // optional bool acquire = 1;
+
+    pub fn clear_acquire(&mut self) {
+        self.acquire = ::std::option::Option::None;
+    }
+
+    pub fn has_acquire(&self) -> bool {
+        self.acquire.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_acquire(&mut self, v: bool) {
+        self.acquire = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_acquire<'a>(&self) -> bool {
+        self.acquire.unwrap_or(false)
+    }
+
+    // optional uint64 request_id = 2;
+
+    pub fn clear_request_id(&mut self) {
+        self.request_id = ::std::option::Option::None;
+    }
+
+    pub fn has_request_id(&self) -> bool {
+        self.request_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_request_id(&mut self, v: u64) {
+        self.request_id = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_request_id<'a>(&self) -> u64 {
+        self.request_id.unwrap_or(0)
+    }
+}
+
+impl ::protobuf::Message for DrainRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !try!(is.eof()) {
+            let (field_number, wire_type) = try!(is.read_tag_unpack());
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_bool());
+                    self.acquire = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint64());
+                    self.request_id = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    let unknown = try!(is.read_unknown(wire_type));
+                    self.mut_unknown_fields().add_value(field_number, unknown);
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.acquire.is_some() {
+            my_size += 2;
+        };
+        for value in self.request_id.iter() {
+            my_size += ::protobuf::rt::value_size(2, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.acquire {
+            try!(os.write_bool(1, v));
+        };
+        if let Some(v) = self.request_id {
+            try!(os.write_uint64(2, v));
+        };
+        try!(os.write_unknown_fields(self.get_unknown_fields()));
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields<'s>(&'s self) -> &'s ::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields<'s>(&'s mut self) -> &'s mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn type_id(&self) -> ::std::any::TypeId {
+        ::std::any::TypeId::of::<DrainRequest>()
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for DrainRequest {
+    fn new() -> DrainRequest {
+        DrainRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<DrainRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_bool_accessor(
+                    "acquire",
+                    DrainRequest::has_acquire,
+                    DrainRequest::get_acquire,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                    "request_id",
+                    DrainRequest::has_request_id,
+                    DrainRequest::get_request_id,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DrainRequest>(
+                    "DrainRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for DrainRequest {
+    fn clear(&mut self) {
+        self.clear_acquire();
+        self.clear_request_id();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::cmp::PartialEq for DrainRequest {
+    fn eq(&self, other: &DrainRequest) -> bool {
+        self.acquire == other.acquire &&
+        self.request_id == other.request_id &&
+        self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl ::std::fmt::Debug for DrainRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+#[derive(Clone,Default)]
+pub struct DrainResponse {
+    // message fields
+    granted: ::std::option::Option<bool>,
+    draining: ::std::option::Option<u32>,
+    request_id: ::std::option::Option<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::std::cell::Cell<u32>,
+}
+
+impl DrainResponse {
+    pub fn new() -> DrainResponse {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static DrainResponse {
+        static mut instance: ::protobuf::lazy::Lazy<DrainResponse> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const DrainResponse,
+        };
+        unsafe {
+            instance.get(|| {
+                DrainResponse {
+                    granted: ::std::option::Option::None,
+                    draining: ::std::option::Option::None,
+                    request_id: ::std::option::Option::None,
+                    unknown_fields: ::protobuf::UnknownFields::new(),
+                    cached_size: ::std::cell::Cell::new(0),
+                }
+            })
+        }
+    }
+
+    // optional bool granted = 1;
+
+    pub fn clear_granted(&mut self) {
+        self.granted = ::std::option::Option::None;
+    }
+
+    pub fn has_granted(&self) -> bool {
+        self.granted.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_granted(&mut self, v: bool) {
+        self.granted = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_granted<'a>(&self) -> bool {
+        self.granted.unwrap_or(false)
+    }
+
+    // optional uint32 draining = 2;
+
+    pub fn clear_draining(&mut self) {
+        self.draining = ::std::option::Option::None;
+    }
+
+    pub fn has_draining(&self) -> bool {
+        self.draining.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_draining(&mut self, v: u32) {
+        self.draining = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_draining<'a>(&self) -> u32 {
+        self.draining.unwrap_or(0)
+    }
+
+    // optional uint64 request_id = 3;
+
+    pub fn clear_request_id(&mut self) {
+        self.request_id = ::std::option::Option::None;
+    }
+
+    pub fn has_request_id(&self) -> bool {
+        self.request_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_request_id(&mut self, v: u64) {
+        self.request_id = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_request_id<'a>(&self) -> u64 {
+        self.request_id.unwrap_or(0)
+    }
+}
+
+impl ::protobuf::Message for DrainResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !try!(is.eof()) {
+            let (field_number, wire_type) = try!(is.read_tag_unpack());
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_bool());
+                    self.granted = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint32());
+                    self.draining = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint64());
+                    self.request_id = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    let unknown = try!(is.read_unknown(wire_type));
+                    self.mut_unknown_fields().add_value(field_number, unknown);
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.granted.is_some() {
+            my_size += 2;
+        };
+        for value in self.draining.iter() {
+            my_size += ::protobuf::rt::value_size(2, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in self.request_id.iter() {
+            my_size += ::protobuf::rt::value_size(3, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.granted {
+            try!(os.write_bool(1, v));
+        };
+        if let Some(v) = self.draining {
+            try!(os.write_uint32(2, v));
+        };
+        if let Some(v) = self.request_id {
+            try!(os.write_uint64(3, v));
+        };
+        try!(os.write_unknown_fields(self.get_unknown_fields()));
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields<'s>(&'s self) -> &'s ::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields<'s>(&'s mut self) -> &'s mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn type_id(&self) -> ::std::any::TypeId {
+        ::std::any::TypeId::of::<DrainResponse>()
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for DrainResponse {
+    fn new() -> DrainResponse {
+        DrainResponse::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<DrainResponse>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_bool_accessor(
+                    "granted",
+                    DrainResponse::has_granted,
+                    DrainResponse::get_granted,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u32_accessor(
+                    "draining",
+                    DrainResponse::has_draining,
+                    DrainResponse::get_draining,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                    "request_id",
+                    DrainResponse::has_request_id,
+                    DrainResponse::get_request_id,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<DrainResponse>(
+                    "DrainResponse",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for DrainResponse {
+    fn clear(&mut self) {
+        self.clear_granted();
+        self.clear_draining();
+        self.clear_request_id();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::cmp::PartialEq for DrainResponse {
+    fn eq(&self, other: &DrainResponse) -> bool {
+        self.granted == other.granted &&
+        self.draining == other.draining &&
+        self.request_id == other.request_id &&
+        self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl ::std::fmt::Debug for DrainResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = &[
+    0x0a, 0x0b, 0x64, 0x72, 0x61, 0x69, 0x6e, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12, 0x07, 0x6d,
+    0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x22, 0x33, 0x0a, 0x0c, 0x44, 0x72, 0x61, 0x69, 0x6e, 0x52,
+    0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x0f, 0x0a, 0x07, 0x61, 0x63, 0x71, 0x75, 0x69, 0x72,
+    0x65, 0x18, 0x01, 0x20, 0x01, 0x28, 0x08, 0x12, 0x12, 0x0a, 0x0a, 0x72, 0x65, 0x71, 0x75, 0x65,
+    0x73, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x04, 0x22, 0x46, 0x0a, 0x0d, 0x44,
+    0x72, 0x61, 0x69, 0x6e, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x0f, 0x0a, 0x07,
+    0x67, 0x72, 0x61, 0x6e, 0x74, 0x65, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x08, 0x12, 0x10, 0x0a,
+    0x08, 0x64, 0x72, 0x61, 0x69, 0x6e, 0x69, 0x6e, 0x67, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0d, 0x12,
+    0x12, 0x0a, 0x0a, 0x72, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x03, 0x20,
+    0x01, 0x28, 0x04, 0x4a, 0xeb, 0x04, 0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x0e, 0x01, 0x0a, 0x08,
+    0x0a, 0x01, 0x02, 0x12, 0x03, 0x00, 0x08, 0x0f, 0x0a, 0x82, 0x01, 0x0a, 0x02, 0x04, 0x00, 0x12,
+    0x04, 0x04, 0x00, 0x07, 0x01, 0x1a, 0x76, 0x20, 0x44, 0x72, 0x61, 0x69, 0x6e, 0x52, 0x65, 0x71,
+    0x75, 0x65, 0x73, 0x74, 0x20, 0x61, 0x73, 0x6b, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x6f,
+    0x6f, 0x72, 0x64, 0x69, 0x6e, 0x61, 0x74, 0x69, 0x6e, 0x67, 0x20, 0x6e, 0x6f, 0x64, 0x65, 0x20,
+    0x66, 0x6f, 0x72, 0x20, 0x61, 0x20, 0x64, 0x72, 0x61, 0x69, 0x6e, 0x20, 0x70, 0x65, 0x72, 0x6d,
+    0x69, 0x74, 0x20, 0x6f, 0x72, 0x20, 0x72, 0x65, 0x74, 0x75, 0x72, 0x6e, 0x73, 0x20, 0x69, 0x74,
+    0x2e, 0x20, 0x54, 0x68, 0x65, 0x20, 0x72, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x20, 0x69, 0x64,
+    0x20, 0x69, 0x73, 0x0a, 0x20, 0x65, 0x63, 0x68, 0x6f, 0x65, 0x64, 0x20, 0x69, 0x6e, 0x20, 0x74,
+    0x68, 0x65, 0x20, 0x72, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x2e, 0x0a, 0x0a, 0x0a, 0x0a,
+    0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x04, 0x08, 0x14, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02,
+    0x00, 0x12, 0x03, 0x05, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x04, 0x12,
+    0x03, 0x05, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x05, 0x12, 0x03, 0x05,
+    0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x05, 0x10, 0x17,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12, 0x03, 0x05, 0x1a, 0x1b, 0x0a, 0x0b,
+    0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x06, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x00, 0x02, 0x01, 0x04, 0x12, 0x03, 0x06, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
+    0x01, 0x05, 0x12, 0x03, 0x06, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01,
+    0x12, 0x03, 0x06, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03,
+    0x06, 0x1f, 0x20, 0x0a, 0x61, 0x0a, 0x02, 0x04, 0x01, 0x12, 0x04, 0x0a, 0x00, 0x0e, 0x01, 0x1a,
+    0x55, 0x20, 0x44, 0x72, 0x61, 0x69, 0x6e, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x20,
+    0x74, 0x65, 0x6c, 0x6c, 0x73, 0x20, 0x77, 0x68, 0x65, 0x74, 0x68, 0x65, 0x72, 0x20, 0x74, 0x68,
+    0x65, 0x20, 0x70, 0x65, 0x72, 0x6d, 0x69, 0x74, 0x20, 0x77, 0x61, 0x73, 0x20, 0x67, 0x72, 0x61,
+    0x6e, 0x74, 0x65, 0x64, 0x20, 0x61, 0x6e, 0x64, 0x20, 0x68, 0x6f, 0x77, 0x20, 0x6d, 0x61, 0x6e,
+    0x79, 0x20, 0x6e, 0x6f, 0x64, 0x65, 0x73, 0x20, 0x61, 0x72, 0x65, 0x20, 0x64, 0x72, 0x61, 0x69,
+    0x6e, 0x69, 0x6e, 0x67, 0x2e, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x01, 0x01, 0x12, 0x03, 0x0a,
+    0x08, 0x15, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x00, 0x12, 0x03, 0x0b, 0x02, 0x1c, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x04, 0x12, 0x03, 0x0b, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x01, 0x02, 0x00, 0x05, 0x12, 0x03, 0x0b, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x01, 0x02, 0x00, 0x01, 0x12, 0x03, 0x0b, 0x10, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
+    0x00, 0x03, 0x12, 0x03, 0x0b, 0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x01, 0x12,
+    0x03, 0x0c, 0x02, 0x1f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x04, 0x12, 0x03, 0x0c,
+    0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x05, 0x12, 0x03, 0x0c, 0x0b, 0x11,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x01, 0x12, 0x03, 0x0c, 0x12, 0x1a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x03, 0x12, 0x03, 0x0c, 0x1d, 0x1e, 0x0a, 0x0b, 0x0a, 0x04,
+    0x04, 0x01, 0x02, 0x02, 0x12, 0x03, 0x0d, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
+    0x02, 0x04, 0x12, 0x03, 0x0d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x05,
+    0x12, 0x03, 0x0d, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x01, 0x12, 0x03,
+    0x0d, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x03, 0x12, 0x03, 0x0d, 0x1f,
+    0x20,
+];
+
+static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
+    lock: ::protobuf::lazy::ONCE_INIT,
+    ptr: 0 as *const ::protobuf::descriptor::FileDescriptorProto,
+};
+
+fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
+    ::protobuf::parse_from_bytes(file_descriptor_proto_data).unwrap()
+}
+
+pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    unsafe {
+        file_descriptor_proto_lazy.get(|| {
+            parse_descriptor_proto()
+        })
+    }
+}
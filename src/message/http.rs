@@ -0,0 +1,490 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::result;
+use std::io::{self, Read};
+
+use protobuf::RepeatedField;
+
+use super::http_header::HttpHeader;
+use super::http_request::HttpRequest;
+use super::http_response::HttpResponse;
+
+const DEFAULT_VERSION: &'static str = "HTTP/1.1";
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Incomplete,
+    InvalidStartLine(String),
+    InvalidHeader(String),
+    InvalidContentLength(String),
+    InvalidChunk,
+}
+
+pub fn split_header_line(line: &str) -> Option<(&str, &str)> {
+    if line.starts_with(' ') || line.starts_with('\t') {
+        return None;
+    }
+    line.find(':').map(|index| {
+        let (name, value) = line.split_at(index);
+        (name.trim(), value[1..].trim())
+    })
+}
+
+pub fn request_from_bytes(bytes: &[u8]) -> Result<HttpRequest> {
+    let (start_line, headers, rest) = try!(parse_head(bytes));
+
+    let parts = start_line.split_whitespace().collect::<Vec<_>>();
+    if parts.len() != 3 || !parts[2].starts_with("HTTP/") {
+        return Err(Error::InvalidStartLine(start_line));
+    }
+
+    let (headers, body) = try!(parse_body(headers, rest, false));
+
+    let mut request = HttpRequest::new();
+    request.set_method(parts[0].to_string());
+    request.set_path(parts[1].to_string());
+    request.set_version(parts[2].to_string());
+    request.set_headers(headers);
+    request.set_body(body);
+    Ok(request)
+}
+
+pub fn request_to_bytes(request: &HttpRequest) -> Vec<u8> {
+    let version = if request.has_version() {
+        request.get_version()
+    } else {
+        DEFAULT_VERSION
+    };
+    let mut bytes = format!("{} {} {}\r\n",
+                            request.get_method(),
+                            request.get_path(),
+                            version)
+                        .into_bytes();
+    write_headers_and_body(&mut bytes, request.get_headers(), request.get_body());
+    bytes
+}
+
+pub fn response_from_bytes(bytes: &[u8]) -> Result<HttpResponse> {
+    let (start_line, headers, rest) = try!(parse_head(bytes));
+
+    let mut parts = start_line.splitn(3, ' ');
+    let version = parts.next().unwrap_or("").to_string();
+    if !version.starts_with("HTTP/") {
+        return Err(Error::InvalidStartLine(start_line.clone()));
+    }
+    let status_code = match parts.next().and_then(|value| value.parse::<u32>().ok()) {
+        Some(status_code) => status_code,
+        None => return Err(Error::InvalidStartLine(start_line.clone())),
+    };
+    let reason = parts.next().unwrap_or("").trim().to_string();
+
+    let (headers, body) = try!(parse_body(headers, rest, true));
+
+    let mut response = HttpResponse::new();
+    response.set_version(version);
+    response.set_status_code(status_code);
+    response.set_reason(reason);
+    response.set_headers(headers);
+    response.set_body(body);
+    Ok(response)
+}
+
+pub fn response_to_bytes(response: &HttpResponse) -> Vec<u8> {
+    let version = if response.has_version() {
+        response.get_version()
+    } else {
+        DEFAULT_VERSION
+    };
+    let mut bytes = format!("{} {} {}\r\n",
+                            version,
+                            response.get_status_code(),
+                            response.get_reason())
+                        .into_bytes();
+    write_headers_and_body(&mut bytes, response.get_headers(), response.get_body());
+    bytes
+}
+
+pub fn request_from_hyper(request: &mut hyper::server::Request) -> io::Result<HttpRequest> {
+    let mut body = Vec::new();
+    try!(request.read_to_end(&mut body));
+
+    let mut result = HttpRequest::new();
+    result.set_method(request.method.to_string());
+    result.set_path(request.uri.to_string());
+    result.set_version(request.version.to_string());
+    result.set_headers(headers_from_hyper(&request.headers));
+    result.set_body(body);
+    Ok(result)
+}
+
+pub fn response_from_hyper(response: &mut hyper::client::Response) -> io::Result<HttpResponse> {
+    let mut body = Vec::new();
+    try!(response.read_to_end(&mut body));
+
+    let mut result = HttpResponse::new();
+    result.set_version(response.version.to_string());
+    result.set_status_code(response.status.to_u16() as u32);
+    result.set_reason(response.status.canonical_reason().unwrap_or("").to_string());
+    result.set_headers(headers_from_hyper(&response.headers));
+    result.set_body(body);
+    Ok(result)
+}
+
+pub fn response_to_hyper(response: &HttpResponse,
+                         mut target: hyper::server::Response)
+                         -> io::Result<()> {
+    *target.status_mut() = hyper::status::StatusCode::from_u16(response.get_status_code() as u16);
+    {
+        let headers = target.headers_mut();
+        for header in response.get_headers() {
+            let name = header.get_name().to_lowercase();
+            if name == "content-length" || name == "transfer-encoding" {
+                continue;
+            }
+            let mut values = headers.get_raw(header.get_name())
+                                    .map(|values| values.to_vec())
+                                    .unwrap_or(Vec::new());
+            values.push(header.get_value().as_bytes().to_vec());
+            headers.set_raw(header.get_name().to_string(), values);
+        }
+    }
+    target.send(response.get_body())
+}
+
+fn headers_from_hyper(headers: &hyper::header::Headers) -> RepeatedField<HttpHeader> {
+    let mut result = RepeatedField::new();
+    for header in headers.iter() {
+        result.push(build_header(header.name(), &header.value_string()));
+    }
+    result
+}
+
+fn parse_head(bytes: &[u8]) -> Result<(String, RepeatedField<HttpHeader>, &[u8])> {
+    let (head, rest) = match find_head_end(bytes) {
+        Some((head_end, body_start)) => (&bytes[..head_end], &bytes[body_start..]),
+        None => return Err(Error::Incomplete),
+    };
+    let head = String::from_utf8_lossy(head);
+
+    let mut lines = Vec::<String>::new();
+    for line in head.split('\n') {
+        let line = line.trim_right_matches('\r');
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if lines.len() < 2 {
+                return Err(Error::InvalidHeader(line.to_string()));
+            }
+            let last = lines.last_mut().unwrap();
+            last.push(' ');
+            last.push_str(line.trim());
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+
+    let start_line = lines.remove(0);
+    let mut headers = RepeatedField::new();
+    for line in lines {
+        match split_header_line(&line) {
+            Some((name, value)) => headers.push(build_header(name, value)),
+            None => return Err(Error::InvalidHeader(line.clone())),
+        }
+    }
+
+    Ok((start_line, headers, rest))
+}
+
+fn find_head_end(bytes: &[u8]) -> Option<(usize, usize)> {
+    for index in 0..bytes.len() {
+        if bytes[index..].starts_with(b"\r\n\r\n") {
+            return Some((index, index + 4));
+        }
+        if bytes[index..].starts_with(b"\n\n") {
+            return Some((index, index + 2));
+        }
+    }
+    None
+}
+
+fn parse_body(headers: RepeatedField<HttpHeader>,
+              rest: &[u8],
+              read_to_end: bool)
+              -> Result<(RepeatedField<HttpHeader>, Vec<u8>)> {
+    let chunked = headers.iter().any(|header| {
+        header.get_name().to_lowercase() == "transfer-encoding" &&
+        header.get_value().to_lowercase() == "chunked"
+    });
+    if chunked {
+        let body = try!(decode_chunks(rest));
+        let mut headers = RepeatedField::from_vec(headers.into_vec()
+                                                         .into_iter()
+                                                         .filter(|header| {
+                                                             header.get_name().to_lowercase() !=
+                                                             "transfer-encoding"
+                                                         })
+                                                         .collect());
+        headers.push(build_header("Content-Length", &body.len().to_string()));
+        return Ok((headers, body));
+    }
+
+    let content_length = headers.iter()
+                                .find(|header| {
+                                    header.get_name().to_lowercase() == "content-length"
+                                })
+                                .map(|header| header.get_value().to_string());
+    let body = match content_length {
+        Some(value) => {
+            let size = match value.parse::<usize>() {
+                Ok(size) => size,
+                Err(_) => return Err(Error::InvalidContentLength(value)),
+            };
+            if rest.len() < size {
+                return Err(Error::Incomplete);
+            }
+            rest[..size].to_vec()
+        }
+        None if read_to_end => rest.to_vec(),
+        None => Vec::new(),
+    };
+
+    Ok((headers, body))
+}
+
+fn decode_chunks(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+    let mut position = 0;
+    loop {
+        let line_end = match bytes[position..].iter().position(|&byte| byte == b'\n') {
+            Some(index) => position + index,
+            None => return Err(Error::Incomplete),
+        };
+        let line = String::from_utf8_lossy(&bytes[position..line_end]);
+        let size = match usize::from_str_radix(line.split(';').next().unwrap().trim(), 16) {
+            Ok(size) => size,
+            Err(_) => return Err(Error::InvalidChunk),
+        };
+        position = line_end + 1;
+
+        if size == 0 {
+            return Ok(body);
+        }
+        if bytes.len() < position + size {
+            return Err(Error::Incomplete);
+        }
+        body.extend_from_slice(&bytes[position..position + size]);
+        position += size;
+
+        if bytes[position..].starts_with(b"\r\n") {
+            position += 2;
+        } else if bytes[position..].starts_with(b"\n") {
+            position += 1;
+        } else {
+            return Err(Error::InvalidChunk);
+        }
+    }
+}
+
+fn write_headers_and_body(bytes: &mut Vec<u8>, headers: &[HttpHeader], body: &[u8]) {
+    let mut content_length = false;
+    for header in headers {
+        if header.get_name().to_lowercase() == "content-length" {
+            content_length = true;
+        }
+        bytes.extend_from_slice(format!("{}: {}\r\n", header.get_name(), header.get_value())
+                                    .as_bytes());
+    }
+    if !content_length && body.len() > 0 {
+        bytes.extend_from_slice(format!("Content-Length: {}\r\n", body.len()).as_bytes());
+    }
+    bytes.extend_from_slice(b"\r\n");
+    bytes.extend_from_slice(body);
+}
+
+fn build_header(name: &str, value: &str) -> HttpHeader {
+    let mut header = HttpHeader::new();
+    header.set_name(name.to_string());
+    header.set_value(value.to_string());
+    header
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::{Mutex, mpsc};
+    use protobuf::RepeatedField;
+    use hyper;
+    use super::super::http_response::HttpResponse;
+    use super::{build_header, request_from_hyper, response_from_hyper, response_to_hyper};
+    use super::{Error, request_from_bytes, request_to_bytes, response_from_bytes,
+                response_to_bytes, split_header_line};
+
+    #[test]
+    fn split_header_line_with_colon_in_value() {
+        assert_eq!(Some(("Host", "localhost:8080")),
+                   split_header_line("Host: localhost:8080"));
+        assert_eq!(None, split_header_line("  folded"));
+        assert_eq!(None, split_header_line("invalid"));
+    }
+
+    #[test]
+    fn request_round_trip() {
+        let bytes = b"POST /test HTTP/1.1\r\n\
+                      Host: localhost:8080\r\n\
+                      Content-Length: 12\r\n\
+                      \r\n\
+                      test message";
+        let request = request_from_bytes(bytes).unwrap();
+        assert_eq!("POST", request.get_method());
+        assert_eq!("/test", request.get_path());
+        assert_eq!("HTTP/1.1", request.get_version());
+        assert_eq!(2, request.get_headers().len());
+        assert_eq!("localhost:8080", request.get_headers()[0].get_value());
+        assert_eq!(b"test message", request.get_body());
+
+        assert_eq!(String::from_utf8_lossy(bytes),
+                   String::from_utf8_lossy(&request_to_bytes(&request)));
+    }
+
+    #[test]
+    fn request_with_folded_headers() {
+        let request = request_from_bytes(b"GET / HTTP/1.1\r\n\
+                                           X-Folded: one\r\n  two\r\n\tthree\r\n\
+                                           X-Other: value\r\n\
+                                           \r\n")
+                          .unwrap();
+        assert_eq!(2, request.get_headers().len());
+        assert_eq!("X-Folded", request.get_headers()[0].get_name());
+        assert_eq!("one two three", request.get_headers()[0].get_value());
+        assert_eq!("value", request.get_headers()[1].get_value());
+    }
+
+    #[test]
+    fn request_with_folded_first_header() {
+        assert_eq!(Error::InvalidHeader("  folded".to_string()),
+                   request_from_bytes(b"GET / HTTP/1.1\r\n  folded\r\n\r\n").unwrap_err());
+    }
+
+    #[test]
+    fn request_with_chunked_body() {
+        let request = request_from_bytes(b"POST / HTTP/1.1\r\n\
+                                           Transfer-Encoding: chunked\r\n\
+                                           \r\n\
+                                           4\r\ntest\r\n\
+                                           8;ext=1\r\n message\r\n\
+                                           0\r\n\
+                                           \r\n")
+                          .unwrap();
+        assert_eq!(b"test message", request.get_body());
+        assert_eq!(1, request.get_headers().len());
+        assert_eq!("Content-Length", request.get_headers()[0].get_name());
+        assert_eq!("12", request.get_headers()[0].get_value());
+    }
+
+    #[test]
+    fn request_with_incomplete_body() {
+        assert_eq!(Error::Incomplete,
+                   request_from_bytes(b"GET / HTTP/1.1\r\nContent-Length: 10\r\n\r\ntest")
+                       .unwrap_err());
+        assert_eq!(Error::Incomplete,
+                   request_from_bytes(b"GET / HTTP/1.1\r\nHost: localhost").unwrap_err());
+    }
+
+    #[test]
+    fn request_with_invalid_start_line() {
+        assert_eq!(Error::InvalidStartLine("GET /".to_string()),
+                   request_from_bytes(b"GET /\r\n\r\n").unwrap_err());
+    }
+
+    #[test]
+    fn response_round_trip() {
+        let bytes = b"HTTP/1.1 503 Service Unavailable\r\n\
+                      Content-Type: text/plain\r\n\
+                      Content-Length: 12\r\n\
+                      \r\n\
+                      test message";
+        let response = response_from_bytes(bytes).unwrap();
+        assert_eq!(503, response.get_status_code());
+        assert_eq!("Service Unavailable", response.get_reason());
+        assert_eq!(b"test message", response.get_body());
+
+        assert_eq!(String::from_utf8_lossy(bytes),
+                   String::from_utf8_lossy(&response_to_bytes(&response)));
+    }
+
+    #[test]
+    fn response_without_reason_and_content_length() {
+        let response = response_from_bytes(b"HTTP/1.0 200\n\ntest message").unwrap();
+        assert_eq!("HTTP/1.0", response.get_version());
+        assert_eq!(200, response.get_status_code());
+        assert_eq!("", response.get_reason());
+        assert_eq!(b"test message", response.get_body());
+    }
+
+    #[test]
+    fn hyper_round_trip() {
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let mut listening = hyper::Server::http("127.0.0.1:0")
+                                .unwrap()
+                                .handle(move |mut request: hyper::server::Request,
+                                              response: hyper::server::Response| {
+                                    tx.lock()
+                                      .unwrap()
+                                      .send(request_from_hyper(&mut request).unwrap())
+                                      .unwrap();
+
+                                    let mut result = HttpResponse::new();
+                                    result.set_status_code(201);
+                                    result.set_headers(RepeatedField::from_vec(vec![
+                                        build_header("X-Test", "one"),
+                                        build_header("Content-Length", "999"),
+                                    ]));
+                                    result.set_body(b"created".to_vec());
+                                    response_to_hyper(&result, response).unwrap();
+                                })
+                                .unwrap();
+
+        let mut response = hyper::Client::new()
+                               .post(&format!("http://{}/test", listening.socket))
+                               .body("test message")
+                               .send()
+                               .unwrap();
+        let response = response_from_hyper(&mut response).unwrap();
+        assert_eq!("HTTP/1.1", response.get_version());
+        assert_eq!(201, response.get_status_code());
+        assert_eq!("Created", response.get_reason());
+        assert_eq!(b"created", response.get_body());
+        assert!(response.get_headers()
+                        .iter()
+                        .any(|header| {
+                            header.get_name() == "X-Test" && header.get_value() == "one"
+                        }));
+        assert!(response.get_headers()
+                        .iter()
+                        .any(|header| {
+                            header.get_name() == "Content-Length" && header.get_value() == "7"
+                        }));
+
+        let request = rx.recv().unwrap();
+        assert_eq!("POST", request.get_method());
+        assert_eq!("/test", request.get_path());
+        assert_eq!("HTTP/1.1", request.get_version());
+        assert_eq!(b"test message", request.get_body());
+
+        listening.close().unwrap();
+    }
+
+}
@@ -15,6 +15,10 @@ pub struct Introduction {
     // message fields
     id: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     public_address: ::protobuf::SingularField<::std::string::String>,
+    protocol_version: ::std::option::Option<u32>,
+    compression: ::std::option::Option<bool>,
+    observed_address: ::protobuf::SingularField<::std::string::String>,
+    zone: ::protobuf::SingularField<::std::string::String>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::std::cell::Cell<u32>,
@@ -35,6 +39,10 @@ impl Introduction {
                 Introduction {
                     id: ::protobuf::SingularField::none(),
                     public_address: ::protobuf::SingularField::none(),
+                    protocol_version: ::std::option::Option::None,
+                    compression: ::std::option::Option::None,
+                    observed_address: ::protobuf::SingularField::none(),
+                    zone: ::protobuf::SingularField::none(),
                     unknown_fields: ::protobuf::UnknownFields::new(),
                     cached_size: ::std::cell::Cell::new(0),
                 }
@@ -113,6 +121,116 @@ impl Introduction {
             None => "",
         }
     }
+
+    // optional uint32 protocol_version = 3;
+
+    pub fn clear_protocol_version(&mut self) {
+        self.protocol_version = ::std::option::Option::None;
+    }
+
+    pub fn has_protocol_version(&self) -> bool {
+        self.protocol_version.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_protocol_version(&mut self, v: u32) {
+        self.protocol_version = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_protocol_version<'a>(&self) -> u32 {
+        self.protocol_version.unwrap_or(0)
+    }
+
+    // optional bool compression = 4;
+
+    pub fn clear_compression(&mut self) {
+        self.compression = ::std::option::Option::None;
+    }
+
+    pub fn has_compression(&self) -> bool {
+        self.compression.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_compression(&mut self, v: bool) {
+        self.compression = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_compression<'a>(&self) -> bool {
+        self.compression.unwrap_or(false)
+    }
+
+    // optional string observed_address = 5;
+
+    pub fn clear_observed_address(&mut self) {
+        self.observed_address.clear();
+    }
+
+    pub fn has_observed_address(&self) -> bool {
+        self.observed_address.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_observed_address(&mut self, v: ::std::string::String) {
+        self.observed_address = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_observed_address<'a>(&'a mut self) -> &'a mut ::std::string::String {
+        if self.observed_address.is_none() {
+            self.observed_address.set_default();
+        };
+        self.observed_address.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_observed_address(&mut self) -> ::std::string::String {
+        self.observed_address.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_observed_address<'a>(&'a self) -> &'a str {
+        match self.observed_address.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    // optional string zone = 6;
+
+    pub fn clear_zone(&mut self) {
+        self.zone.clear();
+    }
+
+    pub fn has_zone(&self) -> bool {
+        self.zone.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_zone(&mut self, v: ::std::string::String) {
+        self.zone = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_zone<'a>(&'a mut self) -> &'a mut ::std::string::String {
+        if self.zone.is_none() {
+            self.zone.set_default();
+        };
+        self.zone.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_zone(&mut self) -> ::std::string::String {
+        self.zone.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_zone<'a>(&'a self) -> &'a str {
+        match self.zone.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
 }
 
 impl ::protobuf::Message for Introduction {
@@ -138,6 +256,34 @@ impl ::protobuf::Message for Introduction {
                     let tmp = self.public_address.set_default();
                     try!(is.read_string_into(tmp))
                 },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint32());
+                    self.protocol_version = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_bool());
+                    self.compression = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = self.observed_address.set_default();
+                    try!(is.read_string_into(tmp))
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = self.zone.set_default();
+                    try!(is.read_string_into(tmp))
+                },
                 _ => {
                     let unknown = try!(is.read_unknown(wire_type));
                     self.mut_unknown_fields().add_value(field_number, unknown);
@@ -157,6 +303,18 @@ impl ::protobuf::Message for Introduction {
         for value in self.public_address.iter() {
             my_size += ::protobuf::rt::string_size(2, &value);
         };
+        for value in self.protocol_version.iter() {
+            my_size += ::protobuf::rt::value_size(3, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        if self.compression.is_some() {
+            my_size += 2;
+        };
+        for value in self.observed_address.iter() {
+            my_size += ::protobuf::rt::string_size(5, &value);
+        };
+        for value in self.zone.iter() {
+            my_size += ::protobuf::rt::string_size(6, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -169,6 +327,18 @@ impl ::protobuf::Message for Introduction {
         if let Some(v) = self.public_address.as_ref() {
             try!(os.write_string(2, &v));
         };
+        if let Some(v) = self.protocol_version {
+            try!(os.write_uint32(3, v));
+        };
+        if let Some(v) = self.compression {
+            try!(os.write_bool(4, v));
+        };
+        if let Some(v) = self.observed_address.as_ref() {
+            try!(os.write_string(5, &v));
+        };
+        if let Some(v) = self.zone.as_ref() {
+            try!(os.write_string(6, &v));
+        };
         try!(os.write_unknown_fields(self.get_unknown_fields()));
         ::std::result::Result::Ok(())
     }
@@ -221,6 +391,26 @@ impl ::protobuf::MessageStatic for Introduction {
                     Introduction::has_public_address,
                     Introduction::get_public_address,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u32_accessor(
+                    "protocol_version",
+                    Introduction::has_protocol_version,
+                    Introduction::get_protocol_version,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_bool_accessor(
+                    "compression",
+                    Introduction::has_compression,
+                    Introduction::get_compression,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor(
+                    "observed_address",
+                    Introduction::has_observed_address,
+                    Introduction::get_observed_address,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor(
+                    "zone",
+                    Introduction::has_zone,
+                    Introduction::get_zone,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Introduction>(
                     "Introduction",
                     fields,
@@ -235,6 +425,10 @@ impl ::protobuf::Clear for Introduction {
     fn clear(&mut self) {
         self.clear_id();
         self.clear_public_address();
+        self.clear_protocol_version();
+        self.clear_compression();
+        self.clear_observed_address();
+        self.clear_zone();
         self.unknown_fields.clear();
     }
 }
@@ -243,6 +437,10 @@ impl ::std::cmp::PartialEq for Introduction {
     fn eq(&self, other: &Introduction) -> bool {
         self.id == other.id &&
         self.public_address == other.public_address &&
+        self.protocol_version == other.protocol_version &&
+        self.compression == other.compression &&
+        self.observed_address == other.observed_address &&
+        self.zone == other.zone &&
         self.unknown_fields == other.unknown_fields
     }
 }
@@ -255,31 +453,54 @@ impl ::std::fmt::Debug for Introduction {
 
 static file_descriptor_proto_data: &'static [u8] = &[
     0x0a, 0x12, 0x69, 0x6e, 0x74, 0x72, 0x6f, 0x64, 0x75, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x70,
-    0x72, 0x6f, 0x74, 0x6f, 0x12, 0x07, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x22, 0x32, 0x0a,
-    0x0c, 0x49, 0x6e, 0x74, 0x72, 0x6f, 0x64, 0x75, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x0a, 0x0a,
-    0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0c, 0x12, 0x16, 0x0a, 0x0e, 0x70, 0x75, 0x62,
-    0x6c, 0x69, 0x63, 0x5f, 0x61, 0x64, 0x64, 0x72, 0x65, 0x73, 0x73, 0x18, 0x02, 0x20, 0x01, 0x28,
-    0x09, 0x4a, 0xc4, 0x02, 0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x07, 0x01, 0x0a, 0x08, 0x0a, 0x01,
-    0x02, 0x12, 0x03, 0x00, 0x08, 0x0f, 0x0a, 0x99, 0x01, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x04,
-    0x00, 0x07, 0x01, 0x1a, 0x8c, 0x01, 0x20, 0x49, 0x6e, 0x74, 0x72, 0x6f, 0x64, 0x75, 0x63, 0x74,
-    0x69, 0x6f, 0x6e, 0x20, 0x69, 0x73, 0x20, 0x61, 0x20, 0x70, 0x61, 0x63, 0x6b, 0x65, 0x74, 0x20,
-    0x74, 0x68, 0x61, 0x74, 0x20, 0x63, 0x61, 0x6e, 0x20, 0x62, 0x65, 0x20, 0x73, 0x65, 0x6e, 0x64,
-    0x20, 0x62, 0x79, 0x20, 0x61, 0x6e, 0x79, 0x20, 0x6e, 0x6f, 0x64, 0x65, 0x20, 0x74, 0x6f, 0x20,
-    0x69, 0x64, 0x65, 0x6e, 0x74, 0x69, 0x66, 0x79, 0x20, 0x69, 0x74, 0x73, 0x65, 0x6c, 0x66, 0x20,
-    0x61, 0x67, 0x61, 0x69, 0x6e, 0x73, 0x74, 0x0a, 0x20, 0x6f, 0x74, 0x68, 0x65, 0x72, 0x20, 0x6e,
-    0x6f, 0x64, 0x65, 0x73, 0x2e, 0x20, 0x54, 0x68, 0x65, 0x20, 0x6f, 0x6e, 0x6c, 0x79, 0x20, 0x70,
-    0x72, 0x6f, 0x70, 0x65, 0x72, 0x74, 0x79, 0x20, 0x69, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20, 0x73,
-    0x65, 0x6e, 0x64, 0x69, 0x6e, 0x67, 0x20, 0x6e, 0x6f, 0x64, 0x65, 0x27, 0x73, 0x20, 0x69, 0x64,
-    0x2e, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x04, 0x08, 0x14, 0x0a, 0x0b,
-    0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x05, 0x02, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x00, 0x02, 0x00, 0x04, 0x12, 0x03, 0x05, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
-    0x00, 0x05, 0x12, 0x03, 0x05, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x01,
-    0x12, 0x03, 0x05, 0x11, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12, 0x03,
-    0x05, 0x16, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x06, 0x02, 0x25,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x04, 0x12, 0x03, 0x06, 0x02, 0x0a, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x05, 0x12, 0x03, 0x06, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x06, 0x12, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
-    0x02, 0x01, 0x03, 0x12, 0x03, 0x06, 0x23, 0x24,
+    0x72, 0x6f, 0x74, 0x6f, 0x12, 0x07, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x22, 0x89, 0x01,
+    0x0a, 0x0c, 0x49, 0x6e, 0x74, 0x72, 0x6f, 0x64, 0x75, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x12, 0x0a,
+    0x0a, 0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0c, 0x12, 0x16, 0x0a, 0x0e, 0x70, 0x75,
+    0x62, 0x6c, 0x69, 0x63, 0x5f, 0x61, 0x64, 0x64, 0x72, 0x65, 0x73, 0x73, 0x18, 0x02, 0x20, 0x01,
+    0x28, 0x09, 0x12, 0x18, 0x0a, 0x10, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x63, 0x6f, 0x6c, 0x5f, 0x76,
+    0x65, 0x72, 0x73, 0x69, 0x6f, 0x6e, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0d, 0x12, 0x13, 0x0a, 0x0b,
+    0x63, 0x6f, 0x6d, 0x70, 0x72, 0x65, 0x73, 0x73, 0x69, 0x6f, 0x6e, 0x18, 0x04, 0x20, 0x01, 0x28,
+    0x08, 0x12, 0x18, 0x0a, 0x10, 0x6f, 0x62, 0x73, 0x65, 0x72, 0x76, 0x65, 0x64, 0x5f, 0x61, 0x64,
+    0x64, 0x72, 0x65, 0x73, 0x73, 0x18, 0x05, 0x20, 0x01, 0x28, 0x09, 0x12, 0x0c, 0x0a, 0x04, 0x7a,
+    0x6f, 0x6e, 0x65, 0x18, 0x06, 0x20, 0x01, 0x28, 0x09, 0x4a, 0xd8, 0x04, 0x0a, 0x06, 0x12, 0x04,
+    0x00, 0x00, 0x0b, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12, 0x03, 0x00, 0x08, 0x0f, 0x0a, 0x99,
+    0x01, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x04, 0x00, 0x0b, 0x01, 0x1a, 0x8c, 0x01, 0x20, 0x49,
+    0x6e, 0x74, 0x72, 0x6f, 0x64, 0x75, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x20, 0x69, 0x73, 0x20, 0x61,
+    0x20, 0x70, 0x61, 0x63, 0x6b, 0x65, 0x74, 0x20, 0x74, 0x68, 0x61, 0x74, 0x20, 0x63, 0x61, 0x6e,
+    0x20, 0x62, 0x65, 0x20, 0x73, 0x65, 0x6e, 0x64, 0x20, 0x62, 0x79, 0x20, 0x61, 0x6e, 0x79, 0x20,
+    0x6e, 0x6f, 0x64, 0x65, 0x20, 0x74, 0x6f, 0x20, 0x69, 0x64, 0x65, 0x6e, 0x74, 0x69, 0x66, 0x79,
+    0x20, 0x69, 0x74, 0x73, 0x65, 0x6c, 0x66, 0x20, 0x61, 0x67, 0x61, 0x69, 0x6e, 0x73, 0x74, 0x0a,
+    0x20, 0x6f, 0x74, 0x68, 0x65, 0x72, 0x20, 0x6e, 0x6f, 0x64, 0x65, 0x73, 0x2e, 0x20, 0x54, 0x68,
+    0x65, 0x20, 0x6f, 0x6e, 0x6c, 0x79, 0x20, 0x70, 0x72, 0x6f, 0x70, 0x65, 0x72, 0x74, 0x79, 0x20,
+    0x69, 0x73, 0x20, 0x74, 0x68, 0x65, 0x20, 0x73, 0x65, 0x6e, 0x64, 0x69, 0x6e, 0x67, 0x20, 0x6e,
+    0x6f, 0x64, 0x65, 0x27, 0x73, 0x20, 0x69, 0x64, 0x2e, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x00,
+    0x01, 0x12, 0x03, 0x04, 0x08, 0x14, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03,
+    0x05, 0x02, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x04, 0x12, 0x03, 0x05, 0x02,
+    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x05, 0x12, 0x03, 0x05, 0x0b, 0x10, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x05, 0x11, 0x13, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12, 0x03, 0x05, 0x16, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
+    0x00, 0x02, 0x01, 0x12, 0x03, 0x06, 0x02, 0x25, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01,
+    0x04, 0x12, 0x03, 0x06, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x05, 0x12,
+    0x03, 0x06, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x06,
+    0x12, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03, 0x06, 0x23, 0x24,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x02, 0x12, 0x03, 0x07, 0x02, 0x27, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x00, 0x02, 0x02, 0x04, 0x12, 0x03, 0x07, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x00, 0x02, 0x02, 0x05, 0x12, 0x03, 0x07, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
+    0x02, 0x01, 0x12, 0x03, 0x07, 0x12, 0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x03,
+    0x12, 0x03, 0x07, 0x25, 0x26, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x03, 0x12, 0x03, 0x08,
+    0x02, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x04, 0x12, 0x03, 0x08, 0x02, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x05, 0x12, 0x03, 0x08, 0x0b, 0x0f, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x03, 0x08, 0x10, 0x1b, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x00, 0x02, 0x03, 0x03, 0x12, 0x03, 0x08, 0x1e, 0x1f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00,
+    0x02, 0x04, 0x12, 0x03, 0x09, 0x02, 0x27, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x04,
+    0x12, 0x03, 0x09, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x05, 0x12, 0x03,
+    0x09, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x03, 0x09, 0x12,
+    0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x03, 0x12, 0x03, 0x09, 0x25, 0x26, 0x0a,
+    0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x05, 0x12, 0x03, 0x0a, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x00, 0x02, 0x05, 0x04, 0x12, 0x03, 0x0a, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
+    0x02, 0x05, 0x05, 0x12, 0x03, 0x0a, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x05,
+    0x01, 0x12, 0x03, 0x0a, 0x12, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x05, 0x03, 0x12,
+    0x03, 0x0a, 0x19, 0x1a,
 ];
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
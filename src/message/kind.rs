@@ -21,6 +21,17 @@ pub enum Kind {
     RequestPacketMessage = 7,
     ResponseMessage = 8,
     ResponsePacketMessage = 9,
+    StatsRequestMessage = 10,
+    StatsResponseMessage = 11,
+    PingMessage = 12,
+    PongMessage = 13,
+    RequestCreditMessage = 14,
+    ResponseCreditMessage = 15,
+    IdleMessage = 16,
+    DrainRequestMessage = 17,
+    DrainResponseMessage = 18,
+    NotificationMessage = 19,
+    GoodbyeMessage = 20,
 }
 
 impl ::protobuf::ProtobufEnum for Kind {
@@ -39,6 +50,17 @@ impl ::protobuf::ProtobufEnum for Kind {
             7 => ::std::option::Option::Some(Kind::RequestPacketMessage),
             8 => ::std::option::Option::Some(Kind::ResponseMessage),
             9 => ::std::option::Option::Some(Kind::ResponsePacketMessage),
+            10 => ::std::option::Option::Some(Kind::StatsRequestMessage),
+            11 => ::std::option::Option::Some(Kind::StatsResponseMessage),
+            12 => ::std::option::Option::Some(Kind::PingMessage),
+            13 => ::std::option::Option::Some(Kind::PongMessage),
+            14 => ::std::option::Option::Some(Kind::RequestCreditMessage),
+            15 => ::std::option::Option::Some(Kind::ResponseCreditMessage),
+            16 => ::std::option::Option::Some(Kind::IdleMessage),
+            17 => ::std::option::Option::Some(Kind::DrainRequestMessage),
+            18 => ::std::option::Option::Some(Kind::DrainResponseMessage),
+            19 => ::std::option::Option::Some(Kind::NotificationMessage),
+            20 => ::std::option::Option::Some(Kind::GoodbyeMessage),
             _ => ::std::option::Option::None
         }
     }
@@ -61,7 +83,7 @@ impl ::std::marker::Copy for Kind {
 
 static file_descriptor_proto_data: &'static [u8] = &[
     0x0a, 0x0a, 0x6b, 0x69, 0x6e, 0x64, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12, 0x07, 0x6d, 0x65,
-    0x73, 0x73, 0x61, 0x67, 0x65, 0x2a, 0xd9, 0x01, 0x0a, 0x04, 0x4b, 0x69, 0x6e, 0x64, 0x12, 0x17,
+    0x73, 0x73, 0x61, 0x67, 0x65, 0x2a, 0xd4, 0x03, 0x0a, 0x04, 0x4b, 0x69, 0x6e, 0x64, 0x12, 0x17,
     0x0a, 0x13, 0x49, 0x6e, 0x74, 0x72, 0x6f, 0x64, 0x75, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x4d, 0x65,
     0x73, 0x73, 0x61, 0x67, 0x65, 0x10, 0x01, 0x12, 0x10, 0x0a, 0x0c, 0x50, 0x65, 0x65, 0x72, 0x73,
     0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x10, 0x02, 0x12, 0x16, 0x0a, 0x12, 0x41, 0x64, 0x64,
@@ -75,32 +97,76 @@ static file_descriptor_proto_data: &'static [u8] = &[
     0x07, 0x12, 0x13, 0x0a, 0x0f, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x4d, 0x65, 0x73,
     0x73, 0x61, 0x67, 0x65, 0x10, 0x08, 0x12, 0x19, 0x0a, 0x15, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e,
     0x73, 0x65, 0x50, 0x61, 0x63, 0x6b, 0x65, 0x74, 0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x10,
-    0x09, 0x4a, 0x9b, 0x03, 0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x0c, 0x01, 0x0a, 0x08, 0x0a, 0x01,
-    0x02, 0x12, 0x03, 0x00, 0x08, 0x0f, 0x0a, 0x0a, 0x0a, 0x02, 0x05, 0x00, 0x12, 0x04, 0x02, 0x00,
-    0x0c, 0x01, 0x0a, 0x0a, 0x0a, 0x03, 0x05, 0x00, 0x01, 0x12, 0x03, 0x02, 0x05, 0x09, 0x0a, 0x0b,
-    0x0a, 0x04, 0x05, 0x00, 0x02, 0x00, 0x12, 0x03, 0x03, 0x02, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x05,
-    0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x03, 0x02, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02,
-    0x00, 0x02, 0x12, 0x03, 0x03, 0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x01, 0x12,
-    0x03, 0x04, 0x02, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x04,
-    0x02, 0x0e, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x01, 0x02, 0x12, 0x03, 0x04, 0x11, 0x12,
-    0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x02, 0x12, 0x03, 0x05, 0x02, 0x19, 0x0a, 0x0c, 0x0a,
-    0x05, 0x05, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03, 0x05, 0x02, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x05,
-    0x00, 0x02, 0x02, 0x02, 0x12, 0x03, 0x05, 0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02,
-    0x03, 0x12, 0x03, 0x06, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x03, 0x01, 0x12,
-    0x03, 0x06, 0x02, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x03, 0x02, 0x12, 0x03, 0x06,
-    0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x04, 0x12, 0x03, 0x07, 0x02, 0x18, 0x0a,
-    0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x04, 0x01, 0x12, 0x03, 0x07, 0x02, 0x13, 0x0a, 0x0c, 0x0a,
-    0x05, 0x05, 0x00, 0x02, 0x04, 0x02, 0x12, 0x03, 0x07, 0x16, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x05,
-    0x00, 0x02, 0x05, 0x12, 0x03, 0x08, 0x02, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x05,
-    0x01, 0x12, 0x03, 0x08, 0x02, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x05, 0x02, 0x12,
-    0x03, 0x08, 0x13, 0x14, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x06, 0x12, 0x03, 0x09, 0x02,
-    0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x06, 0x01, 0x12, 0x03, 0x09, 0x02, 0x16, 0x0a,
-    0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x06, 0x02, 0x12, 0x03, 0x09, 0x19, 0x1a, 0x0a, 0x0b, 0x0a,
-    0x04, 0x05, 0x00, 0x02, 0x07, 0x12, 0x03, 0x0a, 0x02, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00,
-    0x02, 0x07, 0x01, 0x12, 0x03, 0x0a, 0x02, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x07,
-    0x02, 0x12, 0x03, 0x0a, 0x14, 0x15, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x08, 0x12, 0x03,
-    0x0b, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x08, 0x01, 0x12, 0x03, 0x0b, 0x02,
-    0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x08, 0x02, 0x12, 0x03, 0x0b, 0x1a, 0x1b,
+    0x09, 0x12, 0x17, 0x0a, 0x13, 0x53, 0x74, 0x61, 0x74, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73,
+    0x74, 0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x10, 0x0a, 0x12, 0x18, 0x0a, 0x14, 0x53, 0x74,
+    0x61, 0x74, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x4d, 0x65, 0x73, 0x73, 0x61,
+    0x67, 0x65, 0x10, 0x0b, 0x12, 0x0f, 0x0a, 0x0b, 0x50, 0x69, 0x6e, 0x67, 0x4d, 0x65, 0x73, 0x73,
+    0x61, 0x67, 0x65, 0x10, 0x0c, 0x12, 0x0f, 0x0a, 0x0b, 0x50, 0x6f, 0x6e, 0x67, 0x4d, 0x65, 0x73,
+    0x73, 0x61, 0x67, 0x65, 0x10, 0x0d, 0x12, 0x18, 0x0a, 0x14, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73,
+    0x74, 0x43, 0x72, 0x65, 0x64, 0x69, 0x74, 0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x10, 0x0e,
+    0x12, 0x19, 0x0a, 0x15, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x43, 0x72, 0x65, 0x64,
+    0x69, 0x74, 0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x10, 0x0f, 0x12, 0x0f, 0x0a, 0x0b, 0x49,
+    0x64, 0x6c, 0x65, 0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x10, 0x10, 0x12, 0x17, 0x0a, 0x13,
+    0x44, 0x72, 0x61, 0x69, 0x6e, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x4d, 0x65, 0x73, 0x73,
+    0x61, 0x67, 0x65, 0x10, 0x11, 0x12, 0x18, 0x0a, 0x14, 0x44, 0x72, 0x61, 0x69, 0x6e, 0x52, 0x65,
+    0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x10, 0x12, 0x12,
+    0x17, 0x0a, 0x13, 0x4e, 0x6f, 0x74, 0x69, 0x66, 0x69, 0x63, 0x61, 0x74, 0x69, 0x6f, 0x6e, 0x4d,
+    0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x10, 0x13, 0x12, 0x12, 0x0a, 0x0e, 0x47, 0x6f, 0x6f, 0x64,
+    0x62, 0x79, 0x65, 0x4d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x10, 0x14, 0x4a, 0xde, 0x06, 0x0a,
+    0x06, 0x12, 0x04, 0x00, 0x00, 0x17, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12, 0x03, 0x00, 0x08,
+    0x0f, 0x0a, 0x0a, 0x0a, 0x02, 0x05, 0x00, 0x12, 0x04, 0x02, 0x00, 0x17, 0x01, 0x0a, 0x0a, 0x0a,
+    0x03, 0x05, 0x00, 0x01, 0x12, 0x03, 0x02, 0x05, 0x09, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02,
+    0x00, 0x12, 0x03, 0x03, 0x02, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x00, 0x01, 0x12,
+    0x03, 0x03, 0x02, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x00, 0x02, 0x12, 0x03, 0x03,
+    0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x01, 0x12, 0x03, 0x04, 0x02, 0x13, 0x0a,
+    0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x04, 0x02, 0x0e, 0x0a, 0x0c, 0x0a,
+    0x05, 0x05, 0x00, 0x02, 0x01, 0x02, 0x12, 0x03, 0x04, 0x11, 0x12, 0x0a, 0x0b, 0x0a, 0x04, 0x05,
+    0x00, 0x02, 0x02, 0x12, 0x03, 0x05, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x02,
+    0x01, 0x12, 0x03, 0x05, 0x02, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x02, 0x02, 0x12,
+    0x03, 0x05, 0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x03, 0x12, 0x03, 0x06, 0x02,
+    0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x03, 0x01, 0x12, 0x03, 0x06, 0x02, 0x17, 0x0a,
+    0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x03, 0x02, 0x12, 0x03, 0x06, 0x1a, 0x1b, 0x0a, 0x0b, 0x0a,
+    0x04, 0x05, 0x00, 0x02, 0x04, 0x12, 0x03, 0x07, 0x02, 0x18, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00,
+    0x02, 0x04, 0x01, 0x12, 0x03, 0x07, 0x02, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x04,
+    0x02, 0x12, 0x03, 0x07, 0x16, 0x17, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x05, 0x12, 0x03,
+    0x08, 0x02, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x05, 0x01, 0x12, 0x03, 0x08, 0x02,
+    0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x05, 0x02, 0x12, 0x03, 0x08, 0x13, 0x14, 0x0a,
+    0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x06, 0x12, 0x03, 0x09, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05,
+    0x05, 0x00, 0x02, 0x06, 0x01, 0x12, 0x03, 0x09, 0x02, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00,
+    0x02, 0x06, 0x02, 0x12, 0x03, 0x09, 0x19, 0x1a, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x07,
+    0x12, 0x03, 0x0a, 0x02, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x07, 0x01, 0x12, 0x03,
+    0x0a, 0x02, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x07, 0x02, 0x12, 0x03, 0x0a, 0x14,
+    0x15, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x08, 0x12, 0x03, 0x0b, 0x02, 0x1c, 0x0a, 0x0c,
+    0x0a, 0x05, 0x05, 0x00, 0x02, 0x08, 0x01, 0x12, 0x03, 0x0b, 0x02, 0x17, 0x0a, 0x0c, 0x0a, 0x05,
+    0x05, 0x00, 0x02, 0x08, 0x02, 0x12, 0x03, 0x0b, 0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00,
+    0x02, 0x09, 0x12, 0x03, 0x0c, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x09, 0x01,
+    0x12, 0x03, 0x0c, 0x02, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x09, 0x02, 0x12, 0x03,
+    0x0c, 0x18, 0x1a, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x0a, 0x12, 0x03, 0x0d, 0x02, 0x1c,
+    0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0a, 0x01, 0x12, 0x03, 0x0d, 0x02, 0x16, 0x0a, 0x0c,
+    0x0a, 0x05, 0x05, 0x00, 0x02, 0x0a, 0x02, 0x12, 0x03, 0x0d, 0x19, 0x1b, 0x0a, 0x0b, 0x0a, 0x04,
+    0x05, 0x00, 0x02, 0x0b, 0x12, 0x03, 0x0e, 0x02, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02,
+    0x0b, 0x01, 0x12, 0x03, 0x0e, 0x02, 0x0d, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0b, 0x02,
+    0x12, 0x03, 0x0e, 0x10, 0x12, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x0c, 0x12, 0x03, 0x0f,
+    0x02, 0x13, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0c, 0x01, 0x12, 0x03, 0x0f, 0x02, 0x0d,
+    0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0c, 0x02, 0x12, 0x03, 0x0f, 0x10, 0x12, 0x0a, 0x0b,
+    0x0a, 0x04, 0x05, 0x00, 0x02, 0x0d, 0x12, 0x03, 0x10, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x05,
+    0x00, 0x02, 0x0d, 0x01, 0x12, 0x03, 0x10, 0x02, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02,
+    0x0d, 0x02, 0x12, 0x03, 0x10, 0x19, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x0e, 0x12,
+    0x03, 0x11, 0x02, 0x1d, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0e, 0x01, 0x12, 0x03, 0x11,
+    0x02, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x0e, 0x02, 0x12, 0x03, 0x11, 0x1a, 0x1c,
+    0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x0f, 0x12, 0x03, 0x12, 0x02, 0x13, 0x0a, 0x0c, 0x0a,
+    0x05, 0x05, 0x00, 0x02, 0x0f, 0x01, 0x12, 0x03, 0x12, 0x02, 0x0d, 0x0a, 0x0c, 0x0a, 0x05, 0x05,
+    0x00, 0x02, 0x0f, 0x02, 0x12, 0x03, 0x12, 0x10, 0x12, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02,
+    0x10, 0x12, 0x03, 0x13, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x10, 0x01, 0x12,
+    0x03, 0x13, 0x02, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x10, 0x02, 0x12, 0x03, 0x13,
+    0x18, 0x1a, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x11, 0x12, 0x03, 0x14, 0x02, 0x1c, 0x0a,
+    0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x11, 0x01, 0x12, 0x03, 0x14, 0x02, 0x16, 0x0a, 0x0c, 0x0a,
+    0x05, 0x05, 0x00, 0x02, 0x11, 0x02, 0x12, 0x03, 0x14, 0x19, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x05,
+    0x00, 0x02, 0x12, 0x12, 0x03, 0x15, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x12,
+    0x01, 0x12, 0x03, 0x15, 0x02, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x12, 0x02, 0x12,
+    0x03, 0x15, 0x18, 0x1a, 0x0a, 0x0b, 0x0a, 0x04, 0x05, 0x00, 0x02, 0x13, 0x12, 0x03, 0x16, 0x02,
+    0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x13, 0x01, 0x12, 0x03, 0x16, 0x02, 0x10, 0x0a,
+    0x0c, 0x0a, 0x05, 0x05, 0x00, 0x02, 0x13, 0x02, 0x12, 0x03, 0x16, 0x13, 0x15,
 ];
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -1,9 +1,19 @@
 
 mod aknowledge;
 mod container;
+mod credit;
+mod drain;
+mod goodbye;
 mod encrypted;
+mod heartbeat;
+pub mod http;
+mod http_header;
+mod http_request;
+mod http_response;
+mod idle;
 mod introduction;
 mod kind;
+mod notification;
 mod packet;
 mod peer;
 mod peers;
@@ -11,18 +21,29 @@ mod request;
 mod response;
 mod service;
 mod services;
+mod stats;
 
 pub use self::aknowledge::Aknowledge;
 pub use self::container::Container;
+pub use self::credit::Credit;
+pub use self::drain::{DrainRequest, DrainResponse};
 pub use self::encrypted::Encrypted;
 pub use self::encrypted::Encrypted_CipherType;
+pub use self::goodbye::Goodbye;
+pub use self::heartbeat::{Ping, Pong};
+pub use self::http_header::HttpHeader;
+pub use self::http_request::HttpRequest;
+pub use self::http_response::HttpResponse;
+pub use self::idle::Idle;
 pub use self::introduction::Introduction;
 pub use self::kind::Kind;
+pub use self::notification::Notification;
 pub use self::packet::{Packet, Packet_Result};
 pub use self::peer::Peer;
 pub use self::peers::Peers;
 pub use self::request::Request;
 pub use self::response::Response;
 pub use self::response::Response_Kind;
-pub use self::service::Service;
+pub use self::service::{Service, Tag};
 pub use self::services::{AddServices, RemoveServices};
+pub use self::stats::{StatsRequest, StatsResponse};
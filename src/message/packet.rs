@@ -13,7 +13,7 @@ use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 #[derive(Clone,Default)]
 pub struct Packet {
     // message fields
-    request_id: ::std::option::Option<u32>,
+    request_id: ::std::option::Option<u64>,
     result: ::std::option::Option<Packet_Result>,
     message: ::protobuf::SingularField<::std::string::String>,
     payload: ::protobuf::SingularField<::std::vec::Vec<u8>>,
@@ -46,7 +46,7 @@ impl Packet {
         }
     }
 
-    // optional uint32 request_id = 1;
+    // optional uint64 request_id = 1;
 
     pub fn clear_request_id(&mut self) {
         self.request_id = ::std::option::Option::None;
@@ -57,11 +57,11 @@ impl Packet {
     }
 
     // Param is passed by value, moved
-    pub fn set_request_id(&mut self, v: u32) {
+    pub fn set_request_id(&mut self, v: u64) {
         self.request_id = ::std::option::Option::Some(v);
     }
 
-    pub fn get_request_id<'a>(&self) -> u32 {
+    pub fn get_request_id<'a>(&self) -> u64 {
         self.request_id.unwrap_or(0)
     }
 
@@ -170,7 +170,7 @@ impl ::protobuf::Message for Packet {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
                     };
-                    let tmp = try!(is.read_uint32());
+                    let tmp = try!(is.read_uint64());
                     self.request_id = ::std::option::Option::Some(tmp);
                 },
                 2 => {
@@ -226,7 +226,7 @@ impl ::protobuf::Message for Packet {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.request_id {
-            try!(os.write_uint32(1, v));
+            try!(os.write_uint64(1, v));
         };
         if let Some(v) = self.result {
             try!(os.write_enum(2, v as i32));
@@ -279,7 +279,7 @@ impl ::protobuf::MessageStatic for Packet {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_u32_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
                     "request_id",
                     Packet::has_request_id,
                     Packet::get_request_id,
@@ -408,7 +408,7 @@ static file_descriptor_proto_data: &'static [u8] = &[
     0x0a, 0x0c, 0x70, 0x61, 0x63, 0x6b, 0x65, 0x74, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12, 0x07,
     0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x22, 0xb9, 0x03, 0x0a, 0x06, 0x50, 0x61, 0x63, 0x6b,
     0x65, 0x74, 0x12, 0x12, 0x0a, 0x0a, 0x72, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x5f, 0x69, 0x64,
-    0x18, 0x01, 0x20, 0x01, 0x28, 0x0d, 0x12, 0x26, 0x0a, 0x06, 0x72, 0x65, 0x73, 0x75, 0x6c, 0x74,
+    0x18, 0x01, 0x20, 0x01, 0x28, 0x04, 0x12, 0x26, 0x0a, 0x06, 0x72, 0x65, 0x73, 0x75, 0x6c, 0x74,
     0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x16, 0x2e, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65,
     0x2e, 0x50, 0x61, 0x63, 0x6b, 0x65, 0x74, 0x2e, 0x52, 0x65, 0x73, 0x75, 0x6c, 0x74, 0x12, 0x0f,
     0x0a, 0x07, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x18, 0x03, 0x20, 0x01, 0x28, 0x09, 0x12,
@@ -13,8 +13,11 @@ use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 #[derive(Clone,Default)]
 pub struct Request {
     // message fields
-    id: ::std::option::Option<u32>,
+    id: ::std::option::Option<u64>,
     name: ::protobuf::SingularField<::std::string::String>,
+    ttl: ::std::option::Option<u32>,
+    deadline: ::std::option::Option<i64>,
+    trace_id: ::protobuf::SingularField<::std::string::String>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::std::cell::Cell<u32>,
@@ -35,6 +38,9 @@ impl Request {
                 Request {
                     id: ::std::option::Option::None,
                     name: ::protobuf::SingularField::none(),
+                    ttl: ::std::option::Option::None,
+                    deadline: ::std::option::Option::None,
+                    trace_id: ::protobuf::SingularField::none(),
                     unknown_fields: ::protobuf::UnknownFields::new(),
                     cached_size: ::std::cell::Cell::new(0),
                 }
@@ -42,7 +48,7 @@ impl Request {
         }
     }
 
-    // optional uint32 id = 1;
+    // optional uint64 id = 1;
 
     pub fn clear_id(&mut self) {
         self.id = ::std::option::Option::None;
@@ -53,11 +59,11 @@ impl Request {
     }
 
     // Param is passed by value, moved
-    pub fn set_id(&mut self, v: u32) {
+    pub fn set_id(&mut self, v: u64) {
         self.id = ::std::option::Option::Some(v);
     }
 
-    pub fn get_id<'a>(&self) -> u32 {
+    pub fn get_id<'a>(&self) -> u64 {
         self.id.unwrap_or(0)
     }
 
@@ -96,6 +102,80 @@ impl Request {
             None => "",
         }
     }
+
+    // optional uint32 ttl = 3;
+
+    pub fn clear_ttl(&mut self) {
+        self.ttl = ::std::option::Option::None;
+    }
+
+    pub fn has_ttl(&self) -> bool {
+        self.ttl.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ttl(&mut self, v: u32) {
+        self.ttl = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_ttl<'a>(&self) -> u32 {
+        self.ttl.unwrap_or(0)
+    }
+
+    // optional int64 deadline = 4;
+
+    pub fn clear_deadline(&mut self) {
+        self.deadline = ::std::option::Option::None;
+    }
+
+    pub fn has_deadline(&self) -> bool {
+        self.deadline.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_deadline(&mut self, v: i64) {
+        self.deadline = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_deadline<'a>(&self) -> i64 {
+        self.deadline.unwrap_or(0)
+    }
+
+    // optional string trace_id = 5;
+
+    pub fn clear_trace_id(&mut self) {
+        self.trace_id.clear();
+    }
+
+    pub fn has_trace_id(&self) -> bool {
+        self.trace_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_trace_id(&mut self, v: ::std::string::String) {
+        self.trace_id = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_trace_id<'a>(&'a mut self) -> &'a mut ::std::string::String {
+        if self.trace_id.is_none() {
+            self.trace_id.set_default();
+        };
+        self.trace_id.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_trace_id(&mut self) -> ::std::string::String {
+        self.trace_id.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_trace_id<'a>(&'a self) -> &'a str {
+        match self.trace_id.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
 }
 
 impl ::protobuf::Message for Request {
@@ -111,7 +191,7 @@ impl ::protobuf::Message for Request {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
                     };
-                    let tmp = try!(is.read_uint32());
+                    let tmp = try!(is.read_uint64());
                     self.id = ::std::option::Option::Some(tmp);
                 },
                 2 => {
@@ -121,6 +201,27 @@ impl ::protobuf::Message for Request {
                     let tmp = self.name.set_default();
                     try!(is.read_string_into(tmp))
                 },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint32());
+                    self.ttl = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_int64());
+                    self.deadline = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = self.trace_id.set_default();
+                    try!(is.read_string_into(tmp))
+                },
                 _ => {
                     let unknown = try!(is.read_unknown(wire_type));
                     self.mut_unknown_fields().add_value(field_number, unknown);
@@ -140,6 +241,15 @@ impl ::protobuf::Message for Request {
         for value in self.name.iter() {
             my_size += ::protobuf::rt::string_size(2, &value);
         };
+        for value in self.ttl.iter() {
+            my_size += ::protobuf::rt::value_size(3, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in self.deadline.iter() {
+            my_size += ::protobuf::rt::value_size(4, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in self.trace_id.iter() {
+            my_size += ::protobuf::rt::string_size(5, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -147,11 +257,20 @@ impl ::protobuf::Message for Request {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.id {
-            try!(os.write_uint32(1, v));
+            try!(os.write_uint64(1, v));
         };
         if let Some(v) = self.name.as_ref() {
             try!(os.write_string(2, &v));
         };
+        if let Some(v) = self.ttl {
+            try!(os.write_uint32(3, v));
+        };
+        if let Some(v) = self.deadline {
+            try!(os.write_int64(4, v));
+        };
+        if let Some(v) = self.trace_id.as_ref() {
+            try!(os.write_string(5, &v));
+        };
         try!(os.write_unknown_fields(self.get_unknown_fields()));
         ::std::result::Result::Ok(())
     }
@@ -194,7 +313,7 @@ impl ::protobuf::MessageStatic for Request {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_u32_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
                     "id",
                     Request::has_id,
                     Request::get_id,
@@ -204,6 +323,21 @@ impl ::protobuf::MessageStatic for Request {
                     Request::has_name,
                     Request::get_name,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u32_accessor(
+                    "ttl",
+                    Request::has_ttl,
+                    Request::get_ttl,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_i64_accessor(
+                    "deadline",
+                    Request::has_deadline,
+                    Request::get_deadline,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor(
+                    "trace_id",
+                    Request::has_trace_id,
+                    Request::get_trace_id,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Request>(
                     "Request",
                     fields,
@@ -218,6 +352,9 @@ impl ::protobuf::Clear for Request {
     fn clear(&mut self) {
         self.clear_id();
         self.clear_name();
+        self.clear_ttl();
+        self.clear_deadline();
+        self.clear_trace_id();
         self.unknown_fields.clear();
     }
 }
@@ -226,6 +363,9 @@ impl ::std::cmp::PartialEq for Request {
     fn eq(&self, other: &Request) -> bool {
         self.id == other.id &&
         self.name == other.name &&
+        self.ttl == other.ttl &&
+        self.deadline == other.deadline &&
+        self.trace_id == other.trace_id &&
         self.unknown_fields == other.unknown_fields
     }
 }
@@ -238,23 +378,39 @@ impl ::std::fmt::Debug for Request {
 
 static file_descriptor_proto_data: &'static [u8] = &[
     0x0a, 0x0d, 0x72, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12,
-    0x07, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x22, 0x23, 0x0a, 0x07, 0x52, 0x65, 0x71, 0x75,
-    0x65, 0x73, 0x74, 0x12, 0x0a, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0d, 0x12,
-    0x0c, 0x0a, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x4a, 0xdf, 0x01,
-    0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x06, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12, 0x03, 0x00,
-    0x08, 0x0f, 0x0a, 0x35, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x03, 0x00, 0x06, 0x01, 0x1a, 0x29,
-    0x20, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x20, 0x64, 0x65, 0x66, 0x69, 0x6e, 0x65, 0x73,
-    0x20, 0x61, 0x20, 0x72, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x20, 0x74, 0x6f, 0x20, 0x61, 0x20,
-    0x73, 0x65, 0x72, 0x76, 0x69, 0x63, 0x65, 0x2e, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x00, 0x01,
-    0x12, 0x03, 0x03, 0x08, 0x0f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x04,
-    0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x04, 0x12, 0x03, 0x04, 0x02, 0x0a,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x05, 0x12, 0x03, 0x04, 0x0b, 0x11, 0x0a, 0x0c,
-    0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x04, 0x12, 0x14, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x00, 0x02, 0x00, 0x03, 0x12, 0x03, 0x04, 0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00,
-    0x02, 0x01, 0x12, 0x03, 0x05, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x04,
-    0x12, 0x03, 0x05, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x05, 0x12, 0x03,
-    0x05, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x05, 0x12,
-    0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03, 0x05, 0x19, 0x1a,
+    0x07, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x22, 0x54, 0x0a, 0x07, 0x52, 0x65, 0x71, 0x75,
+    0x65, 0x73, 0x74, 0x12, 0x0a, 0x0a, 0x02, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x04, 0x12,
+    0x0c, 0x0a, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x12, 0x0b, 0x0a,
+    0x03, 0x74, 0x74, 0x6c, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0d, 0x12, 0x10, 0x0a, 0x08, 0x64, 0x65,
+    0x61, 0x64, 0x6c, 0x69, 0x6e, 0x65, 0x18, 0x04, 0x20, 0x01, 0x28, 0x03, 0x12, 0x10, 0x0a, 0x08,
+    0x74, 0x72, 0x61, 0x63, 0x65, 0x5f, 0x69, 0x64, 0x18, 0x05, 0x20, 0x01, 0x28, 0x09, 0x4a, 0xae,
+    0x03, 0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x09, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12, 0x03,
+    0x00, 0x08, 0x0f, 0x0a, 0x35, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x03, 0x00, 0x09, 0x01, 0x1a,
+    0x29, 0x20, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x20, 0x64, 0x65, 0x66, 0x69, 0x6e, 0x65,
+    0x73, 0x20, 0x61, 0x20, 0x72, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x20, 0x74, 0x6f, 0x20, 0x61,
+    0x20, 0x73, 0x65, 0x72, 0x76, 0x69, 0x63, 0x65, 0x2e, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x00,
+    0x01, 0x12, 0x03, 0x03, 0x08, 0x0f, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03,
+    0x04, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x04, 0x12, 0x03, 0x04, 0x02,
+    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x05, 0x12, 0x03, 0x04, 0x0b, 0x11, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x04, 0x12, 0x14, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12, 0x03, 0x04, 0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
+    0x00, 0x02, 0x01, 0x12, 0x03, 0x05, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01,
+    0x04, 0x12, 0x03, 0x05, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x05, 0x12,
+    0x03, 0x05, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x05,
+    0x12, 0x16, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03, 0x05, 0x19, 0x1a,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x02, 0x12, 0x03, 0x06, 0x02, 0x1a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x00, 0x02, 0x02, 0x04, 0x12, 0x03, 0x06, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x00, 0x02, 0x02, 0x05, 0x12, 0x03, 0x06, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
+    0x02, 0x01, 0x12, 0x03, 0x06, 0x12, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x03,
+    0x12, 0x03, 0x06, 0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x03, 0x12, 0x03, 0x07,
+    0x02, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x04, 0x12, 0x03, 0x07, 0x02, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x05, 0x12, 0x03, 0x07, 0x0b, 0x10, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x03, 0x07, 0x11, 0x19, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x00, 0x02, 0x03, 0x03, 0x12, 0x03, 0x07, 0x1c, 0x1d, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00,
+    0x02, 0x04, 0x12, 0x03, 0x08, 0x02, 0x1f, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x04,
+    0x12, 0x03, 0x08, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x05, 0x12, 0x03,
+    0x08, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x03, 0x08, 0x12,
+    0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x03, 0x12, 0x03, 0x08, 0x1d, 0x1e,
 ];
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
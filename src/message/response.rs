@@ -13,10 +13,12 @@ use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 #[derive(Clone,Default)]
 pub struct Response {
     // message fields
-    request_id: ::std::option::Option<u32>,
+    request_id: ::std::option::Option<u64>,
     kind: ::std::option::Option<Response_Kind>,
     message: ::protobuf::SingularField<::std::string::String>,
     data: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    redirect_service: ::protobuf::SingularField<::std::string::String>,
+    redirect_node_id: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::std::cell::Cell<u32>,
@@ -39,6 +41,8 @@ impl Response {
                     kind: ::std::option::Option::None,
                     message: ::protobuf::SingularField::none(),
                     data: ::protobuf::SingularField::none(),
+                    redirect_service: ::protobuf::SingularField::none(),
+                    redirect_node_id: ::protobuf::SingularField::none(),
                     unknown_fields: ::protobuf::UnknownFields::new(),
                     cached_size: ::std::cell::Cell::new(0),
                 }
@@ -46,7 +50,7 @@ impl Response {
         }
     }
 
-    // optional uint32 request_id = 1;
+    // optional uint64 request_id = 1;
 
     pub fn clear_request_id(&mut self) {
         self.request_id = ::std::option::Option::None;
@@ -57,11 +61,11 @@ impl Response {
     }
 
     // Param is passed by value, moved
-    pub fn set_request_id(&mut self, v: u32) {
+    pub fn set_request_id(&mut self, v: u64) {
         self.request_id = ::std::option::Option::Some(v);
     }
 
-    pub fn get_request_id<'a>(&self) -> u32 {
+    pub fn get_request_id<'a>(&self) -> u64 {
         self.request_id.unwrap_or(0)
     }
 
@@ -155,6 +159,78 @@ impl Response {
             None => &[],
         }
     }
+
+    // optional string redirect_service = 5;
+
+    pub fn clear_redirect_service(&mut self) {
+        self.redirect_service.clear();
+    }
+
+    pub fn has_redirect_service(&self) -> bool {
+        self.redirect_service.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_redirect_service(&mut self, v: ::std::string::String) {
+        self.redirect_service = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_redirect_service<'a>(&'a mut self) -> &'a mut ::std::string::String {
+        if self.redirect_service.is_none() {
+            self.redirect_service.set_default();
+        };
+        self.redirect_service.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_redirect_service(&mut self) -> ::std::string::String {
+        self.redirect_service.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_redirect_service<'a>(&'a self) -> &'a str {
+        match self.redirect_service.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    // optional bytes redirect_node_id = 6;
+
+    pub fn clear_redirect_node_id(&mut self) {
+        self.redirect_node_id.clear();
+    }
+
+    pub fn has_redirect_node_id(&self) -> bool {
+        self.redirect_node_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_redirect_node_id(&mut self, v: ::std::vec::Vec<u8>) {
+        self.redirect_node_id = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_redirect_node_id<'a>(&'a mut self) -> &'a mut ::std::vec::Vec<u8> {
+        if self.redirect_node_id.is_none() {
+            self.redirect_node_id.set_default();
+        };
+        self.redirect_node_id.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_redirect_node_id(&mut self) -> ::std::vec::Vec<u8> {
+        self.redirect_node_id.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_redirect_node_id<'a>(&'a self) -> &'a [u8] {
+        match self.redirect_node_id.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
 }
 
 impl ::protobuf::Message for Response {
@@ -170,7 +246,7 @@ impl ::protobuf::Message for Response {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
                     };
-                    let tmp = try!(is.read_uint32());
+                    let tmp = try!(is.read_uint64());
                     self.request_id = ::std::option::Option::Some(tmp);
                 },
                 2 => {
@@ -194,6 +270,20 @@ impl ::protobuf::Message for Response {
                     let tmp = self.data.set_default();
                     try!(is.read_bytes_into(tmp))
                 },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = self.redirect_service.set_default();
+                    try!(is.read_string_into(tmp))
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = self.redirect_node_id.set_default();
+                    try!(is.read_bytes_into(tmp))
+                },
                 _ => {
                     let unknown = try!(is.read_unknown(wire_type));
                     self.mut_unknown_fields().add_value(field_number, unknown);
@@ -219,6 +309,12 @@ impl ::protobuf::Message for Response {
         for value in self.data.iter() {
             my_size += ::protobuf::rt::bytes_size(4, &value);
         };
+        for value in self.redirect_service.iter() {
+            my_size += ::protobuf::rt::string_size(5, &value);
+        };
+        for value in self.redirect_node_id.iter() {
+            my_size += ::protobuf::rt::bytes_size(6, &value);
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -226,7 +322,7 @@ impl ::protobuf::Message for Response {
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
         if let Some(v) = self.request_id {
-            try!(os.write_uint32(1, v));
+            try!(os.write_uint64(1, v));
         };
         if let Some(v) = self.kind {
             try!(os.write_enum(2, v as i32));
@@ -237,6 +333,12 @@ impl ::protobuf::Message for Response {
         if let Some(v) = self.data.as_ref() {
             try!(os.write_bytes(4, &v));
         };
+        if let Some(v) = self.redirect_service.as_ref() {
+            try!(os.write_string(5, &v));
+        };
+        if let Some(v) = self.redirect_node_id.as_ref() {
+            try!(os.write_bytes(6, &v));
+        };
         try!(os.write_unknown_fields(self.get_unknown_fields()));
         ::std::result::Result::Ok(())
     }
@@ -279,7 +381,7 @@ impl ::protobuf::MessageStatic for Response {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_singular_u32_accessor(
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
                     "request_id",
                     Response::has_request_id,
                     Response::get_request_id,
@@ -299,6 +401,16 @@ impl ::protobuf::MessageStatic for Response {
                     Response::has_data,
                     Response::get_data,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor(
+                    "redirect_service",
+                    Response::has_redirect_service,
+                    Response::get_redirect_service,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_bytes_accessor(
+                    "redirect_node_id",
+                    Response::has_redirect_node_id,
+                    Response::get_redirect_node_id,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Response>(
                     "Response",
                     fields,
@@ -315,6 +427,8 @@ impl ::protobuf::Clear for Response {
         self.clear_kind();
         self.clear_message();
         self.clear_data();
+        self.clear_redirect_service();
+        self.clear_redirect_node_id();
         self.unknown_fields.clear();
     }
 }
@@ -325,6 +439,8 @@ impl ::std::cmp::PartialEq for Response {
         self.kind == other.kind &&
         self.message == other.message &&
         self.data == other.data &&
+        self.redirect_service == other.redirect_service &&
+        self.redirect_node_id == other.redirect_node_id &&
         self.unknown_fields == other.unknown_fields
     }
 }
@@ -341,6 +457,8 @@ pub enum Response_Kind {
     Unavailable = 2,
     Timeout = 3,
     Internal = 4,
+    InvalidInput = 5,
+    Redirect = 6,
 }
 
 impl ::protobuf::ProtobufEnum for Response_Kind {
@@ -354,6 +472,8 @@ impl ::protobuf::ProtobufEnum for Response_Kind {
             2 => ::std::option::Option::Some(Response_Kind::Unavailable),
             3 => ::std::option::Option::Some(Response_Kind::Timeout),
             4 => ::std::option::Option::Some(Response_Kind::Internal),
+            5 => ::std::option::Option::Some(Response_Kind::InvalidInput),
+            6 => ::std::option::Option::Some(Response_Kind::Redirect),
             _ => ::std::option::Option::None
         }
     }
@@ -376,54 +496,73 @@ impl ::std::marker::Copy for Response_Kind {
 
 static file_descriptor_proto_data: &'static [u8] = &[
     0x0a, 0x0e, 0x72, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f,
-    0x12, 0x07, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x22, 0x9f, 0x01, 0x0a, 0x08, 0x52, 0x65,
+    0x12, 0x07, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x22, 0xf3, 0x01, 0x0a, 0x08, 0x52, 0x65,
     0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x12, 0x0a, 0x0a, 0x72, 0x65, 0x71, 0x75, 0x65, 0x73,
-    0x74, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x0d, 0x12, 0x24, 0x0a, 0x04, 0x6b, 0x69,
+    0x74, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x04, 0x12, 0x24, 0x0a, 0x04, 0x6b, 0x69,
     0x6e, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0e, 0x32, 0x16, 0x2e, 0x6d, 0x65, 0x73, 0x73, 0x61,
     0x67, 0x65, 0x2e, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x2e, 0x4b, 0x69, 0x6e, 0x64,
     0x12, 0x0f, 0x0a, 0x07, 0x6d, 0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x18, 0x03, 0x20, 0x01, 0x28,
-    0x09, 0x12, 0x0c, 0x0a, 0x04, 0x64, 0x61, 0x74, 0x61, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0c, 0x22,
-    0x3a, 0x0a, 0x04, 0x4b, 0x69, 0x6e, 0x64, 0x12, 0x06, 0x0a, 0x02, 0x4f, 0x4b, 0x10, 0x01, 0x12,
-    0x0f, 0x0a, 0x0b, 0x55, 0x6e, 0x61, 0x76, 0x61, 0x69, 0x6c, 0x61, 0x62, 0x6c, 0x65, 0x10, 0x02,
-    0x12, 0x0b, 0x0a, 0x07, 0x54, 0x69, 0x6d, 0x65, 0x6f, 0x75, 0x74, 0x10, 0x03, 0x12, 0x0c, 0x0a,
-    0x08, 0x49, 0x6e, 0x74, 0x65, 0x72, 0x6e, 0x61, 0x6c, 0x10, 0x04, 0x4a, 0xc5, 0x04, 0x0a, 0x06,
-    0x12, 0x04, 0x00, 0x00, 0x0f, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12, 0x03, 0x00, 0x08, 0x0f,
-    0x0a, 0x39, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x03, 0x00, 0x0f, 0x01, 0x1a, 0x2d, 0x20, 0x52,
-    0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x20, 0x64, 0x65, 0x66, 0x69, 0x6e, 0x65, 0x73, 0x20,
-    0x61, 0x20, 0x72, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x20, 0x66, 0x72, 0x6f, 0x6d, 0x20,
-    0x61, 0x20, 0x73, 0x65, 0x72, 0x76, 0x69, 0x63, 0x65, 0x2e, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04,
-    0x00, 0x01, 0x12, 0x03, 0x03, 0x08, 0x10, 0x0a, 0x0c, 0x0a, 0x04, 0x04, 0x00, 0x04, 0x00, 0x12,
-    0x04, 0x04, 0x02, 0x09, 0x03, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x04, 0x00, 0x01, 0x12, 0x03,
-    0x04, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x05,
-    0x04, 0x0b, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x00, 0x01, 0x12, 0x03, 0x05,
-    0x04, 0x06, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x00, 0x02, 0x12, 0x03, 0x05,
-    0x09, 0x0a, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x06, 0x04,
-    0x14, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x06, 0x04,
-    0x0f, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x01, 0x02, 0x12, 0x03, 0x06, 0x12,
-    0x13, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02, 0x02, 0x12, 0x03, 0x07, 0x04, 0x10,
-    0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03, 0x07, 0x04, 0x0b,
-    0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12, 0x03, 0x07, 0x0e, 0x0f,
-    0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02, 0x03, 0x12, 0x03, 0x08, 0x04, 0x11, 0x0a,
-    0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x03, 0x08, 0x04, 0x0c, 0x0a,
-    0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x03, 0x02, 0x12, 0x03, 0x08, 0x0f, 0x10, 0x0a,
-    0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x0b, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05,
-    0x04, 0x00, 0x02, 0x00, 0x04, 0x12, 0x03, 0x0b, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
-    0x02, 0x00, 0x05, 0x12, 0x03, 0x0b, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00,
-    0x01, 0x12, 0x03, 0x0b, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x03, 0x12,
-    0x03, 0x0b, 0x1f, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03, 0x0c, 0x02,
-    0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x04, 0x12, 0x03, 0x0c, 0x02, 0x0a, 0x0a,
-    0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x06, 0x12, 0x03, 0x0c, 0x0b, 0x0f, 0x0a, 0x0c, 0x0a,
-    0x05, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x0c, 0x10, 0x14, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x00, 0x02, 0x01, 0x03, 0x12, 0x03, 0x0c, 0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02,
-    0x02, 0x12, 0x03, 0x0d, 0x02, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x04, 0x12,
-    0x03, 0x0d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x05, 0x12, 0x03, 0x0d,
-    0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03, 0x0d, 0x12, 0x19,
-    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x03, 0x12, 0x03, 0x0d, 0x1c, 0x1d, 0x0a, 0x0b,
-    0x0a, 0x04, 0x04, 0x00, 0x02, 0x03, 0x12, 0x03, 0x0e, 0x02, 0x1a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
-    0x00, 0x02, 0x03, 0x04, 0x12, 0x03, 0x0e, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
-    0x03, 0x05, 0x12, 0x03, 0x0e, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x01,
-    0x12, 0x03, 0x0e, 0x11, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x03, 0x12, 0x03,
-    0x0e, 0x18, 0x19,
+    0x09, 0x12, 0x0c, 0x0a, 0x04, 0x64, 0x61, 0x74, 0x61, 0x18, 0x04, 0x20, 0x01, 0x28, 0x0c, 0x12,
+    0x18, 0x0a, 0x10, 0x72, 0x65, 0x64, 0x69, 0x72, 0x65, 0x63, 0x74, 0x5f, 0x73, 0x65, 0x72, 0x76,
+    0x69, 0x63, 0x65, 0x18, 0x05, 0x20, 0x01, 0x28, 0x09, 0x12, 0x18, 0x0a, 0x10, 0x72, 0x65, 0x64,
+    0x69, 0x72, 0x65, 0x63, 0x74, 0x5f, 0x6e, 0x6f, 0x64, 0x65, 0x5f, 0x69, 0x64, 0x18, 0x06, 0x20,
+    0x01, 0x28, 0x0c, 0x22, 0x5a, 0x0a, 0x04, 0x4b, 0x69, 0x6e, 0x64, 0x12, 0x06, 0x0a, 0x02, 0x4f,
+    0x4b, 0x10, 0x01, 0x12, 0x0f, 0x0a, 0x0b, 0x55, 0x6e, 0x61, 0x76, 0x61, 0x69, 0x6c, 0x61, 0x62,
+    0x6c, 0x65, 0x10, 0x02, 0x12, 0x0b, 0x0a, 0x07, 0x54, 0x69, 0x6d, 0x65, 0x6f, 0x75, 0x74, 0x10,
+    0x03, 0x12, 0x0c, 0x0a, 0x08, 0x49, 0x6e, 0x74, 0x65, 0x72, 0x6e, 0x61, 0x6c, 0x10, 0x04, 0x12,
+    0x10, 0x0a, 0x0c, 0x49, 0x6e, 0x76, 0x61, 0x6c, 0x69, 0x64, 0x49, 0x6e, 0x70, 0x75, 0x74, 0x10,
+    0x05, 0x12, 0x0c, 0x0a, 0x08, 0x52, 0x65, 0x64, 0x69, 0x72, 0x65, 0x63, 0x74, 0x10, 0x06, 0x4a,
+    0xad, 0x06, 0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x13, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12,
+    0x03, 0x00, 0x08, 0x0f, 0x0a, 0x39, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x03, 0x00, 0x13, 0x01,
+    0x1a, 0x2d, 0x20, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x20, 0x64, 0x65, 0x66, 0x69,
+    0x6e, 0x65, 0x73, 0x20, 0x61, 0x20, 0x72, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x20, 0x66,
+    0x72, 0x6f, 0x6d, 0x20, 0x61, 0x20, 0x73, 0x65, 0x72, 0x76, 0x69, 0x63, 0x65, 0x2e, 0x0a, 0x0a,
+    0x0a, 0x0a, 0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x03, 0x08, 0x10, 0x0a, 0x0c, 0x0a, 0x04, 0x04,
+    0x00, 0x04, 0x00, 0x12, 0x04, 0x04, 0x02, 0x0b, 0x03, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x04,
+    0x00, 0x01, 0x12, 0x03, 0x04, 0x07, 0x0b, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02,
+    0x00, 0x12, 0x03, 0x05, 0x04, 0x0b, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x00,
+    0x01, 0x12, 0x03, 0x05, 0x04, 0x06, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x00,
+    0x02, 0x12, 0x03, 0x05, 0x09, 0x0a, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02, 0x01,
+    0x12, 0x03, 0x06, 0x04, 0x14, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x01, 0x01,
+    0x12, 0x03, 0x06, 0x04, 0x0f, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x01, 0x02,
+    0x12, 0x03, 0x06, 0x12, 0x13, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02, 0x02, 0x12,
+    0x03, 0x07, 0x04, 0x10, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12,
+    0x03, 0x07, 0x04, 0x0b, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x02, 0x02, 0x12,
+    0x03, 0x07, 0x0e, 0x0f, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02, 0x03, 0x12, 0x03,
+    0x08, 0x04, 0x11, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x03, 0x01, 0x12, 0x03,
+    0x08, 0x04, 0x0c, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x03, 0x02, 0x12, 0x03,
+    0x08, 0x0f, 0x10, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02, 0x04, 0x12, 0x03, 0x09,
+    0x04, 0x15, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x03, 0x09,
+    0x04, 0x10, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x04, 0x02, 0x12, 0x03, 0x09,
+    0x13, 0x14, 0x0a, 0x0d, 0x0a, 0x06, 0x04, 0x00, 0x04, 0x00, 0x02, 0x05, 0x12, 0x03, 0x0a, 0x04,
+    0x11, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x05, 0x01, 0x12, 0x03, 0x0a, 0x04,
+    0x0c, 0x0a, 0x0e, 0x0a, 0x07, 0x04, 0x00, 0x04, 0x00, 0x02, 0x05, 0x02, 0x12, 0x03, 0x0a, 0x0f,
+    0x10, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x0d, 0x02, 0x21, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x04, 0x12, 0x03, 0x0d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x00, 0x02, 0x00, 0x05, 0x12, 0x03, 0x0d, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
+    0x02, 0x00, 0x01, 0x12, 0x03, 0x0d, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00,
+    0x03, 0x12, 0x03, 0x0d, 0x1f, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x01, 0x12, 0x03,
+    0x0e, 0x02, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x04, 0x12, 0x03, 0x0e, 0x02,
+    0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x06, 0x12, 0x03, 0x0e, 0x0b, 0x0f, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x01, 0x01, 0x12, 0x03, 0x0e, 0x10, 0x14, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x00, 0x02, 0x01, 0x03, 0x12, 0x03, 0x0e, 0x17, 0x18, 0x0a, 0x0b, 0x0a, 0x04, 0x04,
+    0x00, 0x02, 0x02, 0x12, 0x03, 0x0f, 0x02, 0x1e, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02,
+    0x04, 0x12, 0x03, 0x0f, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x05, 0x12,
+    0x03, 0x0f, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x01, 0x12, 0x03, 0x0f,
+    0x12, 0x19, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x02, 0x03, 0x12, 0x03, 0x0f, 0x1c, 0x1d,
+    0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x03, 0x12, 0x03, 0x10, 0x02, 0x1a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x00, 0x02, 0x03, 0x04, 0x12, 0x03, 0x10, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x00, 0x02, 0x03, 0x05, 0x12, 0x03, 0x10, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02,
+    0x03, 0x01, 0x12, 0x03, 0x10, 0x11, 0x15, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x03, 0x03,
+    0x12, 0x03, 0x10, 0x18, 0x19, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x04, 0x12, 0x03, 0x11,
+    0x02, 0x27, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x04, 0x12, 0x03, 0x11, 0x02, 0x0a,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x05, 0x12, 0x03, 0x11, 0x0b, 0x11, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x00, 0x02, 0x04, 0x01, 0x12, 0x03, 0x11, 0x12, 0x22, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x00, 0x02, 0x04, 0x03, 0x12, 0x03, 0x11, 0x25, 0x26, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00,
+    0x02, 0x05, 0x12, 0x03, 0x12, 0x02, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x05, 0x04,
+    0x12, 0x03, 0x12, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x05, 0x05, 0x12, 0x03,
+    0x12, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x05, 0x01, 0x12, 0x03, 0x12, 0x11,
+    0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x05, 0x03, 0x12, 0x03, 0x12, 0x24, 0x25,
 ];
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -0,0 +1,745 @@
+// This file is generated. Do not edit
+// @generated
+
+#![allow(dead_code)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(unused_imports)]
+
+use protobuf::Message as Message_imported_for_functions;
+use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
+
+#[derive(Clone,Default)]
+pub struct StatsRequest {
+    // message fields
+    request_id: ::std::option::Option<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::std::cell::Cell<u32>,
+}
+
+impl StatsRequest {
+    pub fn new() -> StatsRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static StatsRequest {
+        static mut instance: ::protobuf::lazy::Lazy<StatsRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const StatsRequest,
+        };
+        unsafe {
+            instance.get(|| {
+                StatsRequest {
+                    request_id: ::std::option::Option::None,
+                    unknown_fields: ::protobuf::UnknownFields::new(),
+                    cached_size: ::std::cell::Cell::new(0),
+                }
+            })
+        }
+    }
+
+    // optional uint64 request_id = 1;
+
+    pub fn clear_request_id(&mut self) {
+        self.request_id = ::std::option::Option::None;
+    }
+
+    pub fn has_request_id(&self) -> bool {
+        self.request_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_request_id(&mut self, v: u64) {
+        self.request_id = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_request_id<'a>(&self) -> u64 {
+        self.request_id.unwrap_or(0)
+    }
+}
+
+impl ::protobuf::Message for StatsRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !try!(is.eof()) {
+            let (field_number, wire_type) = try!(is.read_tag_unpack());
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint64());
+                    self.request_id = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    let unknown = try!(is.read_unknown(wire_type));
+                    self.mut_unknown_fields().add_value(field_number, unknown);
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in self.request_id.iter() {
+            my_size += ::protobuf::rt::value_size(1, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.request_id {
+            try!(os.write_uint64(1, v));
+        };
+        try!(os.write_unknown_fields(self.get_unknown_fields()));
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields<'s>(&'s self) -> &'s ::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields<'s>(&'s mut self) -> &'s mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn type_id(&self) -> ::std::any::TypeId {
+        ::std::any::TypeId::of::<StatsRequest>()
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for StatsRequest {
+    fn new() -> StatsRequest {
+        StatsRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<StatsRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                    "request_id",
+                    StatsRequest::has_request_id,
+                    StatsRequest::get_request_id,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<StatsRequest>(
+                    "StatsRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for StatsRequest {
+    fn clear(&mut self) {
+        self.clear_request_id();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::cmp::PartialEq for StatsRequest {
+    fn eq(&self, other: &StatsRequest) -> bool {
+        self.request_id == other.request_id &&
+        self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl ::std::fmt::Debug for StatsRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+#[derive(Clone,Default)]
+pub struct StatsResponse {
+    // message fields
+    bytes_sent: ::std::option::Option<u64>,
+    bytes_received: ::std::option::Option<u64>,
+    frames_sent: ::std::option::Option<u64>,
+    frames_received: ::std::option::Option<u64>,
+    last_error: ::protobuf::SingularField<::std::string::String>,
+    clock: ::std::option::Option<i64>,
+    uptime: ::std::option::Option<i64>,
+    request_id: ::std::option::Option<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::std::cell::Cell<u32>,
+}
+
+impl StatsResponse {
+    pub fn new() -> StatsResponse {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static StatsResponse {
+        static mut instance: ::protobuf::lazy::Lazy<StatsResponse> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const StatsResponse,
+        };
+        unsafe {
+            instance.get(|| {
+                StatsResponse {
+                    bytes_sent: ::std::option::Option::None,
+                    bytes_received: ::std::option::Option::None,
+                    frames_sent: ::std::option::Option::None,
+                    frames_received: ::std::option::Option::None,
+                    last_error: ::protobuf::SingularField::none(),
+                    clock: ::std::option::Option::None,
+                    uptime: ::std::option::Option::None,
+                    request_id: ::std::option::Option::None,
+                    unknown_fields: ::protobuf::UnknownFields::new(),
+                    cached_size: ::std::cell::Cell::new(0),
+                }
+            })
+        }
+    }
+
+    // optional uint64 bytes_sent = 1;
+
+    pub fn clear_bytes_sent(&mut self) {
+        self.bytes_sent = ::std::option::Option::None;
+    }
+
+    pub fn has_bytes_sent(&self) -> bool {
+        self.bytes_sent.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_bytes_sent(&mut self, v: u64) {
+        self.bytes_sent = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_bytes_sent<'a>(&self) -> u64 {
+        self.bytes_sent.unwrap_or(0)
+    }
+
+    // optional uint64 bytes_received = 2;
+
+    pub fn clear_bytes_received(&mut self) {
+        self.bytes_received = ::std::option::Option::None;
+    }
+
+    pub fn has_bytes_received(&self) -> bool {
+        self.bytes_received.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_bytes_received(&mut self, v: u64) {
+        self.bytes_received = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_bytes_received<'a>(&self) -> u64 {
+        self.bytes_received.unwrap_or(0)
+    }
+
+    // optional uint64 frames_sent = 3;
+
+    pub fn clear_frames_sent(&mut self) {
+        self.frames_sent = ::std::option::Option::None;
+    }
+
+    pub fn has_frames_sent(&self) -> bool {
+        self.frames_sent.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_frames_sent(&mut self, v: u64) {
+        self.frames_sent = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_frames_sent<'a>(&self) -> u64 {
+        self.frames_sent.unwrap_or(0)
+    }
+
+    // optional uint64 frames_received = 4;
+
+    pub fn clear_frames_received(&mut self) {
+        self.frames_received = ::std::option::Option::None;
+    }
+
+    pub fn has_frames_received(&self) -> bool {
+        self.frames_received.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_frames_received(&mut self, v: u64) {
+        self.frames_received = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_frames_received<'a>(&self) -> u64 {
+        self.frames_received.unwrap_or(0)
+    }
+
+    // optional string last_error = 5;
+
+    pub fn clear_last_error(&mut self) {
+        self.last_error.clear();
+    }
+
+    pub fn has_last_error(&self) -> bool {
+        self.last_error.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_last_error(&mut self, v: ::std::string::String) {
+        self.last_error = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_last_error<'a>(&'a mut self) -> &'a mut ::std::string::String {
+        if self.last_error.is_none() {
+            self.last_error.set_default();
+        };
+        self.last_error.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_last_error(&mut self) -> ::std::string::String {
+        self.last_error.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_last_error<'a>(&'a self) -> &'a str {
+        match self.last_error.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    // optional int64 clock = 6;
+
+    pub fn clear_clock(&mut self) {
+        self.clock = ::std::option::Option::None;
+    }
+
+    pub fn has_clock(&self) -> bool {
+        self.clock.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_clock(&mut self, v: i64) {
+        self.clock = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_clock<'a>(&self) -> i64 {
+        self.clock.unwrap_or(0)
+    }
+
+    // optional int64 uptime = 7;
+
+    pub fn clear_uptime(&mut self) {
+        self.uptime = ::std::option::Option::None;
+    }
+
+    pub fn has_uptime(&self) -> bool {
+        self.uptime.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_uptime(&mut self, v: i64) {
+        self.uptime = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_uptime<'a>(&self) -> i64 {
+        self.uptime.unwrap_or(0)
+    }
+
+    // optional uint64 request_id = 8;
+
+    pub fn clear_request_id(&mut self) {
+        self.request_id = ::std::option::Option::None;
+    }
+
+    pub fn has_request_id(&self) -> bool {
+        self.request_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_request_id(&mut self, v: u64) {
+        self.request_id = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_request_id<'a>(&self) -> u64 {
+        self.request_id.unwrap_or(0)
+    }
+}
+
+impl ::protobuf::Message for StatsResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !try!(is.eof()) {
+            let (field_number, wire_type) = try!(is.read_tag_unpack());
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint64());
+                    self.bytes_sent = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint64());
+                    self.bytes_received = ::std::option::Option::Some(tmp);
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint64());
+                    self.frames_sent = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint64());
+                    self.frames_received = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = self.last_error.set_default();
+                    try!(is.read_string_into(tmp))
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_int64());
+                    self.clock = ::std::option::Option::Some(tmp);
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_int64());
+                    self.uptime = ::std::option::Option::Some(tmp);
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::ProtobufError::WireError("unexpected wire type".to_string()));
+                    };
+                    let tmp = try!(is.read_uint64());
+                    self.request_id = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    let unknown = try!(is.read_unknown(wire_type));
+                    self.mut_unknown_fields().add_value(field_number, unknown);
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in self.bytes_sent.iter() {
+            my_size += ::protobuf::rt::value_size(1, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in self.bytes_received.iter() {
+            my_size += ::protobuf::rt::value_size(2, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in self.frames_sent.iter() {
+            my_size += ::protobuf::rt::value_size(3, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in self.frames_received.iter() {
+            my_size += ::protobuf::rt::value_size(4, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in self.last_error.iter() {
+            my_size += ::protobuf::rt::string_size(5, &value);
+        };
+        for value in self.clock.iter() {
+            my_size += ::protobuf::rt::value_size(6, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in self.uptime.iter() {
+            my_size += ::protobuf::rt::value_size(7, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        for value in self.request_id.iter() {
+            my_size += ::protobuf::rt::value_size(8, *value, ::protobuf::wire_format::WireTypeVarint);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.bytes_sent {
+            try!(os.write_uint64(1, v));
+        };
+        if let Some(v) = self.bytes_received {
+            try!(os.write_uint64(2, v));
+        };
+        if let Some(v) = self.frames_sent {
+            try!(os.write_uint64(3, v));
+        };
+        if let Some(v) = self.frames_received {
+            try!(os.write_uint64(4, v));
+        };
+        if let Some(v) = self.last_error.as_ref() {
+            try!(os.write_string(5, &v));
+        };
+        if let Some(v) = self.clock {
+            try!(os.write_int64(6, v));
+        };
+        if let Some(v) = self.uptime {
+            try!(os.write_int64(7, v));
+        };
+        if let Some(v) = self.request_id {
+            try!(os.write_uint64(8, v));
+        };
+        try!(os.write_unknown_fields(self.get_unknown_fields()));
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields<'s>(&'s self) -> &'s ::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields<'s>(&'s mut self) -> &'s mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn type_id(&self) -> ::std::any::TypeId {
+        ::std::any::TypeId::of::<StatsResponse>()
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for StatsResponse {
+    fn new() -> StatsResponse {
+        StatsResponse::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<StatsResponse>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                    "bytes_sent",
+                    StatsResponse::has_bytes_sent,
+                    StatsResponse::get_bytes_sent,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                    "bytes_received",
+                    StatsResponse::has_bytes_received,
+                    StatsResponse::get_bytes_received,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                    "frames_sent",
+                    StatsResponse::has_frames_sent,
+                    StatsResponse::get_frames_sent,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                    "frames_received",
+                    StatsResponse::has_frames_received,
+                    StatsResponse::get_frames_received,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_string_accessor(
+                    "last_error",
+                    StatsResponse::has_last_error,
+                    StatsResponse::get_last_error,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_i64_accessor(
+                    "clock",
+                    StatsResponse::has_clock,
+                    StatsResponse::get_clock,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_i64_accessor(
+                    "uptime",
+                    StatsResponse::has_uptime,
+                    StatsResponse::get_uptime,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_u64_accessor(
+                    "request_id",
+                    StatsResponse::has_request_id,
+                    StatsResponse::get_request_id,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<StatsResponse>(
+                    "StatsResponse",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for StatsResponse {
+    fn clear(&mut self) {
+        self.clear_bytes_sent();
+        self.clear_bytes_received();
+        self.clear_frames_sent();
+        self.clear_frames_received();
+        self.clear_last_error();
+        self.clear_clock();
+        self.clear_uptime();
+        self.clear_request_id();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::cmp::PartialEq for StatsResponse {
+    fn eq(&self, other: &StatsResponse) -> bool {
+        self.bytes_sent == other.bytes_sent &&
+        self.bytes_received == other.bytes_received &&
+        self.frames_sent == other.frames_sent &&
+        self.frames_received == other.frames_received &&
+        self.last_error == other.last_error &&
+        self.clock == other.clock &&
+        self.uptime == other.uptime &&
+        self.request_id == other.request_id &&
+        self.unknown_fields == other.unknown_fields
+    }
+}
+
+impl ::std::fmt::Debug for StatsResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = &[
+    0x0a, 0x0b, 0x73, 0x74, 0x61, 0x74, 0x73, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12, 0x07, 0x6d,
+    0x65, 0x73, 0x73, 0x61, 0x67, 0x65, 0x22, 0x22, 0x0a, 0x0c, 0x53, 0x74, 0x61, 0x74, 0x73, 0x52,
+    0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x12, 0x12, 0x0a, 0x0a, 0x72, 0x65, 0x71, 0x75, 0x65, 0x73,
+    0x74, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x04, 0x22, 0xb0, 0x01, 0x0a, 0x0d, 0x53,
+    0x74, 0x61, 0x74, 0x73, 0x52, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x73, 0x65, 0x12, 0x12, 0x0a, 0x0a,
+    0x62, 0x79, 0x74, 0x65, 0x73, 0x5f, 0x73, 0x65, 0x6e, 0x74, 0x18, 0x01, 0x20, 0x01, 0x28, 0x04,
+    0x12, 0x16, 0x0a, 0x0e, 0x62, 0x79, 0x74, 0x65, 0x73, 0x5f, 0x72, 0x65, 0x63, 0x65, 0x69, 0x76,
+    0x65, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x04, 0x12, 0x13, 0x0a, 0x0b, 0x66, 0x72, 0x61, 0x6d,
+    0x65, 0x73, 0x5f, 0x73, 0x65, 0x6e, 0x74, 0x18, 0x03, 0x20, 0x01, 0x28, 0x04, 0x12, 0x17, 0x0a,
+    0x0f, 0x66, 0x72, 0x61, 0x6d, 0x65, 0x73, 0x5f, 0x72, 0x65, 0x63, 0x65, 0x69, 0x76, 0x65, 0x64,
+    0x18, 0x04, 0x20, 0x01, 0x28, 0x04, 0x12, 0x12, 0x0a, 0x0a, 0x6c, 0x61, 0x73, 0x74, 0x5f, 0x65,
+    0x72, 0x72, 0x6f, 0x72, 0x18, 0x05, 0x20, 0x01, 0x28, 0x09, 0x12, 0x0d, 0x0a, 0x05, 0x63, 0x6c,
+    0x6f, 0x63, 0x6b, 0x18, 0x06, 0x20, 0x01, 0x28, 0x03, 0x12, 0x0e, 0x0a, 0x06, 0x75, 0x70, 0x74,
+    0x69, 0x6d, 0x65, 0x18, 0x07, 0x20, 0x01, 0x28, 0x03, 0x12, 0x12, 0x0a, 0x0a, 0x72, 0x65, 0x71,
+    0x75, 0x65, 0x73, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x08, 0x20, 0x01, 0x28, 0x04, 0x4a, 0xa7, 0x07,
+    0x0a, 0x06, 0x12, 0x04, 0x00, 0x00, 0x13, 0x01, 0x0a, 0x08, 0x0a, 0x01, 0x02, 0x12, 0x03, 0x00,
+    0x08, 0x0f, 0x0a, 0x71, 0x0a, 0x02, 0x04, 0x00, 0x12, 0x04, 0x04, 0x00, 0x06, 0x01, 0x1a, 0x65,
+    0x20, 0x53, 0x74, 0x61, 0x74, 0x73, 0x52, 0x65, 0x71, 0x75, 0x65, 0x73, 0x74, 0x20, 0x61, 0x73,
+    0x6b, 0x73, 0x20, 0x61, 0x20, 0x70, 0x65, 0x65, 0x72, 0x20, 0x66, 0x6f, 0x72, 0x20, 0x69, 0x74,
+    0x73, 0x20, 0x76, 0x69, 0x65, 0x77, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x68, 0x65, 0x20, 0x63, 0x6f,
+    0x6e, 0x6e, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x20, 0x54, 0x68, 0x65, 0x20, 0x72, 0x65,
+    0x71, 0x75, 0x65, 0x73, 0x74, 0x20, 0x69, 0x64, 0x20, 0x69, 0x73, 0x20, 0x65, 0x63, 0x68, 0x6f,
+    0x65, 0x64, 0x20, 0x69, 0x6e, 0x20, 0x74, 0x68, 0x65, 0x0a, 0x20, 0x72, 0x65, 0x73, 0x70, 0x6f,
+    0x6e, 0x73, 0x65, 0x2e, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x00, 0x01, 0x12, 0x03, 0x04, 0x08,
+    0x14, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x00, 0x02, 0x00, 0x12, 0x03, 0x05, 0x02, 0x21, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x00, 0x02, 0x00, 0x04, 0x12, 0x03, 0x05, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x00, 0x02, 0x00, 0x05, 0x12, 0x03, 0x05, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00,
+    0x02, 0x00, 0x01, 0x12, 0x03, 0x05, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x00, 0x02, 0x00,
+    0x03, 0x12, 0x03, 0x05, 0x1f, 0x20, 0x0a, 0x9a, 0x01, 0x0a, 0x02, 0x04, 0x01, 0x12, 0x04, 0x0a,
+    0x00, 0x13, 0x01, 0x1a, 0x8d, 0x01, 0x20, 0x53, 0x74, 0x61, 0x74, 0x73, 0x52, 0x65, 0x73, 0x70,
+    0x6f, 0x6e, 0x73, 0x65, 0x20, 0x63, 0x6f, 0x6e, 0x74, 0x61, 0x69, 0x6e, 0x73, 0x20, 0x74, 0x68,
+    0x65, 0x20, 0x72, 0x65, 0x73, 0x70, 0x6f, 0x6e, 0x64, 0x69, 0x6e, 0x67, 0x20, 0x6e, 0x6f, 0x64,
+    0x65, 0x27, 0x73, 0x20, 0x76, 0x69, 0x65, 0x77, 0x20, 0x6f, 0x66, 0x20, 0x74, 0x68, 0x65, 0x20,
+    0x63, 0x6f, 0x6e, 0x6e, 0x65, 0x63, 0x74, 0x69, 0x6f, 0x6e, 0x2e, 0x20, 0x54, 0x68, 0x65, 0x20,
+    0x63, 0x6c, 0x6f, 0x63, 0x6b, 0x20, 0x69, 0x73, 0x20, 0x74, 0x68, 0x65, 0x0a, 0x20, 0x6e, 0x6f,
+    0x64, 0x65, 0x27, 0x73, 0x20, 0x63, 0x75, 0x72, 0x72, 0x65, 0x6e, 0x74, 0x20, 0x74, 0x69, 0x6d,
+    0x65, 0x20, 0x69, 0x6e, 0x20, 0x6d, 0x69, 0x6c, 0x6c, 0x69, 0x73, 0x65, 0x63, 0x6f, 0x6e, 0x64,
+    0x73, 0x20, 0x73, 0x69, 0x6e, 0x63, 0x65, 0x20, 0x74, 0x68, 0x65, 0x20, 0x65, 0x70, 0x6f, 0x63,
+    0x68, 0x2e, 0x0a, 0x0a, 0x0a, 0x0a, 0x03, 0x04, 0x01, 0x01, 0x12, 0x03, 0x0a, 0x08, 0x15, 0x0a,
+    0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x00, 0x12, 0x03, 0x0b, 0x02, 0x21, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x01, 0x02, 0x00, 0x04, 0x12, 0x03, 0x0b, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01,
+    0x02, 0x00, 0x05, 0x12, 0x03, 0x0b, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00,
+    0x01, 0x12, 0x03, 0x0b, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x00, 0x03, 0x12,
+    0x03, 0x0b, 0x1f, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x01, 0x12, 0x03, 0x0c, 0x02,
+    0x25, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x04, 0x12, 0x03, 0x0c, 0x02, 0x0a, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x01, 0x05, 0x12, 0x03, 0x0c, 0x0b, 0x11, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x01, 0x02, 0x01, 0x01, 0x12, 0x03, 0x0c, 0x12, 0x20, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x01, 0x02, 0x01, 0x03, 0x12, 0x03, 0x0c, 0x23, 0x24, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02,
+    0x02, 0x12, 0x03, 0x0d, 0x02, 0x22, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x04, 0x12,
+    0x03, 0x0d, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x05, 0x12, 0x03, 0x0d,
+    0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x01, 0x12, 0x03, 0x0d, 0x12, 0x1d,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x02, 0x03, 0x12, 0x03, 0x0d, 0x20, 0x21, 0x0a, 0x0b,
+    0x0a, 0x04, 0x04, 0x01, 0x02, 0x03, 0x12, 0x03, 0x0e, 0x02, 0x26, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x01, 0x02, 0x03, 0x04, 0x12, 0x03, 0x0e, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
+    0x03, 0x05, 0x12, 0x03, 0x0e, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x03, 0x01,
+    0x12, 0x03, 0x0e, 0x12, 0x21, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x03, 0x03, 0x12, 0x03,
+    0x0e, 0x24, 0x25, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x04, 0x12, 0x03, 0x0f, 0x02, 0x21,
+    0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x04, 0x04, 0x12, 0x03, 0x0f, 0x02, 0x0a, 0x0a, 0x0c,
+    0x0a, 0x05, 0x04, 0x01, 0x02, 0x04, 0x05, 0x12, 0x03, 0x0f, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05,
+    0x04, 0x01, 0x02, 0x04, 0x01, 0x12, 0x03, 0x0f, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01,
+    0x02, 0x04, 0x03, 0x12, 0x03, 0x0f, 0x1f, 0x20, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x05,
+    0x12, 0x03, 0x10, 0x02, 0x1b, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x05, 0x04, 0x12, 0x03,
+    0x10, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x05, 0x05, 0x12, 0x03, 0x10, 0x0b,
+    0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x05, 0x01, 0x12, 0x03, 0x10, 0x11, 0x16, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x05, 0x03, 0x12, 0x03, 0x10, 0x19, 0x1a, 0x0a, 0x0b, 0x0a,
+    0x04, 0x04, 0x01, 0x02, 0x06, 0x12, 0x03, 0x11, 0x02, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01,
+    0x02, 0x06, 0x04, 0x12, 0x03, 0x11, 0x02, 0x0a, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x06,
+    0x05, 0x12, 0x03, 0x11, 0x0b, 0x10, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x06, 0x01, 0x12,
+    0x03, 0x11, 0x11, 0x17, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x06, 0x03, 0x12, 0x03, 0x11,
+    0x1a, 0x1b, 0x0a, 0x0b, 0x0a, 0x04, 0x04, 0x01, 0x02, 0x07, 0x12, 0x03, 0x12, 0x02, 0x21, 0x0a,
+    0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02, 0x07, 0x04, 0x12, 0x03, 0x12, 0x02, 0x0a, 0x0a, 0x0c, 0x0a,
+    0x05, 0x04, 0x01, 0x02, 0x07, 0x05, 0x12, 0x03, 0x12, 0x0b, 0x11, 0x0a, 0x0c, 0x0a, 0x05, 0x04,
+    0x01, 0x02, 0x07, 0x01, 0x12, 0x03, 0x12, 0x12, 0x1c, 0x0a, 0x0c, 0x0a, 0x05, 0x04, 0x01, 0x02,
+    0x07, 0x03, 0x12, 0x03, 0x12, 0x1f, 0x20,
+];
+
+static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
+    lock: ::protobuf::lazy::ONCE_INIT,
+    ptr: 0 as *const ::protobuf::descriptor::FileDescriptorProto,
+};
+
+fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
+    ::protobuf::parse_from_bytes(file_descriptor_proto_data).unwrap()
+}
+
+pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    unsafe {
+        file_descriptor_proto_lazy.get(|| {
+            parse_descriptor_proto()
+        })
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::{BTreeMap, HashMap};
+
+use rustc_serialize::json::{Json, ToJson};
+
+use super::{Naming, Value};
+
+pub fn json(values: &HashMap<String, Value>) -> Json {
+    json_with_naming(values, &Naming::default())
+}
+
+/// Builds an object with the values under `metrics` and the labels of the naming under
+/// `labels`, so a metric key can't collide with the labels.
+pub fn json_with_naming(values: &HashMap<String, Value>, naming: &Naming) -> Json {
+    let metrics = values.iter()
+                        .map(|(key, value)| (naming.key(key), value.to_json()))
+                        .collect::<BTreeMap<_, _>>();
+    let mut object = BTreeMap::new();
+    object.insert("metrics".to_string(), Json::Object(metrics));
+    if !naming.labels().is_empty() {
+        object.insert("labels".to_string(), naming.labels().to_json());
+    }
+    Json::Object(object)
+}
+
+pub fn prometheus(values: &HashMap<String, Value>) -> String {
+    prometheus_with_naming(values, &Naming::default())
+}
+
+pub fn prometheus_with_naming(values: &HashMap<String, Value>, naming: &Naming) -> String {
+    let mut keys = values.keys().collect::<Vec<_>>();
+    keys.sort();
+
+    let labels = prometheus_labels(naming);
+    let mut result = String::new();
+    for key in keys {
+        result.push_str(&prometheus_sample(&naming.key(key), &labels, &values[key]));
+    }
+    result
+}
+
+pub fn prometheus_sample(key: &str, labels: &str, value: &Value) -> String {
+    let name = prometheus_name(key);
+    format!("# TYPE {} {}\n{}{} {}\n",
+            name,
+            value.type_name(),
+            name,
+            labels,
+            value)
+}
+
+pub fn prometheus_labels(naming: &Naming) -> String {
+    if naming.labels().is_empty() {
+        return String::new();
+    }
+    let labels = naming.labels()
+                       .iter()
+                       .map(|(name, value)| {
+                           format!("{}=\"{}\"",
+                                   prometheus_name(name),
+                                   value.replace("\\", "\\\\").replace("\"", "\\\""))
+                       })
+                       .collect::<Vec<_>>();
+    format!("{{{}}}", labels.join(","))
+}
+
+pub fn prometheus_name(key: &str) -> String {
+    let mut name = key.chars()
+                      .map(|c| {
+                          if (c.is_alphanumeric() && c.len_utf8() == 1) || c == '_' || c == ':' {
+                              c
+                          } else {
+                              '_'
+                          }
+                      })
+                      .collect::<String>();
+    if name.chars().next().map_or(false, |c| c.is_numeric()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::{BTreeMap, HashMap};
+    use super::{json, json_with_naming, prometheus, prometheus_name, prometheus_with_naming};
+    use super::super::{Naming, Value};
+
+    #[test]
+    fn json_object() {
+        let mut values = HashMap::new();
+        values.insert("requests".to_string(), Value::Counter(3));
+        values.insert("connections".to_string(), Value::Gauge(2));
+
+        assert_eq!("{\"metrics\":{\"connections\":{\"type\":\"gauge\",\"value\":2},\
+                    \"requests\":{\"type\":\"counter\",\"value\":3}}}",
+                   json(&values).to_string());
+    }
+
+    #[test]
+    fn prometheus_text() {
+        let mut values = HashMap::new();
+        values.insert("service.echo.local".to_string(), Value::Counter(3));
+        values.insert("connections".to_string(), Value::Gauge(-2));
+
+        assert_eq!("# TYPE connections gauge\nconnections -2\n# TYPE service_echo_local \
+                    counter\nservice_echo_local 3\n",
+                   prometheus(&values));
+    }
+
+    #[test]
+    fn json_object_with_naming() {
+        let mut values = HashMap::new();
+        values.insert("requests".to_string(), Value::Counter(3));
+        values.insert("labels".to_string(), Value::Gauge(1));
+        let mut labels = BTreeMap::new();
+        labels.insert("cluster".to_string(), "one".to_string());
+
+        assert_eq!("{\"labels\":{\"cluster\":\"one\"},\
+                    \"metrics\":{\"delix.labels\":{\"type\":\"gauge\",\"value\":1},\
+                    \"delix.requests\":{\"type\":\"counter\",\"value\":3}}}",
+                   json_with_naming(&values,
+                                    &Naming::new(Some("delix".to_string()), labels.clone()))
+                       .to_string());
+        assert_eq!("{\"labels\":{\"cluster\":\"one\"},\
+                    \"metrics\":{\"labels\":{\"type\":\"gauge\",\"value\":1},\
+                    \"requests\":{\"type\":\"counter\",\"value\":3}}}",
+                   json_with_naming(&values, &Naming::new(None, labels)).to_string());
+    }
+
+    #[test]
+    fn prometheus_text_with_naming() {
+        let mut values = HashMap::new();
+        values.insert("requests".to_string(), Value::Counter(3));
+        let mut labels = BTreeMap::new();
+        labels.insert("cluster".to_string(), "one".to_string());
+        labels.insert("region".to_string(), "eu\"west".to_string());
+
+        assert_eq!("# TYPE delix_requests counter\n\
+                    delix_requests{cluster=\"one\",region=\"eu\\\"west\"} 3\n",
+                   prometheus_with_naming(&values,
+                                          &Naming::new(Some("delix".to_string()), labels)));
+    }
+
+    #[test]
+    fn prometheus_names() {
+        assert_eq!("service_echo_127_0_0_1", prometheus_name("service.echo.127-0-0-1"));
+        assert_eq!("_1st", prometheus_name("1st"));
+    }
+}
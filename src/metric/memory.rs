@@ -15,7 +15,7 @@
 
 use std::collections::{HashMap, hash_map};
 use std::sync::{Arc, Condvar, RwLock, Mutex, Weak, atomic};
-use super::{Metric, Query, Value, item};
+use super::{Metric, Naming, Query, Value, item};
 
 pub struct Memory {
     entries: RwLock<HashMap<String, Weak<Entry>>>,
@@ -24,11 +24,17 @@ pub struct Memory {
                                  Box<Fn(&str, &Value) -> bool + Send + Sync>,
                                  Arc<(Mutex<bool>, Condvar)>)>>>,
     next_watch_id: RwLock<u16>,
+    naming: Naming,
 }
 
 impl Memory {
     pub fn new() -> Self {
+        Self::with_naming(Naming::default())
+    }
+
+    pub fn with_naming(naming: Naming) -> Self {
         Memory {
+            naming: naming,
             entries: RwLock::new(HashMap::new()),
             watches: Arc::new(RwLock::new(HashMap::new())),
             next_watch_id: RwLock::new(0u16),
@@ -117,16 +123,28 @@ impl Metric for Memory {
     }
 
     fn display(&self) {
-        self.watch("", |key, value| {
+        if !self.naming.labels().is_empty() {
+            info!("metric labels {}", self.naming);
+        }
+        let naming = self.naming.clone();
+        self.watch("", move |key, value| {
             if key != "" {
-                info!("{} = {:?}", key, value);
+                info!("{} = {:?}", naming.key(key), value);
             }
             true
         });
     }
+
+    fn snapshot(&self) -> Option<(Naming, HashMap<String, Value>)> {
+        Some((self.naming.clone(), self.get_all_with_prefix("")))
+    }
 }
 
 impl Query for Memory {
+    fn naming(&self) -> &Naming {
+        &self.naming
+    }
+
     fn get(&self, key: &str) -> Option<Value> {
         let mut entries = self.entries.write().unwrap();
         match entries.entry(key.to_string()) {
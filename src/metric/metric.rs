@@ -13,11 +13,18 @@
 // limitations under the License.
 //
 
-use super::item;
+use std::collections::HashMap;
+
+use super::{Naming, Value, item};
 
 pub trait Metric : Sync + Send {
     fn log(&self, &str, &str, &str);
     fn counter(&self, &str) -> item::Counter;
     fn gauge(&self, &str) -> item::Gauge;
     fn display(&self);
+
+    /// Returns the naming and the current values, if the metric keeps them.
+    fn snapshot(&self) -> Option<(Naming, HashMap<String, Value>)> {
+        None
+    }
 }
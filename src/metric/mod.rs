@@ -14,15 +14,18 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+pub mod format;
 pub mod item;
 mod memory;
 pub mod metric;
+mod naming;
 mod query;
 mod terminal;
 mod value;
 
 pub use self::memory::Memory;
 pub use self::metric::Metric;
+pub use self::naming::Naming;
 pub use self::query::Query;
 pub use self::terminal::Terminal;
 pub use self::value::Value;
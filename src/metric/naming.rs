@@ -0,0 +1,98 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Naming {
+    prefix: Option<String>,
+    labels: BTreeMap<String, String>,
+}
+
+impl Naming {
+    pub fn new(prefix: Option<String>, labels: BTreeMap<String, String>) -> Self {
+        Naming {
+            prefix: prefix.map(|prefix| prefix.trim_right_matches('.').to_string())
+                          .and_then(|prefix| if prefix.is_empty() {
+                              None
+                          } else {
+                              Some(prefix)
+                          }),
+            labels: labels,
+        }
+    }
+
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_ref().map(|prefix| prefix.as_ref())
+    }
+
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    pub fn key(&self, key: &str) -> String {
+        match self.prefix {
+            Some(ref prefix) => format!("{}.{}", prefix, key),
+            None => key.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Naming {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref prefix) = self.prefix {
+            try!(write!(f, "{}", prefix));
+        }
+        if !self.labels.is_empty() {
+            let labels = self.labels
+                             .iter()
+                             .map(|(name, value)| format!("{}=\"{}\"", name, value))
+                             .collect::<Vec<_>>();
+            try!(write!(f, "{{{}}}", labels.join(",")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use super::Naming;
+
+    #[test]
+    fn key_with_prefix() {
+        let naming = Naming::new(Some("cluster_one.".to_string()), BTreeMap::new());
+        assert_eq!("cluster_one.requests", naming.key("requests"));
+    }
+
+    #[test]
+    fn key_without_prefix() {
+        assert_eq!("requests", Naming::default().key("requests"));
+        assert_eq!("requests",
+                   Naming::new(Some("".to_string()), BTreeMap::new()).key("requests"));
+    }
+
+    #[test]
+    fn display() {
+        let mut labels = BTreeMap::new();
+        labels.insert("region".to_string(), "eu".to_string());
+        labels.insert("cluster".to_string(), "one".to_string());
+
+        assert_eq!("delix{cluster=\"one\",region=\"eu\"}",
+                   format!("{}", Naming::new(Some("delix".to_string()), labels)));
+    }
+}
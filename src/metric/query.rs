@@ -15,9 +15,10 @@
 
 use std::collections::HashMap;
 
-use super::Value;
+use super::{Naming, Value};
 
 pub trait Query {
+    fn naming(&self) -> &Naming;
     fn get(&self, &str) -> Option<Value>;
     fn get_all_with_prefix(&self, &str) -> HashMap<String, Value>;
     fn watch<P>(&self, &str, P) where P: Fn(&str, &Value) -> bool + Send + Sync + 'static;
@@ -15,14 +15,14 @@
 
 extern crate rustbox;
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::default::Default;
 use std::sync::RwLock;
 use std::time::Duration;
 
 use self::rustbox::{Color, Key, RustBox};
 
-use super::{Memory, Metric, Query, Value, item};
+use super::{Memory, Metric, Naming, Query, Value, item};
 
 pub struct Terminal {
     refresh_interval_ms: u64,
@@ -38,10 +38,10 @@ enum Screen {
 }
 
 impl Terminal {
-    pub fn new(refresh_interval_ms: u64) -> Self {
+    pub fn new(refresh_interval_ms: u64, naming: Naming) -> Self {
         Terminal {
             refresh_interval_ms: refresh_interval_ms,
-            memory: Memory::new(),
+            memory: Memory::with_naming(naming),
             screen: RwLock::new(Screen::Services),
             log_buffer: RwLock::new(VecDeque::new()),
         }
@@ -50,6 +50,10 @@ impl Terminal {
     fn draw_head(&self, rustbox: &RustBox) {
         let mut line = String::new();
         line.push_str(" Delix");
+        let naming = format!("{}", self.memory.naming());
+        if !naming.is_empty() {
+            line.push_str(&format!(" {}", naming));
+        }
         pad(&mut line, rustbox.width());
 
         rustbox.print(0, 0, rustbox::RB_BOLD, Color::White, Color::Black, &line);
@@ -209,6 +213,10 @@ impl Metric for Terminal {
         self.memory.gauge(key)
     }
 
+    fn snapshot(&self) -> Option<(Naming, HashMap<String, Value>)> {
+        self.memory.snapshot()
+    }
+
     fn display(&self) {
         let rustbox = RustBox::init(Default::default()).unwrap();
 
@@ -13,8 +13,78 @@
 // limitations under the License.
 //
 
+use std::collections::BTreeMap;
+use std::fmt;
+
+use rustc_serialize::json::{Json, ToJson};
+
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 pub enum Value {
     Counter(usize),
     Gauge(isize),
 }
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match *self {
+            Value::Counter(_) => "counter",
+            Value::Gauge(_) => "gauge",
+        }
+    }
+
+    pub fn from_json(json: &Json) -> Option<Value> {
+        let value = match json.find("value") {
+            Some(value) => value,
+            None => return None,
+        };
+        match json.find("type").and_then(|type_name| type_name.as_string()) {
+            Some("counter") => value.as_u64().map(|value| Value::Counter(value as usize)),
+            Some("gauge") => value.as_i64().map(|value| Value::Gauge(value as isize)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Counter(value) => write!(f, "{}", value),
+            Value::Gauge(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl ToJson for Value {
+    fn to_json(&self) -> Json {
+        let mut object = BTreeMap::new();
+        object.insert("type".to_string(), self.type_name().to_json());
+        object.insert("value".to_string(),
+                      match *self {
+                          Value::Counter(value) => Json::U64(value as u64),
+                          Value::Gauge(value) => Json::I64(value as i64),
+                      });
+        Json::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use rustc_serialize::json::{Json, ToJson};
+    use super::Value;
+
+    #[test]
+    fn json_round_trip() {
+        for value in vec![Value::Counter(10), Value::Gauge(-5)] {
+            assert_eq!(Some(value.clone()), Value::from_json(&value.to_json()));
+        }
+    }
+
+    #[test]
+    fn json_with_type_tag() {
+        assert_eq!("{\"type\":\"gauge\",\"value\":-5}",
+                   Value::Gauge(-5).to_json().to_string());
+        assert_eq!(None,
+                   Value::from_json(&Json::from_str("{\"type\":\"unknown\",\"value\":1}").unwrap()));
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::result;
+
+use node::{request, service};
+use transport::{self, direct};
+
+pub type Result<T> = result::Result<T, Error>;
+
+/// The errors of the node API.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// No node provides the named service.
+    NotFound(String),
+    /// The service exists, but can't take the request right now.
+    Unavailable(String),
+    /// The request or the response didn't arrive in time.
+    Timeout,
+    /// A peer violated the protocol, e.g. by announcing an invalid service.
+    Protocol(String),
+    /// An i/o operation failed with the given kind.
+    Io(io::ErrorKind, String),
+    /// Something failed that the caller can't fix.
+    Internal(String),
+    /// The request has been rejected as malformed.
+    InvalidInput(String),
+}
+
+impl Error {
+    pub fn from_request_error(name: &str, error: request::Error) -> Self {
+        match error {
+            request::Error::NoService => Error::NotFound(name.to_string()),
+            request::Error::Timeout => Error::Timeout,
+            request::Error::Io(kind, message) => Error::Io(kind, message),
+            request::Error::Service(service::Error::Unavailable) => {
+                Error::Unavailable(name.to_string())
+            }
+            request::Error::Service(service::Error::Timeout) => Error::Timeout,
+            request::Error::Service(service::Error::Internal(message)) => {
+                Error::Internal(message)
+            }
+            request::Error::Service(service::Error::InvalidInput(message)) => {
+                Error::InvalidInput(message)
+            }
+            request::Error::Service(service::Error::Redirect(target, _)) => {
+                Error::Unavailable(format!("{} (redirected to {})", name, target))
+            }
+        }
+    }
+
+    pub fn from_transport_error(name: &str, error: transport::Error) -> Self {
+        match error {
+            transport::Error::ServiceDoesNotExists |
+            transport::Error::ServiceMap(direct::ServiceMapError::ServiceDoesNotExists) => {
+                Error::NotFound(name.to_string())
+            }
+            transport::Error::ServiceMap(direct::ServiceMapError::TooManyServices(peer_node_id,
+                                                                                  count)) => {
+                Error::Protocol(format!("{} announced too many services ({})",
+                                        peer_node_id,
+                                        count))
+            }
+            transport::Error::ServiceMap(direct::ServiceMapError::InvalidServiceName(name)) => {
+                Error::Protocol(format!("invalid service name [{}]", name))
+            }
+            transport::Error::Io(error) => Error::Io(error.kind(), format!("{}", error)),
+            transport::Error::Announcement(announcement) => {
+                Error::Unavailable(format!("{} peer(s) did not aknowledge service {}",
+                                           announcement.failed.len(),
+                                           name))
+            }
+            error => Error::Internal(format!("{:?}", error)),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NotFound(ref name) => write!(f, "service [{}] not found", name),
+            Error::Unavailable(ref message) => write!(f, "unavailable: {}", message),
+            Error::Timeout => write!(f, "timeout"),
+            Error::Protocol(ref message) => write!(f, "protocol error: {}", message),
+            Error::Io(ref kind, ref message) => write!(f, "io error {:?}: {}", kind, message),
+            Error::Internal(ref message) => write!(f, "internal error: {}", message),
+            Error::InvalidInput(ref message) => write!(f, "invalid input: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::NotFound(_) => "not found",
+            Error::Unavailable(_) => "unavailable",
+            Error::Timeout => "timeout",
+            Error::Protocol(_) => "protocol error",
+            Error::Io(..) => "io error",
+            Error::Internal(_) => "internal error",
+            Error::InvalidInput(_) => "invalid input",
+        }
+    }
+}
+
+impl From<transport::Error> for Error {
+    fn from(error: transport::Error) -> Self {
+        // without a service name, the service related errors can't be reported as such.
+        match error {
+            transport::Error::ServiceDoesNotExists |
+            transport::Error::ServiceMap(direct::ServiceMapError::ServiceDoesNotExists) |
+            transport::Error::Announcement(_) => Error::Internal(format!("{:?}", error)),
+            error => Error::from_transport_error("", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io;
+    use node::{request, service};
+    use transport;
+    use super::Error;
+
+    #[test]
+    fn from_request_error() {
+        assert_eq!(Error::NotFound("test".to_string()),
+                   Error::from_request_error("test", request::Error::NoService));
+        assert_eq!(Error::Unavailable("test".to_string()),
+                   Error::from_request_error("test",
+                                             request::Error::Service(service::Error::Unavailable)));
+        assert_eq!(Error::Timeout,
+                   Error::from_request_error("test",
+                                             request::Error::Service(service::Error::Timeout)));
+    }
+
+    #[test]
+    fn from_transport_error() {
+        assert_eq!(Error::NotFound("test".to_string()),
+                   Error::from_transport_error("test",
+                                               transport::Error::ServiceDoesNotExists));
+        let error = io::Error::new(io::ErrorKind::AddrInUse, "test");
+        assert_eq!(Error::Io(io::ErrorKind::AddrInUse, "test".to_string()),
+                   Error::from_transport_error("test", transport::Error::Io(error)));
+    }
+
+    #[test]
+    fn from_transport_error_without_name() {
+        assert_eq!(Error::Internal("ServiceDoesNotExists".to_string()),
+                   Error::from(transport::Error::ServiceDoesNotExists));
+    }
+
+}
@@ -0,0 +1,179 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+extern crate hyper;
+
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use self::hyper::status::StatusClass;
+use time::Duration;
+
+use util::clock::Clock;
+use util::thread::supervisor;
+use util::time::to_std_duration;
+
+/// Returns `true` if the service is healthy.
+pub type Check = Fn() -> bool + Send + Sync;
+
+/// Tells how often the health of a service is checked and after how many consecutive failed
+/// checks it's taken out of service.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Policy {
+    pub interval: Duration,
+    pub max_failures: usize,
+}
+
+/// Runs the health check of a service periodically and reports when the service goes down or
+/// comes back up. The checks stop when the monitor is dropped.
+pub struct Monitor {
+    healthy: Arc<AtomicBool>,
+    running: Arc<AtomicBool>,
+}
+
+impl Policy {
+    pub fn new(interval: Duration, max_failures: usize) -> Policy {
+        Policy {
+            interval: interval,
+            max_failures: max_failures,
+        }
+    }
+}
+
+impl Monitor {
+    pub fn start(name: &str,
+                 check: Box<Check>,
+                 policy: Policy,
+                 clock: Arc<Clock>,
+                 change_handler: Box<Fn(bool) + Send>)
+                 -> Monitor {
+        let healthy = Arc::new(AtomicBool::new(true));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let name = name.to_string();
+        let healthy_clone = healthy.clone();
+        let running_clone = running.clone();
+        supervisor().spawn("health check", move || {
+            let mut failures = 0;
+            loop {
+                clock.sleep(policy.interval);
+                if !running_clone.load(Ordering::SeqCst) {
+                    return;
+                }
+
+                let passed = check();
+                failures = if passed { 0 } else { failures + 1 };
+                let was_healthy = healthy_clone.load(Ordering::SeqCst);
+                let is_healthy = if was_healthy {
+                    failures < policy.max_failures
+                } else {
+                    passed
+                };
+                if is_healthy == was_healthy || !running_clone.load(Ordering::SeqCst) {
+                    continue;
+                }
+
+                healthy_clone.store(is_healthy, Ordering::SeqCst);
+                if is_healthy {
+                    info!("service [{}] is healthy again", name);
+                } else {
+                    warn!("service [{}] failed {} health checks in a row", name, failures);
+                }
+                change_handler(is_healthy);
+            }
+        });
+
+        Monitor {
+            healthy: healthy,
+            running: running,
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Builds a check that passes if a TCP connection to the address can be established.
+pub fn tcp(address: SocketAddr) -> Box<Check> {
+    Box::new(move || TcpStream::connect(address).is_ok())
+}
+
+/// Builds a check that passes if a `GET` request to the url responds with a success status
+/// within the timeout.
+pub fn http(url: &str, timeout: Duration) -> Box<Check> {
+    let url = url.to_string();
+    Box::new(move || {
+        let mut client = hyper::Client::new();
+        client.set_read_timeout(Some(to_std_duration(timeout)));
+        client.set_write_timeout(Some(to_std_duration(timeout)));
+        match client.get(&url).send() {
+            Ok(response) => response.status.class() == StatusClass::Success,
+            Err(_) => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex, mpsc};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use time::Duration;
+    use util::clock;
+    use super::{Monitor, Policy, tcp};
+
+    #[test]
+    fn report_changes_of_health() {
+        let passing = Arc::new(AtomicBool::new(true));
+        let passing_clone = passing.clone();
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let monitor = Monitor::start("test",
+                                     Box::new(move || passing_clone.load(Ordering::SeqCst)),
+                                     Policy::new(Duration::milliseconds(5), 3),
+                                     Arc::new(clock::System),
+                                     Box::new(move |healthy| {
+                                         tx.lock().unwrap().send(healthy).unwrap();
+                                     }));
+        assert!(monitor.is_healthy());
+
+        passing.store(false, Ordering::SeqCst);
+        assert_eq!(false, rx.recv().unwrap());
+        assert!(!monitor.is_healthy());
+
+        passing.store(true, Ordering::SeqCst);
+        assert_eq!(true, rx.recv().unwrap());
+        assert!(monitor.is_healthy());
+    }
+
+    #[test]
+    fn tcp_check() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        assert!(tcp(address)());
+
+        drop(listener);
+        assert!(!tcp(address)());
+    }
+
+}
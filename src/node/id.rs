@@ -16,14 +16,20 @@
 use std::fmt;
 use std::str::FromStr;
 use rand::random;
+use rustc_serialize::{Decodable, Decoder, Encodable, Encoder};
 use rustc_serialize::hex::{FromHex, FromHexError, ToHex};
+use rustc_serialize::json::{Json, ToJson};
 
 const ID_BITS: usize = 40;
 const ID_BYTES: usize = ID_BITS / 8;
+const SHORT_BYTES: usize = 3;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ID([u8; ID_BYTES]);
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Short(ID);
+
 pub type Result<T> = ::std::result::Result<T, Error>;
 
 #[derive(Debug)]
@@ -55,6 +61,10 @@ impl ID {
         }
         result
     }
+
+    pub fn short(&self) -> Short {
+        Short(*self)
+    }
 }
 
 impl FromStr for ID {
@@ -83,6 +93,48 @@ impl fmt::Debug for ID {
     }
 }
 
+impl fmt::Display for Short {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", (self.0).0[..SHORT_BYTES].to_hex())
+    }
+}
+
+impl fmt::Debug for Short {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ID({}..)", self)
+    }
+}
+
+impl Encodable for ID {
+    fn encode<S: Encoder>(&self, s: &mut S) -> ::std::result::Result<(), S::Error> {
+        s.emit_str(&self.to_hex())
+    }
+}
+
+impl Decodable for ID {
+    fn decode<D: Decoder>(d: &mut D) -> ::std::result::Result<Self, D::Error> {
+        let value = try!(d.read_str());
+        value.parse::<ID>().map_err(|error| d.error(&format!("invalid id {}: {:?}", value, error)))
+    }
+}
+
+impl ToJson for ID {
+    fn to_json(&self) -> Json {
+        Json::String(self.to_hex())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::InvalidLength(length) => {
+                write!(f, "invalid id length {} (expected {})", length, ID_BYTES)
+            }
+            Error::FromHexError(ref error) => write!(f, "invalid id: {}", error),
+        }
+    }
+}
+
 impl From<FromHexError> for Error {
     fn from(error: FromHexError) -> Self {
         Error::FromHexError(error)
@@ -94,6 +146,7 @@ mod tests {
 
     use super::ID;
     use rustc_serialize::hex::ToHex;
+    use rustc_serialize::json::{self, ToJson};
 
     #[test]
     fn test_random_id() {
@@ -111,4 +164,21 @@ mod tests {
         assert!("56789abcdX".parse::<ID>().is_err());
     }
 
+    #[test]
+    fn test_short_display() {
+        let id = "56789abcde".parse::<ID>().unwrap();
+        assert_eq!("56789a", format!("{}", id.short()));
+        assert_eq!("ID(56789a..)", format!("{:?}", id.short()));
+    }
+
+    #[test]
+    fn test_json_coding() {
+        let id = "56789abcde".parse::<ID>().unwrap();
+        assert_eq!("\"56789abcde\"", json::encode(&id).unwrap());
+        assert_eq!("\"56789abcde\"", id.to_json().to_string());
+        assert_eq!(id, json::decode::<ID>("\"56789abcde\"").unwrap());
+
+        assert!(json::decode::<ID>("\"a\"").is_err());
+    }
+
 }
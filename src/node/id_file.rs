@@ -0,0 +1,116 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+extern crate libc;
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use rustc_serialize::hex::ToHex;
+
+use node::ID;
+
+pub struct IdFile {
+    // the lock is released when the file is closed.
+    #[allow(dead_code)]
+    file: File,
+    id: ID,
+}
+
+impl IdFile {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<IdFile> {
+        let path = path.as_ref();
+        let mut file = try!(OpenOptions::new().read(true).write(true).create(true).open(path));
+
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+            let error = io::Error::last_os_error();
+            return Err(io::Error::new(error.kind(),
+                                      format!("could not lock id file {}: {}",
+                                              path.display(),
+                                              error)));
+        }
+
+        let mut content = String::new();
+        try!(file.read_to_string(&mut content));
+
+        let id = if content.trim().is_empty() {
+            let id = ID::new_random();
+            try!(file.seek(SeekFrom::Start(0)));
+            try!(file.set_len(0));
+            try!(writeln!(file, "{}", id.to_hex()));
+            try!(file.sync_all());
+            id
+        } else {
+            try!(content.trim().parse::<ID>().map_err(|error| {
+                io::Error::new(io::ErrorKind::InvalidData,
+                               format!("invalid id file {}: {}", path.display(), error))
+            }))
+        };
+
+        Ok(IdFile {
+            file: file,
+            id: id,
+        })
+    }
+
+    pub fn id(&self) -> ID {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env;
+    use std::fs;
+    use std::io::Write;
+    use super::IdFile;
+
+    #[test]
+    fn keep_id_across_opens() {
+        let path = env::temp_dir().join("delix-id-file-keep-test");
+        let _ = fs::remove_file(&path);
+
+        let id = IdFile::open(&path).unwrap().id();
+        assert_eq!(id, IdFile::open(&path).unwrap().id());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reject_locked_file() {
+        let path = env::temp_dir().join("delix-id-file-lock-test");
+
+        {
+            let _id_file = IdFile::open(&path).unwrap();
+            assert!(IdFile::open(&path).is_err());
+        }
+        assert!(IdFile::open(&path).is_ok());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reject_invalid_id() {
+        let path = env::temp_dir().join("delix-id-file-invalid-test");
+        fs::File::create(&path).unwrap().write_all(b"invalid\n").unwrap();
+
+        assert!(IdFile::open(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
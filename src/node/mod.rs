@@ -14,12 +14,26 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+mod error;
+pub mod health;
 pub mod id;
+mod id_file;
 mod node;
+mod observer;
+pub mod process;
 pub mod request;
 pub mod response;
 pub mod service;
+mod state;
+pub mod stream;
+pub mod topic;
+pub mod version;
 
-pub use self::id::ID;
-pub use self::node::{Node, Error};
-pub use self::service::Service;
+pub use self::id::{ID, Short};
+pub use self::id_file::IdFile;
+pub use self::error::{Error, Result};
+pub use self::node::Node;
+pub use self::observer::Observer;
+pub use self::process::Process;
+pub use self::service::{Metadata, Placement, Service, ServiceHandler, StreamingService};
+pub use self::state::State;
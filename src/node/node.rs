@@ -13,31 +13,49 @@
 // limitations under the License.
 //
 
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::io;
+use std::io::{self, Read};
 use std::result;
-use std::sync::{Arc, mpsc};
+use std::sync::{Arc, Mutex, RwLock, Weak, mpsc};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use discovery::Discovery;
+use rustc_serialize::{Decodable, Encodable, json};
+use time;
+
+use logger::collector::{self, Collector, Record};
+use logger::trace;
 use metric::{self, Metric};
-use node::{ID, Service, request, response};
-use transport;
-use transport::Transport;
+use node::{Error, ID, IdFile, Observer, Placement, Result, Service, request, response, service};
+use node::{health, topic};
+use node::observer::Observation;
+use node::state::{History, State};
+use relay::Relay;
+use transport::{self, ConnectionStats, JoinEvent, JoinReport, PeerInfo, ReconnectState,
+                Transport, direct};
+use util::clock::{self, Clock};
+use util::thread::supervisor;
+use util::time::to_std_duration;
 
 pub struct Node {
     pub id: ID,
-    discovery: Box<Discovery>,
-    transport: Box<Transport>,
+    discovery: Arc<Box<Discovery>>,
+    transport: Arc<Box<Transport>>,
+    state: Arc<History>,
+    metric: Arc<Metric>,
     request_counter: metric::item::Counter,
-}
-
-pub type Result<T> = result::Result<T, Error>;
-
-#[derive(Debug)]
-pub enum Error {
-    NoSocketAddr,
-    Transport(transport::Error),
-    Request(request::Error),
+    request_observer: RwLock<Option<Arc<Observer>>>,
+    exposed_services: RwLock<Option<HashSet<String>>>,
+    log_collector: RwLock<Option<Arc<Collector>>>,
+    clock: Arc<Clock>,
+    relays: Mutex<Vec<Arc<Relay>>>,
+    health_monitors: Mutex<HashMap<String, health::Monitor>>,
+    subscriptions: topic::Subscriptions,
+    // keeps the id file locked as long as the node lives.
+    #[allow(dead_code)]
+    id_file: Option<IdFile>,
 }
 
 impl Node {
@@ -45,61 +63,616 @@ impl Node {
                transport: Box<Transport>,
                metric: Arc<Metric>)
                -> Result<Self> {
-        let node_id = ID::new_random();
+        Self::build(ID::new_random(), None, discovery, transport, metric)
+    }
+
+    pub fn with_id(node_id: ID,
+                   discovery: Box<Discovery>,
+                   transport: Box<Transport>,
+                   metric: Arc<Metric>)
+                   -> Result<Self> {
+        Self::build(node_id, None, discovery, transport, metric)
+    }
+
+    pub fn with_id_file(id_file: IdFile,
+                        discovery: Box<Discovery>,
+                        transport: Box<Transport>,
+                        metric: Arc<Metric>)
+                        -> Result<Self> {
+        Self::build(id_file.id(), Some(id_file), discovery, transport, metric)
+    }
 
+    fn build(node_id: ID,
+             id_file: Option<IdFile>,
+             discovery: Box<Discovery>,
+             transport: Box<Transport>,
+             metric: Arc<Metric>)
+             -> Result<Self> {
         try!(transport.bind(node_id));
 
+        let transport = Arc::new(transport);
+        let transport_clone = transport.clone();
+        discovery.set_peer_lost_handler(Box::new(move |public_address| {
+            info!("{}: lost peer {}", node_id.short(), public_address);
+            if let Err(error) = transport_clone.remove_peer(public_address) {
+                error!("{}: failed to remove peer {}: {:?}",
+                       node_id.short(),
+                       public_address,
+                       error);
+            }
+        }));
+
+        let state = Arc::new(History::new(State::Discovering, &*metric));
+
+        let discovery = Arc::new(discovery);
+        let discovery_weak = Arc::downgrade(&discovery);
+        let transport_weak = Arc::downgrade(&transport);
+        let state_clone = state.clone();
+        let rejoining = Arc::new(AtomicBool::new(false));
+        transport.set_peer_count_handler(Box::new(move |count| {
+            if count > 0 {
+                if state_clone.current() != State::Discovering {
+                    transition(node_id, &state_clone, State::Joined);
+                }
+                return;
+            }
+
+            transition(node_id, &state_clone, State::Degraded);
+            if rejoining.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            info!("{}: lost all peers - rejoining", node_id.short());
+            let discovery_weak = discovery_weak.clone();
+            let transport_weak = transport_weak.clone();
+            let state = state_clone.clone();
+            let rejoining = rejoining.clone();
+            supervisor().spawn("rejoin", move || {
+                rejoin(node_id, &discovery_weak, &transport_weak, &state);
+                rejoining.store(false, Ordering::SeqCst);
+            });
+        }));
+
         Ok(Node {
             id: node_id,
             discovery: discovery,
             transport: transport,
+            state: state,
             request_counter: metric.counter("requests"),
+            metric: metric,
+            request_observer: RwLock::new(None),
+            exposed_services: RwLock::new(None),
+            log_collector: RwLock::new(None),
+            clock: Arc::new(clock::System),
+            relays: Mutex::new(Vec::new()),
+            health_monitors: Mutex::new(HashMap::new()),
+            subscriptions: topic::Subscriptions::new(),
+            id_file: id_file,
         })
     }
 
-    pub fn join(&self) {
+    pub fn join(&self) -> Option<JoinReport> {
+        self.join_with_progress(&|event| debug!("{}: {}", self.id.short(), event))
+    }
+
+    pub fn join_with_progress(&self, progress: &Fn(&JoinEvent)) -> Option<JoinReport> {
+        transition(self.id, &self.state, State::Discovering);
+        self.discovery.reset();
         while let Some(address) = self.discovery.next() {
             info!("discovered node at {}", address);
-            match self.transport.join(address, self.id) {
-                Ok(()) => break,
+            match self.transport.join(address, self.id, progress) {
+                Ok(report) => {
+                    transition(self.id, &self.state, State::Joined);
+                    return Some(report);
+                }
                 Err(error) => {
-                    error!("{}: failed to connect to {}: {:?}", self.id, address, error);
+                    error!("{}: failed to connect to {}: {:?}",
+                           self.id.short(),
+                           address,
+                           error);
                 }
             }
         }
+        transition(self.id, &self.state, State::Alone);
+        None
+    }
+
+    /// Registers a relay that is shut down when the node leaves the network.
+    pub fn add_relay(&self, relay: Arc<Relay>) {
+        self.relays.lock().unwrap().push(relay);
+    }
+
+    /// Shuts down all registered relays in reverse order of registration and disconnects from
+    /// the network afterwards, so no request is accepted by a relay once the node is gone.
+    pub fn leave(&self) {
+        self.health_monitors.lock().unwrap().clear();
+        self.shutdown_relays();
+        if let Err(error) = self.transport.leave() {
+            error!("{}: failed to leave network: {:?}", self.id.short(), error);
+        }
+        info!("{}: left network", self.id.short());
+    }
+
+    /// Leaves the network gracefully. The peers are told to remove the services of this node
+    /// right away and the pending requests are given time to finish before the connections are
+    /// closed.
+    pub fn shutdown(&self) {
+        self.health_monitors.lock().unwrap().clear();
+        self.shutdown_relays();
+        if let Err(error) = self.transport.shutdown() {
+            error!("{}: failed to shut down: {:?}", self.id.short(), error);
+        }
+        info!("{}: shut down", self.id.short());
+    }
+
+    pub fn state(&self) -> State {
+        self.state.current()
+    }
+
+    /// Returns the naming and the current values of the node's metric, if it keeps them.
+    pub fn metric_snapshot(&self) -> Option<(metric::Naming, HashMap<String, metric::Value>)> {
+        self.metric.snapshot()
+    }
+
+    pub fn state_history(&self) -> Vec<(State, time::Tm)> {
+        self.state.transitions()
+    }
+
+    pub fn connection_stats(&self, peer_node_id: ID) -> Result<(ConnectionStats, ConnectionStats)> {
+        self.transport.connection_stats(peer_node_id).map_err(|error| {
+            match error {
+                transport::Error::ConnectionMap(direct::ConnectionMapError::DoesNotExists) => {
+                    Error::Unavailable(format!("no connection to peer {}", peer_node_id))
+                }
+                error => Error::from(error),
+            }
+        })
+    }
+
+    /// Asks the coordinating node for the permission to drain. Returns `false` if the maximum
+    /// number of nodes is already draining.
+    pub fn acquire_drain_permit(&self) -> Result<bool> {
+        Ok(try!(self.transport.acquire_drain_permit()))
+    }
+
+    pub fn release_drain_permit(&self) -> Result<()> {
+        Ok(try!(self.transport.release_drain_permit()))
     }
 
     pub fn register(&self, name: &str, f: Box<Service>) -> Result<()> {
-        try!(self.transport.register(name, f));
+        self.register_with_placement(name, f, Placement::Any)
+    }
+
+    pub fn register_with_placement(&self,
+                                   name: &str,
+                                   f: Box<Service>,
+                                   placement: Placement)
+                                   -> Result<()> {
+        self.register_with_weight(name, f, placement, service::DEFAULT_WEIGHT)
+    }
+
+    /// Registers a service with a weight that tells weighted balancers on other nodes how much
+    /// traffic this node can take relative to the others, e.g. `4` for a machine that is four
+    /// times as big as one with the default weight of `1`. A weight of zero counts as one.
+    pub fn register_with_weight(&self,
+                                name: &str,
+                                f: Box<Service>,
+                                placement: Placement,
+                                weight: u32)
+                                -> Result<()> {
+        self.register_with_metadata(name, f, placement, weight, service::Metadata::new())
+    }
+
+    /// Registers a service with metadata like its `version` or `region`. The metadata is announced
+    /// together with the service, so callers can send requests to the providers with certain
+    /// tags using `request_tagged`.
+    pub fn register_with_metadata(&self,
+                                  name: &str,
+                                  f: Box<Service>,
+                                  placement: Placement,
+                                  weight: u32,
+                                  metadata: service::Metadata)
+                                  -> Result<()> {
+        if name.starts_with(topic::SERVICE_PREFIX) {
+            return Err(Error::InvalidInput(format!("service name [{}] is reserved for topics",
+                                                   name)));
+        }
+        let placement = self.exposed_placement(name, placement);
+        self.transport
+            .register(name, f, placement, cmp::max(weight, 1), metadata)
+            .map_err(|error| Error::from_transport_error(name, error))
+    }
+
+    /// Registers a service together with a health check that runs periodically. If the check
+    /// fails the number of times given by the policy in a row, the service is deregistered and
+    /// the peers stop sending requests to it. It's registered again after the next passing check.
+    pub fn register_with_health_check(&self,
+                                      name: &str,
+                                      f: Box<Service>,
+                                      check: Box<health::Check>,
+                                      policy: health::Policy)
+                                      -> Result<()> {
+        let f = Arc::new(f);
+        let f_clone = f.clone();
+        try!(self.register(name,
+                           Box::new(move |request: Box<request::Reader>| f_clone(request))));
+
+        let node_id = self.id;
+        let transport_weak = Arc::downgrade(&self.transport);
+        let placement = self.exposed_placement(name, Placement::Any);
+        let name_clone = name.to_string();
+        let change_handler = Box::new(move |healthy: bool| {
+            let transport = match transport_weak.upgrade() {
+                Some(transport) => transport,
+                None => return,
+            };
+            let result = if healthy {
+                let f = f.clone();
+                transport.register(&name_clone,
+                                   Box::new(move |request: Box<request::Reader>| f(request)),
+                                   placement,
+                                   service::DEFAULT_WEIGHT,
+                                   service::Metadata::new())
+            } else {
+                transport.deregister(&name_clone)
+            };
+            if let Err(error) = result {
+                error!("{}: failed to update service [{}] after health change: {:?}",
+                       node_id.short(),
+                       name_clone,
+                       error);
+            }
+        });
+        let monitor = health::Monitor::start(name,
+                                             check,
+                                             policy,
+                                             self.clock.clone(),
+                                             change_handler);
+        self.health_monitors.lock().unwrap().insert(name.to_string(), monitor);
         Ok(())
     }
 
+    pub fn register_handler(&self,
+                            name: &str,
+                            handler: Arc<service::ServiceHandler>)
+                            -> Result<()> {
+        self.register(name, service::from_handler(handler))
+    }
+
+    pub fn register_with_validators(&self,
+                                    name: &str,
+                                    f: Box<Service>,
+                                    request_validator: Option<Box<service::Validator>>,
+                                    response_validator: Option<Box<service::Validator>>)
+                                    -> Result<()> {
+        self.register(name, service::validate(f, request_validator, response_validator))
+    }
+
+    /// Registers a service that takes and returns typed values, which are sent as JSON. See
+    /// `service::json`.
+    pub fn register_json<T, R, F>(&self, name: &str, f: F) -> Result<()>
+        where T: Decodable + 'static,
+              R: Encodable + 'static,
+              F: Fn(T) -> result::Result<R, service::Error> + Send + Sync + 'static
+    {
+        self.register(name, service::json(f))
+    }
+
+    /// Registers a service that streams its response. See `service::StreamingService`.
+    pub fn register_streaming(&self, name: &str, f: Box<service::StreamingService>) -> Result<()> {
+        self.register(name, service::streaming(f))
+    }
+
     pub fn deregister(&self, name: &str) -> Result<()> {
-        try!(self.transport.deregister(name));
-        Ok(())
+        // an unhealthy service is already deregistered from the transport.
+        if let Some(monitor) = self.health_monitors.lock().unwrap().remove(name) {
+            if !monitor.is_healthy() {
+                return Ok(());
+            }
+        }
+        self.transport
+            .deregister(name)
+            .map_err(|error| Error::from_transport_error(name, error))
+    }
+
+    pub fn has_service(&self, name: &str) -> bool {
+        self.transport.has_service(name)
     }
 
-    pub fn request_bytes(&self, name: &str, request: &[u8]) -> request::Result<Vec<u8>> {
+    /// Returns the connected peers ordered by node id.
+    pub fn peers(&self) -> Vec<PeerInfo> {
+        self.transport.peers()
+    }
+
+    /// Returns the reconnect history of the peers whose connections dropped.
+    pub fn reconnect_states(&self) -> Vec<ReconnectState> {
+        self.transport.reconnect_states()
+    }
+
+    /// Returns how many requests the local services handled per service and calling node.
+    pub fn service_usage(&self) -> Vec<(String, ID, usize)> {
+        self.transport.service_usage()
+    }
+
+    /// Sends the bytes to the service and returns the bytes of the response. With a timeout, the
+    /// call returns `Error::Timeout` if the response doesn't arrive in time. A late response is
+    /// dropped.
+    pub fn request_bytes(&self,
+                         name: &str,
+                         request: &[u8],
+                         timeout: Option<time::Duration>)
+                         -> Result<Vec<u8>> {
+        let (tx, rx) = mpsc::channel();
+        let reader = Box::new(io::Cursor::new(request.to_vec()));
+        let response_tx = tx.clone();
+        let response_handler: Box<response::Handler> = Box::new(move |mut reader| {
+            let mut response = Vec::new();
+            let result = io::copy(&mut reader, &mut response)
+                             .map(|_| response)
+                             .map_err(|error| Error::Io(error.kind(), format!("{}", error)));
+            // the caller might have given up already.
+            let _ = response_tx.send(result);
+        });
+
+        let no_response = Error::Internal("no response".to_string());
+        let timeout = match timeout {
+            Some(timeout) => timeout,
+            None => {
+                drop(tx);
+                try!(self.request(name, None, reader, response_handler));
+                return rx.recv().unwrap_or(Err(no_response));
+            }
+        };
+
+        // the transport blocks until the response has been handled, so the request runs on a
+        // worker thread while the caller waits for the result only until the timeout.
+        let (response_handler, observation) = self.start_request(name, response_handler);
+        let transport = self.transport.clone();
+        let request_name = name.to_string();
+        supervisor().spawn("bytes request", move || {
+            let result = transport.request(&request_name, None, reader, response_handler);
+            if let Err(error) = finish_request(&request_name, observation, result) {
+                let _ = tx.send(Err(error));
+            }
+        });
+
+        match rx.recv_timeout(to_std_duration(cmp::max(timeout, time::Duration::zero()))) {
+            Ok(result) => result,
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(Error::Timeout),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(no_response),
+        }
+    }
+
+    /// Sends the value as JSON to the service and decodes the JSON response. See `request_bytes`
+    /// for the timeout.
+    pub fn request_json<T, R>(&self,
+                              name: &str,
+                              request: &T,
+                              timeout: Option<time::Duration>)
+                              -> Result<R>
+        where T: Encodable,
+              R: Decodable
+    {
+        let request = try!(json::encode(request).map_err(|error| {
+            Error::InvalidInput(format!("invalid request: {}", error))
+        }));
+        let response = try!(self.request_bytes(name, request.as_bytes(), timeout));
+        String::from_utf8(response)
+            .map_err(|error| format!("{}", error))
+            .and_then(|response| json::decode(&response).map_err(|error| format!("{}", error)))
+            .map_err(|message| Error::Internal(format!("invalid response: {}", message)))
+    }
+
+    /// Sends the request to every node that provides the service and returns the response or the
+    /// error of each node, ordered by node id. The request body is read completely before it's
+    /// sent.
+    pub fn broadcast(&self,
+                     name: &str,
+                     mut reader: Box<request::Reader>)
+                     -> Result<Vec<(ID, Result<Vec<u8>>)>> {
+        let node_ids = self.transport.providers(name);
+        if node_ids.is_empty() {
+            return Err(Error::NotFound(name.to_string()));
+        }
+        let mut request = Vec::new();
+        try!(reader.read_to_end(&mut request)
+                   .map_err(|error| Error::Io(error.kind(), format!("{}", error))));
+
+        // all requests of the broadcast belong to the same trace.
+        let trace_id = trace::trace_id().unwrap_or_else(trace::generate);
         let (tx, rx) = mpsc::channel();
+        for &node_id in &node_ids {
+            let response_tx = tx.clone();
+            let response_handler: Box<response::Handler> = Box::new(move |mut reader| {
+                let mut response = Vec::new();
+                let result = io::copy(&mut reader, &mut response)
+                                 .map(|_| response)
+                                 .map_err(|error| Error::Io(error.kind(), format!("{}", error)));
+                let _ = response_tx.send((node_id, result));
+            });
 
-        try!(self.request(name,
-                          Box::new(io::Cursor::new(request.to_vec())),
-                          Box::new(move |mut reader| {
-                              let mut response = Vec::new();
-                              io::copy(&mut reader, &mut response).unwrap();
-                              tx.send(response).unwrap();
-                          })));
+            let (response_handler, observation) = self.start_request(name, response_handler);
+            let transport = self.transport.clone();
+            let name = name.to_string();
+            let reader = Box::new(io::Cursor::new(request.clone()));
+            let trace_id = trace_id.clone();
+            let request_tx = tx.clone();
+            supervisor().spawn("broadcast request", move || {
+                trace::set_trace_id(Some(trace_id));
+                let result = transport.request_on(&name, node_id, reader, response_handler);
+                if let Err(error) = finish_request(&name, observation, result) {
+                    let _ = request_tx.send((node_id, Err(error)));
+                }
+                trace::set_trace_id(None);
+            });
+        }
+
+        let mut results = rx.iter().take(node_ids.len()).collect::<Vec<_>>();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(results)
+    }
 
-        Ok(rx.recv().unwrap())
+    /// Restricts the services that are announced to peers to the given names. Services that are
+    /// registered afterwards and are not in the list stay local to this node.
+    pub fn set_exposed_services(&self, names: &[String]) {
+        *self.exposed_services.write().unwrap() = Some(names.iter().cloned().collect());
     }
 
+    /// Registers the built-in log collector service on this node. It's announced to the peers even
+    /// if it's not in the list of exposed services.
+    pub fn set_log_collector(&self, log_collector: Arc<Collector>) -> Result<()> {
+        try!(self.transport
+                 .register(collector::SERVICE_NAME,
+                           collector::service(log_collector.clone()),
+                           Placement::Any,
+                           service::DEFAULT_WEIGHT,
+                           service::Metadata::new())
+                 .map_err(|error| Error::from_transport_error(collector::SERVICE_NAME, error)));
+        *self.log_collector.write().unwrap() = Some(log_collector);
+        Ok(())
+    }
+
+    /// Returns the log records of the trace that have been collected by this node.
+    pub fn trace_records(&self, trace_id: &str) -> Option<Vec<Record>> {
+        self.log_collector
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|log_collector| log_collector.trace(trace_id))
+    }
+
+    pub fn set_request_observer(&self, observer: Arc<Observer>) {
+        *self.request_observer.write().unwrap() = Some(observer);
+    }
+
+    /// Sends a request to the service. Requests with the same key are sticky to the same node if
+    /// the service is balanced by a balancer with key affinity, like the consistent hash.
     pub fn request(&self,
                    name: &str,
+                   key: Option<&[u8]>,
                    reader: Box<request::Reader>,
                    response_handler: Box<response::Handler>)
-                   -> request::Result<()> {
+                   -> Result<()> {
+        let (response_handler, observation) = self.start_request(name, response_handler);
+        finish_request(name,
+                       observation,
+                       self.transport.request(name, key, reader, response_handler))
+    }
+
+    /// Sends a request to one of the nodes whose service carries all the given tags. Tagged
+    /// requests bypass the balancer and pick one of the matching nodes at random.
+    pub fn request_tagged(&self,
+                          name: &str,
+                          tags: &service::Metadata,
+                          reader: Box<request::Reader>,
+                          response_handler: Box<response::Handler>)
+                          -> Result<()> {
+        let (response_handler, observation) = self.start_request(name, response_handler);
+        finish_request(name,
+                       observation,
+                       self.transport.request_tagged(name, tags, reader, response_handler))
+    }
+
+    /// Sends a one-way notification to the service. It's handed over without waiting for the
+    /// service to handle it, so neither its response nor its errors get back to the caller.
+    pub fn notify(&self, name: &str, reader: Box<request::Reader>) -> Result<()> {
         self.request_counter.increment();
-        Ok(try!(self.transport.request(name, reader, response_handler)))
+        self.transport
+            .notify(name, reader)
+            .map_err(|error| Error::from_request_error(name, error))
+    }
+
+    /// Subscribes the handler to the topic. The subscription is announced to the peers as the
+    /// service `topic.<topic>`, even if it's not in the list of exposed services.
+    pub fn subscribe_topic(&self, topic: &str, handler: Box<topic::Handler>) -> Result<()> {
+        let service = match self.subscriptions.add(topic, handler) {
+            Some(service) => service,
+            None => return Ok(()),
+        };
+        let name = topic::service_name(topic);
+        self.transport
+            .register(&name,
+                      service,
+                      Placement::Any,
+                      service::DEFAULT_WEIGHT,
+                      service::Metadata::new())
+            .map_err(|error| {
+                self.subscriptions.remove(topic);
+                Error::from_transport_error(&name, error)
+            })
+    }
+
+    /// Removes all handlers of the topic from this node.
+    pub fn unsubscribe_topic(&self, topic: &str) -> Result<()> {
+        if !self.subscriptions.remove(topic) {
+            return Err(Error::NotFound(topic.to_string()));
+        }
+        self.deregister(&topic::service_name(topic))
+    }
+
+    /// Publishes the message to every node that is subscribed to the topic, including this one.
+    /// The message is handed over like a notification, so the handlers run after this call
+    /// returns. Returns the number of nodes the message was handed to.
+    pub fn publish(&self, topic: &str, message: &[u8]) -> Result<usize> {
+        self.request_counter.increment();
+        let name = topic::service_name(topic);
+
+        // all deliveries of the message belong to the same trace.
+        let generated = trace::trace_id().is_none();
+        if generated {
+            trace::set_trace_id(Some(trace::generate()));
+        }
+        let mut count = 0;
+        for node_id in self.transport.providers(&name) {
+            let reader = Box::new(io::Cursor::new(message.to_vec()));
+            match self.transport.notify_on(&name, node_id, reader) {
+                Ok(()) => count += 1,
+                Err(error) => {
+                    debug!("{}: failed to publish to {} on {}: {:?}",
+                           self.id.short(),
+                           topic,
+                           node_id.short(),
+                           error);
+                }
+            }
+        }
+        if generated {
+            trace::set_trace_id(None);
+        }
+        Ok(count)
+    }
+
+    fn exposed_placement(&self, name: &str, placement: Placement) -> Placement {
+        match *self.exposed_services.read().unwrap() {
+            Some(ref exposed_services) if !exposed_services.contains(name) => Placement::Local,
+            _ => placement,
+        }
+    }
+
+    fn shutdown_relays(&self) {
+        let relays = self.relays.lock().unwrap().drain(..).collect::<Vec<_>>();
+        for relay in relays.iter().rev() {
+            relay.shutdown();
+        }
+    }
+
+    fn start_request(&self,
+                     name: &str,
+                     response_handler: Box<response::Handler>)
+                     -> (Box<response::Handler>, Option<Observation>) {
+        self.request_counter.increment();
+
+        let observation = self.request_observer
+                              .read()
+                              .unwrap()
+                              .as_ref()
+                              .map(|observer| {
+                                  Observation::start(observer.clone(), self.clock.clone(), name)
+                              });
+        let response_handler = match observation {
+            Some(ref observation) => observation.wrap(response_handler),
+            None => response_handler,
+        };
+        (response_handler, observation)
     }
 }
 
@@ -109,14 +682,62 @@ impl fmt::Debug for Node {
     }
 }
 
-impl From<transport::Error> for Error {
-    fn from(error: transport::Error) -> Self {
-        Error::Transport(error)
+fn finish_request(name: &str,
+                  observation: Option<Observation>,
+                  result: request::Result<()>)
+                  -> Result<()> {
+    if let (Some(observation), &Err(ref error)) = (observation, &result) {
+        observation.error(error);
+    }
+    result.map_err(|error| Error::from_request_error(name, error))
+}
+
+fn transition(node_id: ID, state: &History, new_state: State) {
+    if state.set(new_state) {
+        info!("{}: state changed to {}", node_id.short(), new_state);
     }
 }
 
-impl From<request::Error> for Error {
-    fn from(error: request::Error) -> Self {
-        Error::Request(error)
+fn rejoin(node_id: ID,
+          discovery: &Weak<Box<Discovery>>,
+          transport: &Weak<Box<Transport>>,
+          state: &History) {
+    transition(node_id, state, State::Discovering);
+    if let Some(discovery) = discovery.upgrade() {
+        discovery.reset();
+    }
+    loop {
+        let address = match discovery.upgrade() {
+            Some(discovery) => {
+                match discovery.next() {
+                    Some(address) => address,
+                    None => {
+                        transition(node_id, state, State::Alone);
+                        return;
+                    }
+                }
+            }
+            None => return,
+        };
+        let transport = match transport.upgrade() {
+            Some(transport) => transport,
+            None => return,
+        };
+        info!("discovered node at {}", address);
+        match transport.join(address,
+                             node_id,
+                             &|event| debug!("{}: {}", node_id.short(), event)) {
+            Ok(report) => {
+                info!("{}: rejoined network - {}", node_id.short(), report);
+                transition(node_id, state, State::Joined);
+                return;
+            }
+            Err(error) => {
+                error!("{}: failed to connect to {}: {:?}",
+                       node_id.short(),
+                       address,
+                       error);
+            }
+        }
     }
 }
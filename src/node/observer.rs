@@ -0,0 +1,191 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+
+use std::io;
+use std::sync::Arc;
+use time::{Duration, SteadyTime};
+
+use node::{request, response};
+use util::clock::Clock;
+
+pub trait Observer : Send + Sync {
+    fn on_dispatch(&self, _: &str) {}
+    fn on_first_byte(&self, _: &str, _: Duration) {}
+    fn on_complete(&self, _: &str, _: Duration) {}
+    fn on_error(&self, _: &str, _: &request::Error, _: Duration) {}
+}
+
+#[derive(Clone)]
+pub struct Observation {
+    observer: Arc<Observer>,
+    clock: Arc<Clock>,
+    name: String,
+    start: SteadyTime,
+}
+
+impl Observation {
+    pub fn start(observer: Arc<Observer>, clock: Arc<Clock>, name: &str) -> Observation {
+        observer.on_dispatch(name);
+        Observation {
+            observer: observer,
+            start: clock.now(),
+            clock: clock,
+            name: name.to_string(),
+        }
+    }
+
+    pub fn wrap(&self, mut handler: Box<response::Handler>) -> Box<response::Handler> {
+        let observation = self.clone();
+        Box::new(move |reader| {
+            observation.observer.on_first_byte(&observation.name, observation.elapsed());
+            (&mut *handler)(Box::new(Reader::new(reader, observation.clone())))
+        })
+    }
+
+    pub fn error(&self, error: &request::Error) {
+        self.observer.on_error(&self.name, error, self.elapsed());
+    }
+
+    fn complete(&self) {
+        self.observer.on_complete(&self.name, self.elapsed());
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.clock.now() - self.start
+    }
+}
+
+struct Reader {
+    parent: Box<response::Reader>,
+    observation: Observation,
+    finished: bool,
+}
+
+impl Reader {
+    fn new(parent: Box<response::Reader>, observation: Observation) -> Reader {
+        Reader {
+            parent: parent,
+            observation: observation,
+            finished: false,
+        }
+    }
+}
+
+impl io::Read for Reader {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        match self.parent.read(buffer) {
+            Ok(0) if !buffer.is_empty() && !self.finished => {
+                self.finished = true;
+                self.observation.complete();
+                Ok(0)
+            }
+            Err(error) => {
+                if !self.finished {
+                    self.finished = true;
+                    self.observation
+                        .error(&request::Error::Io(error.kind(), format!("{}", error)));
+                }
+                Err(error)
+            }
+            result => result,
+        }
+    }
+}
+
+impl Drop for Reader {
+    fn drop(&mut self) {
+        // a handler that stops reading early is done with the response as well.
+        if !self.finished {
+            self.observation.complete();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{self, Read};
+    use std::sync::{Arc, Mutex, mpsc};
+    use time::Duration;
+    use node::{request, response};
+    use util::clock::Mock;
+    use super::{Observation, Observer};
+
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Observer for Recorder {
+        fn on_dispatch(&self, name: &str) {
+            self.events.lock().unwrap().push(format!("dispatch {}", name));
+        }
+
+        fn on_first_byte(&self, name: &str, elapsed: Duration) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("first byte {} {}", name, elapsed.num_milliseconds()));
+        }
+
+        fn on_complete(&self, name: &str, elapsed: Duration) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("complete {} {}", name, elapsed.num_milliseconds()));
+        }
+
+        fn on_error(&self, name: &str, error: &request::Error, elapsed: Duration) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("error {} {:?} {}", name, error, elapsed.num_milliseconds()));
+        }
+    }
+
+    #[test]
+    fn observe_response() {
+        let recorder = Arc::new(Recorder { events: Mutex::new(Vec::new()) });
+        let clock = Arc::new(Mock::new());
+        let observation = Observation::start(recorder.clone(), clock.clone(), "echo");
+
+        let (tx, rx) = mpsc::channel();
+        let mut handler = observation.wrap(Box::new(move |mut reader: Box<response::Reader>| {
+            let mut response = String::new();
+            reader.read_to_string(&mut response).unwrap();
+            tx.send(response).unwrap();
+        }));
+
+        clock.advance(Duration::milliseconds(20));
+        (&mut *handler)(Box::new(io::Cursor::new(b"test".to_vec())));
+        assert_eq!("test", rx.recv().unwrap());
+
+        assert_eq!(vec!["dispatch echo", "first byte echo 20", "complete echo 20"],
+                   *recorder.events.lock().unwrap());
+    }
+
+    #[test]
+    fn observe_error() {
+        let recorder = Arc::new(Recorder { events: Mutex::new(Vec::new()) });
+        let clock = Arc::new(Mock::new());
+        let observation = Observation::start(recorder.clone(), clock.clone(), "echo");
+
+        clock.advance(Duration::milliseconds(50));
+        observation.error(&request::Error::Timeout);
+
+        assert_eq!(vec!["dispatch echo", "error echo Timeout 50"],
+                   *recorder.events.lock().unwrap());
+    }
+}
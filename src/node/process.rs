@@ -0,0 +1,230 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::io::{self, Read, Write};
+use std::process;
+use std::sync::Mutex;
+
+use node::{request, service};
+use node::service::{Service, ServiceHandler};
+use util::reader::read_size;
+use util::writer::write_size;
+
+const STATUS_OK: u8 = 0;
+const STATUS_UNAVAILABLE: u8 = 1;
+const STATUS_TIMEOUT: u8 = 2;
+const STATUS_INTERNAL: u8 = 3;
+const STATUS_INVALID_INPUT: u8 = 4;
+
+/// Runs a service handler in a child process, so it can't take the node down with it. Requests
+/// are written to the child's stdin and the responses read from its stdout, one at a time. Each
+/// message is a status byte followed by the size of the payload (a big endian `u64`) and the
+/// payload itself. Requests always have the status `0`, responses use `0` for success, `1` for
+/// unavailable, `2` for timeout, `3` for an internal error and `4` for an invalid input. The
+/// payload of an error is its message. A child that exits is started again with the next request.
+pub struct Process {
+    command: String,
+    arguments: Vec<String>,
+    child: Mutex<Option<process::Child>>,
+}
+
+impl Process {
+    pub fn new(command: &str, arguments: &[String]) -> Process {
+        Process {
+            command: command.to_string(),
+            arguments: arguments.to_vec(),
+            child: Mutex::new(None),
+        }
+    }
+
+    fn spawn(&self) -> io::Result<process::Child> {
+        let child = try!(process::Command::new(&self.command)
+                             .args(&self.arguments)
+                             .stdin(process::Stdio::piped())
+                             .stdout(process::Stdio::piped())
+                             .spawn());
+        info!("started service process {} ({})", self.command, child.id());
+        Ok(child)
+    }
+
+    fn exchange(child: &mut process::Child, request: &[u8]) -> io::Result<(u8, Vec<u8>)> {
+        try!(write_message(child.stdin.as_mut().unwrap(), STATUS_OK, request));
+        read_message(child.stdout.as_mut().unwrap())
+    }
+}
+
+impl ServiceHandler for Process {
+    fn call(&self, mut request: Box<request::Reader>) -> service::Result {
+        let mut buffer = Vec::new();
+        if let Err(error) = request.read_to_end(&mut buffer) {
+            return Err(service::Error::Internal(format!("{:?}", error.kind())));
+        }
+
+        let mut child = self.child.lock().unwrap();
+        if child.is_none() {
+            *child = Some(try!(self.spawn().map_err(|error| {
+                error!("could not start service process {}: {}", self.command, error);
+                service::Error::Unavailable
+            })));
+        }
+
+        let result = Self::exchange(child.as_mut().unwrap(), &buffer);
+        let (status, payload) = match result {
+            Ok(response) => response,
+            Err(error) => {
+                let mut dead_child = child.take().unwrap();
+                let _ = dead_child.kill();
+                warn!("service process {} ({}) failed: {} - exited with {:?}",
+                      self.command,
+                      dead_child.id(),
+                      error,
+                      dead_child.wait().ok());
+                return Err(service::Error::Unavailable);
+            }
+        };
+
+        let message = String::from_utf8_lossy(&payload).into_owned();
+        match status {
+            STATUS_OK => Ok(Box::new(io::Cursor::new(payload))),
+            STATUS_UNAVAILABLE => Err(service::Error::Unavailable),
+            STATUS_TIMEOUT => Err(service::Error::Timeout),
+            STATUS_INVALID_INPUT => Err(service::Error::InvalidInput(message)),
+            _ => Err(service::Error::Internal(message)),
+        }
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.child.lock().unwrap().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Serves the requests that are read from stdin with the given service until stdin is closed.
+/// It's the counterpart of `Process` and meant to be called in the child's main function.
+pub fn serve(service: &Service) -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    serve_over(service, &mut stdin.lock(), &mut stdout.lock())
+}
+
+fn serve_over(service: &Service, reader: &mut Read, writer: &mut Write) -> io::Result<()> {
+    loop {
+        let request = match read_message(reader) {
+            Ok((_, request)) => request,
+            Err(ref error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(error) => return Err(error),
+        };
+
+        let (status, payload) = match service(Box::new(io::Cursor::new(request))) {
+            Ok(mut response) => {
+                let mut payload = Vec::new();
+                match response.read_to_end(&mut payload) {
+                    Ok(_) => (STATUS_OK, payload),
+                    Err(error) => (STATUS_INTERNAL, format!("{:?}", error.kind()).into_bytes()),
+                }
+            }
+            Err(service::Error::Unavailable) => (STATUS_UNAVAILABLE, Vec::new()),
+            Err(service::Error::Timeout) => (STATUS_TIMEOUT, Vec::new()),
+            Err(service::Error::InvalidInput(message)) => {
+                (STATUS_INVALID_INPUT, message.into_bytes())
+            }
+            Err(service::Error::Internal(message)) => (STATUS_INTERNAL, message.into_bytes()),
+            Err(service::Error::Redirect(..)) => {
+                (STATUS_INTERNAL, b"redirects are not supported".to_vec())
+            }
+        };
+        try!(write_message(writer, status, &payload));
+    }
+}
+
+fn write_message(writer: &mut Write, status: u8, payload: &[u8]) -> io::Result<()> {
+    try!(writer.write_all(&[status]));
+    try!(write_size(writer, payload.len()));
+    try!(writer.write_all(payload));
+    writer.flush()
+}
+
+fn read_message(reader: &mut Read) -> io::Result<(u8, Vec<u8>)> {
+    let mut status = [0u8];
+    if try!(reader.read(&mut status)) == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "unexpected EOF"));
+    }
+    let size = try!(read_size(reader));
+    let mut payload = Vec::new();
+    try!((&mut *reader).take(size as u64).read_to_end(&mut payload));
+    if payload.len() < size {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "unexpected EOF"));
+    }
+    Ok((status[0], payload))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{self, Read};
+    use node::{request, service};
+    use node::service::ServiceHandler;
+    use super::{Process, serve_over, write_message, read_message};
+
+    #[test]
+    fn echo_over_pipe() {
+        // cat answers every request with an identical, successful response.
+        let process = Process::new("cat", &[]);
+        for text in &["first", "second"] {
+            let mut response = process.call(Box::new(io::Cursor::new(text.as_bytes().to_vec())))
+                                      .unwrap();
+            let mut content = String::new();
+            response.read_to_string(&mut content).unwrap();
+            assert_eq!(*text, content);
+        }
+    }
+
+    #[test]
+    fn exited_process_is_unavailable() {
+        let process = Process::new("true", &[]);
+        assert_eq!(Some(service::Error::Unavailable),
+                   process.call(Box::new(io::Cursor::new(b"test".to_vec()))).err());
+    }
+
+    #[test]
+    fn serve_requests() {
+        let mut input = Vec::new();
+        write_message(&mut input, 0, b"ok").unwrap();
+        write_message(&mut input, 0, b"fail").unwrap();
+
+        let mut output = Vec::new();
+        serve_over(&|mut request: Box<request::Reader>| -> service::Result {
+                       let mut content = String::new();
+                       request.read_to_string(&mut content).unwrap();
+                       if content == "ok" {
+                           Ok(Box::new(io::Cursor::new(b"done".to_vec())))
+                       } else {
+                           Err(service::Error::InvalidInput(content))
+                       }
+                   },
+                   &mut io::Cursor::new(input),
+                   &mut output)
+            .unwrap();
+
+        let mut output = io::Cursor::new(output);
+        assert_eq!((0, b"done".to_vec()), read_message(&mut output).unwrap());
+        assert_eq!((4, b"fail".to_vec()), read_message(&mut output).unwrap());
+    }
+
+}
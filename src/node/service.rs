@@ -13,16 +13,307 @@
 // limitations under the License.
 //
 
+use std::collections::BTreeMap;
+use std::io::{self, Read};
 use std::result;
-use super::{request, response};
+use std::sync::{Arc, mpsc};
+use rustc_serialize::{Decodable, Encodable, json};
+use super::{ID, request, response, stream};
+use util::thread::supervisor;
 
 pub type Service = Fn(Box<request::Reader>) -> Result + Send + Sync;
 
+/// A service that streams its response. Everything that is written before a flush is sent to
+/// the caller right away, so the handler can push data for as long as it likes. The response
+/// ends when the handler returns.
+pub type StreamingService = Fn(Box<request::Reader>, &mut stream::Writer)
+                               -> result::Result<(), Error> + Send + Sync;
+
+pub trait ServiceHandler : Send + Sync {
+    fn call(&self, Box<request::Reader>) -> Result;
+}
+
 pub type Result = result::Result<Box<response::Reader>, Error>;
 
+pub type Validator = Fn(&[u8]) -> result::Result<(), String> + Send + Sync;
+
+/// Key/value pairs that describe a registered service, e.g. its `version` or `region`. They are
+/// announced to the peers, so callers can pick the providers that carry certain tags.
+pub type Metadata = BTreeMap<String, String>;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Error {
     Unavailable,
     Timeout,
     Internal(String),
+    InvalidInput(String),
+    /// Asks the caller to send the request to another service, optionally on the given node.
+    Redirect(String, Option<ID>),
+}
+
+/// The weight of services that are registered without one. Weights are relative capacity hints
+/// for balancers that distribute requests proportionally.
+pub const DEFAULT_WEIGHT: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Placement {
+    Any,
+    SameZone,
+    /// The service is not announced to any peer and can only be requested from the node itself.
+    Local,
+}
+
+impl Placement {
+    pub fn allows(&self, zone: Option<&str>, peer_zone: Option<&str>) -> bool {
+        match *self {
+            Placement::Any => true,
+            Placement::SameZone => zone.is_some() && zone == peer_zone,
+            Placement::Local => false,
+        }
+    }
+}
+
+impl<F> ServiceHandler for F where F: Fn(Box<request::Reader>) -> Result + Send + Sync
+{
+    fn call(&self, request: Box<request::Reader>) -> Result {
+        self(request)
+    }
+}
+
+pub fn from_handler(handler: Arc<ServiceHandler>) -> Box<Service> {
+    Box::new(move |request: Box<request::Reader>| handler.call(request))
+}
+
+pub fn validate(service: Box<Service>,
+                request_validator: Option<Box<Validator>>,
+                response_validator: Option<Box<Validator>>)
+                -> Box<Service> {
+    Box::new(move |mut request: Box<request::Reader>| -> Result {
+        let request: Box<request::Reader> = match request_validator {
+            Some(ref validator) => {
+                let mut buffer = Vec::new();
+                try!(read_all(&mut request, &mut buffer));
+                if let Err(message) = validator(&buffer) {
+                    return Err(Error::InvalidInput(format!("invalid request: {}", message)));
+                }
+                Box::new(io::Cursor::new(buffer))
+            }
+            None => request,
+        };
+
+        let mut response = try!(service(request));
+
+        match response_validator {
+            Some(ref validator) => {
+                let mut buffer = Vec::new();
+                try!(read_all(&mut response, &mut buffer));
+                if let Err(message) = validator(&buffer) {
+                    return Err(Error::Internal(format!("invalid response: {}", message)));
+                }
+                Ok(Box::new(io::Cursor::new(buffer)))
+            }
+            None => Ok(response),
+        }
+    })
+}
+
+/// Turns a handler of typed values into a service. The request body is decoded from JSON and
+/// the returned value is encoded to JSON. A request that can't be decoded fails with
+/// `InvalidInput`.
+pub fn json<T, R, F>(f: F) -> Box<Service>
+    where T: Decodable + 'static,
+          R: Encodable + 'static,
+          F: Fn(T) -> result::Result<R, Error> + Send + Sync + 'static
+{
+    Box::new(move |mut request: Box<request::Reader>| -> Result {
+        let mut content = String::new();
+        if let Err(error) = request.read_to_string(&mut content) {
+            return Err(Error::InvalidInput(format!("invalid request: {}", error)));
+        }
+        let value = try!(json::decode::<T>(&content).map_err(|error| {
+            Error::InvalidInput(format!("invalid request: {}", error))
+        }));
+        let response = try!(json::encode(&try!(f(value))).map_err(|error| {
+            Error::Internal(format!("invalid response: {}", error))
+        }));
+        Ok(Box::new(io::Cursor::new(response.into_bytes())))
+    })
+}
+
+/// Turns a streaming service into a service. The handler runs in its own thread. An error that
+/// is returned before the first flush is the result of the request, a later one aborts the
+/// streamed response.
+pub fn streaming(service: Box<StreamingService>) -> Box<Service> {
+    let service = Arc::new(service);
+    Box::new(move |request: Box<request::Reader>| -> Result {
+        let (mut writer, reader, flushed_rx) = stream::pipe();
+        let (result_tx, result_rx) = mpsc::channel();
+        let service = service.clone();
+        supervisor().spawn("streaming service", move || {
+            let result = service(request, &mut writer);
+            let _ = result_tx.send(result.clone());
+            if let Err(error) = result {
+                writer.fail(io::Error::new(io::ErrorKind::Other, format!("{:?}", error)));
+            }
+        });
+
+        if flushed_rx.recv().is_ok() {
+            return Ok(Box::new(reader));
+        }
+        // the writer is gone without a flush, so the handler has finished.
+        match result_rx.recv() {
+            Ok(Ok(())) => Ok(Box::new(reader)),
+            Ok(Err(error)) => Err(error),
+            Err(_) => Err(Error::Internal("streaming service panicked".to_string())),
+        }
+    })
+}
+
+fn read_all(reader: &mut Read, buffer: &mut Vec<u8>) -> result::Result<(), Error> {
+    reader.read_to_end(buffer)
+          .map(|_| ())
+          .map_err(|error| Error::Internal(format!("{:?}", error.kind())))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{self, Read, Write};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use node::{request, stream};
+    use super::{Error, Placement, Result, Service, ServiceHandler, from_handler, json,
+                streaming, validate};
+
+    #[derive(RustcDecodable)]
+    struct Sum {
+        a: i32,
+        b: i32,
+    }
+
+    struct Counter {
+        count: Mutex<usize>,
+    }
+
+    impl ServiceHandler for Counter {
+        fn call(&self, _: Box<request::Reader>) -> Result {
+            let mut count = self.count.lock().unwrap();
+            *count += 1;
+            Ok(Box::new(io::Cursor::new(format!("{}", *count).into_bytes())))
+        }
+    }
+
+    #[test]
+    fn shared_handler() {
+        let counter = Arc::new(Counter { count: Mutex::new(0) });
+        let service = Arc::new(from_handler(counter.clone()));
+
+        let join_handles = (0..4)
+                               .map(|_| {
+                                   let service = service.clone();
+                                   thread::spawn(move || read(&service, b"").unwrap())
+                               })
+                               .collect::<Vec<_>>();
+        for join_handle in join_handles {
+            join_handle.join().unwrap();
+        }
+
+        assert_eq!(4, *counter.count.lock().unwrap());
+        assert_eq!("5", read(&service, b"").unwrap());
+    }
+
+    #[test]
+    fn validate_request_and_response() {
+        let service = validate(Box::new(|mut request: Box<request::Reader>| -> Result {
+                                   let mut content = Vec::new();
+                                   request.read_to_end(&mut content).unwrap();
+                                   Ok(Box::new(io::Cursor::new(content)))
+                               }),
+                               Some(Box::new(|request: &[u8]| {
+                                   if request.starts_with(b"{") {
+                                       Ok(())
+                                   } else {
+                                       Err("expected object".to_string())
+                                   }
+                               })),
+                               Some(Box::new(|response: &[u8]| {
+                                   if response.len() < 8 {
+                                       Ok(())
+                                   } else {
+                                       Err("too long".to_string())
+                                   }
+                               })));
+
+        assert_eq!("{}", read(&service, b"{}").unwrap());
+        assert_eq!(Error::InvalidInput("invalid request: expected object".to_string()),
+                   read(&service, b"[]").unwrap_err());
+        assert_eq!(Error::Internal("invalid response: too long".to_string()),
+                   read(&service, b"{\"a\":\"b\"}").unwrap_err());
+    }
+
+    #[test]
+    fn typed_json_values() {
+        let service = json(|sum: Sum| -> ::std::result::Result<Vec<i32>, Error> {
+            if sum.a < 0 {
+                return Err(Error::Unavailable);
+            }
+            Ok(vec![sum.a + sum.b])
+        });
+
+        assert_eq!("[3]", read(&service, b"{\"a\":1,\"b\":2}").unwrap());
+        assert_eq!(Error::Unavailable,
+                   read(&service, b"{\"a\":-1,\"b\":2}").unwrap_err());
+        match read(&service, b"{\"a\":1}") {
+            Err(Error::InvalidInput(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn stream_response() {
+        let (tx, rx) = ::std::sync::mpsc::channel::<()>();
+        let rx = ::std::sync::Mutex::new(rx);
+        let service = streaming(Box::new(move |_: Box<request::Reader>,
+                                               writer: &mut stream::Writer|
+                                               -> ::std::result::Result<(), Error> {
+            writer.write_all(b"first").unwrap();
+            writer.flush().unwrap();
+            // the caller reads the first part before the rest is written.
+            rx.lock().unwrap().recv().unwrap();
+            writer.write_all(b" second").unwrap();
+            Ok(())
+        }));
+
+        let mut response = service(Box::new(io::empty())).unwrap();
+        let mut buffer = [0u8; 16];
+        assert_eq!(5, response.read(&mut buffer).unwrap());
+        tx.send(()).unwrap();
+        let mut content = String::new();
+        response.read_to_string(&mut content).unwrap();
+        assert_eq!(" second", content);
+
+        let failing = streaming(Box::new(|_: Box<request::Reader>,
+                                          _: &mut stream::Writer|
+                                          -> ::std::result::Result<(), Error> {
+            Err(Error::Unavailable)
+        }));
+        assert_eq!(Error::Unavailable, read(&failing, b"").unwrap_err());
+    }
+
+    #[test]
+    fn placement_allows_zones() {
+        assert!(Placement::Any.allows(None, Some("b")));
+        assert!(Placement::SameZone.allows(Some("a"), Some("a")));
+        assert!(!Placement::SameZone.allows(Some("a"), Some("b")));
+        assert!(!Placement::SameZone.allows(Some("a"), None));
+        assert!(!Placement::SameZone.allows(None, None));
+        assert!(!Placement::Local.allows(Some("a"), Some("a")));
+    }
+
+    fn read(service: &Box<Service>, request: &[u8]) -> ::std::result::Result<String, Error> {
+        let mut response = try!(service(Box::new(io::Cursor::new(request.to_vec()))));
+        let mut content = String::new();
+        response.read_to_string(&mut content).unwrap();
+        Ok(content)
+    }
 }
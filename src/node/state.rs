@@ -0,0 +1,128 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::RwLock;
+
+use time;
+
+use metric::{Metric, item};
+
+const MAXIMAL_HISTORY: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    Discovering,
+    Joined,
+    Alone,
+    Degraded,
+}
+
+pub struct History {
+    transitions: RwLock<VecDeque<(State, time::Tm)>>,
+    state_gauge: item::Gauge,
+    transitions_counter: item::Counter,
+}
+
+impl History {
+    pub fn new(state: State, metric: &Metric) -> Self {
+        let mut transitions = VecDeque::with_capacity(MAXIMAL_HISTORY);
+        transitions.push_back((state, time::now_utc()));
+
+        let state_gauge = metric.gauge("state");
+        state_gauge.set(state as isize);
+
+        History {
+            transitions: RwLock::new(transitions),
+            state_gauge: state_gauge,
+            transitions_counter: metric.counter("state_transitions"),
+        }
+    }
+
+    pub fn current(&self) -> State {
+        self.transitions.read().unwrap().back().map(|&(state, _)| state).unwrap()
+    }
+
+    pub fn set(&self, state: State) -> bool {
+        let mut transitions = self.transitions.write().unwrap();
+        if transitions.back().map(|&(current, _)| current == state).unwrap_or(false) {
+            return false;
+        }
+
+        while transitions.len() >= MAXIMAL_HISTORY {
+            transitions.pop_front();
+        }
+        transitions.push_back((state, time::now_utc()));
+
+        self.state_gauge.set(state as isize);
+        self.transitions_counter.increment();
+        true
+    }
+
+    pub fn transitions(&self) -> Vec<(State, time::Tm)> {
+        self.transitions.read().unwrap().iter().cloned().collect()
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            State::Discovering => write!(f, "discovering"),
+            State::Joined => write!(f, "joined"),
+            State::Alone => write!(f, "alone"),
+            State::Degraded => write!(f, "degraded"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use metric::{Memory, Query, Value};
+    use super::{History, MAXIMAL_HISTORY, State};
+
+    #[test]
+    fn transitions() {
+        let metric = Memory::new();
+        let history = History::new(State::Discovering, &metric);
+
+        assert!(history.set(State::Joined));
+        assert!(!history.set(State::Joined));
+        assert!(history.set(State::Degraded));
+
+        assert_eq!(State::Degraded, history.current());
+        assert_eq!(vec![State::Discovering, State::Joined, State::Degraded],
+                   history.transitions()
+                          .into_iter()
+                          .map(|(state, _)| state)
+                          .collect::<Vec<_>>());
+        assert_eq!(Some(Value::Gauge(State::Degraded as isize)), metric.get("state"));
+        assert_eq!(Some(Value::Counter(2)), metric.get("state_transitions"));
+    }
+
+    #[test]
+    fn limited_history() {
+        let metric = Memory::new();
+        let history = History::new(State::Discovering, &metric);
+
+        for _ in 0..MAXIMAL_HISTORY {
+            history.set(State::Joined);
+            history.set(State::Degraded);
+        }
+
+        assert_eq!(MAXIMAL_HISTORY, history.transitions().len());
+    }
+}
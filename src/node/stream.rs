@@ -0,0 +1,147 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::io::{self, Read, Write};
+use std::mem;
+use std::sync::mpsc;
+
+/// The number of flushed chunks that can be queued before the writer blocks.
+const CHUNK_CAPACITY: usize = 16;
+
+/// The writing end of a streamed response. Written bytes are buffered until the writer is flushed,
+/// which hands them over as one chunk. The reader blocks if it falls behind by too many chunks.
+pub struct Writer {
+    buffer: Vec<u8>,
+    tx: mpsc::SyncSender<io::Result<Vec<u8>>>,
+    flushed_tx: Option<mpsc::Sender<()>>,
+}
+
+/// The reading end of a streamed response. It returns the chunks as they are flushed and reaches
+/// the end when the writer is dropped.
+pub struct Reader {
+    chunk: io::Cursor<Vec<u8>>,
+    rx: mpsc::Receiver<io::Result<Vec<u8>>>,
+}
+
+/// Creates a connected writer and reader. The receiver gets a message once the first chunk has
+/// been flushed.
+pub fn pipe() -> (Writer, Reader, mpsc::Receiver<()>) {
+    let (tx, rx) = mpsc::sync_channel(CHUNK_CAPACITY);
+    let (flushed_tx, flushed_rx) = mpsc::channel();
+    (Writer {
+        buffer: Vec::new(),
+        tx: tx,
+        flushed_tx: Some(flushed_tx),
+    },
+     Reader {
+        chunk: io::Cursor::new(Vec::new()),
+        rx: rx,
+    },
+     flushed_rx)
+}
+
+impl Writer {
+    /// Aborts the stream. The reader returns the error after the chunks that have been flushed
+    /// so far.
+    pub fn fail(mut self, error: io::Error) {
+        let _ = self.flush();
+        let _ = self.tx.send(Err(error));
+    }
+}
+
+impl io::Write for Writer {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buffer);
+        Ok(buffer.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = mem::replace(&mut self.buffer, Vec::new());
+        if self.tx.send(Ok(chunk)).is_err() {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "stream reader is gone"));
+        }
+        if let Some(flushed_tx) = self.flushed_tx.take() {
+            let _ = flushed_tx.send(());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl io::Read for Reader {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let size = try!(self.chunk.read(buffer));
+            if size > 0 || buffer.is_empty() {
+                return Ok(size);
+            }
+            match self.rx.recv() {
+                Ok(Ok(chunk)) => self.chunk = io::Cursor::new(chunk),
+                Ok(Err(error)) => return Err(error),
+                Err(_) => return Ok(0),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{self, Read, Write};
+    use std::thread;
+    use super::pipe;
+
+    #[test]
+    fn read_flushed_chunks() {
+        let (mut writer, mut reader, flushed_rx) = pipe();
+        writer.write_all(b"first ").unwrap();
+        assert!(flushed_rx.try_recv().is_err());
+        writer.flush().unwrap();
+        assert!(flushed_rx.try_recv().is_ok());
+
+        let mut buffer = [0u8; 16];
+        assert_eq!(6, reader.read(&mut buffer).unwrap());
+        assert_eq!(b"first ", &buffer[..6]);
+
+        let join_handle = thread::spawn(move || {
+            writer.write_all(b"second").unwrap();
+            writer.fail(io::Error::new(io::ErrorKind::Other, "test"));
+        });
+        assert_eq!(6, reader.read(&mut buffer).unwrap());
+        assert_eq!(b"second", &buffer[..6]);
+        assert_eq!(io::ErrorKind::Other, reader.read(&mut buffer).unwrap_err().kind());
+        join_handle.join().unwrap();
+    }
+
+    #[test]
+    fn end_when_writer_is_dropped() {
+        let (mut writer, mut reader, _) = pipe();
+        writer.write_all(b"test").unwrap();
+        drop(writer);
+
+        let mut content = String::new();
+        reader.read_to_string(&mut content).unwrap();
+        assert_eq!("test", content);
+    }
+
+}
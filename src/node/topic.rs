@@ -0,0 +1,121 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::sync::{Arc, RwLock};
+
+use super::{Service, request, service};
+
+/// The prefix of the service names that stand for the subscriptions to a topic. A node that
+/// subscribes to a topic registers such a service, so the subscription is announced to the peers
+/// like any other service. `Node::register` rejects names with this prefix.
+pub const SERVICE_PREFIX: &'static str = "topic.";
+
+/// Handles a message that has been published to a topic.
+pub type Handler = Fn(&[u8]) + Send + Sync;
+
+/// The topics this node is subscribed to. All handlers of a topic share a single service.
+pub struct Subscriptions {
+    topics: Arc<RwLock<HashMap<String, Vec<Arc<Box<Handler>>>>>>,
+}
+
+impl Subscriptions {
+    pub fn new() -> Subscriptions {
+        Subscriptions { topics: Arc::new(RwLock::new(HashMap::new())) }
+    }
+
+    /// Adds the handler to the topic. Returns the service that delivers the messages to the
+    /// handlers, if it's the first subscription to the topic.
+    pub fn add(&self, topic: &str, handler: Box<Handler>) -> Option<Box<Service>> {
+        let mut topics = self.topics.write().unwrap();
+        let first = !topics.contains_key(topic);
+        topics.entry(topic.to_string()).or_insert_with(Vec::new).push(Arc::new(handler));
+        if first {
+            Some(self.service(topic))
+        } else {
+            None
+        }
+    }
+
+    /// Removes all handlers of the topic. Returns `false` if there were none.
+    pub fn remove(&self, topic: &str) -> bool {
+        self.topics.write().unwrap().remove(topic).is_some()
+    }
+
+    fn service(&self, topic: &str) -> Box<Service> {
+        let topics = self.topics.clone();
+        let topic = topic.to_string();
+        Box::new(move |mut request: Box<request::Reader>| -> service::Result {
+            let mut message = Vec::new();
+            if let Err(error) = request.read_to_end(&mut message) {
+                return Err(service::Error::InvalidInput(format!("{}", error)));
+            }
+            let handlers = topics.read()
+                                 .unwrap()
+                                 .get(&topic)
+                                 .cloned()
+                                 .unwrap_or_else(Vec::new);
+            for handler in handlers {
+                handler(&message);
+            }
+            Ok(Box::new(io::empty()))
+        })
+    }
+}
+
+/// Returns the name of the service that stands for the subscriptions to the topic.
+pub fn service_name(topic: &str) -> String {
+    format!("{}{}", SERVICE_PREFIX, topic)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use super::Subscriptions;
+
+    #[test]
+    fn deliver_to_all_handlers() {
+        let subscriptions = Subscriptions::new();
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let received_clone = received.clone();
+        let service = subscriptions.add("events",
+                                        Box::new(move |message: &[u8]| {
+                                            received_clone.lock()
+                                                          .unwrap()
+                                                          .push(("one", message.to_vec()));
+                                        }))
+                                   .unwrap();
+        let received_clone = received.clone();
+        assert!(subscriptions.add("events",
+                                  Box::new(move |message: &[u8]| {
+                                      received_clone.lock()
+                                                    .unwrap()
+                                                    .push(("two", message.to_vec()));
+                                  }))
+                             .is_none());
+
+        service(Box::new(io::Cursor::new(b"started".to_vec()))).unwrap();
+        assert_eq!(vec![("one", b"started".to_vec()), ("two", b"started".to_vec())],
+                   *received.lock().unwrap());
+
+        assert!(subscriptions.remove("events"));
+        assert!(!subscriptions.remove("events"));
+    }
+
+}
@@ -0,0 +1,184 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::fmt;
+
+/// The metadata key that holds the version of a service.
+pub const METADATA_KEY: &'static str = "version";
+
+/// A semantic version like `1.2.3`. Missing minor and patch numbers count as zero and
+/// pre-release or build suffixes are ignored.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Any,
+    Exact,
+    AtLeast,
+    Caret,
+    Tilde,
+}
+
+/// A version requirement like `^1.2`, `~1.2.3`, `=1.2.3`, `>=1.2` or `*`. Requirements without
+/// an operator are caret requirements, so `1.2` matches every version from `1.2.0` up to but
+/// excluding `2.0.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Requirement {
+    operator: Operator,
+    version: Version,
+}
+
+impl Version {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Version {
+        Version {
+            major: major,
+            minor: minor,
+            patch: patch,
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Version> {
+        let text = text.trim().trim_left_matches('v');
+        let text = text.split(|c| c == '-' || c == '+').next().unwrap_or("");
+        let mut numbers = Vec::new();
+        for part in text.split('.') {
+            match part.parse::<u64>() {
+                Ok(number) => numbers.push(number),
+                Err(_) => return None,
+            }
+        }
+        if numbers.len() > 3 {
+            return None;
+        }
+        numbers.resize(3, 0);
+        Some(Version::new(numbers[0], numbers[1], numbers[2]))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl Requirement {
+    pub fn parse(text: &str) -> Option<Requirement> {
+        let text = text.trim();
+        if text == "*" {
+            return Some(Requirement {
+                operator: Operator::Any,
+                version: Version::new(0, 0, 0),
+            });
+        }
+        let (operator, version) = if text.starts_with(">=") {
+            (Operator::AtLeast, &text[2..])
+        } else if text.starts_with('=') {
+            (Operator::Exact, &text[1..])
+        } else if text.starts_with('^') {
+            (Operator::Caret, &text[1..])
+        } else if text.starts_with('~') {
+            (Operator::Tilde, &text[1..])
+        } else {
+            (Operator::Caret, text)
+        };
+        Version::parse(version).map(|version| {
+            Requirement {
+                operator: operator,
+                version: version,
+            }
+        })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        let minimum = &self.version;
+        match self.operator {
+            Operator::Any => true,
+            Operator::Exact => version == minimum,
+            Operator::AtLeast => version >= minimum,
+            Operator::Caret if minimum.major == 0 => {
+                version >= minimum && version.major == 0 && version.minor == minimum.minor
+            }
+            Operator::Caret => version >= minimum && version.major == minimum.major,
+            Operator::Tilde => {
+                version >= minimum && version.major == minimum.major &&
+                version.minor == minimum.minor
+            }
+        }
+    }
+}
+
+/// Splits a requested name like `echo@^1.2` into the service name and the version requirement.
+pub fn split(name: &str) -> (&str, Option<&str>) {
+    match name.find('@') {
+        Some(index) => (&name[..index], Some(&name[index + 1..])),
+        None => (name, None),
+    }
+}
+
+/// Returns the name of the service without the version requirement.
+pub fn service_name(name: &str) -> &str {
+    split(name).0
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Requirement, Version, split};
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(Some(Version::new(1, 2, 3)), Version::parse("1.2.3"));
+        assert_eq!(Some(Version::new(1, 2, 0)), Version::parse("v1.2"));
+        assert_eq!(Some(Version::new(2, 0, 0)), Version::parse("2.0.0-beta.1"));
+        assert_eq!(None, Version::parse("one"));
+        assert_eq!(None, Version::parse("1.2.3.4"));
+    }
+
+    #[test]
+    fn match_requirement() {
+        let caret = Requirement::parse("^1.2").unwrap();
+        assert!(caret.matches(&Version::new(1, 2, 0)));
+        assert!(caret.matches(&Version::new(1, 9, 4)));
+        assert!(!caret.matches(&Version::new(1, 1, 9)));
+        assert!(!caret.matches(&Version::new(2, 0, 0)));
+        assert_eq!(Some(caret), Requirement::parse("1.2"));
+
+        let zero = Requirement::parse("^0.3.1").unwrap();
+        assert!(zero.matches(&Version::new(0, 3, 5)));
+        assert!(!zero.matches(&Version::new(0, 4, 0)));
+
+        let tilde = Requirement::parse("~1.2").unwrap();
+        assert!(tilde.matches(&Version::new(1, 2, 7)));
+        assert!(!tilde.matches(&Version::new(1, 3, 0)));
+
+        assert!(Requirement::parse("=1.2.3").unwrap().matches(&Version::new(1, 2, 3)));
+        assert!(!Requirement::parse("=1.2.3").unwrap().matches(&Version::new(1, 2, 4)));
+        assert!(Requirement::parse(">=1.2").unwrap().matches(&Version::new(3, 0, 0)));
+        assert!(Requirement::parse("*").unwrap().matches(&Version::new(0, 0, 1)));
+        assert_eq!(None, Requirement::parse("^latest"));
+    }
+
+    #[test]
+    fn split_name() {
+        assert_eq!(("echo", Some("^1.2")), split("echo@^1.2"));
+        assert_eq!(("echo", None), split("echo"));
+    }
+
+}
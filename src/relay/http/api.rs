@@ -15,17 +15,24 @@
 extern crate hyper;
 extern crate rustc_serialize;
 
+use std::collections::BTreeMap;
 use std::io::Read;
 use std::net::SocketAddr;
 use std::result;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use self::hyper::method::Method;
 use self::hyper::server::{Listening, Request, Response};
 use self::hyper::status::StatusCode;
 use self::hyper::uri::RequestUri::AbsolutePath;
-use rustc_serialize::json;
+use rustc_serialize::json::{self, Json};
+use time;
 
+use metric::format;
+use node::ID;
+use transport::{ConnectionStats, PeerInfo, ReconnectState};
+use util::thread::supervisor;
 use super::logic::{Logic, Service};
 
 pub struct Api {
@@ -87,6 +94,111 @@ fn handle(logic: &Arc<Logic>, request: Request, mut response: Response) -> Resul
 
                     *response.status_mut() = StatusCode::Ok;
                 }
+                Method::Get if path == "/state" => {
+                    *response.status_mut() = StatusCode::Ok;
+                    response.send(build_state(logic).to_string().as_bytes()).unwrap();
+                }
+                Method::Get if path == "/metrics" || path == "/metrics/prometheus" => {
+                    match logic.metric_snapshot() {
+                        Some((naming, values)) if path == "/metrics" => {
+                            *response.status_mut() = StatusCode::Ok;
+                            let content = format::json_with_naming(&values, &naming).to_string();
+                            response.send(content.as_bytes()).unwrap();
+                        }
+                        Some((naming, values)) => {
+                            *response.status_mut() = StatusCode::Ok;
+                            let content = format::prometheus_with_naming(&values, &naming);
+                            response.send(content.as_bytes()).unwrap();
+                        }
+                        None => {
+                            *response.status_mut() = StatusCode::NotFound;
+                            response.send(b"metric values are not kept").unwrap();
+                        }
+                    }
+                }
+                Method::Get if path == "/usage" => {
+                    *response.status_mut() = StatusCode::Ok;
+                    response.send(build_usage(logic).to_string().as_bytes()).unwrap();
+                }
+                Method::Get if path == "/reconnects" => {
+                    *response.status_mut() = StatusCode::Ok;
+                    let reconnects = Json::Array(logic.reconnect_states()
+                                                      .iter()
+                                                      .map(build_reconnect_state)
+                                                      .collect());
+                    response.send(reconnects.to_string().as_bytes()).unwrap();
+                }
+                Method::Get if path == "/peers" => {
+                    *response.status_mut() = StatusCode::Ok;
+                    let peers = Json::Array(logic.peers().iter().map(build_peer_info).collect());
+                    response.send(peers.to_string().as_bytes()).unwrap();
+                }
+                Method::Get if path == "/threads" => {
+                    *response.status_mut() = StatusCode::Ok;
+                    response.send(build_threads().to_string().as_bytes()).unwrap();
+                }
+                Method::Get if path.starts_with("/traces/") => {
+                    let (_, trace_id) = path.split_at(8);
+
+                    match logic.trace_records(trace_id) {
+                        Some(records) => {
+                            *response.status_mut() = StatusCode::Ok;
+                            response.send(json::encode(&records).unwrap().as_bytes()).unwrap();
+                        }
+                        None => {
+                            *response.status_mut() = StatusCode::NotFound;
+                            response.send(b"log collection is disabled").unwrap();
+                        }
+                    }
+                }
+                Method::Get if path.starts_with("/peers/") && path.ends_with("/stats") => {
+                    let peer_node_id = match ID::from_str(path[7..].split('/').next().unwrap()) {
+                        Ok(peer_node_id) => peer_node_id,
+                        Err(_) => {
+                            *response.status_mut() = StatusCode::BadRequest;
+                            return Ok(());
+                        }
+                    };
+
+                    match logic.connection_stats(peer_node_id) {
+                        Ok((local, remote)) => {
+                            let mut object = BTreeMap::new();
+                            object.insert("local".to_string(), build_connection_stats(&local));
+                            object.insert("remote".to_string(), build_connection_stats(&remote));
+                            *response.status_mut() = StatusCode::Ok;
+                            response.send(Json::Object(object).to_string().as_bytes()).unwrap();
+                        }
+                        Err(error) => {
+                            *response.status_mut() = StatusCode::BadGateway;
+                            response.send(format!("{}", error).as_bytes()).unwrap();
+                        }
+                    }
+                }
+                Method::Post if path == "/drain" => {
+                    match logic.acquire_drain_permit() {
+                        Ok(true) => {
+                            *response.status_mut() = StatusCode::Ok;
+                        }
+                        Ok(false) => {
+                            *response.status_mut() = StatusCode::Conflict;
+                        }
+                        Err(error) => {
+                            *response.status_mut() = StatusCode::BadGateway;
+                            response.send(format!("{}", error).as_bytes()).unwrap();
+                        }
+                    }
+                }
+                Method::Delete if path == "/drain" => {
+                    match logic.release_drain_permit() {
+                        Ok(()) => {
+                            *response.status_mut() = StatusCode::Ok;
+                        }
+                        Err(error) => {
+                            *response.status_mut() = StatusCode::BadGateway;
+                            response.send(format!("{}", error).as_bytes()).unwrap();
+                        }
+                    }
+                }
                 _ => {
                     *response.status_mut() = StatusCode::NotFound;
                 }
@@ -97,3 +209,106 @@ fn handle(logic: &Arc<Logic>, request: Request, mut response: Response) -> Resul
 
     Ok(())
 }
+
+fn build_state(logic: &Arc<Logic>) -> Json {
+    let history = logic.state_history();
+
+    let mut object = BTreeMap::new();
+    object.insert("state".to_string(),
+                  Json::String(history.last()
+                                      .map(|&(state, _)| state.to_string())
+                                      .unwrap_or(String::new())));
+    object.insert("history".to_string(),
+                  Json::Array(history.into_iter()
+                                     .map(|(state, at)| {
+                                         let mut transition = BTreeMap::new();
+                                         transition.insert("state".to_string(),
+                                                           Json::String(state.to_string()));
+                                         transition.insert("at".to_string(),
+                                                           Json::String(format!("{}",
+                                                                                at.rfc3339())));
+                                         Json::Object(transition)
+                                     })
+                                     .collect()));
+    Json::Object(object)
+}
+
+fn build_usage(logic: &Arc<Logic>) -> Json {
+    let mut object = BTreeMap::new();
+    for (name, caller_node_id, count) in logic.service_usage() {
+        if let Json::Object(ref mut callers) = *object.entry(name)
+                                                      .or_insert(Json::Object(BTreeMap::new())) {
+            callers.insert(caller_node_id.to_string(), Json::U64(count as u64));
+        }
+    }
+    Json::Object(object)
+}
+
+fn build_reconnect_state(state: &ReconnectState) -> Json {
+    let mut object = BTreeMap::new();
+    object.insert("peer".to_string(), Json::String(state.peer_node_id.to_string()));
+    object.insert("address".to_string(), Json::String(state.address.to_string()));
+    object.insert("reconnecting".to_string(), Json::Boolean(state.reconnecting));
+    object.insert("attempts".to_string(), Json::U64(state.attempts as u64));
+    object.insert("reconnects".to_string(), Json::U64(state.reconnects as u64));
+    object.insert("backoff_ms".to_string(),
+                  Json::I64(state.backoff.num_milliseconds()));
+    object.insert("last_error".to_string(),
+                  state.last_error
+                       .as_ref()
+                       .map(|last_error| Json::String(last_error.to_string()))
+                       .unwrap_or(Json::Null));
+    Json::Object(object)
+}
+
+fn build_peer_info(peer: &PeerInfo) -> Json {
+    let mut object = BTreeMap::new();
+    object.insert("id".to_string(), Json::String(peer.node_id.to_string()));
+    object.insert("address".to_string(),
+                  Json::String(peer.public_address.to_string()));
+    object.insert("direction".to_string(),
+                  Json::String(format!("{:?}", peer.direction).to_lowercase()));
+    object.insert("connected_since".to_string(),
+                  Json::String(format!("{}", peer.connected_since.rfc3339())));
+    object.insert("services".to_string(), Json::U64(peer.services as u64));
+    Json::Object(object)
+}
+
+fn build_connection_stats(stats: &ConnectionStats) -> Json {
+    let mut object = BTreeMap::new();
+    object.insert("bytes_sent".to_string(), Json::U64(stats.bytes_sent as u64));
+    object.insert("bytes_received".to_string(),
+                  Json::U64(stats.bytes_received as u64));
+    object.insert("frames_sent".to_string(), Json::U64(stats.frames_sent as u64));
+    object.insert("frames_received".to_string(),
+                  Json::U64(stats.frames_received as u64));
+    object.insert("last_error".to_string(),
+                  stats.last_error
+                       .as_ref()
+                       .map(|last_error| Json::String(last_error.to_string()))
+                       .unwrap_or(Json::Null));
+    let clock = stats.clock.to_timespec();
+    object.insert("clock_ms".to_string(),
+                  Json::I64(clock.sec * 1000 + clock.nsec as i64 / 1000000));
+    object.insert("uptime_ms".to_string(),
+                  Json::I64(stats.uptime.num_milliseconds()));
+    Json::Object(object)
+}
+
+fn build_threads() -> Json {
+    let now = time::now_utc();
+    Json::Array(supervisor()
+                    .tasks()
+                    .into_iter()
+                    .map(|task| {
+                        let mut object = BTreeMap::new();
+                        object.insert("name".to_string(), Json::String(task.name));
+                        object.insert("state".to_string(),
+                                      Json::String(format!("{:?}", task.state).to_lowercase()));
+                        object.insert("restarts".to_string(), Json::U64(task.restarts as u64));
+                        object.insert("uptime_ms".to_string(),
+                                      Json::I64((now - task.started_at).num_milliseconds()));
+                        Json::Object(object)
+                    })
+                    .collect())
+}
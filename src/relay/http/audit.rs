@@ -0,0 +1,225 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::cmp;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use rand;
+use time;
+
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+
+/// Records the heads and optionally the first bytes of the bodies of a sampled fraction of the
+/// relayed requests and their responses.
+pub struct Audit {
+    writer: Mutex<Box<io::Write + Send>>,
+    sample_rate: f64,
+    body_limit: usize,
+}
+
+/// A single sampled exchange. It's written to the audit file as soon as the last reference to it
+/// is gone, which is after both the request and the response have been read.
+pub struct Sample {
+    audit: Arc<Audit>,
+    service: String,
+    started_at: time::Tm,
+    request: Mutex<Buffer>,
+    response: Mutex<Buffer>,
+}
+
+/// Passes the bytes of the parent reader through and records them in a sample.
+pub struct Tee<R> {
+    parent: R,
+    sample: Arc<Sample>,
+    response: bool,
+}
+
+struct Buffer {
+    bytes: Vec<u8>,
+    head_size: Option<usize>,
+    body_limit: usize,
+}
+
+impl Audit {
+    pub fn create<P: AsRef<Path>>(path: P,
+                                  sample_rate: f64,
+                                  body_limit: usize)
+                                  -> io::Result<Audit> {
+        let file = try!(fs::OpenOptions::new().create(true).append(true).open(path));
+        Ok(Self::new(Box::new(file), sample_rate, body_limit))
+    }
+
+    pub fn new(writer: Box<io::Write + Send>, sample_rate: f64, body_limit: usize) -> Audit {
+        Audit {
+            writer: Mutex::new(writer),
+            sample_rate: sample_rate,
+            body_limit: body_limit,
+        }
+    }
+
+    /// Returns a sample for the request to the given service, if it has been picked.
+    pub fn sample(audit: &Arc<Audit>, service: &str) -> Option<Arc<Sample>> {
+        if audit.sample_rate <= 0.0 || rand::random::<f64>() >= audit.sample_rate {
+            return None;
+        }
+        Some(Arc::new(Sample {
+            audit: audit.clone(),
+            service: service.to_string(),
+            started_at: time::now_utc(),
+            request: Mutex::new(Buffer::new(audit.body_limit)),
+            response: Mutex::new(Buffer::new(audit.body_limit)),
+        }))
+    }
+
+    fn write(&self, sample: &Sample) -> io::Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        try!(write!(writer,
+                    "=== {} {}\r\n",
+                    sample.started_at.rfc3339(),
+                    sample.service));
+        try!(writer.write_all(&sample.request.lock().unwrap().bytes));
+        try!(writer.write_all(b"\r\n--- response\r\n"));
+        try!(writer.write_all(&sample.response.lock().unwrap().bytes));
+        try!(writer.write_all(b"\r\n\r\n"));
+        writer.flush()
+    }
+}
+
+impl Sample {
+    pub fn tee_request<R: io::Read>(sample: &Arc<Sample>, parent: R) -> Tee<R> {
+        Tee {
+            parent: parent,
+            sample: sample.clone(),
+            response: false,
+        }
+    }
+
+    pub fn tee_response<R: io::Read>(sample: &Arc<Sample>, parent: R) -> Tee<R> {
+        Tee {
+            parent: parent,
+            sample: sample.clone(),
+            response: true,
+        }
+    }
+
+    /// Records a response that has been generated by the relay itself.
+    pub fn record_response(&self, bytes: &[u8]) {
+        self.response.lock().unwrap().push(bytes);
+    }
+}
+
+impl Drop for Sample {
+    fn drop(&mut self) {
+        if let Err(error) = self.audit.write(self) {
+            error!("could not write audit sample: {:?}", error);
+        }
+    }
+}
+
+impl<R: io::Read> io::Read for Tee<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let size = try!(self.parent.read(buffer));
+        if self.response {
+            self.sample.response.lock().unwrap().push(&buffer[..size]);
+        } else {
+            self.sample.request.lock().unwrap().push(&buffer[..size]);
+        }
+        Ok(size)
+    }
+}
+
+impl Buffer {
+    fn new(body_limit: usize) -> Buffer {
+        Buffer {
+            bytes: Vec::new(),
+            head_size: None,
+            body_limit: body_limit,
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        let limit = match self.head_size {
+            Some(head_size) => head_size + self.body_limit,
+            None => MAX_HEAD_SIZE + self.body_limit,
+        };
+        let size = cmp::min(bytes.len(), limit.saturating_sub(self.bytes.len()));
+        self.bytes.extend_from_slice(&bytes[..size]);
+
+        if self.head_size.is_none() {
+            if let Some(position) = self.bytes.windows(4).position(|window| window == b"\r\n\r\n") {
+                self.head_size = Some(position + 4);
+                self.bytes.truncate(position + 4 + self.body_limit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{self, Read, Write};
+    use std::sync::{Arc, Mutex};
+    use super::{Audit, Sample};
+
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Shared {
+        fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buffer)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn record_heads_and_limited_bodies() {
+        let bytes = Arc::new(Mutex::new(Vec::new()));
+        let audit = Arc::new(Audit::new(Box::new(Shared(bytes.clone())), 1.0, 4));
+
+        {
+            let sample = Audit::sample(&audit, "echo").unwrap();
+            let mut request = Sample::tee_request(&sample,
+                                                  io::Cursor::new(b"POST / HTTP/1.1\r\n\r\nrequest"
+                                                                      .to_vec()));
+            let mut response = Sample::tee_response(&sample,
+                                                    io::Cursor::new(b"HTTP/1.1 200 OK\r\n\r\nok"
+                                                                        .to_vec()));
+            drop(sample);
+
+            assert!(io::copy(&mut request, &mut io::sink()).is_ok());
+            let mut text = String::new();
+            assert!(response.read_to_string(&mut text).is_ok());
+            assert_eq!("HTTP/1.1 200 OK\r\n\r\nok", text);
+            assert!(bytes.lock().unwrap().is_empty());
+        }
+
+        let text = String::from_utf8(bytes.lock().unwrap().clone()).unwrap();
+        assert!(text.starts_with("=== "));
+        assert!(text.ends_with(" echo\r\nPOST / HTTP/1.1\r\n\r\nrequ\r\n--- response\r\nHTTP/1.1 \
+                                200 OK\r\n\r\nok\r\n\r\n"));
+    }
+
+    #[test]
+    fn skip_without_sample_rate() {
+        let audit = Arc::new(Audit::new(Box::new(io::sink()), 0.0, 0));
+        assert!(Audit::sample(&audit, "echo").is_none());
+    }
+
+}
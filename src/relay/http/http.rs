@@ -13,34 +13,116 @@
 // limitations under the License.
 //
 
+use std::fmt;
 use std::io::{self, Write};
 use std::net::{self, SocketAddr};
-use std::sync::Arc;
+use std::result;
+use std::sync::{Arc, Mutex};
 
 use time::Duration;
 
-use node::{Node, request, service};
+use logger::trace;
+use message::HttpResponse;
+use message::http::{self, split_header_line};
+use node::{self, Node, service};
 use util::net::TcpServer;
 use util::reader;
 use util::time::to_std_duration;
 use super::api::Api;
+use super::audit::{Audit, Sample};
 use super::logic::Logic;
 use super::super::{Relay, Result};
 
 pub struct Http {
     logic: Arc<Logic>,
 
-    #[allow(dead_code)]
-    server: Option<TcpServer>,
-
-    #[allow(dead_code)]
-    api: Option<Api>,
+    server: Mutex<Option<TcpServer>>,
+    api: Mutex<Option<Api>>,
 }
 
 enum StatusCode {
+    BadRequest,
     InternalServerError,
     BadGateway,
     ServiceUnavailable,
+    Rejected(u16),
+}
+
+/// The parsed head of a relayed request, as seen by a filter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Head {
+    pub method: String,
+    pub path: String,
+    pub version: String,
+    pub service: String,
+    pub headers: Vec<(String, String)>,
+}
+
+/// A filter can modify the head of a request before it is relayed or reject it with a status
+/// code and a message.
+pub type Filter = Fn(&mut Head) -> result::Result<(), (u16, String)> + Send + Sync;
+
+impl Head {
+    pub fn parse(text: &str) -> Option<Head> {
+        let mut lines = text.lines();
+        let request_line = match lines.next() {
+            Some(line) => line,
+            None => return None,
+        };
+        let parts = request_line.split_whitespace().collect::<Vec<_>>();
+        if parts.len() != 3 {
+            return None;
+        }
+
+        let mut headers = Vec::new();
+        for line in lines {
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = split_header_line(line) {
+                headers.push((name.to_string(), value.to_string()));
+            }
+        }
+
+        Some(Head {
+            method: parts[0].to_string(),
+            path: parts[1].to_string(),
+            version: parts[2].to_string(),
+            service: String::new(),
+            headers: headers,
+        })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|&&(ref key, _)| key.to_lowercase() == name.to_lowercase())
+            .map(|&(_, ref value)| value.as_ref())
+    }
+
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        if let Some(entry) = self.headers
+                                 .iter_mut()
+                                 .find(|entry| entry.0.to_lowercase() == name.to_lowercase()) {
+            entry.1 = value.to_string();
+            return;
+        }
+        self.headers.push((name.to_string(), value.to_string()));
+    }
+
+    pub fn remove_header(&mut self, name: &str) {
+        self.headers.retain(|&(ref key, _)| key.to_lowercase() != name.to_lowercase());
+    }
+}
+
+impl fmt::Display for Head {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "{} {} {}\r\n", self.method, self.path, self.version));
+        for &(ref name, ref value) in &self.headers {
+            try!(write!(f, "{}: {}\r\n", name, value));
+        }
+        write!(f, "\r\n")
+    }
 }
 
 impl Http {
@@ -48,16 +130,23 @@ impl Http {
                 address: Option<SocketAddr>,
                 api_address: Option<SocketAddr>,
                 header_field: &str,
+                key_header_field: Option<&str>,
+                trace_header_field: Option<&str>,
                 read_timeout: Option<Duration>,
                 write_timeout: Option<Duration>,
-                services_path: Option<String>)
+                services_path: Option<String>,
+                filter: Option<Arc<Filter>>,
+                audit: Option<Audit>)
                 -> Result<Self> {
 
-        let logic = Arc::new(Logic::new(node.clone(), services_path));
+        let logic = Arc::new(Logic::new(node.clone(), services_path, trace_header_field));
+        let audit = audit.map(Arc::new);
 
         let server = if let Some(address) = address {
             let node = node.clone();
             let header_field = header_field.to_string();
+            let key_header_field = key_header_field.map(|value| value.to_lowercase());
+            let trace_header_field = trace_header_field.map(|value| value.to_lowercase());
             Some(try!(TcpServer::bind(address, move |mut stream| {
                 stream.set_read_timeout(read_timeout.map(|value| to_std_duration(value))).unwrap();
                 stream.set_write_timeout(write_timeout.map(|value| to_std_duration(value)))
@@ -65,10 +154,23 @@ impl Http {
 
                 let node = node.clone();
                 let header_field = header_field.clone();
+                let key_header_field = key_header_field.clone();
+                let trace_header_field = trace_header_field.clone();
+                let filter = filter.clone();
+                let audit = audit.clone();
                 Box::new(move || {
-                    if let Err(error) = handle_connection(&mut stream, &node, &header_field) {
+                    if let Err(error) = handle_connection(&mut stream,
+                                                          &node,
+                                                          &header_field,
+                                                          key_header_field.as_ref()
+                                                                          .map(String::as_str),
+                                                          trace_header_field.as_ref()
+                                                                            .map(String::as_str),
+                                                          filter.as_ref(),
+                                                          audit.as_ref()) {
                         error!("http error: {:?}", error);
                     }
+                    trace::set_trace_id(None);
                 })
             })))
         } else {
@@ -83,8 +185,8 @@ impl Http {
 
         Ok(Http {
             logic: logic,
-            server: server,
-            api: api,
+            server: Mutex::new(server),
+            api: Mutex::new(api),
         })
     }
 
@@ -98,6 +200,13 @@ impl Relay for Http {
         try!(self.logic.load_services());
         Ok(())
     }
+
+    fn shutdown(&self) {
+        // dropping the server joins the connection threads, so pending requests are answered
+        // before the api goes away.
+        self.server.lock().unwrap().take();
+        self.api.lock().unwrap().take();
+    }
 }
 
 impl From<io::Error> for service::Error {
@@ -111,58 +220,161 @@ impl From<io::Error> for service::Error {
 
 fn handle_connection(stream: &mut net::TcpStream,
                      node: &Arc<Node>,
-                     header_field: &str)
+                     header_field: &str,
+                     key_header_field: Option<&str>,
+                     trace_header_field: Option<&str>,
+                     filter: Option<&Arc<Filter>>,
+                     audit: Option<&Arc<Audit>>)
                      -> io::Result<()> {
 
     let header_field = header_field.to_lowercase();
     let mut http_reader = reader::Http::new(stream.try_clone().unwrap());
     let mut service_name = String::new();
+    let mut key = None;
+    let mut trace_id = None;
     try!(http_reader.read_header(|name, value| {
         if name == header_field {
             service_name = value.to_string();
         }
+        if Some(name) == key_header_field {
+            key = Some(value.as_bytes().to_vec());
+        }
+        if Some(name) == trace_header_field {
+            trace_id = Some(value.to_string());
+        }
     }));
+    trace::set_trace_id(trace_id);
+
+    if let Some(filter) = filter {
+        let mut head = match http_reader.header().and_then(Head::parse) {
+            Some(head) => head,
+            None => {
+                let response = build_text_response(StatusCode::BadRequest, "invalid request head");
+                try!(stream.write_all(&response));
+                return Ok(());
+            }
+        };
+        head.service = service_name.clone();
+
+        if let Err((status_code, message)) = filter(&mut head) {
+            let response = build_text_response(StatusCode::Rejected(status_code), &message);
+            try!(stream.write_all(&response));
+            return Ok(());
+        }
+
+        service_name = head.service.clone();
+        http_reader.set_header(head.to_string());
+    }
+
+    let sample = audit.and_then(|audit| Audit::sample(audit, &service_name));
 
     let mut stream_clone = stream.try_clone().unwrap();
-    let response_handler = move |mut reader| {
+    let sample_clone = sample.clone();
+    let response_handler = move |reader: Box<node::response::Reader>| {
+        let mut reader: Box<io::Read> = match sample_clone {
+            Some(ref sample) => Box::new(Sample::tee_response(sample, reader)),
+            None => reader,
+        };
         if let Err(e) = io::copy(&mut reader, &mut stream_clone) {
             error!("response error: {:?}", e);
         }
     };
 
+    let request: Box<node::request::Reader> = match sample {
+        Some(ref sample) => Box::new(Sample::tee_request(sample, http_reader)),
+        None => Box::new(http_reader),
+    };
     let result = node.request(&service_name,
-                              Box::new(http_reader),
+                              key.as_ref().map(Vec::as_slice),
+                              request,
                               Box::new(response_handler));
 
     let response = match result {
         Ok(_) => Vec::new(),
-        Err(request::Error::NoService) => {
+        Err(node::Error::NotFound(_)) => {
             build_text_response(StatusCode::BadGateway,
                                 &format!("service [{}] not found", service_name))
         }
-        Err(request::Error::Service(service::Error::Unavailable)) => {
+        Err(node::Error::InvalidInput(ref message)) => {
+            build_text_response(StatusCode::BadRequest, message)
+        }
+        Err(node::Error::Unavailable(_)) => {
             build_text_response(StatusCode::ServiceUnavailable,
                                 &format!("service [{}] is unavailable", service_name))
         }
         Err(error) => {
-            build_text_response(StatusCode::InternalServerError,
-                                &format!("error [{:?}]", error))
+            build_text_response(StatusCode::InternalServerError, &format!("error [{}]", error))
         }
     };
+    if let Some(ref sample) = sample {
+        sample.record_response(&response);
+    }
     try!(stream.write_all(&response));
     Ok(())
 }
 
 fn build_text_response(status_code: StatusCode, message: &str) -> Vec<u8> {
-    match status_code {
-        StatusCode::InternalServerError => {
-            format!("HTTP/1.1 500 Internal Server Error\r\n\r\n{}", message).into_bytes()
-        }
-        StatusCode::BadGateway => {
-            format!("HTTP/1.1 502 Bad Gateway\r\n\r\n{}", message).into_bytes()
-        }
-        StatusCode::ServiceUnavailable => {
-            format!("HTTP/1.1 503 Service Unavailable\r\n\r\n{}", message).into_bytes()
-        }
+    let code = match status_code {
+        StatusCode::BadRequest => 400,
+        StatusCode::InternalServerError => 500,
+        StatusCode::BadGateway => 502,
+        StatusCode::ServiceUnavailable => 503,
+        StatusCode::Rejected(code) => code,
+    };
+    let mut response = HttpResponse::new();
+    response.set_status_code(code as u32);
+    response.set_reason(reason_phrase(code).to_string());
+    response.set_body(message.as_bytes().to_vec());
+    http::response_to_bytes(&response)
+}
+
+fn reason_phrase(code: u16) -> &'static str {
+    match code {
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "Rejected",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Head, StatusCode, build_text_response};
+
+    #[test]
+    fn parse_and_rewrite_head() {
+        let mut head = Head::parse("GET /test HTTP/1.1\r\nX-Delix-Service: echo\r\nHost: \
+                                    localhost\r\n\r\n")
+                           .unwrap();
+        assert_eq!("GET", head.method);
+        assert_eq!("/test", head.path);
+        assert_eq!(Some("echo"), head.header("x-delix-service"));
+
+        head.set_header("host", "example.com");
+        head.set_header("X-Tenant", "one");
+        head.remove_header("x-delix-service");
+        assert_eq!("GET /test HTTP/1.1\r\nHost: example.com\r\nX-Tenant: one\r\n\r\n",
+                   head.to_string());
+    }
+
+    #[test]
+    fn build_rejected_response() {
+        assert_eq!("HTTP/1.1 429 Too Many Requests\r\nContent-Length: 4\r\n\r\nslow",
+                   String::from_utf8(build_text_response(StatusCode::Rejected(429), "slow"))
+                       .unwrap());
     }
+
+    #[test]
+    fn parse_invalid_head() {
+        assert_eq!(None, Head::parse("GET\r\n\r\n"));
+        assert_eq!(None, Head::parse(""));
+    }
+
 }
@@ -14,6 +14,7 @@
 
 extern crate rustc_serialize;
 
+use std::collections::HashMap;
 use std::fs;
 use std::io::{self, Read, Write};
 use std::net;
@@ -21,13 +22,20 @@ use std::path::Path;
 use std::sync::Arc;
 
 use rustc_serialize::json;
+use time;
 
-use node::Node;
+use logger::collector::Record;
+use metric::{Naming, Value};
+use logger::trace;
+use node::{self, ID, Node, State};
+use transport::{ConnectionStats, PeerInfo, ReconnectState};
 use util::reader;
+use super::http::Head;
 
 pub struct Logic {
     node: Arc<Node>,
     services_path: Option<String>,
+    trace_header_field: Option<String>,
 }
 
 #[derive(Debug, RustcDecodable, RustcEncodable)]
@@ -36,10 +44,14 @@ pub struct Service {
 }
 
 impl Logic {
-    pub fn new(node: Arc<Node>, services_path: Option<String>) -> Self {
+    pub fn new(node: Arc<Node>,
+               services_path: Option<String>,
+               trace_header_field: Option<&str>)
+               -> Self {
         Logic {
             node: node,
             services_path: services_path,
+            trace_header_field: trace_header_field.map(|value| value.to_string()),
         }
     }
 
@@ -62,18 +74,76 @@ impl Logic {
         Ok(())
     }
 
+    pub fn state_history(&self) -> Vec<(State, time::Tm)> {
+        self.node.state_history()
+    }
+
+    pub fn metric_snapshot(&self) -> Option<(Naming, HashMap<String, Value>)> {
+        self.node.metric_snapshot()
+    }
+
+    pub fn connection_stats(&self,
+                            peer_node_id: ID)
+                            -> node::Result<(ConnectionStats, ConnectionStats)> {
+        self.node.connection_stats(peer_node_id)
+    }
+
+    pub fn service_usage(&self) -> Vec<(String, ID, usize)> {
+        self.node.service_usage()
+    }
+
+    pub fn reconnect_states(&self) -> Vec<ReconnectState> {
+        self.node.reconnect_states()
+    }
+
+    pub fn peers(&self) -> Vec<PeerInfo> {
+        self.node.peers()
+    }
+
+    pub fn trace_records(&self, trace_id: &str) -> Option<Vec<Record>> {
+        self.node.trace_records(trace_id)
+    }
+
+    pub fn acquire_drain_permit(&self) -> node::Result<bool> {
+        self.node.acquire_drain_permit()
+    }
+
+    pub fn release_drain_permit(&self) -> node::Result<()> {
+        self.node.release_drain_permit()
+    }
+
     pub fn add_service(&self, name: &str, address: &str) {
         let name_clone = name.to_string();
         let address_clone = address.to_string();
+        let trace_header_field = self.trace_header_field.clone();
         self.node
             .register(name,
-                      Box::new(move |mut request| {
-                          let mut stream = try!(net::TcpStream::connect(&*address_clone));
-
-                          try!(io::copy(&mut request, &mut stream));
+                      Box::new(move |mut request: Box<node::request::Reader>| {
+                          let outer_trace_id = trace::trace_id();
+                          if let Some(ref trace_header_field) = trace_header_field {
+                              let mut http_request = reader::Http::new(request);
+                              let field = trace_header_field.to_lowercase();
+                              let mut trace_id = outer_trace_id.clone();
+                              try!(http_request.read_header(|name, value| {
+                                  if name == field && trace_id.is_none() {
+                                      trace_id = Some(value.to_string());
+                                  }
+                              }));
+                              // the service gets the trace id of the request in the header.
+                              let head = http_request.header().and_then(Head::parse);
+                              if let (Some(mut head), Some(value)) = (head, trace_id.clone()) {
+                                  head.set_header(trace_header_field, &value);
+                                  http_request.set_header(head.to_string());
+                              }
+                              trace::set_trace_id(trace_id);
+                              request = Box::new(http_request);
+                          }
+
+                          let result = forward(&mut request, &address_clone);
                           debug!("handled request to {}", name_clone);
+                          trace::set_trace_id(outer_trace_id);
 
-                          Ok(Box::new(reader::Http::new(stream)))
+                          Ok(Box::new(reader::Http::new(try!(result))))
                       }))
             .unwrap();
 
@@ -98,3 +168,9 @@ impl Logic {
         }
     }
 }
+
+fn forward<R: Read>(request: &mut R, address: &str) -> io::Result<net::TcpStream> {
+    let mut stream = try!(net::TcpStream::connect(address));
+    try!(io::copy(request, &mut stream));
+    Ok(stream)
+}
@@ -13,7 +13,13 @@
 // limitations under the License.
 
 mod api;
+mod audit;
 mod http;
 mod logic;
+#[cfg(feature = "scripting")]
+mod script;
 
-pub use self::http::Http;
+pub use self::audit::Audit;
+pub use self::http::{Filter, Head, Http};
+#[cfg(feature = "scripting")]
+pub use self::script::{Error as ScriptError, Script};
@@ -0,0 +1,314 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::result;
+
+use super::http::Head;
+
+/// A list of rules that is applied to the head of every relayed request. Each line holds one
+/// rule of the form `[if <condition> {and <condition>} then] <action>`, e.g.
+///
+/// ```text
+/// if header "X-Tenant" == "beta" then route "echo-beta"
+/// if path prefix "/admin" and method != "GET" then reject 403 "forbidden"
+/// set "X-Relay" "delix"
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Script {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Rule {
+    conditions: Vec<(Subject, Operator, String)>,
+    action: Action,
+}
+
+#[derive(Debug, PartialEq)]
+enum Subject {
+    Method,
+    Path,
+    Service,
+    Header(String),
+}
+
+#[derive(Debug, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Prefix,
+    Contains,
+}
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Reject(u16, String),
+    Route(String),
+    Set(String, String),
+    Remove(String),
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+}
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Syntax(usize, String),
+}
+
+impl Script {
+    pub fn parse(source: &str) -> Result<Script> {
+        let mut rules = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let tokens = try!(tokenize(line).map_err(|message| Error::Syntax(index + 1, message)));
+            if tokens.is_empty() {
+                continue;
+            }
+            rules.push(try!(parse_rule(&tokens).map_err(|message| {
+                Error::Syntax(index + 1, message)
+            })));
+        }
+        Ok(Script { rules: rules })
+    }
+
+    /// Applies all matching rules in order. The first matching `reject` stops the evaluation.
+    pub fn apply(&self, head: &mut Head) -> result::Result<(), (u16, String)> {
+        for rule in &self.rules {
+            if !rule.conditions.iter().all(|condition| matches(head, condition)) {
+                continue;
+            }
+            match rule.action {
+                Action::Reject(code, ref message) => return Err((code, message.clone())),
+                Action::Route(ref service) => head.service = service.clone(),
+                Action::Set(ref name, ref value) => head.set_header(name, value),
+                Action::Remove(ref name) => head.remove_header(name),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Syntax(line, ref message) => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+fn matches(head: &Head, condition: &(Subject, Operator, String)) -> bool {
+    let (ref subject, ref operator, ref expected) = *condition;
+    let expected = expected.as_str();
+    let value = match *subject {
+        Subject::Method => head.method.as_str(),
+        Subject::Path => head.path.as_str(),
+        Subject::Service => head.service.as_str(),
+        Subject::Header(ref name) => head.header(name).unwrap_or(""),
+    };
+    match *operator {
+        Operator::Equal => value == expected,
+        Operator::NotEqual => value != expected,
+        Operator::Prefix => value.starts_with(expected),
+        Operator::Contains => value.contains(expected),
+    }
+}
+
+fn tokenize(line: &str) -> result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '#' {
+            break;
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => {
+                        match chars.next() {
+                            Some(c) => text.push(c),
+                            None => return Err("unterminated string".to_string()),
+                        }
+                    }
+                    Some(c) => text.push(c),
+                    None => return Err("unterminated string".to_string()),
+                }
+            }
+            tokens.push(Token::Text(text));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' || c == '#' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_rule(tokens: &[Token]) -> result::Result<Rule, String> {
+    let mut tokens = tokens.iter();
+    let mut conditions = Vec::new();
+
+    let mut word = try!(next_word(&mut tokens));
+    if word == "if" {
+        loop {
+            conditions.push(try!(parse_condition(&mut tokens)));
+            match try!(next_word(&mut tokens)).as_ref() {
+                "and" => {}
+                "then" => break,
+                other => return Err(format!("expected 'and' or 'then', got '{}'", other)),
+            }
+        }
+        word = try!(next_word(&mut tokens));
+    }
+
+    let action = match word.as_ref() {
+        "reject" => {
+            let code = try!(next_word(&mut tokens));
+            let status = match code.parse::<u16>() {
+                Ok(status) if status >= 400 && status < 600 => status,
+                _ => return Err(format!("invalid status code '{}'", code)),
+            };
+            Action::Reject(status, try!(next_text(&mut tokens)))
+        }
+        "route" => Action::Route(try!(next_text(&mut tokens))),
+        "set" => {
+            let name = try!(next_text(&mut tokens));
+            Action::Set(name, try!(next_text(&mut tokens)))
+        }
+        "remove" => Action::Remove(try!(next_text(&mut tokens))),
+        other => return Err(format!("unknown action '{}'", other)),
+    };
+
+    if tokens.next().is_some() {
+        return Err("unexpected input after action".to_string());
+    }
+
+    Ok(Rule {
+        conditions: conditions,
+        action: action,
+    })
+}
+
+fn parse_condition<'a, I>(tokens: &mut I) -> result::Result<(Subject, Operator, String), String>
+    where I: Iterator<Item = &'a Token>
+{
+    let subject = match try!(next_word(tokens)).as_ref() {
+        "method" => Subject::Method,
+        "path" => Subject::Path,
+        "service" => Subject::Service,
+        "header" => Subject::Header(try!(next_text(tokens))),
+        other => return Err(format!("unknown subject '{}'", other)),
+    };
+    let operator = match try!(next_word(tokens)).as_ref() {
+        "==" => Operator::Equal,
+        "!=" => Operator::NotEqual,
+        "prefix" => Operator::Prefix,
+        "contains" => Operator::Contains,
+        other => return Err(format!("unknown operator '{}'", other)),
+    };
+    Ok((subject, operator, try!(next_text(tokens))))
+}
+
+fn next_word<'a, I>(tokens: &mut I) -> result::Result<String, String>
+    where I: Iterator<Item = &'a Token>
+{
+    match tokens.next() {
+        Some(&Token::Word(ref word)) => Ok(word.clone()),
+        Some(&Token::Text(ref text)) => Err(format!("expected keyword, got \"{}\"", text)),
+        None => Err("unexpected end of line".to_string()),
+    }
+}
+
+fn next_text<'a, I>(tokens: &mut I) -> result::Result<String, String>
+    where I: Iterator<Item = &'a Token>
+{
+    match tokens.next() {
+        Some(&Token::Text(ref text)) => Ok(text.clone()),
+        Some(&Token::Word(ref word)) => Err(format!("expected quoted string, got '{}'", word)),
+        None => Err("unexpected end of line".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Error, Script};
+    use super::super::http::Head;
+
+    fn build_head() -> Head {
+        let mut head = Head::parse("POST /admin/users HTTP/1.1\r\nX-Tenant: beta\r\n\r\n").unwrap();
+        head.service = "echo".to_string();
+        head
+    }
+
+    #[test]
+    fn apply_rules() {
+        let script = Script::parse("# tenant routing\n\
+                                    if header \"x-tenant\" == \"beta\" then route \"echo-beta\"\n\
+                                    \n\
+                                    set \"X-Relay\" \"delix\" # mark relayed requests\n\
+                                    remove \"X-Tenant\"")
+                         .unwrap();
+
+        let mut head = build_head();
+        assert_eq!(Ok(()), script.apply(&mut head));
+        assert_eq!("echo-beta", head.service);
+        assert_eq!(Some("delix"), head.header("x-relay"));
+        assert_eq!(None, head.header("x-tenant"));
+    }
+
+    #[test]
+    fn apply_reject() {
+        let script = Script::parse("if path prefix \"/admin\" and method != \"GET\" then reject \
+                                    403 \"read only\"\n\
+                                    route \"other\"")
+                         .unwrap();
+
+        let mut head = build_head();
+        assert_eq!(Err((403, "read only".to_string())), script.apply(&mut head));
+        assert_eq!("echo", head.service);
+
+        head.method = "GET".to_string();
+        assert_eq!(Ok(()), script.apply(&mut head));
+        assert_eq!("other", head.service);
+    }
+
+    #[test]
+    fn parse_invalid_script() {
+        assert_eq!(Err(Error::Syntax(2, "unknown action 'drop'".to_string())),
+                   Script::parse("route \"echo\"\ndrop"));
+        assert_eq!(Err(Error::Syntax(1, "unterminated string".to_string())),
+                   Script::parse("route \"echo"));
+        assert_eq!(Err(Error::Syntax(1, "invalid status code '200'".to_string())),
+                   Script::parse("reject 200 \"ok\""));
+        assert_eq!(Err(Error::Syntax(1, "expected 'and' or 'then', got 'or'".to_string())),
+                   Script::parse("if method == \"GET\" or path == \"/\" then route \"echo\""));
+    }
+
+}
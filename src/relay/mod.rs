@@ -18,4 +18,6 @@ mod relay;
 mod http;
 
 pub use relay::relay::{Error, Relay, Result};
-pub use relay::http::Http;
+pub use relay::http::{Audit, Filter, Head, Http};
+#[cfg(feature = "scripting")]
+pub use relay::http::{Script, ScriptError};
@@ -16,8 +16,11 @@
 use std::io;
 use std::result;
 
-pub trait Relay {
+pub trait Relay : Send + Sync {
     fn load(&self) -> Result<()>;
+
+    /// Stops accepting new requests and waits for the in-flight ones to finish.
+    fn shutdown(&self) {}
 }
 
 pub type Result<T> = result::Result<T, Error>;
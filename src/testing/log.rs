@@ -13,12 +13,12 @@
 // limitations under the License.
 //
 
-extern crate log;
-
 use std::sync::{self, Arc};
 
-use delix::logger;
-use delix::metric;
+use log;
+
+use logger;
+use metric;
 
 static START: sync::Once = sync::ONCE_INIT;
 
@@ -13,21 +13,20 @@
 // limitations under the License.
 //
 
-extern crate delix;
+mod assert;
+mod log;
+mod node;
+mod relay;
 
-#[allow(dead_code)] mod assert;
-#[allow(dead_code)] mod log;
-#[allow(dead_code)] mod node;
-#[allow(dead_code)] mod relay;
-
-pub use helper::assert::*;
-pub use helper::log::*;
-pub use helper::node::*;
-pub use helper::relay::*;
+pub use self::assert::{assert_contains_all, assert_response};
+pub use self::log::set_up;
+pub use self::node::{build_node, build_node_in_zone, build_routing_node, wait_for_discovering,
+                     wait_for_endpoints, wait_for_joined, wait_for_requests, wait_for_services};
+pub use self::relay::build_http_relay;
 
 use std::sync::mpsc;
 
-#[allow(dead_code)] pub fn recv_all<T>(rx: &mpsc::Receiver<T>) -> Vec<T> {
+pub fn recv_all<T>(rx: &mpsc::Receiver<T>) -> Vec<T> {
     let mut result = Vec::new();
     loop {
         result.push(match rx.try_recv() {
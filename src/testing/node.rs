@@ -13,24 +13,48 @@
 // limitations under the License.
 //
 
-extern crate time;
-
 use std::net::ToSocketAddrs;
 use std::sync::Arc;
 
-use self::time::Duration;
+use time::Duration;
 
-use delix::discovery::Constant;
-use delix::metric::{self, Query};
-use delix::node::Node;
-use delix::transport::Direct;
-use delix::transport::cipher;
-use delix::transport::direct::balancer;
+use discovery::Constant;
+use metric::{self, Query};
+use node::Node;
+use transport::Direct;
+use transport::cipher;
+use transport::direct::{self, balancer};
 
 pub fn build_node(local_address: &str,
                   discover_addresses: &[&str],
                   request_timeout: Option<i64>)
                   -> (Arc<Node>, Arc<metric::Memory>) {
+    build_node_in_zone(local_address, discover_addresses, request_timeout, None)
+}
+
+pub fn build_node_in_zone(local_address: &str,
+                          discover_addresses: &[&str],
+                          request_timeout: Option<i64>,
+                          zone: Option<&str>)
+                          -> (Arc<Node>, Arc<metric::Memory>) {
+    build(local_address, None, discover_addresses, request_timeout, zone, 0)
+}
+
+pub fn build_routing_node(local_address: &str,
+                          public_address: Option<&str>,
+                          discover_addresses: &[&str],
+                          max_hops: usize)
+                          -> (Arc<Node>, Arc<metric::Memory>) {
+    build(local_address, public_address, discover_addresses, None, None, max_hops)
+}
+
+fn build(local_address: &str,
+         public_address: Option<&str>,
+         discover_addresses: &[&str],
+         request_timeout: Option<i64>,
+         zone: Option<&str>,
+         max_hops: usize)
+         -> (Arc<Node>, Arc<metric::Memory>) {
 
     let cipher = Box::new(cipher::Symmetric::new(b"000102030405060708090a0b0c0d0e0f", None)
                               .unwrap());
@@ -47,14 +71,22 @@ pub fn build_node(local_address: &str,
                                                              .collect()));
 
     let metric = Arc::new(metric::Memory::new());
-    let transport = Box::new(Direct::new(cipher,
-                                         balancer_factory,
-                                         metric.clone(),
-                                         local_address.to_socket_addrs().unwrap().next().unwrap(),
-                                         None,
-                                         request_timeout.map(|value| {
-                                             Duration::milliseconds(value)
-                                         })));
+    let mut config = direct::Config::new(vec![local_address.to_socket_addrs()
+                                                            .unwrap()
+                                                            .next()
+                                                            .unwrap()]);
+    config.public_address = public_address.map(|address| {
+        address.to_socket_addrs().unwrap().next().unwrap()
+    });
+    config.request_timeout = request_timeout.map(|value| Duration::milliseconds(value));
+    config.aknowledge_timeout = Some(Duration::milliseconds(1000));
+    config.join_parallelism = 4;
+    config.join_dial_timeout = Some(Duration::milliseconds(1000));
+    config.coalesce_size = 0;
+    config.reconnect_max_backoff = Duration::milliseconds(0);
+    config.zone = zone.map(|zone| zone.to_string());
+    config.max_hops = max_hops;
+    let transport = Box::new(Direct::new(cipher, balancer_factory, metric.clone(), config));
 
     let node = Arc::new(Node::new(discovery, transport, metric.clone()).unwrap());
     node.join();
@@ -13,14 +13,13 @@
 // limitations under the License.
 //
 
-extern crate time;
-
 use std::net::ToSocketAddrs;
 use std::sync::Arc;
-use self::time::Duration;
 
-use delix::node::Node;
-use delix::relay::{self, Relay};
+use time::Duration;
+
+use node::Node;
+use relay::{self, Relay};
 
 pub fn build_http_relay(node: &Arc<Node>,
                         address: Option<&str>,
@@ -35,9 +34,13 @@ pub fn build_http_relay(node: &Arc<Node>,
                                       value.to_socket_addrs().unwrap().next().unwrap()
                                   }),
                                   "X-Delix-Service",
+                                  None,
+                                  None,
                                   Some(Duration::milliseconds(100)),
                                   Some(Duration::milliseconds(100)),
-                                  services_path.map(|value| value.to_string()))
+                                  services_path.map(|value| value.to_string()),
+                                  None,
+                                  None)
                     .unwrap();
 
     relay.load().unwrap();
@@ -0,0 +1,166 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use time::SteadyTime;
+
+use util::clock::{self, Clock};
+use util::{reader, writer};
+
+/// Records the decrypted traffic of all streams it's attached to. Each record holds the stream
+/// id, the direction, the milliseconds since the capture started and the plain bytes of one
+/// frame.
+pub struct Capture {
+    writer: Mutex<Box<io::Write + Send>>,
+    next_stream_id: AtomicUsize,
+    clock: Arc<Clock>,
+    started_at: SteadyTime,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    pub stream_id: u64,
+    pub direction: Direction,
+    pub offset_ms: u64,
+    pub bytes: Vec<u8>,
+}
+
+impl Capture {
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Capture> {
+        let file = try!(fs::File::create(path));
+        Ok(Self::with_clock(Box::new(io::BufWriter::new(file)), Arc::new(clock::System)))
+    }
+
+    pub fn with_clock(writer: Box<io::Write + Send>, clock: Arc<Clock>) -> Capture {
+        Capture {
+            writer: Mutex::new(writer),
+            next_stream_id: AtomicUsize::new(1),
+            started_at: clock.now(),
+            clock: clock,
+        }
+    }
+
+    pub fn next_stream_id(&self) -> u64 {
+        self.next_stream_id.fetch_add(1, Ordering::SeqCst) as u64
+    }
+
+    pub fn record(&self, stream_id: u64, direction: Direction, bytes: &[u8]) -> io::Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        let offset_ms = (self.clock.now() - self.started_at).num_milliseconds();
+        try!(writer::write_size(&mut *writer, stream_id as usize));
+        try!(writer.write_all(&[match direction {
+                                    Direction::Inbound => 0,
+                                    Direction::Outbound => 1,
+                                }]));
+        try!(writer::write_size(&mut *writer, offset_ms as usize));
+        try!(writer::write_size(&mut *writer, bytes.len()));
+        try!(writer.write_all(bytes));
+        writer.flush()
+    }
+}
+
+/// Reads the next record of a capture. Returns `None` at the end of the capture.
+pub fn read_record<R>(reader: &mut R) -> io::Result<Option<Record>>
+    where R: io::Read
+{
+    let stream_id = match reader::read_size(reader) {
+        Ok(stream_id) => stream_id as u64,
+        Err(ref error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    let mut direction = [0u8; 1];
+    try!(reader.read_exact(&mut direction));
+    let direction = match direction[0] {
+        0 => Direction::Inbound,
+        1 => Direction::Outbound,
+        value => {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                      format!("invalid direction {}", value)))
+        }
+    };
+    let offset_ms = try!(reader::read_size(reader)) as u64;
+    let size = try!(reader::read_size(reader));
+    let mut bytes = vec![0u8; size];
+    try!(reader.read_exact(&mut bytes));
+    Ok(Some(Record {
+        stream_id: stream_id,
+        direction: direction,
+        offset_ms: offset_ms,
+        bytes: bytes,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+    use time::Duration;
+    use super::{Capture, Direction, Record, read_record};
+    use util::clock::Mock;
+
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Shared {
+        fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buffer)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn record_and_read() {
+        let bytes = Arc::new(Mutex::new(Vec::new()));
+        let clock = Arc::new(Mock::new());
+        let capture = Capture::with_clock(Box::new(Shared(bytes.clone())), clock.clone());
+
+        let stream_id = capture.next_stream_id();
+        assert!(capture.record(stream_id, Direction::Outbound, b"ping").is_ok());
+        clock.advance(Duration::milliseconds(20));
+        assert!(capture.record(stream_id, Direction::Inbound, b"pong").is_ok());
+
+        let mut reader = io::Cursor::new(bytes.lock().unwrap().clone());
+        assert_eq!(Some(Record {
+                       stream_id: 1,
+                       direction: Direction::Outbound,
+                       offset_ms: 0,
+                       bytes: b"ping".to_vec(),
+                   }),
+                   read_record(&mut reader).unwrap());
+        assert_eq!(Some(Record {
+                       stream_id: 1,
+                       direction: Direction::Inbound,
+                       offset_ms: 20,
+                       bytes: b"pong".to_vec(),
+                   }),
+                   read_record(&mut reader).unwrap());
+        assert_eq!(None, read_record(&mut reader).unwrap());
+    }
+
+}
@@ -13,12 +13,57 @@
 // limitations under the License.
 //
 
+use std::io::{self, Read, Write};
+use std::iter;
 use std::result;
 
+use byteorder::{BigEndian, WriteBytesExt};
+use crypto::hmac::Hmac;
+use crypto::mac::Mac;
+use crypto::sha2::Sha256;
+
+use util::{reader, writer};
+
 pub trait Cipher : Send + Sync {
     fn box_clone(&self) -> Box<Cipher>;
-    fn encrypt(&self, &[u8]) -> Result<Vec<u8>>;
-    fn decrypt(&self, &[u8]) -> Result<Vec<u8>>;
+    fn encrypt(&self, &[u8], &Associated) -> Result<Vec<u8>>;
+    fn decrypt(&self, &[u8], &Associated) -> Result<Vec<u8>>;
+
+    fn handshake(&self, _: &mut Channel, _: Role) -> Result<Box<Cipher>> {
+        Ok(self.box_clone())
+    }
+}
+
+pub trait Channel : io::Read + io::Write {}
+
+impl<T> Channel for T where T: io::Read + io::Write {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Role {
+    Initiator,
+    Responder,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Associated {
+    pub kind: u32,
+    pub sequence: u64,
+}
+
+impl Associated {
+    pub fn new(kind: u32, sequence: u64) -> Associated {
+        Associated {
+            kind: kind,
+            sequence: sequence,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(12);
+        bytes.write_u32::<BigEndian>(self.kind).unwrap();
+        bytes.write_u64::<BigEndian>(self.sequence).unwrap();
+        bytes
+    }
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -29,4 +74,53 @@ pub enum Error {
     Write,
     Read,
     DecryptionFailed,
+    AssociatedDataMismatch,
+    HandshakeRequired,
+    HandshakeFailed,
+    UnknownPeer,
+    UnsupportedCipherType,
+    NoCommonCipherType,
+    MissingKey,
+    DuplicateKey(u32),
+    UnknownKey(u32),
+    NoncesExhausted,
+}
+
+pub fn write_message(channel: &mut Channel, message: &[u8]) -> Result<()> {
+    try!(writer::write_size(channel, message.len()).map_err(|_| Error::Write));
+    try!(channel.write_all(message).map_err(|_| Error::Write));
+    channel.flush().map_err(|_| Error::Write)
+}
+
+pub fn read_message(channel: &mut Channel, max_size: usize) -> Result<Vec<u8>> {
+    let size = try!(reader::read_size(channel).map_err(|_| Error::Read));
+    if size > max_size {
+        return Err(Error::HandshakeFailed);
+    }
+    let mut message = iter::repeat(0).take(size).collect::<Vec<u8>>();
+    try!(channel.read_exact(&mut message).map_err(|_| Error::Read));
+    Ok(message)
+}
+
+pub fn read_exact_message(channel: &mut Channel, size: usize) -> Result<Vec<u8>> {
+    let message = try!(read_message(channel, size));
+    if message.len() != size {
+        return Err(Error::HandshakeFailed);
+    }
+    Ok(message)
+}
+
+pub fn hkdf(chaining_key: &[u8], input_key: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let temporary_key = hmac(chaining_key, input_key);
+    let first = hmac(&temporary_key, &[1]);
+    let mut input = first.clone();
+    input.push(2);
+    let second = hmac(&temporary_key, &input);
+    (first, second)
+}
+
+pub fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::new(Sha256::new(), key);
+    mac.input(data);
+    mac.result().code().to_vec()
 }
@@ -14,9 +14,16 @@
 //
 
 pub mod cipher;
+pub mod noise;
+pub mod nonce;
+pub mod plain;
 pub mod stream;
 pub mod symmetric;
 
-pub use self::cipher::{Cipher, Error, Result};
+pub use self::cipher::{Associated, Channel, Cipher, Error, Result, Role, hkdf, hmac,
+                       read_exact_message, read_message, write_message};
+pub use self::noise::Noise;
+pub use self::nonce::{FixedNonceSource, NonceSource, RandomNonceSource};
+pub use self::plain::Plain;
 pub use self::stream::Stream;
-pub use self::symmetric::Symmetric;
+pub use self::symmetric::{Keyring, Symmetric};
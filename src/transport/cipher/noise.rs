@@ -0,0 +1,346 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+
+use std::collections::HashSet;
+use std::iter;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use crypto::aead::{AeadEncryptor, AeadDecryptor};
+use crypto::chacha20poly1305::ChaCha20Poly1305;
+use crypto::curve25519::{curve25519, curve25519_base};
+use crypto::digest::Digest;
+use crypto::sha2::Sha256;
+use rand::random;
+
+use transport::cipher::{Associated, Channel, Cipher, Error, Result, Role, hkdf,
+                        read_exact_message, write_message};
+
+const PROTOCOL_NAME: &'static [u8] = b"Noise_XX_25519_ChaChaPoly_SHA256";
+const PROLOGUE: &'static [u8] = b"delix";
+const KEY_SIZE: usize = 32;
+const TAG_SIZE: usize = 16;
+
+pub struct Noise {
+    private_key: Vec<u8>,
+    public_key: Vec<u8>,
+    peer_keys: HashSet<Vec<u8>>,
+}
+
+impl Noise {
+    pub fn new(private_key: &[u8], peer_keys: &[Vec<u8>]) -> Result<Noise> {
+        if private_key.len() != KEY_SIZE {
+            return Err(Error::InvalidKeyLength(private_key.len()));
+        }
+        if let Some(peer_key) = peer_keys.iter().find(|peer_key| peer_key.len() != KEY_SIZE) {
+            return Err(Error::InvalidKeyLength(peer_key.len()));
+        }
+
+        Ok(Noise {
+            private_key: private_key.to_vec(),
+            public_key: curve25519_base(private_key).to_vec(),
+            peer_keys: peer_keys.iter().cloned().collect(),
+        })
+    }
+
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    fn check_peer_key(&self, peer_key: &[u8]) -> Result<()> {
+        if self.peer_keys.contains(peer_key) {
+            Ok(())
+        } else {
+            Err(Error::UnknownPeer)
+        }
+    }
+}
+
+impl Cipher for Noise {
+    fn box_clone(&self) -> Box<Cipher> {
+        Box::new(Noise {
+            private_key: self.private_key.clone(),
+            public_key: self.public_key.clone(),
+            peer_keys: self.peer_keys.clone(),
+        })
+    }
+
+    fn encrypt(&self, _: &[u8], _: &Associated) -> Result<Vec<u8>> {
+        Err(Error::HandshakeRequired)
+    }
+
+    fn decrypt(&self, _: &[u8], _: &Associated) -> Result<Vec<u8>> {
+        Err(Error::HandshakeRequired)
+    }
+
+    fn handshake(&self, channel: &mut Channel, role: Role) -> Result<Box<Cipher>> {
+        let mut state = SymmetricState::new();
+        let ephemeral_key = random::<[u8; KEY_SIZE]>();
+        let ephemeral_public_key = curve25519_base(&ephemeral_key);
+
+        match role {
+            Role::Initiator => {
+                // -> e
+                let mut message = ephemeral_public_key.to_vec();
+                state.mix_hash(&ephemeral_public_key);
+                message.extend(state.encrypt_and_hash(&[]));
+                try!(write_message(channel, &message));
+
+                // <- e, ee, s, es
+                let message = try!(read_exact_message(channel, KEY_SIZE * 2 + TAG_SIZE * 2));
+                let (peer_ephemeral_public_key, rest) = message.split_at(KEY_SIZE);
+                let (encrypted_peer_key, payload) = rest.split_at(KEY_SIZE + TAG_SIZE);
+                state.mix_hash(peer_ephemeral_public_key);
+                state.mix_key(&curve25519(&ephemeral_key, peer_ephemeral_public_key));
+                let peer_key = try!(state.decrypt_and_hash(encrypted_peer_key));
+                state.mix_key(&curve25519(&ephemeral_key, &peer_key));
+                try!(state.decrypt_and_hash(payload));
+                try!(self.check_peer_key(&peer_key));
+
+                // -> s, se
+                let mut message = state.encrypt_and_hash(&self.public_key);
+                state.mix_key(&curve25519(&self.private_key, peer_ephemeral_public_key));
+                message.extend(state.encrypt_and_hash(&[]));
+                try!(write_message(channel, &message));
+            }
+            Role::Responder => {
+                // -> e
+                let peer_ephemeral_public_key = try!(read_exact_message(channel, KEY_SIZE));
+                state.mix_hash(&peer_ephemeral_public_key);
+                try!(state.decrypt_and_hash(&[]));
+
+                // <- e, ee, s, es
+                let mut message = ephemeral_public_key.to_vec();
+                state.mix_hash(&ephemeral_public_key);
+                state.mix_key(&curve25519(&ephemeral_key, &peer_ephemeral_public_key));
+                message.extend(state.encrypt_and_hash(&self.public_key));
+                state.mix_key(&curve25519(&self.private_key, &peer_ephemeral_public_key));
+                message.extend(state.encrypt_and_hash(&[]));
+                try!(write_message(channel, &message));
+
+                // -> s, se
+                let message = try!(read_exact_message(channel, KEY_SIZE + TAG_SIZE * 2));
+                let (encrypted_peer_key, payload) = message.split_at(KEY_SIZE + TAG_SIZE);
+                let peer_key = try!(state.decrypt_and_hash(encrypted_peer_key));
+                state.mix_key(&curve25519(&ephemeral_key, &peer_key));
+                try!(state.decrypt_and_hash(payload));
+                try!(self.check_peer_key(&peer_key));
+            }
+        }
+
+        let (initiator_key, responder_key) = state.split();
+        Ok(Box::new(match role {
+            Role::Initiator => Session::new(initiator_key, responder_key),
+            Role::Responder => Session::new(responder_key, initiator_key),
+        }))
+    }
+}
+
+struct Session {
+    send_key: Vec<u8>,
+    receive_key: Vec<u8>,
+}
+
+impl Session {
+    fn new(send_key: Vec<u8>, receive_key: Vec<u8>) -> Session {
+        Session {
+            send_key: send_key,
+            receive_key: receive_key,
+        }
+    }
+}
+
+impl Cipher for Session {
+    fn box_clone(&self) -> Box<Cipher> {
+        Box::new(Session::new(self.send_key.clone(), self.receive_key.clone()))
+    }
+
+    fn encrypt(&self, plain_text: &[u8], associated: &Associated) -> Result<Vec<u8>> {
+        Ok(encrypt(&self.send_key,
+                   associated.sequence,
+                   &associated.to_bytes(),
+                   plain_text))
+    }
+
+    fn decrypt(&self, cipher_text: &[u8], associated: &Associated) -> Result<Vec<u8>> {
+        decrypt(&self.receive_key,
+                associated.sequence,
+                &associated.to_bytes(),
+                cipher_text)
+    }
+}
+
+struct SymmetricState {
+    chaining_key: Vec<u8>,
+    hash: Vec<u8>,
+    key: Option<Vec<u8>>,
+    nonce: u64,
+}
+
+impl SymmetricState {
+    fn new() -> SymmetricState {
+        let mut state = SymmetricState {
+            chaining_key: PROTOCOL_NAME.to_vec(),
+            hash: PROTOCOL_NAME.to_vec(),
+            key: None,
+            nonce: 0,
+        };
+        state.mix_hash(PROLOGUE);
+        state
+    }
+
+    fn mix_hash(&mut self, data: &[u8]) {
+        let mut hash = Sha256::new();
+        hash.input(&self.hash);
+        hash.input(data);
+        hash.result(&mut self.hash);
+    }
+
+    fn mix_key(&mut self, input_key: &[u8]) {
+        let (chaining_key, key) = hkdf(&self.chaining_key, input_key);
+        self.chaining_key = chaining_key;
+        self.key = Some(key);
+        self.nonce = 0;
+    }
+
+    fn encrypt_and_hash(&mut self, plain_text: &[u8]) -> Vec<u8> {
+        let cipher_text = match self.key {
+            Some(ref key) => encrypt(key, self.nonce, &self.hash, plain_text),
+            None => plain_text.to_vec(),
+        };
+        if self.key.is_some() {
+            self.nonce += 1;
+        }
+        self.mix_hash(&cipher_text);
+        cipher_text
+    }
+
+    fn decrypt_and_hash(&mut self, cipher_text: &[u8]) -> Result<Vec<u8>> {
+        let plain_text = match self.key {
+            Some(ref key) => {
+                try!(decrypt(key, self.nonce, &self.hash, cipher_text)
+                         .map_err(|_| Error::HandshakeFailed))
+            }
+            None => cipher_text.to_vec(),
+        };
+        if self.key.is_some() {
+            self.nonce += 1;
+        }
+        self.mix_hash(cipher_text);
+        Ok(plain_text)
+    }
+
+    fn split(&self) -> (Vec<u8>, Vec<u8>) {
+        hkdf(&self.chaining_key, &[])
+    }
+}
+
+fn encrypt(key: &[u8], nonce: u64, associated: &[u8], plain_text: &[u8]) -> Vec<u8> {
+    let mut cipher = ChaCha20Poly1305::new(key, &nonce_bytes(nonce), associated);
+    let mut cipher_text = iter::repeat(0).take(plain_text.len()).collect::<Vec<u8>>();
+    let mut tag = [0; TAG_SIZE];
+    cipher.encrypt(plain_text, &mut cipher_text, &mut tag);
+    cipher_text.extend_from_slice(&tag);
+    cipher_text
+}
+
+fn decrypt(key: &[u8], nonce: u64, associated: &[u8], cipher_text: &[u8]) -> Result<Vec<u8>> {
+    if cipher_text.len() < TAG_SIZE {
+        return Err(Error::DecryptionFailed);
+    }
+    let (cipher_text, tag) = cipher_text.split_at(cipher_text.len() - TAG_SIZE);
+
+    let mut cipher = ChaCha20Poly1305::new(key, &nonce_bytes(nonce), associated);
+    let mut plain_text = iter::repeat(0).take(cipher_text.len()).collect::<Vec<u8>>();
+    if !cipher.decrypt(cipher_text, &mut plain_text, tag) {
+        return Err(Error::DecryptionFailed);
+    }
+    Ok(plain_text)
+}
+
+fn nonce_bytes(nonce: u64) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(8);
+    bytes.write_u64::<LittleEndian>(nonce).unwrap();
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net;
+    use std::thread;
+    use super::Noise;
+    use super::super::{Associated, Cipher, Error, Result, Role};
+
+    #[test]
+    fn handshake_and_exchange() {
+        let (initiator, responder) = build_pair(true);
+        let initiator = initiator.unwrap();
+        let responder = responder.unwrap();
+
+        let cipher_text = initiator.encrypt(b"test message", &Associated::new(1, 0)).unwrap();
+        assert_eq!(b"test message".to_vec(),
+                   responder.decrypt(&cipher_text, &Associated::new(1, 0)).unwrap());
+
+        let cipher_text = responder.encrypt(b"test answer", &Associated::new(1, 0)).unwrap();
+        assert_eq!(b"test answer".to_vec(),
+                   initiator.decrypt(&cipher_text, &Associated::new(1, 0)).unwrap());
+
+        assert_eq!(Err(Error::DecryptionFailed),
+                   responder.decrypt(&cipher_text, &Associated::new(1, 0)));
+        assert_eq!(Err(Error::DecryptionFailed),
+                   initiator.decrypt(&cipher_text, &Associated::new(1, 1)));
+    }
+
+    #[test]
+    fn reject_unknown_peer() {
+        let (_, responder) = build_pair(false);
+        assert_eq!(Some(Error::UnknownPeer), responder.err());
+    }
+
+    #[test]
+    fn require_handshake() {
+        let noise = Noise::new(&[1; 32], &[]).unwrap();
+        assert_eq!(Err(Error::HandshakeRequired),
+                   noise.encrypt(b"test message", &Associated::new(1, 0)));
+    }
+
+    fn build_pair(known: bool) -> (Result<Box<Cipher>>, Result<Box<Cipher>>) {
+        let initiator_key = [1; 32];
+        let responder_key = [2; 32];
+        let initiator_public_key = Noise::new(&initiator_key, &[]).unwrap().public_key().to_vec();
+        let responder_public_key = Noise::new(&responder_key, &[]).unwrap().public_key().to_vec();
+
+        let initiator = Noise::new(&initiator_key, &[responder_public_key]).unwrap();
+        let responder = Noise::new(&responder_key,
+                                   &if known {
+                                       vec![initiator_public_key]
+                                   } else {
+                                       Vec::new()
+                                   })
+                            .unwrap();
+
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let join_handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            responder.handshake(&mut stream, Role::Responder)
+        });
+
+        let mut stream = net::TcpStream::connect(address).unwrap();
+        let initiator = initiator.handshake(&mut stream, Role::Initiator);
+        (initiator, join_handle.join().unwrap())
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use rand::random;
+
+pub const NONCE_SIZE: usize = 12;
+
+pub trait NonceSource : Send + Sync {
+    fn box_clone(&self) -> Box<NonceSource>;
+    fn next(&self) -> Vec<u8>;
+}
+
+pub struct RandomNonceSource;
+
+impl RandomNonceSource {
+    pub fn new() -> RandomNonceSource {
+        RandomNonceSource
+    }
+}
+
+impl NonceSource for RandomNonceSource {
+    fn box_clone(&self) -> Box<NonceSource> {
+        Box::new(RandomNonceSource)
+    }
+
+    fn next(&self) -> Vec<u8> {
+        random::<[u8; NONCE_SIZE]>().to_vec()
+    }
+}
+
+pub struct FixedNonceSource {
+    nonce: Vec<u8>,
+}
+
+impl FixedNonceSource {
+    pub fn new(nonce: &[u8]) -> FixedNonceSource {
+        FixedNonceSource { nonce: nonce.to_vec() }
+    }
+}
+
+impl NonceSource for FixedNonceSource {
+    fn box_clone(&self) -> Box<NonceSource> {
+        Box::new(FixedNonceSource::new(&self.nonce))
+    }
+
+    fn next(&self) -> Vec<u8> {
+        self.nonce.clone()
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use transport::cipher::{Associated, Cipher, Error, Result};
+
+const ASSOCIATED_SIZE: usize = 12;
+
+/// Passes messages through unencrypted. It's meant for local development only, since it
+/// provides neither confidentiality nor authentication.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Plain;
+
+impl Plain {
+    pub fn new() -> Plain {
+        Plain
+    }
+}
+
+impl Cipher for Plain {
+    fn box_clone(&self) -> Box<Cipher> {
+        Box::new(*self)
+    }
+
+    fn encrypt(&self, plain_text: &[u8], associated: &Associated) -> Result<Vec<u8>> {
+        let mut result = associated.to_bytes();
+        result.extend_from_slice(plain_text);
+        Ok(result)
+    }
+
+    fn decrypt(&self, cipher_text: &[u8], associated: &Associated) -> Result<Vec<u8>> {
+        if cipher_text.len() < ASSOCIATED_SIZE {
+            return Err(Error::Read);
+        }
+        let (associated_bytes, plain_text) = cipher_text.split_at(ASSOCIATED_SIZE);
+        if associated_bytes != &associated.to_bytes()[..] {
+            return Err(Error::AssociatedDataMismatch);
+        }
+        Ok(plain_text.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Plain;
+    use super::super::{Associated, Cipher, Error};
+
+    #[test]
+    fn pass_through() {
+        let cipher = Plain::new();
+        let cipher_text = cipher.encrypt(b"test message", &Associated::new(1, 2)).unwrap();
+        assert_eq!(b"test message", &cipher_text[12..]);
+        assert_eq!(b"test message".to_vec(),
+                   cipher.decrypt(&cipher_text, &Associated::new(1, 2)).unwrap());
+        assert_eq!(Err(Error::AssociatedDataMismatch),
+                   cipher.decrypt(&cipher_text, &Associated::new(1, 3)));
+        assert_eq!(Err(Error::Read), cipher.decrypt(b"short", &Associated::new(1, 2)));
+    }
+
+}
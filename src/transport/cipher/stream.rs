@@ -15,15 +15,46 @@
 
 use std::io;
 use std::iter;
+use std::mem;
 use std::net;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use transport::cipher::{self, Cipher};
+use transport::capture::{Capture, Direction};
+use transport::cipher::{self, Associated, Cipher};
 use util::{reader, writer};
+use util::rate::Limiter;
+
+const FRAME_KIND: u32 = 1;
+const MAX_FRAME_SIZE: usize = 128 * 1024 * 1024;
+
+/// Upper bound of the bytes that the encryption adds to a frame.
+pub const FRAME_OVERHEAD: usize = 256;
+
+/// The byte and frame counters of a stream. They are shared with the clones of the stream and
+/// can be read without access to it.
+#[derive(Clone)]
+pub struct Counters {
+    bytes_read: Arc<AtomicUsize>,
+    bytes_written: Arc<AtomicUsize>,
+    frames_read: Arc<AtomicUsize>,
+    frames_written: Arc<AtomicUsize>,
+}
 
 pub struct Stream<T> {
     parent: T,
     cipher: Box<Cipher>,
     buffer: io::Cursor<Vec<u8>>,
+    bytes_read: Arc<AtomicUsize>,
+    bytes_written: Arc<AtomicUsize>,
+    frames_read: Arc<AtomicUsize>,
+    frames_written: Arc<AtomicUsize>,
+    pending: Vec<u8>,
+    coalesce_size: usize,
+    max_frame_size: usize,
+    read_limiter: Option<Arc<Limiter>>,
+    write_limiter: Option<Arc<Limiter>>,
+    capture: Option<(Arc<Capture>, u64)>,
 }
 
 impl<T> Stream<T> {
@@ -32,17 +63,107 @@ impl<T> Stream<T> {
             parent: parent,
             cipher: cipher,
             buffer: io::Cursor::new(Vec::new()),
+            bytes_read: Arc::new(AtomicUsize::new(0)),
+            bytes_written: Arc::new(AtomicUsize::new(0)),
+            frames_read: Arc::new(AtomicUsize::new(0)),
+            frames_written: Arc::new(AtomicUsize::new(0)),
+            pending: Vec::new(),
+            coalesce_size: 0,
+            max_frame_size: MAX_FRAME_SIZE,
+            read_limiter: None,
+            write_limiter: None,
+            capture: None,
         }
     }
 
+    pub fn set_limiters(&mut self,
+                        read_limiter: Option<Arc<Limiter>>,
+                        write_limiter: Option<Arc<Limiter>>) {
+        self.read_limiter = read_limiter;
+        self.write_limiter = write_limiter;
+    }
+
+    /// Records the plain bytes of all frames, that are read or written from now on. Clones of
+    /// this stream record under the same stream id.
+    pub fn set_capture(&mut self, capture: Arc<Capture>) {
+        let stream_id = capture.next_stream_id();
+        self.capture = Some((capture, stream_id));
+    }
+
+    pub fn set_coalesce_size(&mut self, coalesce_size: usize) {
+        self.coalesce_size = coalesce_size;
+    }
+
+    /// Limits the size of the encrypted frames that are read. A larger frame is rejected with
+    /// `InvalidData` before any space is allocated for it.
+    pub fn set_max_frame_size(&mut self, max_frame_size: usize) {
+        self.max_frame_size = max_frame_size;
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
     pub fn get_ref(&self) -> &T {
         &self.parent
     }
+
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read.load(Ordering::SeqCst)
+    }
+
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written.load(Ordering::SeqCst)
+    }
+
+    pub fn frames_read(&self) -> usize {
+        self.frames_read.load(Ordering::SeqCst)
+    }
+
+    pub fn frames_written(&self) -> usize {
+        self.frames_written.load(Ordering::SeqCst)
+    }
+
+    pub fn counters(&self) -> Counters {
+        Counters {
+            bytes_read: self.bytes_read.clone(),
+            bytes_written: self.bytes_written.clone(),
+            frames_read: self.frames_read.clone(),
+            frames_written: self.frames_written.clone(),
+        }
+    }
+}
+
+impl Counters {
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read.load(Ordering::SeqCst)
+    }
+
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written.load(Ordering::SeqCst)
+    }
+
+    pub fn frames_read(&self) -> usize {
+        self.frames_read.load(Ordering::SeqCst)
+    }
+
+    pub fn frames_written(&self) -> usize {
+        self.frames_written.load(Ordering::SeqCst)
+    }
 }
 
 impl Stream<net::TcpStream> {
     pub fn try_clone(&self) -> io::Result<Self> {
-        Ok(Self::new(try!(self.parent.try_clone()), self.cipher.box_clone()))
+        let mut stream = Self::new(try!(self.parent.try_clone()), self.cipher.box_clone());
+        stream.bytes_read = self.bytes_read.clone();
+        stream.bytes_written = self.bytes_written.clone();
+        stream.frames_read = self.frames_read.clone();
+        stream.frames_written = self.frames_written.clone();
+        stream.max_frame_size = self.max_frame_size;
+        stream.read_limiter = self.read_limiter.clone();
+        stream.write_limiter = self.write_limiter.clone();
+        stream.capture = self.capture.clone();
+        Ok(stream)
     }
 }
 
@@ -50,30 +171,78 @@ impl<T> io::Write for Stream<T>
     where T: io::Write
 {
     fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
-        let encrypted_bytes = try!(self.cipher.encrypt(buffer));
+        if self.coalesce_size == 0 {
+            try!(self.write_frame(buffer));
+            return Ok(buffer.len());
+        }
 
-        try!(writer::write_size(&mut self.parent, encrypted_bytes.len()));
-        try!(self.parent.write(&encrypted_bytes));
+        self.pending.extend_from_slice(buffer);
+        if self.pending.len() >= self.coalesce_size {
+            try!(self.write_pending());
+        }
 
         Ok(buffer.len())
     }
 
     fn flush(&mut self) -> io::Result<()> {
+        try!(self.write_pending());
         self.parent.flush()
     }
 }
 
+impl<T> Stream<T>
+    where T: io::Write
+{
+    fn write_pending(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let pending = mem::replace(&mut self.pending, Vec::new());
+        self.write_frame(&pending)
+    }
+
+    fn write_frame(&mut self, buffer: &[u8]) -> io::Result<()> {
+        record(&self.capture, Direction::Outbound, buffer);
+
+        let sequence = self.frames_written.fetch_add(1, Ordering::SeqCst) as u64;
+        let encrypted_bytes = try!(self.cipher
+                                       .encrypt(buffer, &Associated::new(FRAME_KIND, sequence)));
+
+        if let Some(ref write_limiter) = self.write_limiter {
+            write_limiter.consume(mem::size_of::<u64>() + encrypted_bytes.len());
+        }
+
+        let size = try!(writer::write_size(&mut self.parent, encrypted_bytes.len()));
+        try!(self.parent.write_all(&encrypted_bytes));
+        self.bytes_written.fetch_add(size + encrypted_bytes.len(), Ordering::SeqCst);
+
+        Ok(())
+    }
+}
+
 impl<T> io::Read for Stream<T>
     where T: io::Read
 {
     fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
         if self.buffer.position() as usize >= self.buffer.get_ref().len() {
             let encrypted_size = try!(reader::read_size(&mut self.parent));
+            if encrypted_size > self.max_frame_size {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          format!("frame size {} exceeds limit", encrypted_size)));
+            }
 
             let mut encrypted_bytes = iter::repeat(0u8).take(encrypted_size).collect::<Vec<u8>>();
             try!(self.parent.read_exact(&mut encrypted_bytes));
+            self.bytes_read.fetch_add(mem::size_of::<u64>() + encrypted_size, Ordering::SeqCst);
+            if let Some(ref read_limiter) = self.read_limiter {
+                read_limiter.consume(mem::size_of::<u64>() + encrypted_size);
+            }
+            let sequence = self.frames_read.fetch_add(1, Ordering::SeqCst) as u64;
 
-            let decrypted_bytes = try!(self.cipher.decrypt(&encrypted_bytes));
+            let decrypted_bytes = try!(self.cipher.decrypt(&encrypted_bytes,
+                                                           &Associated::new(FRAME_KIND,
+                                                                            sequence)));
+            record(&self.capture, Direction::Inbound, &decrypted_bytes);
             self.buffer = io::Cursor::new(decrypted_bytes);
         }
 
@@ -81,9 +250,17 @@ impl<T> io::Read for Stream<T>
     }
 }
 
+fn record(capture: &Option<(Arc<Capture>, u64)>, direction: Direction, bytes: &[u8]) {
+    if let Some((ref capture, stream_id)) = *capture {
+        if let Err(error) = capture.record(stream_id, direction, bytes) {
+            error!("failed to capture frame of stream {}: {:?}", stream_id, error);
+        }
+    }
+}
+
 impl Clone for Stream<net::TcpStream> {
     fn clone(&self) -> Self {
-        Self::new(self.parent.try_clone().unwrap(), self.cipher.box_clone())
+        self.try_clone().unwrap()
     }
 }
 
@@ -106,16 +283,46 @@ mod tests {
         let mut stream = Stream::new(Vec::new(), build_cipher());
         assert!(stream.write_all(b"test message").is_ok());
 
-        assert_eq!("00000000000000300801120c0000000000000000000000001a0c3db3f427b9f6c3ff90e81d0d2\
-                    2102958d0a32be787b9c59da25053419e41",
+        assert_eq!("00000000000000340801120c0000000000000000000000001a0c3db3f427b9f6c3ff90e81d0d2\
+                    2109a9df093ffd062fdccebce0456f2046628013000",
                    stream.get_ref().to_hex());
+        assert_eq!(60, stream.bytes_written());
+        assert_eq!(1, stream.frames_written());
+    }
+
+    #[test]
+    fn write_coalesced() {
+        let mut stream = Stream::new(Vec::new(), build_cipher());
+        stream.set_coalesce_size(1024);
+        assert!(stream.write_all(b"test ").is_ok());
+        assert!(stream.write_all(b"message").is_ok());
+        assert!(stream.has_pending());
+        assert_eq!(0, stream.frames_written());
+
+        assert!(stream.flush().is_ok());
+        assert!(!stream.has_pending());
+        assert_eq!("00000000000000340801120c0000000000000000000000001a0c3db3f427b9f6c3ff90e81d0d2\
+                    2109a9df093ffd062fdccebce0456f2046628013000",
+                   stream.get_ref().to_hex());
+        assert_eq!(1, stream.frames_written());
+    }
+
+    #[test]
+    fn write_coalesced_above_size() {
+        let mut stream = Stream::new(Vec::new(), build_cipher());
+        stream.set_coalesce_size(8);
+        assert!(stream.write_all(b"test ").is_ok());
+        assert_eq!(0, stream.frames_written());
+        assert!(stream.write_all(b"message").is_ok());
+        assert!(!stream.has_pending());
+        assert_eq!(1, stream.frames_written());
     }
 
     #[test]
     fn read() {
-        let mut stream = Stream::new(io::Cursor::new("00000000000000300801120c000000000000000000\
-                                                      0000001a0c3db3f427b9f6c3ff90e81d0d22102958\
-                                                      d0a32be787b9c59da25053419e41"
+        let mut stream = Stream::new(io::Cursor::new("00000000000000340801120c000000000000000000\
+                                                      0000001a0c3db3f427b9f6c3ff90e81d0d22109a9d\
+                                                      f093ffd062fdccebce0456f2046628013000"
                                                          .from_hex()
                                                          .ok()
                                                          .unwrap()
@@ -125,6 +332,40 @@ mod tests {
         let mut buffer = [0u8; 12];
         assert!(stream.read_exact(&mut buffer).is_ok());
         assert_eq!("test message", String::from_utf8_lossy(&buffer));
+        assert_eq!(60, stream.bytes_read());
+        assert_eq!(1, stream.frames_read());
+    }
+
+    #[test]
+    fn read_failure_on_replayed_frame() {
+        let frame = "00000000000000340801120c0000000000000000000000001a0c3db3f427b9f6c3ff90e81d0d2\
+                     2109a9df093ffd062fdccebce0456f2046628013000"
+                        .from_hex()
+                        .ok()
+                        .unwrap();
+        let mut bytes = frame.clone();
+        bytes.extend_from_slice(&frame);
+        let mut stream = Stream::new(io::Cursor::new(bytes), build_cipher());
+
+        let mut buffer = [0u8; 12];
+        assert!(stream.read_exact(&mut buffer).is_ok());
+        assert!(stream.read_exact(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn read_failure_on_frame_beyond_limit() {
+        let frame = "00000000000000340801120c0000000000000000000000001a0c3db3f427b9f6c3ff90e81d0d2\
+                     2109a9df093ffd062fdccebce0456f2046628013000"
+                        .from_hex()
+                        .ok()
+                        .unwrap();
+        let mut stream = Stream::new(io::Cursor::new(frame), build_cipher());
+        stream.set_max_frame_size(51);
+
+        let mut buffer = [0u8; 12];
+        assert_eq!(io::ErrorKind::InvalidData,
+                   stream.read_exact(&mut buffer).unwrap_err().kind());
+        assert_eq!(0, stream.frames_read());
     }
 
     fn build_cipher() -> Box<Cipher> {
@@ -13,93 +13,422 @@
 // limitations under the License.
 //
 
+use std::fmt;
 use std::iter;
+use std::sync::{Arc, RwLock};
 
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use crypto::aes::KeySize;
 use crypto::aes_gcm::AesGcm;
 use crypto::aead::{AeadEncryptor, AeadDecryptor};
-use protobuf::{self, Message};
+use crypto::chacha20poly1305::ChaCha20Poly1305;
+use crypto::curve25519::{curve25519, curve25519_base};
+use crypto::util::fixed_time_eq;
+use protobuf::{self, Message, ProtobufEnum};
 use rand::random;
 
-use message;
-use transport::cipher::{Cipher, Error, Result};
+use message::{self, Encrypted_CipherType as CipherType};
+use transport::cipher::{Associated, Channel, Cipher, Error, FixedNonceSource, NonceSource,
+                        RandomNonceSource, Result, Role, hkdf, hmac, read_exact_message,
+                        read_message, write_message};
 
-const NONCE_SIZE: usize = 12;
+const AESGCM_NONCE_SIZE: usize = 12;
+const CHACHA20POLY1305_NONCE_SIZE: usize = 8;
+const CHACHA20POLY1305_NONCE_PREFIX_SIZE: usize = 4;
+const TAG_SIZE: usize = 16;
+const EXCHANGE_KEY_SIZE: usize = 32;
+const MAX_OFFER_SIZE: usize = 255;
 
 pub struct Symmetric {
-    key_size: KeySize,
-    key: Vec<u8>,
-    nonce: Option<Vec<u8>>,
+    keyring: Keyring,
+    peer_keyring: Keyring,
+    nonce_source: Box<NonceSource>,
+    nonce_prefix: [u8; CHACHA20POLY1305_NONCE_PREFIX_SIZE],
+    cipher_types: Vec<CipherType>,
+}
+
+/// Set of identified keys that is shared between a symmetric cipher and all its clones. Frames
+/// are encrypted with the key of the highest id and decrypted with the key their id refers to.
+#[derive(Clone)]
+pub struct Keyring {
+    keys: Arc<RwLock<Vec<Key>>>,
+}
+
+#[derive(Clone, PartialEq)]
+struct Key {
+    id: u32,
+    bytes: Vec<u8>,
 }
 
 impl Symmetric {
     pub fn new(key: &[u8], nonce: Option<&[u8]>) -> Result<Symmetric> {
-        let key_size = match key.len() {
-            16 => KeySize::KeySize128,
-            24 => KeySize::KeySize192,
-            32 => KeySize::KeySize256,
-            _ => return Err(Error::InvalidKeyLength(key.len())),
+        let nonce_source: Box<NonceSource> = match nonce {
+            Some(nonce) => Box::new(FixedNonceSource::new(nonce)),
+            None => Box::new(RandomNonceSource::new()),
         };
+        Self::with_nonce_source(key, nonce_source)
+    }
+
+    pub fn with_nonce_source(key: &[u8], nonce_source: Box<NonceSource>) -> Result<Symmetric> {
+        let cipher_types = if key.len() == 24 {
+            vec![CipherType::AESGCM]
+        } else {
+            vec![CipherType::AESGCM, CipherType::CHACHA20POLY1305]
+        };
+        Self::with_cipher_types(key, nonce_source, &cipher_types)
+    }
+
+    pub fn with_cipher_types(key: &[u8],
+                             nonce_source: Box<NonceSource>,
+                             cipher_types: &[CipherType])
+                             -> Result<Symmetric> {
+        let keyring = try!(Keyring::new(&[(0, key.to_vec())]));
+        Self::with_keyring(keyring, nonce_source, cipher_types)
+    }
+
+    pub fn with_keyring(keyring: Keyring,
+                        nonce_source: Box<NonceSource>,
+                        cipher_types: &[CipherType])
+                        -> Result<Symmetric> {
+        if cipher_types.is_empty() {
+            return Err(Error::NoCommonCipherType);
+        }
+        if cipher_types.contains(&CipherType::CHACHA20POLY1305) &&
+           !keyring.cipher_types().contains(&CipherType::CHACHA20POLY1305) {
+            return Err(Error::InvalidKeyLength(24));
+        }
 
         Ok(Symmetric {
-            key_size: key_size,
-            key: key.to_vec(),
-            nonce: nonce.map(|nonce| nonce.to_vec()),
+            peer_keyring: keyring.clone(),
+            keyring: keyring,
+            nonce_source: nonce_source,
+            nonce_prefix: random(),
+            cipher_types: cipher_types.to_vec(),
         })
     }
+
+    pub fn cipher_type(&self) -> CipherType {
+        self.cipher_types[0]
+    }
+
+    pub fn keyring(&self) -> &Keyring {
+        &self.keyring
+    }
+
+    // the 64 bit nonce of chacha20-poly1305 is too short to be picked at random. it's built from
+    // the frame sequence and a random prefix. the prefix only makes collisions between ciphers
+    // that share a configured key unlikely - the directions of a connection are kept apart by the
+    // separate keys the handshake derives for each of them.
+    fn sequence_nonce(&self, sequence: u64) -> Result<Vec<u8>> {
+        if sequence > u32::max_value() as u64 {
+            return Err(Error::NoncesExhausted);
+        }
+        let mut nonce = Vec::with_capacity(CHACHA20POLY1305_NONCE_SIZE);
+        nonce.extend_from_slice(&self.nonce_prefix);
+        nonce.write_u32::<BigEndian>(sequence as u32).unwrap();
+        Ok(nonce)
+    }
+}
+
+impl Keyring {
+    pub fn new(keys: &[(u32, Vec<u8>)]) -> Result<Keyring> {
+        Ok(Keyring { keys: Arc::new(RwLock::new(try!(build_keys(keys)))) })
+    }
+
+    /// Replaces all keys. Ciphers that share the keyring pick up the new keys with their next
+    /// frame, so keys that are left out are retired.
+    pub fn replace(&self, keys: &[(u32, Vec<u8>)]) -> Result<()> {
+        let keys = try!(build_keys(keys));
+        *self.keys.write().unwrap() = keys;
+        Ok(())
+    }
+
+    pub fn ids(&self) -> Vec<u32> {
+        self.keys.read().unwrap().iter().map(|key| key.id).collect()
+    }
+
+    /// Returns the cipher types that can be used with all keys.
+    pub fn cipher_types(&self) -> Vec<CipherType> {
+        if self.keys.read().unwrap().iter().any(|key| key.bytes.len() == 24) {
+            vec![CipherType::AESGCM]
+        } else {
+            vec![CipherType::AESGCM, CipherType::CHACHA20POLY1305]
+        }
+    }
+
+    fn current(&self) -> Key {
+        self.keys.read().unwrap()[0].clone()
+    }
+
+    fn get(&self, id: u32) -> Option<Key> {
+        self.keys.read().unwrap().iter().find(|key| key.id == id).cloned()
+    }
+}
+
+impl fmt::Debug for Keyring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Keyring({:?})", self.ids())
+    }
+}
+
+impl Key {
+    fn size(&self) -> KeySize {
+        match self.bytes.len() {
+            16 => KeySize::KeySize128,
+            24 => KeySize::KeySize192,
+            _ => KeySize::KeySize256,
+        }
+    }
+
+    fn check(&self, cipher_type: CipherType) -> Result<()> {
+        if cipher_type == CipherType::CHACHA20POLY1305 && self.bytes.len() == 24 {
+            return Err(Error::InvalidKeyLength(self.bytes.len()));
+        }
+        Ok(())
+    }
+}
+
+// sorts the keys by descending id, so the newest one comes first.
+fn build_keys(keys: &[(u32, Vec<u8>)]) -> Result<Vec<Key>> {
+    if keys.is_empty() {
+        return Err(Error::MissingKey);
+    }
+    let mut result = Vec::new();
+    for &(id, ref bytes) in keys {
+        match bytes.len() {
+            16 | 24 | 32 => {}
+            length => return Err(Error::InvalidKeyLength(length)),
+        }
+        if result.iter().any(|key: &Key| key.id == id) {
+            return Err(Error::DuplicateKey(id));
+        }
+        result.push(Key {
+            id: id,
+            bytes: bytes.clone(),
+        });
+    }
+    result.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(result)
 }
 
 impl Cipher for Symmetric {
     fn box_clone(&self) -> Box<Cipher> {
-        Box::new(Self::new(&self.key, None).unwrap())
+        Box::new(Symmetric {
+            keyring: self.keyring.clone(),
+            peer_keyring: self.peer_keyring.clone(),
+            nonce_source: self.nonce_source.box_clone(),
+            nonce_prefix: random(),
+            cipher_types: self.cipher_types.clone(),
+        })
     }
 
-    fn encrypt(&self, plain_text: &[u8]) -> Result<Vec<u8>> {
-        let nonce_random = random::<[u8; NONCE_SIZE]>().to_vec();
-        let nonce = self.nonce.as_ref().unwrap_or(&nonce_random);
+    fn encrypt(&self, plain_text: &[u8], associated: &Associated) -> Result<Vec<u8>> {
+        let cipher_type = self.cipher_type();
+        let key = self.keyring.current();
+        try!(key.check(cipher_type));
+        let nonce = match cipher_type {
+            CipherType::AESGCM => self.nonce_source.next(),
+            CipherType::CHACHA20POLY1305 => try!(self.sequence_nonce(associated.sequence)),
+        };
 
-        let mut cipher = AesGcm::new(self.key_size, &self.key, nonce, &[]);
         let mut cipher_text = iter::repeat(0).take(plain_text.len()).collect::<Vec<u8>>();
-        let mut tag = iter::repeat(0).take(16).collect::<Vec<u8>>();
-        cipher.encrypt(plain_text, &mut cipher_text, &mut tag);
+        let mut tag = iter::repeat(0).take(TAG_SIZE).collect::<Vec<u8>>();
+        match cipher_type {
+            CipherType::AESGCM => {
+                AesGcm::new(key.size(), &key.bytes, &nonce, &associated.to_bytes())
+                    .encrypt(plain_text, &mut cipher_text, &mut tag)
+            }
+            CipherType::CHACHA20POLY1305 => {
+                ChaCha20Poly1305::new(&key.bytes, &nonce, &associated.to_bytes())
+                    .encrypt(plain_text, &mut cipher_text, &mut tag)
+            }
+        }
 
         let mut encrypted = message::Encrypted::new();
-        encrypted.set_cipher_type(message::Encrypted_CipherType::AESGCM);
+        encrypted.set_cipher_type(cipher_type);
         encrypted.set_cipher_text(cipher_text);
-        encrypted.set_nonce(nonce.to_vec());
+        encrypted.set_nonce(nonce);
         encrypted.set_tag(tag);
+        encrypted.set_kind(associated.kind);
+        encrypted.set_sequence(associated.sequence);
+        if key.id != 0 {
+            encrypted.set_key_id(key.id);
+        }
         encrypted.write_to_bytes().map_err(|_| Error::Write)
     }
 
-    fn decrypt(&self, cipher_text: &[u8]) -> Result<Vec<u8>> {
+    fn decrypt(&self, cipher_text: &[u8], associated: &Associated) -> Result<Vec<u8>> {
         let encrypted = match protobuf::parse_from_bytes::<message::Encrypted>(cipher_text) {
             Ok(encrypted) => encrypted,
             Err(_) => return Err(Error::Read),
         };
 
-        let mut cipher = AesGcm::new(self.key_size, &self.key, encrypted.get_nonce(), &[]);
+        if encrypted.get_kind() != associated.kind ||
+           encrypted.get_sequence() != associated.sequence {
+            return Err(Error::AssociatedDataMismatch);
+        }
+
+        let cipher_type = encrypted.get_cipher_type();
+        if !self.cipher_types.contains(&cipher_type) {
+            return Err(Error::UnsupportedCipherType);
+        }
+
+        let key_id = encrypted.get_key_id();
+        let key = try!(self.peer_keyring.get(key_id).ok_or(Error::UnknownKey(key_id)));
+        try!(key.check(cipher_type));
+
         let mut plain_text = iter::repeat(0)
                                  .take(encrypted.get_cipher_text().len())
                                  .collect::<Vec<u8>>();
-        if !cipher.decrypt(encrypted.get_cipher_text(),
-                           &mut plain_text,
-                           encrypted.get_tag()) {
+        let success = match cipher_type {
+            CipherType::AESGCM => {
+                if encrypted.get_nonce().len() != AESGCM_NONCE_SIZE {
+                    return Err(Error::DecryptionFailed);
+                }
+                AesGcm::new(key.size(),
+                            &key.bytes,
+                            encrypted.get_nonce(),
+                            &associated.to_bytes())
+                    .decrypt(encrypted.get_cipher_text(),
+                             &mut plain_text,
+                             encrypted.get_tag())
+            }
+            CipherType::CHACHA20POLY1305 => {
+                if encrypted.get_nonce().len() != CHACHA20POLY1305_NONCE_SIZE {
+                    return Err(Error::DecryptionFailed);
+                }
+                ChaCha20Poly1305::new(&key.bytes, encrypted.get_nonce(), &associated.to_bytes())
+                    .decrypt(encrypted.get_cipher_text(),
+                             &mut plain_text,
+                             encrypted.get_tag())
+            }
+        };
+        if !success {
             return Err(Error::DecryptionFailed);
         }
         Ok(plain_text)
     }
+
+    fn handshake(&self, channel: &mut Channel, role: Role) -> Result<Box<Cipher>> {
+        let offer = self.cipher_types
+                        .iter()
+                        .map(|cipher_type| cipher_type.value() as u8)
+                        .collect::<Vec<u8>>();
+        let ephemeral_key = random::<[u8; EXCHANGE_KEY_SIZE]>();
+        let ephemeral_public_key = curve25519_base(&ephemeral_key);
+        try!(write_message(channel, &offer));
+        try!(write_message(channel, &ephemeral_public_key));
+
+        let peer_offer_bytes = try!(read_message(channel, MAX_OFFER_SIZE));
+        let peer_ephemeral_public_key = try!(read_exact_message(channel, EXCHANGE_KEY_SIZE));
+        let peer_offer = peer_offer_bytes.iter()
+                                         .filter_map(|value| CipherType::from_i32(*value as i32))
+                                         .collect::<Vec<CipherType>>();
+
+        // both sides pick the first type of the initiator's offer that the responder supports.
+        let (preferred, supported) = match role {
+            Role::Initiator => (&self.cipher_types, &peer_offer),
+            Role::Responder => (&peer_offer, &self.cipher_types),
+        };
+        let selected = preferred.iter().find(|&cipher_type| supported.contains(cipher_type));
+        let cipher_type = try!(selected.cloned().ok_or(Error::NoCommonCipherType));
+
+        let transcript = match role {
+            Role::Initiator => {
+                transcript(&ephemeral_public_key,
+                           &peer_ephemeral_public_key,
+                           &offer,
+                           &peer_offer_bytes)
+            }
+            Role::Responder => {
+                transcript(&peer_ephemeral_public_key,
+                           &ephemeral_public_key,
+                           &peer_offer_bytes,
+                           &offer)
+            }
+        };
+
+        // both sides prove the knowledge of a shared key, so nobody in between can replace the
+        // exchanged public keys.
+        let key = self.keyring.current();
+        let mut confirmation = Vec::new();
+        confirmation.write_u32::<BigEndian>(key.id).unwrap();
+        confirmation.extend(confirm(&key.bytes, role, &transcript));
+        try!(write_message(channel, &confirmation));
+
+        let peer_confirmation = try!(read_exact_message(channel, confirmation.len()));
+        let (mut peer_key_id, peer_tag) = peer_confirmation.split_at(4);
+        let peer_key_id = try!(peer_key_id.read_u32::<BigEndian>()
+                                          .map_err(|_| Error::HandshakeFailed));
+        let peer_key = try!(self.keyring.get(peer_key_id).ok_or(Error::UnknownKey(peer_key_id)));
+        let peer_role = match role {
+            Role::Initiator => Role::Responder,
+            Role::Responder => Role::Initiator,
+        };
+        if !fixed_time_eq(&confirm(&peer_key.bytes, peer_role, &transcript), peer_tag) {
+            return Err(Error::HandshakeFailed);
+        }
+
+        let shared_key = curve25519(&ephemeral_key, &peer_ephemeral_public_key);
+        if shared_key.iter().all(|byte| *byte == 0) {
+            return Err(Error::HandshakeFailed);
+        }
+
+        // each direction gets its own key, so a frame can't be reflected back to its sender and
+        // the nonces of both sides never meet under the same key.
+        let (initiator_key, responder_key) = hkdf(&transcript, &shared_key);
+        let (key, peer_key) = match role {
+            Role::Initiator => (initiator_key, responder_key),
+            Role::Responder => (responder_key, initiator_key),
+        };
+
+        let mut session = try!(Self::with_keyring(try!(Keyring::new(&[(0, key)])),
+                                                  self.nonce_source.box_clone(),
+                                                  &[cipher_type]));
+        session.peer_keyring = try!(Keyring::new(&[(0, peer_key)]));
+        Ok(Box::new(session))
+    }
+}
+
+fn transcript(initiator_public_key: &[u8],
+              responder_public_key: &[u8],
+              initiator_offer: &[u8],
+              responder_offer: &[u8])
+              -> Vec<u8> {
+    let mut transcript = Vec::new();
+    transcript.extend_from_slice(initiator_public_key);
+    transcript.extend_from_slice(responder_public_key);
+    transcript.push(initiator_offer.len() as u8);
+    transcript.extend_from_slice(initiator_offer);
+    transcript.extend_from_slice(responder_offer);
+    transcript
+}
+
+fn confirm(key: &[u8], role: Role, transcript: &[u8]) -> Vec<u8> {
+    let mut data = match role {
+        Role::Initiator => b"initiator".to_vec(),
+        Role::Responder => b"responder".to_vec(),
+    };
+    data.extend_from_slice(transcript);
+    hmac(key, &data)
 }
 
 #[cfg(test)]
 mod tests {
 
+    use std::net;
+    use std::thread;
     use rustc_serialize::hex::{FromHex, ToHex};
-    use super::Symmetric;
-    use super::super::{Cipher, Error};
+    use protobuf::{self, Message};
+    use message::{self, Encrypted_CipherType as CipherType};
+    use super::{Keyring, Symmetric};
+    use super::super::{Associated, Cipher, Error, FixedNonceSource, RandomNonceSource, Result,
+                       Role};
 
     struct Set {
         key: Vec<u8>,
         nonce: Vec<u8>,
+        associated: Associated,
         plain_text: Vec<u8>,
         cipher_text: Vec<u8>,
     }
@@ -108,9 +437,10 @@ mod tests {
         [Set {
              key: "000102030405060708090a0b0c0d0e0f".from_hex().ok().unwrap(),
              nonce: "000000000000000000000000".from_hex().ok().unwrap(),
+             associated: Associated::new(1, 7),
              plain_text: b"test message".to_vec(),
-             cipher_text: "0801120c0000000000000000000000001a0c3db3f427b9f6c3ff90e81d0d22102958d0a\
-                           32be787b9c59da25053419e41"
+             cipher_text: "0801120c0000000000000000000000001a0c3db3f427b9f6c3ff90e81d0d2210f16d9ae\
+                           e5482efffce829fc9471693ae28013007"
                               .from_hex()
                               .ok()
                               .unwrap(),
@@ -118,9 +448,10 @@ mod tests {
          Set {
              key: "000102030405060708090a0b0c0d0e0f0001020304050607".from_hex().ok().unwrap(),
              nonce: "000000000000000000000000".from_hex().ok().unwrap(),
+             associated: Associated::new(1, 7),
              plain_text: b"test message".to_vec(),
-             cipher_text: "0801120c0000000000000000000000001a0c499864dd11b518a12286eacf221076b2587\
-                           5be43895b643f78087bf38494"
+             cipher_text: "0801120c0000000000000000000000001a0c499864dd11b518a12286eacf2210ec3207e\
+                           8b5d43175c47a2f2810179c2728013007"
                               .from_hex()
                               .ok()
                               .unwrap(),
@@ -131,9 +462,10 @@ mod tests {
                       .ok()
                       .unwrap(),
              nonce: "000000000000000000000000".from_hex().ok().unwrap(),
+             associated: Associated::new(1, 7),
              plain_text: b"test message".to_vec(),
-             cipher_text: "0801120c0000000000000000000000001a0c057376ca7d93a3d3d411d1a02210fa7ebca\
-                           97ce635db1901e71a75c6f79c"
+             cipher_text: "0801120c0000000000000000000000001a0c057376ca7d93a3d3d411d1a02210f412318\
+                           0ab939b33e685458cc9c9909228013007"
                               .from_hex()
                               .ok()
                               .unwrap(),
@@ -144,7 +476,7 @@ mod tests {
     fn encrypt() {
         for set in sets().iter() {
             let cipher = Symmetric::new(&set.key, Some(&set.nonce)).unwrap();
-            let cipher_text = cipher.encrypt(&set.plain_text).unwrap();
+            let cipher_text = cipher.encrypt(&set.plain_text, &set.associated).unwrap();
             assert_eq!(set.cipher_text.to_hex(), cipher_text.to_hex());
         }
     }
@@ -153,7 +485,7 @@ mod tests {
     fn decrypt() {
         for set in sets().iter() {
             let cipher = Symmetric::new(&set.key, Some(&set.nonce)).unwrap();
-            let plain_text = cipher.decrypt(&set.cipher_text).unwrap();
+            let plain_text = cipher.decrypt(&set.cipher_text, &set.associated).unwrap();
             assert_eq!(String::from_utf8_lossy(&set.plain_text),
                        String::from_utf8_lossy(&plain_text));
         }
@@ -163,19 +495,250 @@ mod tests {
     fn decrypt_with_cloned_cipher() {
         for set in sets().iter() {
             let cipher = Symmetric::new(&set.key, Some(&set.nonce)).unwrap().box_clone();
-            let plain_text = cipher.decrypt(&set.cipher_text).unwrap();
+            let plain_text = cipher.decrypt(&set.cipher_text, &set.associated).unwrap();
             assert_eq!(String::from_utf8_lossy(&set.plain_text),
                        String::from_utf8_lossy(&plain_text));
         }
     }
 
+    #[test]
+    fn encrypt_with_nonce_source() {
+        for set in sets().iter() {
+            let cipher = Symmetric::with_nonce_source(&set.key,
+                                                      Box::new(FixedNonceSource::new(&set.nonce)))
+                             .unwrap();
+            let cipher_text = cipher.encrypt(&set.plain_text, &set.associated).unwrap();
+            assert_eq!(set.cipher_text.to_hex(), cipher_text.to_hex());
+        }
+    }
+
     #[test]
     fn decrypt_failure_on_invalid_key() {
         for set in sets().iter() {
             let cipher = Symmetric::new(b"--invalid  key--", Some(&set.nonce)).unwrap();
             assert_eq!(Err(Error::DecryptionFailed),
-                       cipher.decrypt(&set.cipher_text));
+                       cipher.decrypt(&set.cipher_text, &set.associated));
+        }
+    }
+
+    #[test]
+    fn decrypt_failure_on_unexpected_sequence() {
+        for set in sets().iter() {
+            let cipher = Symmetric::new(&set.key, Some(&set.nonce)).unwrap();
+            assert_eq!(Err(Error::AssociatedDataMismatch),
+                       cipher.decrypt(&set.cipher_text,
+                                      &Associated::new(set.associated.kind,
+                                                       set.associated.sequence + 1)));
+        }
+    }
+
+    #[test]
+    fn decrypt_failure_on_invalid_nonce() {
+        for set in sets().iter() {
+            let cipher = Symmetric::new(&set.key, Some(&set.nonce)).unwrap();
+            let mut encrypted = protobuf::parse_from_bytes::<message::Encrypted>(&set.cipher_text)
+                                    .unwrap();
+            encrypted.set_nonce(vec![0; 4]);
+            assert_eq!(Err(Error::DecryptionFailed),
+                       cipher.decrypt(&encrypted.write_to_bytes().unwrap(), &set.associated));
         }
     }
 
+    #[test]
+    fn rotate_keys() {
+        let old_key = b"0123456789abcdef".to_vec();
+        let new_key = b"fedcba9876543210".to_vec();
+        let keyring = Keyring::new(&[(1, old_key.clone())]).unwrap();
+        let cipher = Symmetric::with_keyring(keyring.clone(),
+                                             Box::new(RandomNonceSource::new()),
+                                             &[CipherType::AESGCM])
+                         .unwrap()
+                         .box_clone();
+        let old_cipher_text = cipher.encrypt(b"test message", &Associated::new(1, 1)).unwrap();
+
+        keyring.replace(&[(1, old_key.clone()), (2, new_key.clone())]).unwrap();
+        let new_cipher_text = cipher.encrypt(b"test message", &Associated::new(1, 2)).unwrap();
+        assert_eq!(2,
+                   protobuf::parse_from_bytes::<message::Encrypted>(&new_cipher_text)
+                       .unwrap()
+                       .get_key_id());
+        assert_eq!(b"test message".to_vec(),
+                   cipher.decrypt(&old_cipher_text, &Associated::new(1, 1)).unwrap());
+
+        let outdated = Symmetric::with_keyring(Keyring::new(&[(1, old_key)]).unwrap(),
+                                               Box::new(RandomNonceSource::new()),
+                                               &[CipherType::AESGCM])
+                           .unwrap();
+        assert_eq!(Err(Error::UnknownKey(2)),
+                   outdated.decrypt(&new_cipher_text, &Associated::new(1, 2)));
+
+        keyring.replace(&[(2, new_key)]).unwrap();
+        assert_eq!(b"test message".to_vec(),
+                   cipher.decrypt(&new_cipher_text, &Associated::new(1, 2)).unwrap());
+        assert_eq!(Err(Error::UnknownKey(1)),
+                   cipher.decrypt(&old_cipher_text, &Associated::new(1, 1)));
+    }
+
+    #[test]
+    fn reject_invalid_keyring() {
+        assert_eq!(Error::MissingKey, Keyring::new(&[]).err().unwrap());
+        assert_eq!(Error::DuplicateKey(1),
+                   Keyring::new(&[(1, vec![0; 16]), (1, vec![1; 16])]).err().unwrap());
+        assert_eq!(Error::InvalidKeyLength(8),
+                   Keyring::new(&[(1, vec![0; 8])]).err().unwrap());
+    }
+
+    #[test]
+    fn encrypt_and_decrypt_with_chacha20_poly1305() {
+        let key = "000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f"
+                      .from_hex()
+                      .ok()
+                      .unwrap();
+        let cipher = Symmetric::with_cipher_types(&key,
+                                                  Box::new(RandomNonceSource::new()),
+                                                  &[CipherType::CHACHA20POLY1305])
+                         .unwrap();
+        let cipher_text = cipher.encrypt(b"test message", &Associated::new(1, 7)).unwrap();
+        assert_eq!(b"test message".to_vec(),
+                   cipher.decrypt(&cipher_text, &Associated::new(1, 7)).unwrap());
+
+        let aes_only = Symmetric::with_cipher_types(&key,
+                                                    Box::new(RandomNonceSource::new()),
+                                                    &[CipherType::AESGCM])
+                           .unwrap();
+        assert_eq!(Err(Error::UnsupportedCipherType),
+                   aes_only.decrypt(&cipher_text, &Associated::new(1, 7)));
+    }
+
+    #[test]
+    fn build_chacha20_poly1305_nonce_from_sequence() {
+        let cipher = Symmetric::with_cipher_types(&[0; 32],
+                                                  Box::new(RandomNonceSource::new()),
+                                                  &[CipherType::CHACHA20POLY1305])
+                         .unwrap();
+        let nonce = |sequence| {
+            let cipher_text = cipher.encrypt(b"test message", &Associated::new(1, sequence))
+                                    .unwrap();
+            protobuf::parse_from_bytes::<message::Encrypted>(&cipher_text)
+                .unwrap()
+                .get_nonce()
+                .to_vec()
+        };
+
+        let (first, second) = (nonce(1), nonce(2));
+        assert_eq!(8, first.len());
+        assert_eq!(&first[..4], &second[..4]);
+        assert_eq!(vec![0, 0, 0, 1], &first[4..]);
+        assert_eq!(vec![0, 0, 0, 2], &second[4..]);
+
+        assert_eq!(Err(Error::NoncesExhausted),
+                   cipher.encrypt(b"test message", &Associated::new(1, 1 << 32)));
+    }
+
+    #[test]
+    fn reject_chacha20_poly1305_with_192_bit_key() {
+        assert!(Symmetric::with_cipher_types(&[0; 24],
+                                             Box::new(RandomNonceSource::new()),
+                                             &[CipherType::CHACHA20POLY1305])
+                    .is_err());
+    }
+
+    #[test]
+    fn negotiate_cipher_type() {
+        let (initiator, responder) = handshake(&[CipherType::CHACHA20POLY1305,
+                                                 CipherType::AESGCM],
+                                               &[CipherType::AESGCM,
+                                                 CipherType::CHACHA20POLY1305]);
+        let initiator = initiator.unwrap();
+        let responder = responder.unwrap();
+
+        let cipher_text = responder.encrypt(b"test message", &Associated::new(1, 0)).unwrap();
+        assert_eq!(b"test message".to_vec(),
+                   initiator.decrypt(&cipher_text, &Associated::new(1, 0)).unwrap());
+
+        let only_aes = Symmetric::with_cipher_types(&[0; 32],
+                                                    Box::new(RandomNonceSource::new()),
+                                                    &[CipherType::AESGCM])
+                           .unwrap();
+        assert_eq!(Err(Error::UnsupportedCipherType),
+                   only_aes.decrypt(&cipher_text, &Associated::new(1, 0)));
+    }
+
+    #[test]
+    fn negotiate_without_common_cipher_type() {
+        let (initiator, responder) = handshake(&[CipherType::CHACHA20POLY1305],
+                                               &[CipherType::AESGCM]);
+        assert_eq!(Some(Error::NoCommonCipherType), initiator.err());
+        assert_eq!(Some(Error::NoCommonCipherType), responder.err());
+    }
+
+    #[test]
+    fn derive_session_key() {
+        let (initiator, responder) = handshake(&[CipherType::AESGCM], &[CipherType::AESGCM]);
+        let initiator = initiator.unwrap();
+        let responder = responder.unwrap();
+
+        let cipher_text = initiator.encrypt(b"test message", &Associated::new(1, 0)).unwrap();
+        assert_eq!(b"test message".to_vec(),
+                   responder.decrypt(&cipher_text, &Associated::new(1, 0)).unwrap());
+
+        let shared = Symmetric::new(&[0; 32], None).unwrap();
+        assert_eq!(Err(Error::DecryptionFailed),
+                   shared.decrypt(&cipher_text, &Associated::new(1, 0)));
+    }
+
+    #[test]
+    fn derive_key_per_direction() {
+        let (initiator, responder) = handshake(&[CipherType::CHACHA20POLY1305],
+                                               &[CipherType::CHACHA20POLY1305]);
+        let initiator = initiator.unwrap();
+        let responder = responder.unwrap();
+
+        let cipher_text = initiator.encrypt(b"test message", &Associated::new(1, 0)).unwrap();
+        assert_eq!(Err(Error::DecryptionFailed),
+                   initiator.decrypt(&cipher_text, &Associated::new(1, 0)));
+
+        let cipher_text = responder.encrypt(b"test message", &Associated::new(1, 0)).unwrap();
+        assert_eq!(b"test message".to_vec(),
+                   initiator.decrypt(&cipher_text, &Associated::new(1, 0)).unwrap());
+        assert_eq!(Err(Error::DecryptionFailed),
+                   responder.decrypt(&cipher_text, &Associated::new(1, 0)));
+    }
+
+    #[test]
+    fn handshake_failure_on_different_keys() {
+        let (initiator, responder) = handshake_with(Symmetric::new(&[0; 32], None).unwrap(),
+                                                    Symmetric::new(&[1; 32], None).unwrap());
+        assert_eq!(Some(Error::HandshakeFailed), initiator.err());
+        assert_eq!(Some(Error::HandshakeFailed), responder.err());
+    }
+
+    fn handshake(initiator_types: &[CipherType],
+                 responder_types: &[CipherType])
+                 -> (Result<Box<Cipher>>, Result<Box<Cipher>>) {
+        handshake_with(Symmetric::with_cipher_types(&[0; 32],
+                                                    Box::new(RandomNonceSource::new()),
+                                                    initiator_types)
+                           .unwrap(),
+                       Symmetric::with_cipher_types(&[0; 32],
+                                                    Box::new(RandomNonceSource::new()),
+                                                    responder_types)
+                           .unwrap())
+    }
+
+    fn handshake_with(initiator: Symmetric,
+                      responder: Symmetric)
+                      -> (Result<Box<Cipher>>, Result<Box<Cipher>>) {
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let join_handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            responder.handshake(&mut stream, Role::Responder)
+        });
+
+        let mut stream = net::TcpStream::connect(address).unwrap();
+        let initiator = initiator.handshake(&mut stream, Role::Initiator);
+        (initiator, join_handle.join().unwrap())
+    }
+
 }
@@ -0,0 +1,62 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::io;
+
+use node::ID;
+
+#[derive(Debug)]
+pub struct Announcement {
+    pub aknowledged: Vec<ID>,
+    pub failed: Vec<(ID, io::Error)>,
+}
+
+impl Announcement {
+    pub fn new() -> Self {
+        Announcement {
+            aknowledged: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    pub fn failed_peer_node_ids(&self) -> Vec<ID> {
+        self.failed.iter().map(|&(peer_node_id, _)| peer_node_id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io;
+    use node::ID;
+    use super::Announcement;
+
+    #[test]
+    fn failed_peers() {
+        let id_one = ID::new_random();
+        let id_two = ID::new_random();
+
+        let mut announcement = Announcement::new();
+        announcement.aknowledged.push(id_one);
+        announcement.failed.push((id_two, io::Error::new(io::ErrorKind::Other, "test")));
+        assert!(!announcement.is_complete());
+        assert_eq!(vec![id_two], announcement.failed_peer_node_ids());
+    }
+
+}
@@ -19,4 +19,14 @@ use super::super::Link;
 
 pub trait Balancer : Iterator<Item = Link> + Send + Sync {
     fn set_links(&mut self, &[Link]);
+
+    /// Sets the weights of the current links. Balancers that don't distribute by capacity
+    /// ignore them.
+    fn set_weights(&mut self, _weights: &[(Link, u32)]) {}
+
+    /// Selects the link for a request with the given key. Balancers without key affinity ignore
+    /// the key.
+    fn select(&mut self, _key: Option<&[u8]>) -> Option<Link> {
+        self.next()
+    }
 }
@@ -0,0 +1,180 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::iter::Iterator;
+use std::sync::Arc;
+
+use super::balancer::Balancer;
+use super::factory::Factory;
+use super::super::Link;
+use super::super::tracker::Statistic;
+
+const POINTS_PER_LINK: usize = 64;
+
+pub struct ConsistentHashFactory;
+
+impl ConsistentHashFactory {
+    pub fn new() -> Self {
+        ConsistentHashFactory
+    }
+}
+
+impl Factory for ConsistentHashFactory {
+    fn set_statistic(&mut self, _: Arc<Statistic>) {}
+
+    fn build(&self, _: &str) -> Box<Balancer<Item = Link>> {
+        Box::new(ConsistentHash::new())
+    }
+}
+
+/// Places the links on a hash ring and sends every request key to the next link on the ring, so
+/// requests with the same key stick to the same link. Adding or removing a link only moves the
+/// keys of its neighbours. Requests without a key are distributed round robin.
+pub struct ConsistentHash {
+    links: Vec<Link>,
+    ring: Vec<(u64, Link)>,
+    index: usize,
+}
+
+impl ConsistentHash {
+    pub fn new() -> Self {
+        ConsistentHash {
+            links: Vec::new(),
+            ring: Vec::new(),
+            index: 0,
+        }
+    }
+}
+
+impl Balancer for ConsistentHash {
+    fn set_links(&mut self, links: &[Link]) {
+        self.links = links.to_vec();
+        self.ring = Vec::with_capacity(links.len() * POINTS_PER_LINK);
+        for link in links {
+            let label = match *link {
+                Link::Local => b"local".to_vec(),
+                Link::Remote(ref peer_node_id) => peer_node_id.to_vec(),
+            };
+            for point in 0..POINTS_PER_LINK {
+                let mut bytes = label.clone();
+                bytes.push(point as u8);
+                self.ring.push((hash(&bytes), *link));
+            }
+        }
+        self.ring.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    fn select(&mut self, key: Option<&[u8]>) -> Option<Link> {
+        let key = match key {
+            Some(key) => key,
+            None => return self.next(),
+        };
+        if self.ring.is_empty() {
+            return None;
+        }
+        let value = hash(key);
+        let position = match self.ring.binary_search_by(|&(point, _)| point.cmp(&value)) {
+            Ok(position) => position,
+            Err(position) => position % self.ring.len(),
+        };
+        Some(self.ring[position].1)
+    }
+}
+
+impl Iterator for ConsistentHash {
+    type Item = Link;
+
+    fn next(&mut self) -> Option<Link> {
+        if self.links.is_empty() {
+            return None;
+        }
+        self.index = (self.index + 1) % self.links.len();
+        Some(self.links[self.index])
+    }
+}
+
+/// FNV-1a, which is stable across nodes and releases.
+fn hash(bytes: &[u8]) -> u64 {
+    let mut value = 0xcbf29ce484222325u64;
+    for byte in bytes {
+        value ^= *byte as u64;
+        value = value.wrapping_mul(0x100000001b3);
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::ConsistentHashFactory;
+    use super::super::Factory;
+    use node::ID;
+    use transport::direct::Link;
+
+    #[test]
+    fn same_key_sticks_to_link() {
+        let mut balancer = ConsistentHashFactory::new().build("test");
+        let links = vec![Link::Local,
+                         Link::Remote(ID::new_random()),
+                         Link::Remote(ID::new_random())];
+        balancer.set_links(&links);
+
+        for key in &[b"one".to_vec(), b"two".to_vec(), b"three".to_vec()] {
+            let link = balancer.select(Some(&key[..])).unwrap();
+            for _ in 0..4 {
+                assert_eq!(Some(link), balancer.select(Some(&key[..])));
+            }
+        }
+    }
+
+    #[test]
+    fn removing_a_link_only_moves_its_keys() {
+        let mut balancer = ConsistentHashFactory::new().build("test");
+        let removed = Link::Remote(ID::new_random());
+        let links = vec![Link::Local, Link::Remote(ID::new_random()), removed];
+        balancer.set_links(&links);
+
+        let keys = (0..100).map(|index| format!("key-{}", index).into_bytes()).collect::<Vec<_>>();
+        let before = keys.iter()
+                         .map(|key| balancer.select(Some(&key[..])).unwrap())
+                         .collect::<Vec<_>>();
+
+        balancer.set_links(&links[..2]);
+        for (key, link) in keys.iter().zip(before) {
+            let selected = balancer.select(Some(&key[..])).unwrap();
+            if link == removed {
+                assert!(selected != removed);
+            } else {
+                assert_eq!(link, selected);
+            }
+        }
+    }
+
+    #[test]
+    fn without_key() {
+        let mut balancer = ConsistentHashFactory::new().build("test");
+        let link_one = Link::Local;
+        let link_two = Link::Remote(ID::new_random());
+        balancer.set_links(&[link_one, link_two]);
+
+        let links = balancer.take(4).collect::<Vec<_>>();
+        assert_eq!(links[0], links[2]);
+        assert!(links[0] != links[1]);
+
+        let mut balancer = ConsistentHashFactory::new().build("test");
+        assert_eq!(None, balancer.select(Some(&b"key"[..])));
+    }
+
+}
@@ -71,14 +71,23 @@ impl DynamicRoundRobin {
             return;
         }
 
-        let durations = self.links
+        // ejected links are left out, unless all links are ejected.
+        let mut links = self.links
                             .iter()
-                            .map(|link| self.statistic.average(&self.name, link))
+                            .filter(|link| !self.statistic.is_ejected(&self.name, link))
+                            .cloned()
                             .collect::<Vec<_>>();
+        if links.is_empty() {
+            links = self.links.clone();
+        }
+
+        let durations = links.iter()
+                             .map(|link| self.statistic.average(&self.name, link))
+                             .collect::<Vec<_>>();
 
         let longest = durations.iter().max().unwrap();
         if longest == &Duration::zero() {
-            self.queue.append(&mut self.links.clone());
+            self.queue.append(&mut links.clone());
             self.queue.reverse();
             return;
         }
@@ -96,7 +105,7 @@ impl DynamicRoundRobin {
 
         for (index, &count) in counts.iter().enumerate() {
             for _ in 0..count {
-                self.queue.push(self.links[index]);
+                self.queue.push(links[index]);
             }
             self.queue.reverse();
         }
@@ -106,7 +115,10 @@ impl DynamicRoundRobin {
 impl Balancer for DynamicRoundRobin {
     fn set_links(&mut self, links: &[Link]) {
         self.links = links.to_vec();
-        self.queue = Vec::new();
+
+        // keep the current round for links that are still present, new links join with the next round.
+        let links = &self.links;
+        self.queue.retain(|link| links.contains(link));
     }
 }
 
@@ -171,4 +183,75 @@ mod tests {
         assert_eq!(vec![link_one, link_one, link_two],
                    balancer.take(3).collect::<Vec<_>>());
     }
+
+    #[test]
+    fn round_preserved_across_link_updates() {
+        let remote_id = ID::new_random();
+
+        let statistic = Arc::new(Statistic::new());
+        statistic.push(Subject::local("test"), Duration::milliseconds(50));
+        statistic.push(Subject::remote("test", remote_id),
+                       Duration::milliseconds(100));
+
+        let mut factory = DynamicRoundRobinFactory::new();
+        factory.set_statistic(statistic);
+        let mut balancer = factory.build("test");
+
+        let link_one = Link::Local;
+        let link_two = Link::Remote(remote_id);
+        let link_three = Link::Remote(ID::new_random());
+        balancer.set_links(&[link_one, link_two]);
+
+        assert_eq!(Some(link_one), balancer.next());
+
+        balancer.set_links(&[link_one, link_two, link_three]);
+
+        assert_eq!(vec![link_one, link_two],
+                   balancer.take(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn removed_links_dropped_from_current_round() {
+        let remote_id = ID::new_random();
+
+        let statistic = Arc::new(Statistic::new());
+        statistic.push(Subject::local("test"), Duration::milliseconds(50));
+        statistic.push(Subject::remote("test", remote_id),
+                       Duration::milliseconds(100));
+
+        let mut factory = DynamicRoundRobinFactory::new();
+        factory.set_statistic(statistic);
+        let mut balancer = factory.build("test");
+
+        let link_one = Link::Local;
+        let link_two = Link::Remote(remote_id);
+        balancer.set_links(&[link_one, link_two]);
+
+        assert_eq!(Some(link_one), balancer.next());
+
+        balancer.set_links(&[link_two]);
+
+        assert_eq!(vec![link_two, link_two],
+                   balancer.take(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn ejected_links_left_out() {
+        let remote_id = ID::new_random();
+
+        let statistic = Arc::new(Statistic::new());
+        statistic.set_ejection(1, Duration::seconds(60));
+        statistic.push_failure(Subject::remote("test", remote_id));
+
+        let mut factory = DynamicRoundRobinFactory::new();
+        factory.set_statistic(statistic);
+        let mut balancer = factory.build("test");
+
+        let link_one = Link::Local;
+        let link_two = Link::Remote(remote_id);
+        balancer.set_links(&[link_one, link_two]);
+
+        assert_eq!(vec![link_one, link_one],
+                   balancer.take(2).collect::<Vec<_>>());
+    }
 }
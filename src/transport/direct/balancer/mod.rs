@@ -15,8 +15,14 @@
 
 pub mod balancer;
 pub mod factory;
+mod consistent_hash;
 mod dynamic_round_robin;
+mod registry;
+mod weighted_round_robin;
 
 pub use self::balancer::Balancer;
 pub use self::factory::Factory;
+pub use self::consistent_hash::{ConsistentHash, ConsistentHashFactory};
 pub use self::dynamic_round_robin::{DynamicRoundRobin, DynamicRoundRobinFactory};
+pub use self::registry::{Constructor, Registry, registry};
+pub use self::weighted_round_robin::{WeightedRoundRobin, WeightedRoundRobinFactory};
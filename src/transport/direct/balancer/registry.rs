@@ -0,0 +1,120 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::{ONCE_INIT, Once, RwLock};
+
+use super::factory::Factory;
+use super::{ConsistentHashFactory, DynamicRoundRobinFactory, WeightedRoundRobinFactory};
+
+static INIT: Once = ONCE_INIT;
+static mut REGISTRY: *const Registry = 0 as *const Registry;
+
+/// Returns the process wide registry of balancer types.
+pub fn registry() -> &'static Registry {
+    unsafe {
+        INIT.call_once(|| {
+            REGISTRY = Box::into_raw(Box::new(Registry::new()));
+        });
+        &*REGISTRY
+    }
+}
+
+pub type Constructor = Fn() -> Box<Factory> + Send + Sync;
+
+/// Maps balancer type names to constructors of their factories. The built-in balancers are
+/// registered from the start.
+pub struct Registry {
+    constructors: RwLock<HashMap<String, Box<Constructor>>>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        let registry = Registry { constructors: RwLock::new(HashMap::new()) };
+        registry.register("dynamic_round_robin",
+                          Box::new(|| -> Box<Factory> {
+                              Box::new(DynamicRoundRobinFactory::new())
+                          }));
+        registry.register("weighted_round_robin",
+                          Box::new(|| -> Box<Factory> {
+                              Box::new(WeightedRoundRobinFactory::new())
+                          }));
+        registry.register("consistent_hash",
+                          Box::new(|| -> Box<Factory> { Box::new(ConsistentHashFactory::new()) }));
+        registry
+    }
+
+    /// Registers a balancer type under the given name. A type that has been registered under
+    /// the same name before gets replaced.
+    pub fn register(&self, name: &str, constructor: Box<Constructor>) {
+        self.constructors.write().unwrap().insert(name.to_string(), constructor);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.constructors.read().unwrap().contains_key(name)
+    }
+
+    /// Returns the registered names in alphabetical order.
+    pub fn names(&self) -> Vec<String> {
+        let mut names = self.constructors.read().unwrap().keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    pub fn build(&self, name: &str) -> Option<Box<Factory>> {
+        self.constructors.read().unwrap().get(name).map(|constructor| constructor())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+
+    use super::Registry;
+    use super::super::{Balancer, ConsistentHashFactory, Factory};
+    use transport::direct::Link;
+    use transport::direct::tracker::Statistic;
+
+    struct CustomFactory;
+
+    impl Factory for CustomFactory {
+        fn set_statistic(&mut self, _: Arc<Statistic>) {}
+
+        fn build(&self, name: &str) -> Box<Balancer<Item = Link>> {
+            ConsistentHashFactory::new().build(name)
+        }
+    }
+
+    #[test]
+    fn register_and_build() {
+        let registry = Registry::new();
+        assert!(registry.contains("dynamic_round_robin"));
+        assert!(!registry.contains("custom"));
+        assert!(registry.build("custom").is_none());
+
+        registry.register("custom", Box::new(|| -> Box<Factory> { Box::new(CustomFactory) }));
+
+        assert!(registry.contains("custom"));
+        assert_eq!(vec!["consistent_hash",
+                        "custom",
+                        "dynamic_round_robin",
+                        "weighted_round_robin"],
+                   registry.names());
+        let mut balancer = registry.build("custom").unwrap().build("test");
+        balancer.set_links(&[Link::Local]);
+        assert_eq!(Some(Link::Local), balancer.next());
+    }
+
+}
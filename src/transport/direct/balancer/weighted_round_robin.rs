@@ -0,0 +1,153 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::iter::Iterator;
+use std::sync::Arc;
+
+use super::balancer::Balancer;
+use super::factory::Factory;
+use super::super::Link;
+use super::super::tracker::Statistic;
+use node::service;
+
+pub struct WeightedRoundRobinFactory;
+
+impl WeightedRoundRobinFactory {
+    pub fn new() -> Self {
+        WeightedRoundRobinFactory
+    }
+}
+
+impl Factory for WeightedRoundRobinFactory {
+    fn set_statistic(&mut self, _: Arc<Statistic>) {}
+
+    fn build(&self, _: &str) -> Box<Balancer<Item = Link>> {
+        Box::new(WeightedRoundRobin::new())
+    }
+}
+
+/// Distributes the requests proportionally to the weights of the links. The picks of a round are
+/// interleaved, so a heavy link doesn't get its whole share in one burst.
+pub struct WeightedRoundRobin {
+    entries: Vec<WeightedLink>,
+}
+
+struct WeightedLink {
+    link: Link,
+    weight: i64,
+    current: i64,
+}
+
+impl WeightedRoundRobin {
+    pub fn new() -> Self {
+        WeightedRoundRobin { entries: Vec::new() }
+    }
+}
+
+impl Balancer for WeightedRoundRobin {
+    fn set_links(&mut self, links: &[Link]) {
+        self.entries.retain(|entry| links.contains(&entry.link));
+        for link in links {
+            if !self.entries.iter().any(|entry| entry.link == *link) {
+                self.entries.push(WeightedLink {
+                    link: *link,
+                    weight: service::DEFAULT_WEIGHT as i64,
+                    current: 0,
+                });
+            }
+        }
+    }
+
+    fn set_weights(&mut self, weights: &[(Link, u32)]) {
+        for entry in &mut self.entries {
+            if let Some(&(_, weight)) = weights.iter().find(|&&(link, _)| link == entry.link) {
+                entry.weight = weight as i64;
+            }
+        }
+    }
+}
+
+impl Iterator for WeightedRoundRobin {
+    type Item = Link;
+
+    fn next(&mut self) -> Option<Link> {
+        let total = self.entries.iter().map(|entry| entry.weight).sum::<i64>();
+        let mut selected: Option<usize> = None;
+        for index in 0..self.entries.len() {
+            self.entries[index].current += self.entries[index].weight;
+            selected = match selected {
+                Some(selected) if self.entries[selected].current >=
+                                  self.entries[index].current => Some(selected),
+                _ => Some(index),
+            };
+        }
+
+        selected.map(|index| {
+            self.entries[index].current -= total;
+            self.entries[index].link
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::WeightedRoundRobinFactory;
+    use super::super::Factory;
+    use node::ID;
+    use transport::direct::Link;
+
+    #[test]
+    fn distribution_by_weight() {
+        let mut balancer = WeightedRoundRobinFactory::new().build("test");
+
+        let link_one = Link::Local;
+        let link_two = Link::Remote(ID::new_random());
+        balancer.set_links(&[link_one, link_two]);
+        balancer.set_weights(&[(link_one, 1), (link_two, 3)]);
+
+        assert_eq!(vec![link_two, link_one, link_two, link_two],
+                   balancer.take(4).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn equal_weights() {
+        let mut balancer = WeightedRoundRobinFactory::new().build("test");
+
+        let link_one = Link::Local;
+        let link_two = Link::Remote(ID::new_random());
+        balancer.set_links(&[link_one, link_two]);
+
+        assert_eq!(vec![link_one, link_two, link_one, link_two],
+                   balancer.take(4).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn removed_links_are_not_selected() {
+        let mut balancer = WeightedRoundRobinFactory::new().build("test");
+
+        let link_one = Link::Local;
+        let link_two = Link::Remote(ID::new_random());
+        balancer.set_links(&[link_one, link_two]);
+        balancer.set_weights(&[(link_one, 1), (link_two, 5)]);
+        balancer.set_links(&[link_one]);
+
+        assert_eq!(vec![link_one, link_one], balancer.take(2).collect::<Vec<_>>());
+
+        balancer.set_links(&[]);
+        assert_eq!(None, balancer.next());
+    }
+
+}
@@ -0,0 +1,209 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use time::{Duration, SteadyTime};
+
+use metric::Metric;
+use util::clock::{self, Clock};
+use super::Link;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum State {
+    Closed,
+    HalfOpen,
+    Open,
+}
+
+/// Keeps a circuit breaker per service and link. After the configured number of consecutive
+/// failures, the breaker of a link opens and requests to it fail fast. After the cool-down, it
+/// half-opens and lets a single trial request through, which either closes or re-opens it. The
+/// state is exported as the `service.<name>.outbound.<link>.breaker` gauge (0 = closed,
+/// 1 = half open, 2 = open).
+pub struct Breaker {
+    metric: Arc<Metric>,
+    clock: Arc<Clock>,
+    settings: RwLock<Option<(usize, Duration)>>,
+    circuits: Mutex<HashMap<(String, Link), Circuit>>,
+}
+
+struct Circuit {
+    failures: usize,
+    opened_at: Option<SteadyTime>,
+    trial: bool,
+}
+
+impl Breaker {
+    pub fn new(metric: Arc<Metric>) -> Breaker {
+        Self::with_clock(metric, Arc::new(clock::System))
+    }
+
+    pub fn with_clock(metric: Arc<Metric>, clock: Arc<Clock>) -> Breaker {
+        Breaker {
+            metric: metric,
+            clock: clock,
+            settings: RwLock::new(None),
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Enables the breakers. Without this call, all links are always allowed.
+    pub fn enable(&self, consecutive_failures: usize, cool_down: Duration) {
+        *self.settings.write().unwrap() = Some((consecutive_failures, cool_down));
+    }
+
+    /// Returns true if a request may be sent over the link.
+    pub fn allow(&self, name: &str, link: &Link) -> bool {
+        let cool_down = match *self.settings.read().unwrap() {
+            Some((_, cool_down)) => cool_down,
+            None => return true,
+        };
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = match circuits.get_mut(&(name.to_string(), *link)) {
+            Some(circuit) => circuit,
+            None => return true,
+        };
+        let opened_at = match circuit.opened_at {
+            Some(opened_at) => opened_at,
+            None => return true,
+        };
+        if circuit.trial || self.clock.now() < opened_at + cool_down {
+            return false;
+        }
+        circuit.trial = true;
+        self.set_gauge(name, link, State::HalfOpen);
+        true
+    }
+
+    pub fn succeed(&self, name: &str, link: &Link) {
+        if self.settings.read().unwrap().is_none() {
+            return;
+        }
+        let closed = self.circuits
+                         .lock()
+                         .unwrap()
+                         .remove(&(name.to_string(), *link))
+                         .map(|circuit| circuit.opened_at.is_some())
+                         .unwrap_or(false);
+        if closed {
+            info!("closed breaker of {} to {:?}", name, link);
+            self.set_gauge(name, link, State::Closed);
+        }
+    }
+
+    pub fn fail(&self, name: &str, link: &Link) {
+        let threshold = match *self.settings.read().unwrap() {
+            Some((threshold, _)) => threshold,
+            None => return,
+        };
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry((name.to_string(), *link)).or_insert(Circuit {
+            failures: 0,
+            opened_at: None,
+            trial: false,
+        });
+        circuit.failures += 1;
+        if circuit.trial || (circuit.opened_at.is_none() && circuit.failures >= threshold) {
+            warn!("opened breaker of {} to {:?} after {} consecutive failures",
+                  name,
+                  link,
+                  circuit.failures);
+            circuit.opened_at = Some(self.clock.now());
+            circuit.trial = false;
+            self.set_gauge(name, link, State::Open);
+        }
+    }
+
+    pub fn state(&self, name: &str, link: &Link) -> State {
+        match self.circuits.lock().unwrap().get(&(name.to_string(), *link)) {
+            Some(&Circuit { trial: true, .. }) => State::HalfOpen,
+            Some(&Circuit { opened_at: Some(_), .. }) => State::Open,
+            _ => State::Closed,
+        }
+    }
+
+    fn set_gauge(&self, name: &str, link: &Link, state: State) {
+        let link = match *link {
+            Link::Local => "local".to_string(),
+            Link::Remote(ref peer_node_id) => peer_node_id.to_string(),
+        };
+        self.metric
+            .gauge(&format!("service.{}.outbound.{}.breaker", name, link))
+            .set(match state {
+                State::Closed => 0,
+                State::HalfOpen => 1,
+                State::Open => 2,
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+    use time::Duration;
+
+    use super::{Breaker, State};
+    use metric::{self, Metric};
+    use node::ID;
+    use transport::direct::Link;
+    use util::clock::Mock;
+
+    #[test]
+    fn open_and_half_open() {
+        let metric = Arc::new(metric::Memory::new());
+        let clock = Arc::new(Mock::new());
+        let breaker = Breaker::with_clock(metric.clone(), clock.clone());
+        breaker.enable(2, Duration::seconds(10));
+        let peer_node_id = ID::new_random();
+        let link = Link::Remote(peer_node_id);
+        let gauge = format!("service.echo.outbound.{}.breaker", peer_node_id);
+
+        breaker.fail("echo", &link);
+        assert!(breaker.allow("echo", &link));
+        breaker.fail("echo", &link);
+        assert_eq!(State::Open, breaker.state("echo", &link));
+        assert!(!breaker.allow("echo", &link));
+        assert!(breaker.allow("other", &link));
+        assert_eq!(Some(metric::Value::Gauge(2)), metric.get(&gauge));
+
+        clock.advance(Duration::seconds(10));
+        assert!(breaker.allow("echo", &link));
+        assert_eq!(State::HalfOpen, breaker.state("echo", &link));
+        assert!(!breaker.allow("echo", &link));
+        breaker.fail("echo", &link);
+        assert_eq!(State::Open, breaker.state("echo", &link));
+        assert!(!breaker.allow("echo", &link));
+
+        clock.advance(Duration::seconds(10));
+        assert!(breaker.allow("echo", &link));
+        breaker.succeed("echo", &link);
+        assert_eq!(State::Closed, breaker.state("echo", &link));
+        assert!(breaker.allow("echo", &link));
+        assert_eq!(Some(metric::Value::Gauge(0)), metric.get(&gauge));
+    }
+
+    #[test]
+    fn disabled() {
+        let breaker = Breaker::new(Arc::new(metric::Memory::new()));
+        for _ in 0..10 {
+            breaker.fail("echo", &Link::Local);
+        }
+        assert!(breaker.allow("echo", &Link::Local));
+        assert_eq!(State::Closed, breaker.state("echo", &Link::Local));
+    }
+
+}
@@ -0,0 +1,111 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::net::SocketAddr;
+use time::Duration;
+
+use transport::Capture;
+use util::memory::Watermark;
+use util::net::Proxy;
+use super::container;
+use super::retry;
+
+const DEFAULT_JOIN_PARALLELISM: usize = 8;
+const DEFAULT_COALESCE_SIZE: usize = 1024;
+const DEFAULT_RECONNECT_MAX_BACKOFF_MS: i64 = 30000;
+const DEFAULT_FLOW_CONTROL_WINDOW: usize = 16;
+const DEFAULT_PACKET_BUFFER_SIZE: usize = 64 * 1024;
+const DEFAULT_DRAIN_LIMIT: usize = 1;
+const DEFAULT_DRAIN_LEASE_TTL_S: i64 = 300;
+
+/// The settings of the direct transport. `Config::new` sets the defaults for everything besides
+/// the local addresses, so only the deviating fields have to be set.
+pub struct Config {
+    pub local_addresses: Vec<SocketAddr>,
+    pub public_address: Option<SocketAddr>,
+    pub request_timeout: Option<Duration>,
+    pub service_grace_period: Option<Duration>,
+    pub max_services_per_peer: Option<usize>,
+    pub max_service_name_length: Option<usize>,
+    pub strict_service_names: bool,
+    pub aknowledge_timeout: Option<Duration>,
+    pub join_parallelism: usize,
+    pub join_dial_timeout: Option<Duration>,
+    pub wide_request_ids: bool,
+    pub coalesce_delay: Option<Duration>,
+    pub coalesce_size: usize,
+    pub heartbeat_interval: Option<Duration>,
+    pub heartbeat_timeout: Option<Duration>,
+    pub reconnect_backoff: Option<Duration>,
+    pub reconnect_max_backoff: Duration,
+    pub compression_threshold: Option<usize>,
+    pub flow_control_window: usize,
+    pub packet_buffer_size: usize,
+    pub idle_timeout: Option<Duration>,
+    pub zone: Option<String>,
+    pub drain_limit: usize,
+    pub drain_lease_ttl: Duration,
+    pub upload_limit: Option<usize>,
+    pub download_limit: Option<usize>,
+    pub max_message_size: usize,
+    pub capture: Option<Capture>,
+    pub max_hops: usize,
+    pub proxy: Option<Proxy>,
+    pub memory: Option<Watermark>,
+    pub outlier_ejection: Option<(usize, Duration)>,
+    pub breaker: Option<(usize, Duration)>,
+    pub retry_policies: retry::Policies,
+}
+
+impl Config {
+    pub fn new(local_addresses: Vec<SocketAddr>) -> Config {
+        Config {
+            local_addresses: local_addresses,
+            public_address: None,
+            request_timeout: None,
+            service_grace_period: None,
+            max_services_per_peer: None,
+            max_service_name_length: None,
+            strict_service_names: false,
+            aknowledge_timeout: None,
+            join_parallelism: DEFAULT_JOIN_PARALLELISM,
+            join_dial_timeout: None,
+            wide_request_ids: false,
+            coalesce_delay: None,
+            coalesce_size: DEFAULT_COALESCE_SIZE,
+            heartbeat_interval: None,
+            heartbeat_timeout: None,
+            reconnect_backoff: None,
+            reconnect_max_backoff: Duration::milliseconds(DEFAULT_RECONNECT_MAX_BACKOFF_MS),
+            compression_threshold: None,
+            flow_control_window: DEFAULT_FLOW_CONTROL_WINDOW,
+            packet_buffer_size: DEFAULT_PACKET_BUFFER_SIZE,
+            idle_timeout: None,
+            zone: None,
+            drain_limit: DEFAULT_DRAIN_LIMIT,
+            drain_lease_ttl: Duration::seconds(DEFAULT_DRAIN_LEASE_TTL_S),
+            upload_limit: None,
+            download_limit: None,
+            max_message_size: container::MAX_CONTAINER_SIZE,
+            capture: None,
+            max_hops: 0,
+            proxy: None,
+            memory: None,
+            outlier_ejection: None,
+            breaker: None,
+            retry_policies: retry::Policies::new(),
+        }
+    }
+}
@@ -13,51 +13,139 @@
 // limitations under the License.
 //
 
+use std::collections::HashSet;
 use std::fmt;
-use std::io::{self, Read, Write};
+use std::io;
 use std::net::{self, SocketAddr};
 use std::result;
-use std::sync::{Arc, Mutex, mpsc};
+use std::sync::{Arc, Mutex, Weak, mpsc};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread;
+use std::u32;
+use time::{self, Duration, SteadyTime, Timespec};
 
 use message;
 use node::{ID, request, service};
 use super::packet;
 use super::dispatcher::Dispatcher;
 use super::container::{self, Container};
+use super::writer::{Priority, Writer};
 use super::super::cipher;
+use transport::{ConnectionStats, Direction};
+use util::clock::Clock;
+use util::rate;
+use util::thread::supervisor;
+use util::time::to_std_duration;
 
 pub struct Connection {
-    tx_stream: Arc<Mutex<cipher::Stream<net::TcpStream>>>,
+    writer: Arc<Writer>,
     thread: Option<thread::JoinHandle<()>>,
 
     node_id: ID,
     peer_node_id: ID,
     peer_public_address: SocketAddr,
+    peer_protocol_version: u32,
+    observed_address: Option<SocketAddr>,
+    peer_zone: Option<String>,
+    direction: Direction,
+    compression_threshold: Option<usize>,
+    max_message_size: usize,
+    request_window: Arc<packet::Window>,
+    response_window: Arc<packet::Window>,
+    packet_pool: Arc<packet::Pool>,
+    clock: Arc<Clock>,
+
+    started_at: time::Tm,
+    last_active: Arc<Mutex<SteadyTime>>,
+    counters: cipher::stream::Counters,
+    pending_requests: Arc<Mutex<HashSet<u64>>>,
+    pending_responses: Arc<AtomicUsize>,
+    last_error: Arc<Mutex<Option<String>>>,
 
     aknowledges_tx: Mutex<mpsc::Sender<mpsc::Sender<()>>>,
+    aknowledge_timeout: Option<Duration>,
+    stats_waiters: Arc<Waiters<ConnectionStats>>,
+    drain_waiters: Arc<Waiters<(bool, usize)>>,
 
     error_handler: Arc<Mutex<Option<Box<Fn(ID, &io::Error) + Send>>>>,
     drop_handler: Box<Fn(ID) + Send + Sync>,
 }
 
 pub struct Handlers {
-    pub add_services: Box<Fn(ID, Vec<String>) + Send>,
+    pub add_services: Box<Fn(ID, Vec<(String, u32, u32, service::Metadata)>) + Send>,
     pub remove_services: Box<Fn(ID, Vec<String>) + Send>,
-    pub request: Box<Fn(ID, u32, &str, Box<request::Reader>) + Send + 'static>,
-    pub response: Box<Fn(u32, service::Result) -> result::Result<(), io::Error> + Send>,
+    pub request: Box<Fn(ID, u64, &str, Option<u32>, Option<Timespec>, Option<String>,
+                        Box<request::Reader>) + Send + 'static>,
+    pub response: Box<Fn(u64, service::Result) -> result::Result<(), io::Error> + Send>,
+    pub idle: Box<Fn(ID, SocketAddr) + Send>,
+    pub goodbye: Box<Fn(ID, SocketAddr) + Send>,
+    pub drain: Box<Fn(ID, bool) -> (bool, usize) + Send>,
+    pub notification: Box<Fn(ID, &str, Option<u32>, Option<String>, Vec<u8>) + Send>,
     pub drop: Box<Fn(ID) + Send + Sync>,
 }
 
+/// The settings that all connections of a transport share.
+#[derive(Clone)]
+pub struct Options {
+    pub zone: Option<String>,
+    pub aknowledge_timeout: Option<Duration>,
+    pub coalesce_delay: Option<Duration>,
+    pub coalesce_size: usize,
+    pub heartbeat_interval: Option<Duration>,
+    pub heartbeat_timeout: Option<Duration>,
+    pub compression_threshold: Option<usize>,
+    pub flow_control_window: usize,
+    pub upload_limit: Option<usize>,
+    pub download_limit: Option<usize>,
+    pub max_message_size: usize,
+    pub packet_pool: Arc<packet::Pool>,
+    pub clock: Arc<Clock>,
+}
+
+/// The state the reader thread of a connection needs to process inbound containers.
+struct Inbound {
+    node_id: ID,
+    peer_node_id: ID,
+    peer_public_address: SocketAddr,
+    writer: Arc<Writer>,
+    aknowledges_rx: mpsc::Receiver<mpsc::Sender<()>>,
+    stats_waiters: Arc<Waiters<ConnectionStats>>,
+    drain_waiters: Arc<Waiters<(bool, usize)>>,
+    pending_requests: Arc<Mutex<HashSet<u64>>>,
+    pending_responses: Arc<AtomicUsize>,
+    started_at: time::Tm,
+    last_error: Arc<Mutex<Option<String>>>,
+    clock: Arc<Clock>,
+    request_dispatcher: Dispatcher,
+    response_dispatcher: Dispatcher,
+    request_window: Arc<packet::Window>,
+    response_window: Arc<packet::Window>,
+    add_services_handler: Box<Fn(ID, Vec<(String, u32, u32, service::Metadata)>) + Send>,
+    remove_services_handler: Box<Fn(ID, Vec<String>) + Send>,
+    request_handler: Box<Fn(ID, u64, &str, Option<u32>, Option<Timespec>, Option<String>,
+                            Box<request::Reader>) + Send + 'static>,
+    response_handler: Box<Fn(u64, service::Result) -> result::Result<(), io::Error> + Send>,
+    idle_handler: Box<Fn(ID, SocketAddr) + Send>,
+    goodbye_handler: Box<Fn(ID, SocketAddr) + Send>,
+    drain_handler: Box<Fn(ID, bool) -> (bool, usize) + Send>,
+    notification_handler: Box<Fn(ID, &str, Option<u32>, Option<String>, Vec<u8>) + Send>,
+}
+
 impl Connection {
     pub fn new_inbound(stream: cipher::Stream<net::TcpStream>,
                        node_id: ID,
                        public_address: SocketAddr,
                        peers: &[(ID, SocketAddr)],
+                       options: &Options,
                        handlers: Handlers)
                        -> io::Result<Connection> {
 
-        let (connection, sender) = try!(Self::new(stream, node_id, public_address, handlers));
+        let (connection, sender) = try!(Self::new(stream,
+                                                  node_id,
+                                                  public_address,
+                                                  Direction::Inbound,
+                                                  options,
+                                                  handlers));
 
         try!(connection.send_peers(peers));
         sender.send(true).unwrap();
@@ -68,10 +156,16 @@ impl Connection {
     pub fn new_outbound(stream: cipher::Stream<net::TcpStream>,
                         node_id: ID,
                         public_address: SocketAddr,
+                        options: &Options,
                         handlers: Handlers)
                         -> io::Result<(Connection, Vec<(ID, SocketAddr)>)> {
 
-        let (connection, sender) = try!(Self::new(stream, node_id, public_address, handlers));
+        let (connection, sender) = try!(Self::new(stream,
+                                                  node_id,
+                                                  public_address,
+                                                  Direction::Outbound,
+                                                  options,
+                                                  handlers));
 
         let peers = try!(connection.receive_peers());
         sender.send(true).unwrap();
@@ -82,49 +176,190 @@ impl Connection {
     fn new(stream: cipher::Stream<net::TcpStream>,
            node_id: ID,
            public_address: SocketAddr,
+           direction: Direction,
+           options: &Options,
            handlers: Handlers)
            -> io::Result<(Connection, mpsc::Sender<bool>)> {
 
-        let tx_stream = Arc::new(Mutex::new(stream.try_clone().unwrap()));
-        let tx_stream_clone = tx_stream.clone();
+        let Options { zone,
+                      aknowledge_timeout,
+                      coalesce_delay,
+                      coalesce_size,
+                      heartbeat_interval,
+                      heartbeat_timeout,
+                      compression_threshold,
+                      flow_control_window,
+                      upload_limit,
+                      download_limit,
+                      max_message_size,
+                      packet_pool,
+                      clock } = options.clone();
+
+        let mut stream = stream;
+        // a frame holds a container and at most the coalesced bytes that were written before it.
+        stream.set_max_frame_size(max_message_size + coalesce_size +
+                                  cipher::stream::FRAME_OVERHEAD);
+        stream.set_limiters(download_limit.map(|limit| {
+                                Arc::new(rate::Limiter::with_clock(limit, clock.clone()))
+                            }),
+                            upload_limit.map(|limit| {
+                                Arc::new(rate::Limiter::with_clock(limit, clock.clone()))
+                            }));
+
+        let counters = stream.counters();
+        let writer = Arc::new(try!(Writer::new(stream.try_clone().unwrap())));
+        let writer_clone = writer.clone();
         let mut rx_stream = stream;
 
         let (aknowledges_tx, aknowledges_rx) = mpsc::channel();
+        let stats_waiters = Arc::new(Waiters::new());
+        let stats_waiters_clone = stats_waiters.clone();
+        let drain_waiters = Arc::new(Waiters::new());
+        let drain_waiters_clone = drain_waiters.clone();
 
         let Handlers{ add_services: add_services_handler,
                       remove_services: remove_services_handler,
                       request: request_handler,
                       response: response_handler,
+                      idle: idle_handler,
+                      goodbye: goodbye_handler,
+                      drain: drain_handler,
+                      notification: notification_handler,
                       drop: drop_handler } = handlers;
         let error_handler: Arc<Mutex<Option<Box<Fn(ID, &io::Error) + Send>>>> =
             Arc::new(Mutex::new(None));
         let error_handler_clone = error_handler.clone();
 
-        let (peer_node_id, peer_public_address) = {
-            let mut tx_stream = tx_stream.lock().unwrap();
-            try!(container::pack_introduction(node_id, public_address).write(&mut *tx_stream));
-            try!(container::unpack_introduction(try!(Container::read(&mut *tx_stream))))
+        let (peer_node_id,
+             peer_public_address,
+             peer_protocol_version,
+             peer_compression,
+             observed_address,
+             peer_zone) = {
+            let mut tx_stream = writer.stream();
+            let peer_address = tx_stream.get_ref().peer_addr().ok();
+            try!(container::pack_introduction(node_id,
+                                              public_address,
+                                              peer_address,
+                                              compression_threshold.is_some(),
+                                              zone)
+                     .write(&mut *tx_stream));
+            try!(container::unpack_introduction(try!(Container::read_limited(&mut *tx_stream,
+                                                                             max_message_size))))
+        };
+        let compression_threshold = if peer_compression {
+            compression_threshold
+        } else {
+            None
+        };
+        let flow_control = peer_protocol_version >= container::FLOW_CONTROL_VERSION;
+        let flow_control_window = if flow_control {
+            flow_control_window
+        } else {
+            0
         };
+        let request_window = Arc::new(packet::Window::new(flow_control_window));
+        let request_window_clone = request_window.clone();
+        let response_window = Arc::new(packet::Window::new(flow_control_window));
+        let response_window_clone = response_window.clone();
+
+        if let Some(coalesce_delay) = coalesce_delay {
+            writer.stream().set_coalesce_size(coalesce_size);
+            let writer_weak = Arc::downgrade(&writer);
+            supervisor().spawn("connection flusher", move || {
+                flush_periodically(&writer_weak, coalesce_delay);
+            });
+        }
+
+        let pending_requests = Arc::new(Mutex::new(HashSet::new()));
+        let pending_requests_clone = pending_requests.clone();
+        let pending_responses = Arc::new(AtomicUsize::new(0));
+        let pending_responses_clone = pending_responses.clone();
+        let started_at = clock.utc();
+        let last_error = Arc::new(Mutex::new(None));
+        let last_error_clone = last_error.clone();
+        let last_seen = Arc::new(Mutex::new(clock.now()));
+        let last_seen_clone = last_seen.clone();
+        let last_active = Arc::new(Mutex::new(clock.now()));
+        let last_active_clone = last_active.clone();
+        let clock_clone = clock.clone();
+
+        if let Some(heartbeat_interval) = heartbeat_interval {
+            if peer_protocol_version >= container::HEARTBEAT_VERSION {
+                let writer_weak = Arc::downgrade(&writer);
+                let last_seen = last_seen.clone();
+                let last_error = last_error.clone();
+                let error_handler = error_handler.clone();
+                let clock = clock.clone();
+                supervisor().spawn("connection heartbeat", move || {
+                    send_heartbeats(peer_node_id,
+                                    &writer_weak,
+                                    &*clock,
+                                    &last_seen,
+                                    &last_error,
+                                    &error_handler,
+                                    heartbeat_interval,
+                                    heartbeat_timeout);
+                });
+            }
+        }
 
         let (sender, receiver) = mpsc::channel();
-        let thread = Some(thread::spawn(move || {
+        let thread = Some(supervisor().spawn("connection reader", move || {
             receiver.recv().unwrap();
-            let request_dispatcher = Dispatcher::new();
-            let response_dispatcher = Dispatcher::new();
+            let (request_dispatcher, response_dispatcher) = if flow_control {
+                (Dispatcher::with_credit_handler(credit_handler(&writer_clone,
+                                                                container::PacketType::Request)),
+                 Dispatcher::with_credit_handler(credit_handler(&writer_clone,
+                                                                container::PacketType::Response)))
+            } else {
+                (Dispatcher::new(), Dispatcher::new())
+            };
+            let inbound = Inbound {
+                node_id: node_id,
+                peer_node_id: peer_node_id,
+                peer_public_address: peer_public_address,
+                writer: writer_clone,
+                aknowledges_rx: aknowledges_rx,
+                stats_waiters: stats_waiters_clone,
+                drain_waiters: drain_waiters_clone,
+                pending_requests: pending_requests_clone,
+                pending_responses: pending_responses_clone,
+                started_at: started_at,
+                last_error: last_error_clone.clone(),
+                clock: clock_clone.clone(),
+                request_dispatcher: request_dispatcher,
+                response_dispatcher: response_dispatcher,
+                request_window: request_window_clone.clone(),
+                response_window: response_window_clone.clone(),
+                add_services_handler: add_services_handler,
+                remove_services_handler: remove_services_handler,
+                request_handler: request_handler,
+                response_handler: response_handler,
+                idle_handler: idle_handler,
+                goodbye_handler: goodbye_handler,
+                drain_handler: drain_handler,
+                notification_handler: notification_handler,
+            };
             loop {
-                match process_inbound_container(node_id,
-                                                peer_node_id,
-                                                &mut rx_stream,
-                                                &tx_stream_clone,
-                                                &aknowledges_rx,
-                                                &request_dispatcher,
-                                                &response_dispatcher,
-                                                &add_services_handler,
-                                                &remove_services_handler,
-                                                &request_handler,
-                                                &response_handler) {
-                    Ok(()) => {}
+                let container = cast_eof_to_aborted(Container::read_limited(&mut rx_stream,
+                                                                            max_message_size));
+                let result = container.and_then(|container| {
+                    if is_activity(container.get_kind()) {
+                        *last_active_clone.lock().unwrap() = clock_clone.now();
+                    }
+                    inbound.process(container, &mut rx_stream)
+                });
+                match result {
+                    Ok(()) => {
+                        *last_seen_clone.lock().unwrap() = clock_clone.now();
+                    }
                     Err(ref error) => {
+                        record_error(&last_error_clone, error);
+                        request_window_clone.close();
+                        response_window_clone.close();
+                        inbound.stats_waiters.close();
+                        inbound.drain_waiters.close();
                         if let Some(error_handler) = error_handler_clone.lock().unwrap().take() {
                             error_handler(peer_node_id, error);
                         }
@@ -135,12 +370,31 @@ impl Connection {
         }));
 
         Ok((Connection {
-            tx_stream: tx_stream,
+            writer: writer,
             thread: thread,
             node_id: node_id,
             peer_node_id: peer_node_id,
             peer_public_address: peer_public_address,
+            peer_protocol_version: peer_protocol_version,
+            observed_address: observed_address,
+            peer_zone: peer_zone,
+            direction: direction,
+            compression_threshold: compression_threshold,
+            max_message_size: max_message_size,
+            request_window: request_window,
+            response_window: response_window,
+            packet_pool: packet_pool,
+            clock: clock,
+            started_at: started_at,
+            last_active: last_active,
+            counters: counters,
+            pending_requests: pending_requests,
+            pending_responses: pending_responses,
+            last_error: last_error,
             aknowledges_tx: Mutex::new(aknowledges_tx),
+            aknowledge_timeout: aknowledge_timeout,
+            stats_waiters: stats_waiters,
+            drain_waiters: drain_waiters,
             error_handler: error_handler,
             drop_handler: drop_handler,
         },
@@ -155,12 +409,91 @@ impl Connection {
         self.peer_public_address
     }
 
+    pub fn peer_protocol_version(&self) -> u32 {
+        self.peer_protocol_version
+    }
+
+    pub fn observed_address(&self) -> Option<SocketAddr> {
+        self.observed_address
+    }
+
+    pub fn peer_zone(&self) -> Option<&str> {
+        self.peer_zone.as_ref().map(|zone| zone.as_ref())
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn started_at(&self) -> time::Tm {
+        self.started_at
+    }
+
     pub fn peer_address(&self) -> Option<SocketAddr> {
-        self.tx_stream.lock().unwrap().get_ref().peer_addr().ok()
+        self.writer.socket().peer_addr().ok()
     }
 
     pub fn local_address(&self) -> Option<SocketAddr> {
-        self.tx_stream.lock().unwrap().get_ref().local_addr().ok()
+        self.writer.socket().local_addr().ok()
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.clock.utc() - self.started_at
+    }
+
+    pub fn bytes_sent(&self) -> usize {
+        self.counters.bytes_written()
+    }
+
+    pub fn bytes_received(&self) -> usize {
+        self.counters.bytes_read()
+    }
+
+    pub fn pending_requests(&self) -> usize {
+        self.pending_requests.lock().unwrap().len()
+    }
+
+    /// Stops counting a request as pending, whose response isn't awaited anymore, e.g. because
+    /// it timed out or got cancelled.
+    pub fn abandon_request(&self, id: u64) {
+        self.pending_requests.lock().unwrap().remove(&id);
+    }
+
+    pub fn pending_responses(&self) -> usize {
+        self.pending_responses.load(Ordering::SeqCst)
+    }
+
+    pub fn stats(&self) -> ConnectionStats {
+        build_stats(&self.counters,
+                    self.started_at,
+                    &self.last_error,
+                    &*self.clock)
+    }
+
+    pub fn request_stats(&self) -> io::Result<ConnectionStats> {
+        if self.peer_protocol_version < container::STATS_VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("peer {} does not support stats requests",
+                                              self.peer_node_id)));
+        }
+
+        self.stats_waiters.call(&self.writer,
+                                self.aknowledge_timeout,
+                                "stats request",
+                                container::pack_stats_request)
+    }
+
+    pub fn request_drain(&self, acquire: bool) -> io::Result<(bool, usize)> {
+        if self.peer_protocol_version < container::DRAIN_VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("peer {} does not support drain requests",
+                                              self.peer_node_id)));
+        }
+
+        self.drain_waiters.call(&self.writer,
+                                self.aknowledge_timeout,
+                                "drain request",
+                                |request_id| container::pack_drain_request(request_id, acquire))
     }
 
     pub fn set_error_handler(&self, f: Box<Fn(ID, &io::Error) + Send>) {
@@ -171,73 +504,154 @@ impl Connection {
         *self.error_handler.lock().unwrap() = None;
     }
 
-    pub fn send_add_services(&self, service_names: &[String]) -> io::Result<()> {
-        let (tx, rx) = mpsc::channel();
-        self.aknowledges_tx.lock().unwrap().send(tx).unwrap();
-        {
-            let mut tx_stream = self.tx_stream.lock().unwrap();
-            try!(container::pack_add_services(service_names).write(&mut *tx_stream));
+    pub fn is_idle(&self, idle_timeout: Duration) -> bool {
+        self.peer_protocol_version >= container::IDLE_VERSION && self.pending_requests() == 0 &&
+        self.pending_responses() == 0 &&
+        self.clock.now() - *self.last_active.lock().unwrap() >= idle_timeout
+    }
+
+    pub fn send_idle(&self) -> io::Result<()> {
+        self.writer.send(Priority::Control, container::pack_idle(), true)
+    }
+
+    /// Tells the peer that this node shuts down. Peers that don't support it are skipped and
+    /// notice the shutdown when the connection is closed.
+    pub fn send_goodbye(&self) -> io::Result<()> {
+        if self.peer_protocol_version < container::GOODBYE_VERSION {
+            return Ok(());
         }
-        rx.recv().unwrap();
-        Ok(())
+        self.writer.send(Priority::Control, container::pack_goodbye(), true)
     }
 
-    pub fn send_remove_services(&self, service_names: &[String]) -> io::Result<()> {
-        let (tx, rx) = mpsc::channel();
-        self.aknowledges_tx.lock().unwrap().send(tx).unwrap();
-        {
-            let mut tx_stream = self.tx_stream.lock().unwrap();
-            try!(container::pack_remove_services(service_names).write(&mut *tx_stream));
+    pub fn send_add_services(&self,
+                             services: &[(String, u32, service::Metadata)])
+                             -> io::Result<()> {
+        self.send_aknowledged(container::pack_add_services(services))
+    }
+
+    /// Announces services that are reachable over this node. Peers that don't support routing
+    /// are skipped.
+    pub fn send_add_routes(&self, routes: &[(String, u32)]) -> io::Result<()> {
+        if self.peer_protocol_version < container::ROUTING_VERSION || routes.is_empty() {
+            return Ok(());
         }
-        rx.recv().unwrap();
-        Ok(())
+        self.send_aknowledged(container::pack_routes(routes))
+    }
+
+    pub fn send_remove_services(&self, service_names: &[String]) -> io::Result<()> {
+        self.send_aknowledged(container::pack_remove_services(service_names))
     }
 
     pub fn send_request(&self,
-                        id: u32,
+                        id: u64,
                         name: &str,
+                        ttl: Option<u32>,
+                        deadline: Option<Timespec>,
+                        trace_id: Option<&str>,
                         reader: &mut request::Reader)
                         -> io::Result<()> {
+        if id > u32::MAX as u64 &&
+           self.peer_protocol_version < container::WIDE_REQUEST_IDS_VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("peer {} does not support 64 bit request ids",
+                                              self.peer_node_id)));
+        }
+
+        self.touch();
         self.catch_error((), || {
-            {
-                let mut tx_stream = self.tx_stream.lock().unwrap();
-                try!(container::pack_request(id, name).write(&mut *tx_stream));
-            }
+            let container = container::pack_request(id, name, ttl, deadline, trace_id);
+            try!(self.writer.send(Priority::Data, container, false));
+            self.pending_requests.lock().unwrap().insert(id);
 
-            try!(packet::request::copy(id, reader, |buffer| {
-                let mut tx_stream = self.tx_stream.lock().unwrap();
-                try!(tx_stream.write(buffer));
+            let mut packet_buffer = self.packet_pool.get();
+            let result = packet::request::copy(id,
+                                               self.compression_threshold,
+                                               &mut packet_buffer,
+                                               reader,
+                                               |buffer| {
+                try!(self.request_window.acquire(id));
+                try!(self.writer.send_bytes(buffer, true));
                 Ok(buffer.len())
-            }));
+            });
+            self.request_window.finish(id);
+            try!(result);
 
             Ok(())
         })
     }
 
+    /// Queues a notification without waiting for it to be written. Write errors show up at the
+    /// reading side of the connection.
+    pub fn send_notification(&self,
+                             name: &str,
+                             ttl: Option<u32>,
+                             trace_id: Option<&str>,
+                             payload: Vec<u8>)
+                             -> io::Result<()> {
+        if self.peer_protocol_version < container::NOTIFICATION_VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("peer {} does not support notifications",
+                                              self.peer_node_id)));
+        }
+
+        let mut container = container::pack_notification(name, payload, ttl, trace_id);
+        if container.size() > self.max_message_size {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      format!("notification exceeds the maximum message size \
+                                               of {} bytes",
+                                              self.max_message_size)));
+        }
+        if let Some(compression_threshold) = self.compression_threshold {
+            try!(container.compress(compression_threshold));
+        }
+
+        self.touch();
+        self.writer.post(Priority::Data, container, true);
+        Ok(())
+    }
+
     pub fn send_response(&self,
-                         request_id: u32,
+                         request_id: u64,
                          mut service_result: service::Result)
                          -> io::Result<()> {
-        self.catch_error((), || {
-            {
-                let mut tx_stream = self.tx_stream.lock().unwrap();
-                try!(container::pack_response(request_id, &service_result).write(&mut *tx_stream));
-            }
+        self.touch();
+        let redirect = match service_result {
+            Err(service::Error::Redirect(..)) => true,
+            _ => false,
+        };
+        if redirect && self.peer_protocol_version < container::REDIRECT_VERSION {
+            service_result = Err(service::Error::Unavailable);
+        }
+        // the response stays pending until it's written completely, so a shutdown waits for it.
+        let result = self.catch_error((), || {
+            try!(self.writer.send(Priority::Data,
+                                  container::pack_response(request_id, &service_result),
+                                  false));
 
             if let Ok(ref mut reader) = service_result {
-                try!(packet::response::copy(request_id, reader, |buffer| {
-                    let mut tx_stream = self.tx_stream.lock().unwrap();
-                    try!(tx_stream.write_all(buffer));
+                let mut packet_buffer = self.packet_pool.get();
+                let result = packet::response::copy(request_id,
+                                                    self.compression_threshold,
+                                                    &mut packet_buffer,
+                                                    reader,
+                                                    |buffer| {
+                    try!(self.response_window.acquire(request_id));
+                    try!(self.writer.send_bytes(buffer, true));
                     Ok(buffer.len())
-                }));
+                });
+                self.response_window.finish(request_id);
+                try!(result);
             }
+            try!(self.writer.flush());
 
             Ok(())
-        })
+        });
+        decrement(&self.pending_responses);
+        result
     }
 
     pub fn shutdown(&self) {
-        match self.tx_stream.lock().unwrap().get_ref().shutdown(net::Shutdown::Both) {
+        match self.writer.socket().shutdown(net::Shutdown::Both) {
             Ok(()) => {}
             Err(ref error) if error.kind() == io::ErrorKind::NotConnected => {}
             Err(ref error) => panic!(format!("{:?}", error)),
@@ -245,14 +659,37 @@ impl Connection {
     }
 
     fn send_peers(&self, peers: &[(ID, SocketAddr)]) -> io::Result<()> {
-        let mut tx_stream = self.tx_stream.lock().unwrap();
-        try!(container::pack_peers(peers).write(&mut *tx_stream));
-        Ok(())
+        self.writer.send(Priority::Control, container::pack_peers(peers), true)
     }
 
     fn receive_peers(&self) -> io::Result<Vec<(ID, SocketAddr)>> {
-        let mut tx_stream = self.tx_stream.lock().unwrap();
-        Ok(try!(container::unpack_peers(try!(Container::read(&mut *tx_stream)))))
+        let mut tx_stream = self.writer.stream();
+        Ok(try!(container::unpack_peers(try!(Container::read_limited(&mut *tx_stream,
+                                                                     self.max_message_size)))))
+    }
+
+    fn send_aknowledged(&self, container: Container) -> io::Result<()> {
+        self.touch();
+        let (tx, rx) = mpsc::channel();
+        self.aknowledges_tx.lock().unwrap().send(tx).unwrap();
+        try!(self.writer.send(Priority::Control, container, false));
+
+        let error = match receive(&rx, self.aknowledge_timeout, "aknowledge") {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+
+        record_error(&self.last_error, &error);
+        if let Some(error_handler) = self.error_handler.lock().unwrap().take() {
+            error_handler(self.peer_node_id, &error);
+        }
+        self.shutdown();
+
+        Err(error)
+    }
+
+    fn touch(&self) {
+        *self.last_active.lock().unwrap() = self.clock.now();
     }
 
     fn catch_error<F, T>(&self, default: T, f: F) -> io::Result<T>
@@ -261,6 +698,7 @@ impl Connection {
         match f() {
             Ok(value) => Ok(value),
             Err(ref error) => {
+                record_error(&self.last_error, error);
                 if let Some(error_handler) = self.error_handler.lock().unwrap().take() {
                     error_handler(self.peer_node_id, error);
                 } else {
@@ -276,24 +714,32 @@ impl fmt::Display for Connection {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let (Some(local_address), Some(peer_address)) = (self.local_address(),
                                                             self.peer_address()) {
-            write!(f,
-                   "(Direct connection {} ({}) -> {} ({}))",
-                   self.node_id,
-                   local_address,
-                   self.peer_node_id,
-                   peer_address)
+            try!(write!(f,
+                        "(Direct connection {} ({}) -> {} ({})",
+                        self.node_id,
+                        local_address,
+                        self.peer_node_id,
+                        peer_address));
         } else {
-            write!(f,
-                   "(Direct connection {} (-) -> {} (-))",
-                   self.node_id,
-                   self.peer_node_id)
+            try!(write!(f,
+                        "(Direct connection {} (-) -> {} (-)",
+                        self.node_id,
+                        self.peer_node_id));
         }
+        write!(f,
+               " up {}s, {} bytes sent, {} bytes received, {} requests and {} responses pending)",
+               self.uptime().num_seconds(),
+               self.bytes_sent(),
+               self.bytes_received(),
+               self.pending_requests(),
+               self.pending_responses())
     }
 }
 
 impl Drop for Connection {
     fn drop(&mut self) {
         if let Some(join_handle) = self.thread.take() {
+            info!("{}: disconnect {}", self.node_id.short(), self);
             self.shutdown();
             join_handle.join().unwrap();
             (self.drop_handler)(self.peer_node_id);
@@ -301,78 +747,338 @@ impl Drop for Connection {
     }
 }
 
-fn process_inbound_container(node_id: ID,
-                             peer_node_id: ID,
-                             rx_stream: &mut cipher::Stream<net::TcpStream>,
-                             tx_stream: &Arc<Mutex<cipher::Stream<net::TcpStream>>>,
-                             aknowledges_rx: &mpsc::Receiver<mpsc::Sender<()>>,
-                             request_dispatcher: &Dispatcher,
-                             response_dispatcher: &Dispatcher,
-                             add_services_handler: &Box<Fn(ID, Vec<String>) + Send>,
-                             remove_services_handler: &Box<Fn(ID, Vec<String>) + Send>,
-                             request_handler: &Box<Fn(ID, u32, &str, Box<request::Reader>) + Send + 'static>,
-                             response_handler: &Box<Fn(u32, service::Result) -> result::Result<(), io::Error> + Send>)
-                             -> io::Result<()> {
-    let container = try!(cast_eof_to_aborted(Container::read(rx_stream)));
-    match container.get_kind() {
-        message::Kind::AddServicesMessage => {
-            add_services_handler(peer_node_id,
-                                 try!(container::unpack_add_services(container)));
-            {
-                let mut tx_stream = tx_stream.lock().unwrap();
-                try!(container::pack_aknowledge().write(&mut *tx_stream));
+impl Inbound {
+    fn process(&self,
+               container: Container,
+               rx_stream: &mut cipher::Stream<net::TcpStream>)
+               -> io::Result<()> {
+        match container.get_kind() {
+            message::Kind::AddServicesMessage => {
+                let routes = try!(container::unpack_routes(container));
+                (self.add_services_handler)(self.peer_node_id, routes);
+                self.writer.post(Priority::Control, container::pack_aknowledge(), false);
+            }
+            message::Kind::RemoveServicesMessage => {
+                let services = try!(container::unpack_remove_services(container));
+                (self.remove_services_handler)(self.peer_node_id, services);
+                self.writer.post(Priority::Control, container::pack_aknowledge(), false);
+            }
+            message::Kind::AknowledgeMessage => {
+                try!(container::unpack_aknowledge(container));
+                let tx: mpsc::Sender<()> = self.aknowledges_rx.recv().unwrap();
+                // ignore error cause the sender could have timed out already.
+                let _ = tx.send(());
+            }
+            message::Kind::RequestMessage => {
+                let (request_id, name, ttl, deadline, trace_id) =
+                    try!(container::unpack_request(container));
+
+                let reader = self.request_dispatcher.begin(request_id);
+                self.pending_responses.fetch_add(1, Ordering::SeqCst);
+
+                (self.request_handler)(self.peer_node_id,
+                                       request_id,
+                                       &name,
+                                       ttl,
+                                       deadline,
+                                       trace_id,
+                                       reader);
+            }
+            message::Kind::RequestPacketMessage => {
+                let (request_id, result) = try!(container::unpack_packet(container));
+
+                self.request_dispatcher.dispatch(request_id, result).unwrap();
+            }
+            message::Kind::ResponseMessage => {
+                let (request_id, service_result) =
+                    try!(container::unpack_response(container,
+                                                    Box::new(io::Cursor::new(Vec::new()))));
+
+                let reader = self.response_dispatcher.begin(request_id);
+                self.pending_requests.lock().unwrap().remove(&request_id);
+
+                let service_result = match service_result {
+                    Ok(_) => Ok(reader),
+                    Err(error) => Err(error),
+                };
+
+                try!((self.response_handler)(request_id, service_result));
+            }
+            message::Kind::ResponsePacketMessage => {
+                let (request_id, result) = try!(container::unpack_packet(container));
+
+                self.response_dispatcher.dispatch(request_id, result).unwrap();
+            }
+            message::Kind::RequestCreditMessage => {
+                let (request_id, packets) = try!(container::unpack_credit(container));
+                self.request_window.release(request_id, packets as usize);
+            }
+            message::Kind::ResponseCreditMessage => {
+                let (request_id, packets) = try!(container::unpack_credit(container));
+                self.response_window.release(request_id, packets as usize);
+            }
+            message::Kind::StatsRequestMessage => {
+                let request_id = try!(container::unpack_stats_request(container));
+                let stats = build_stats(&rx_stream.counters(),
+                                        self.started_at,
+                                        &self.last_error,
+                                        &*self.clock);
+                self.writer.post(Priority::Control,
+                                 container::pack_stats_response(request_id, &stats),
+                                 false);
+            }
+            message::Kind::PingMessage => {
+                try!(container::unpack_ping(container));
+                self.writer.post(Priority::Control, container::pack_pong(), false);
+            }
+            message::Kind::PongMessage => {
+                try!(container::unpack_pong(container));
+            }
+            message::Kind::IdleMessage => {
+                try!(container::unpack_idle(container));
+                (self.idle_handler)(self.peer_node_id, self.peer_public_address);
+                return Err(io::Error::new(io::ErrorKind::ConnectionAborted,
+                                          "peer closed idle connection"));
+            }
+            message::Kind::GoodbyeMessage => {
+                try!(container::unpack_goodbye(container));
+                (self.goodbye_handler)(self.peer_node_id, self.peer_public_address);
+            }
+            message::Kind::StatsResponseMessage => {
+                let (request_id, stats) = try!(container::unpack_stats_response(container));
+                if !self.stats_waiters.resolve(request_id, stats) {
+                    debug!("{}: drop unrequested stats response from {}",
+                           self.node_id.short(),
+                           self.peer_node_id.short());
+                }
+            }
+            message::Kind::DrainRequestMessage => {
+                let (request_id, acquire) = try!(container::unpack_drain_request(container));
+                let (granted, draining) = (self.drain_handler)(self.peer_node_id, acquire);
+                self.writer.post(Priority::Control,
+                                 container::pack_drain_response(request_id, granted, draining),
+                                 false);
+            }
+            message::Kind::DrainResponseMessage => {
+                let (request_id, result) = try!(container::unpack_drain_response(container));
+                if !self.drain_waiters.resolve(request_id, result) {
+                    debug!("{}: drop unrequested drain response from {}",
+                           self.node_id.short(),
+                           self.peer_node_id.short());
+                }
+            }
+            message::Kind::NotificationMessage => {
+                let (name, payload, ttl, trace_id) =
+                    try!(container::unpack_notification(container));
+                (self.notification_handler)(self.peer_node_id, &name, ttl, trace_id, payload);
+            }
+            _ => {
+                error!("{}: got unexpected container {:?}",
+                       self.node_id.short(),
+                       container.get_kind());
             }
         }
-        message::Kind::RemoveServicesMessage => {
-            remove_services_handler(peer_node_id,
-                                    try!(container::unpack_remove_services(container)));
-            {
-                let mut tx_stream = tx_stream.lock().unwrap();
-                try!(container::pack_aknowledge().write(&mut *tx_stream));
+        Ok(())
+    }
+}
+
+fn is_activity(kind: message::Kind) -> bool {
+    match kind {
+        message::Kind::PingMessage | message::Kind::PongMessage => false,
+        _ => true,
+    }
+}
+
+fn credit_handler(writer: &Arc<Writer>, pt: container::PacketType) -> Box<Fn(u64) + Send + Sync> {
+    let writer = Arc::downgrade(writer);
+    Box::new(move |request_id| {
+        if let Some(writer) = writer.upgrade() {
+            // errors are ignored, because the reader will notice a broken connection.
+            writer.post(Priority::Control, container::pack_credit(pt, request_id, 1), true);
+        }
+    })
+}
+
+fn build_stats(counters: &cipher::stream::Counters,
+               started_at: time::Tm,
+               last_error: &Mutex<Option<String>>,
+               clock: &Clock)
+               -> ConnectionStats {
+    let clock = clock.utc();
+    ConnectionStats {
+        bytes_sent: counters.bytes_written(),
+        bytes_received: counters.bytes_read(),
+        frames_sent: counters.frames_written(),
+        frames_received: counters.frames_read(),
+        last_error: last_error.lock().unwrap().clone(),
+        clock: clock,
+        uptime: clock - started_at,
+    }
+}
+
+fn flush_periodically(writer: &Weak<Writer>, coalesce_delay: Duration) {
+    loop {
+        thread::sleep(to_std_duration(coalesce_delay));
+        let writer = match writer.upgrade() {
+            Some(writer) => writer,
+            None => break,
+        };
+        if !writer.stream().has_pending() {
+            continue;
+        }
+        if let Err(error) = writer.flush() {
+            debug!("flushing coalesced containers failed: {:?}", error);
+            break;
+        }
+    }
+}
+
+fn send_heartbeats(peer_node_id: ID,
+                   writer: &Weak<Writer>,
+                   clock: &Clock,
+                   last_seen: &Mutex<SteadyTime>,
+                   last_error: &Mutex<Option<String>>,
+                   error_handler: &Mutex<Option<Box<Fn(ID, &io::Error) + Send>>>,
+                   heartbeat_interval: Duration,
+                   heartbeat_timeout: Option<Duration>) {
+    loop {
+        clock.sleep(heartbeat_interval);
+        let writer = match writer.upgrade() {
+            Some(writer) => writer,
+            None => break,
+        };
+
+        if let Some(heartbeat_timeout) = heartbeat_timeout {
+            let silence = clock.now() - *last_seen.lock().unwrap();
+            if silence > heartbeat_timeout {
+                let error = io::Error::new(io::ErrorKind::TimedOut,
+                                           format!("peer {} missed heartbeats for {}ms",
+                                                   peer_node_id,
+                                                   silence.num_milliseconds()));
+                record_error(last_error, &error);
+                if let Some(error_handler) = error_handler.lock().unwrap().take() {
+                    error_handler(peer_node_id, &error);
+                }
+                break;
             }
         }
-        message::Kind::AknowledgeMessage => {
-            try!(container::unpack_aknowledge(container));
-            let tx: mpsc::Sender<()> = aknowledges_rx.recv().unwrap();
-            tx.send(()).unwrap();
+
+        if let Err(error) = writer.send(Priority::Control, container::pack_ping(), false) {
+            debug!("sending heartbeat to {} failed: {:?}", peer_node_id, error);
+            break;
         }
-        message::Kind::RequestMessage => {
-            let (request_id, name) = try!(container::unpack_request(container));
+    }
+}
 
-            let reader = request_dispatcher.begin(request_id);
+/// The callers that wait for the responses to one kind of control request. Each request gets an
+/// id that the peer echoes, so the late response to a timed out request can't reach the caller
+/// that comes after it.
+struct Waiters<T> {
+    next_id: AtomicUsize,
+    senders: Mutex<Option<Vec<(u64, mpsc::Sender<T>)>>>,
+}
 
-            request_handler(peer_node_id, request_id, &name, reader);
+impl<T> Waiters<T> {
+    fn new() -> Waiters<T> {
+        Waiters {
+            next_id: AtomicUsize::new(1),
+            senders: Mutex::new(Some(Vec::new())),
         }
-        message::Kind::RequestPacketMessage => {
-            let (request_id, result) = try!(container::unpack_packet(container));
+    }
 
-            request_dispatcher.dispatch(request_id, result).unwrap();
+    /// Sends the request that `pack` builds for a new id and waits for its response.
+    fn call<F>(&self,
+               writer: &Writer,
+               timeout: Option<Duration>,
+               name: &str,
+               pack: F)
+               -> io::Result<T>
+        where F: FnOnce(u64) -> Container
+    {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) as u64;
+        let (tx, rx) = mpsc::channel();
+        match *self.senders.lock().unwrap() {
+            Some(ref mut senders) => senders.push((id, tx)),
+            None => {
+                return Err(io::Error::new(io::ErrorKind::ConnectionAborted, "connection aborted"))
+            }
         }
-        message::Kind::ResponseMessage => {
-            let (request_id, service_result) =
-                try!(container::unpack_response(container, Box::new(io::Cursor::new(Vec::new()))));
 
-            let reader = response_dispatcher.begin(request_id);
+        let result = writer.send(Priority::Control, pack(id), false)
+                           .and_then(|_| receive(&rx, timeout, name));
+        if result.is_err() {
+            self.remove(id);
+        }
+        result
+    }
 
-            let service_result = match service_result {
-                Ok(_) => Ok(reader),
-                Err(error) => Err(error),
-            };
+    /// Hands the response to the caller that waits for it. Peers before the request ids answer
+    /// in the order of the requests, so a response without an id goes to the oldest caller.
+    fn resolve(&self, id: Option<u64>, value: T) -> bool {
+        let mut senders = self.senders.lock().unwrap();
+        let senders = match *senders {
+            Some(ref mut senders) => senders,
+            None => return false,
+        };
+        let index = match id {
+            Some(id) => senders.iter().position(|&(waiter_id, _)| waiter_id == id),
+            None if senders.is_empty() => None,
+            None => Some(0),
+        };
+        match index {
+            Some(index) => {
+                // ignore error cause the caller could have timed out in the meantime.
+                let _ = senders.remove(index).1.send(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drops all waiting callers, so they see the connection as aborted.
+    fn close(&self) {
+        *self.senders.lock().unwrap() = None;
+    }
 
-            try!(response_handler(request_id, service_result));
+    fn remove(&self, id: u64) {
+        if let Some(ref mut senders) = *self.senders.lock().unwrap() {
+            senders.retain(|&(waiter_id, _)| waiter_id != id);
         }
-        message::Kind::ResponsePacketMessage => {
-            let (request_id, result) = try!(container::unpack_packet(container));
+    }
+}
 
-            response_dispatcher.dispatch(request_id, result).unwrap();
+/// Waits for the answer to a control message. Without a timeout, it waits until the connection
+/// is closed.
+fn receive<T>(rx: &mpsc::Receiver<T>, timeout: Option<Duration>, name: &str) -> io::Result<T> {
+    let result = match timeout {
+        Some(timeout) => rx.recv_timeout(to_std_duration(timeout)),
+        None => rx.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+    };
+    result.map_err(|error| {
+        match error {
+            mpsc::RecvTimeoutError::Timeout => {
+                io::Error::new(io::ErrorKind::TimedOut, format!("{} timed out", name))
+            }
+            mpsc::RecvTimeoutError::Disconnected => {
+                io::Error::new(io::ErrorKind::ConnectionAborted, "connection aborted")
+            }
         }
-        _ => {
-            error!("{}: got unexpected container {:?}",
-                   node_id,
-                   container.get_kind());
+    })
+}
+
+fn record_error(last_error: &Mutex<Option<String>>, error: &io::Error) {
+    *last_error.lock().unwrap() = Some(format!("{}", error));
+}
+
+fn decrement(counter: &AtomicUsize) {
+    let mut current = counter.load(Ordering::SeqCst);
+    while current > 0 {
+        let previous = counter.compare_and_swap(current, current - 1, Ordering::SeqCst);
+        if previous == current {
+            break;
         }
+        current = previous;
     }
-    Ok(())
 }
 
 fn cast_eof_to_aborted<T>(result: io::Result<T>) -> io::Result<T> {
@@ -384,3 +1090,138 @@ fn cast_eof_to_aborted<T>(result: io::Result<T>) -> io::Result<T> {
         Err(error) => Err(error),
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::net;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use time::Duration;
+    use node::ID;
+    use transport::cipher::{self, Plain};
+    use util::clock;
+    use util::time::to_std_duration;
+    use super::{Connection, Handlers, Options};
+    use super::super::container;
+    use super::super::packet;
+
+    #[test]
+    fn display_without_locking_the_stream() {
+        let (inbound_node_id, outbound_node_id) = (ID::new_random(), ID::new_random());
+        let options = build_options();
+
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let options_clone = options.clone();
+        let join_handle = thread::spawn(move || {
+            let (tcp_stream, _) = listener.accept().unwrap();
+            Connection::new_inbound(cipher::Stream::new(tcp_stream, Box::new(Plain::new())),
+                                    inbound_node_id,
+                                    address,
+                                    &[],
+                                    &options_clone,
+                                    build_handlers())
+                .unwrap()
+        });
+
+        let tcp_stream = net::TcpStream::connect(address).unwrap();
+        let (outbound, _) =
+            Connection::new_outbound(cipher::Stream::new(tcp_stream, Box::new(Plain::new())),
+                                     outbound_node_id,
+                                     address,
+                                     &options,
+                                     build_handlers())
+                .unwrap();
+        let _inbound = join_handle.join().unwrap();
+
+        // a writer that holds the stream doesn't block the formatting.
+        let _tx_stream = outbound.writer.stream();
+        let text = format!("{}", outbound);
+
+        assert!(text.starts_with(&format!("(Direct connection {} (", outbound_node_id)));
+        assert!(text.contains(&format!("-> {} (", inbound_node_id)));
+        assert!(text.contains(&format!("{} bytes sent", outbound.bytes_sent())));
+        assert!(text.ends_with("0 requests and 0 responses pending)"));
+        assert!(outbound.bytes_sent() > 0);
+    }
+
+    #[test]
+    fn drain_response_after_timed_out_request() {
+        let (inbound_node_id, outbound_node_id) = (ID::new_random(), ID::new_random());
+        let mut options = build_options();
+        options.aknowledge_timeout = Some(Duration::milliseconds(200));
+
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let options_clone = options.clone();
+        let join_handle = thread::spawn(move || {
+            let (tcp_stream, _) = listener.accept().unwrap();
+            // the first answer is late, so it arrives while the second request waits.
+            let calls = AtomicUsize::new(0);
+            let mut handlers = build_handlers();
+            handlers.drain = Box::new(move |_, _| {
+                if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    thread::sleep(to_std_duration(Duration::milliseconds(300)));
+                    (true, 1)
+                } else {
+                    (false, 2)
+                }
+            });
+            Connection::new_inbound(cipher::Stream::new(tcp_stream, Box::new(Plain::new())),
+                                    inbound_node_id,
+                                    address,
+                                    &[],
+                                    &options_clone,
+                                    handlers)
+                .unwrap()
+        });
+
+        let tcp_stream = net::TcpStream::connect(address).unwrap();
+        let (outbound, _) =
+            Connection::new_outbound(cipher::Stream::new(tcp_stream, Box::new(Plain::new())),
+                                     outbound_node_id,
+                                     address,
+                                     &options,
+                                     build_handlers())
+                .unwrap();
+        let _inbound = join_handle.join().unwrap();
+
+        assert!(outbound.request_drain(true).is_err());
+        assert_eq!((false, 2), outbound.request_drain(false).unwrap());
+    }
+
+    fn build_options() -> Options {
+        Options {
+            zone: None,
+            aknowledge_timeout: None,
+            coalesce_delay: None,
+            coalesce_size: 0,
+            heartbeat_interval: None,
+            heartbeat_timeout: None,
+            compression_threshold: None,
+            flow_control_window: 0,
+            upload_limit: None,
+            download_limit: None,
+            max_message_size: container::MAX_CONTAINER_SIZE,
+            packet_pool: Arc::new(packet::Pool::new(64 * 1024)),
+            clock: Arc::new(clock::System),
+        }
+    }
+
+    fn build_handlers() -> Handlers {
+        Handlers {
+            add_services: Box::new(|_, _| {}),
+            remove_services: Box::new(|_, _| {}),
+            request: Box::new(|_, _, _, _, _, _, _| {}),
+            response: Box::new(|_, _| Ok(())),
+            idle: Box::new(|_, _| {}),
+            goodbye: Box::new(|_, _| {}),
+            drain: Box::new(|_, _| (false, 0)),
+            notification: Box::new(|_, _, _, _, _| {}),
+            drop: Box::new(|_| {}),
+        }
+    }
+
+}
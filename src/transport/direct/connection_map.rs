@@ -18,14 +18,17 @@ use std::io;
 use std::net::SocketAddr;
 use std::result;
 use std::sync::{Arc, Mutex, RwLock, mpsc};
-use std::thread;
+use time::{Duration, Timespec};
 
 use metric::{self, Metric};
-use node::{ID, request, service};
-use transport::direct::Connection;
+use node::{ID, Placement, request, service};
+use transport::PeerInfo;
+use transport::direct::{Announcement, Connection};
+use util::thread::supervisor;
 
 pub struct ConnectionMap {
-    map: Arc<RwLock<HashMap<ID, Connection>>>,
+    map: Arc<RwLock<HashMap<ID, Arc<Connection>>>>,
+    count_handler: Arc<RwLock<Option<Box<Fn(usize) + Send + Sync>>>>,
     tx: Mutex<mpsc::Sender<ID>>,
     connections_gauge: Arc<metric::item::Gauge>,
 }
@@ -42,43 +45,59 @@ impl ConnectionMap {
     pub fn new(metric: Arc<Metric>) -> Self {
         let map = Arc::new(RwLock::new(HashMap::default()));
         let map_clone = map.clone();
+        let count_handler = Arc::new(RwLock::new(None));
+        let count_handler_clone = count_handler.clone();
 
         let connections_gauge = Arc::new(metric.gauge("connections"));
         let connections_gauge_clone = connections_gauge.clone();
 
         let (tx, rx) = mpsc::channel::<ID>();
-        thread::spawn(move || {
+        supervisor().spawn("connection map", move || {
             for peer_node_id in rx {
-                map_clone.write().unwrap().remove(&peer_node_id);
+                let count = {
+                    let mut map = map_clone.write().unwrap();
+                    map.remove(&peer_node_id);
+                    map.len()
+                };
                 connections_gauge_clone.change(-1);
+                notify(&count_handler_clone, count);
             }
         });
         ConnectionMap {
             map: map,
+            count_handler: count_handler,
             tx: Mutex::new(tx),
             connections_gauge: connections_gauge,
         }
     }
 
     pub fn add(&self, connection: Connection) -> Result<()> {
-        let mut map = self.map.write().unwrap();
-        if map.contains_key(&connection.peer_node_id()) {
-            return Err(Error::AlreadyExists);
-        }
-
-        let tx = self.tx.lock().unwrap().clone();
-        connection.set_error_handler(Box::new(move |peer_node_id, error| {
-            if error.kind() != io::ErrorKind::ConnectionAborted {
-                error!("got connection error: {:?}", error);
+        let count = {
+            let mut map = self.map.write().unwrap();
+            if map.contains_key(&connection.peer_node_id()) {
+                return Err(Error::AlreadyExists);
             }
-            tx.send(peer_node_id).unwrap();
-        }));
 
-        map.insert(connection.peer_node_id(), connection);
+            let tx = self.tx.lock().unwrap().clone();
+            connection.set_error_handler(Box::new(move |peer_node_id, error| {
+                if error.kind() != io::ErrorKind::ConnectionAborted {
+                    error!("got connection error: {:?}", error);
+                }
+                tx.send(peer_node_id).unwrap();
+            }));
+
+            map.insert(connection.peer_node_id(), Arc::new(connection));
+            map.len()
+        };
         self.connections_gauge.change(1);
+        notify(&self.count_handler, count);
         Ok(())
     }
 
+    pub fn set_count_handler(&self, handler: Box<Fn(usize) + Send + Sync>) {
+        *self.count_handler.write().unwrap() = Some(handler);
+    }
+
     pub fn contains_key(&self, peer_node_id: &ID) -> bool {
         self.map.read().unwrap().contains_key(peer_node_id)
     }
@@ -104,36 +123,108 @@ impl ConnectionMap {
             .collect()
     }
 
-    pub fn send_add_services(&self, services: &[String]) -> io::Result<()> {
-        let mut map = self.map.write().unwrap();
-        for (_, connection) in map.iter_mut() {
-            try!(connection.send_add_services(services));
-        }
-        Ok(())
+    /// Returns the connected peers. The service counts are left at zero.
+    pub fn peer_infos(&self) -> Vec<PeerInfo> {
+        self.map
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(peer_node_id, peer_connection)| {
+                PeerInfo {
+                    node_id: *peer_node_id,
+                    public_address: peer_connection.peer_public_address(),
+                    direction: peer_connection.direction(),
+                    connected_since: peer_connection.started_at(),
+                    services: 0,
+                }
+            })
+            .collect()
     }
 
-    pub fn send_remove_services(&self, services: &[String]) -> io::Result<()> {
-        let mut map = self.map.write().unwrap();
-        for (_, connection) in map.iter_mut() {
-            try!(connection.send_remove_services(services));
-        }
-        Ok(())
+    pub fn idle_id_public_address_pairs(&self, idle_timeout: Duration) -> Vec<(ID, SocketAddr)> {
+        self.map
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|&(_, peer_connection)| peer_connection.is_idle(idle_timeout))
+            .map(|(peer_node_id, peer_connection)| {
+                (*peer_node_id, peer_connection.peer_public_address())
+            })
+            .collect()
+    }
+
+    pub fn placed_peer_node_ids(&self, zone: Option<&str>, placement: Placement) -> Vec<ID> {
+        self.map
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|&(_, peer_connection)| placement.allows(zone, peer_connection.peer_zone()))
+            .map(|(peer_node_id, _)| *peer_node_id)
+            .collect()
+    }
+
+    pub fn peer_node_ids_since(&self, protocol_version: u32) -> Vec<ID> {
+        self.map
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|&(_, peer_connection)| {
+                peer_connection.peer_protocol_version() >= protocol_version
+            })
+            .map(|(peer_node_id, _)| *peer_node_id)
+            .collect()
+    }
+
+    pub fn remove(&self, peer_node_id: &ID) {
+        self.tx.lock().unwrap().send(*peer_node_id).unwrap();
+    }
+
+    pub fn send_add_services(&self, services: &[(String, u32, service::Metadata)]) -> Announcement {
+        self.send_add_services_to(&self.peer_node_ids(), services)
+    }
+
+    pub fn send_add_services_to(&self,
+                                peer_node_ids: &[ID],
+                                services: &[(String, u32, service::Metadata)])
+                                -> Announcement {
+        self.announce(peer_node_ids,
+                      |connection| connection.send_add_services(services))
+    }
+
+    pub fn send_remove_services(&self, services: &[String]) -> Announcement {
+        self.send_remove_services_to(&self.peer_node_ids(), services)
+    }
+
+    pub fn send_remove_services_to(&self,
+                                   peer_node_ids: &[ID],
+                                   services: &[String])
+                                   -> Announcement {
+        self.announce(peer_node_ids,
+                      |connection| connection.send_remove_services(services))
     }
 
     pub fn send_request(&self,
                         peer_node_id: &ID,
-                        id: u32,
+                        id: u64,
                         name: &str,
+                        deadline: Option<Timespec>,
+                        trace_id: Option<&str>,
                         reader: &mut request::Reader)
                         -> io::Result<()> {
         let map = self.map.read().unwrap();
         let connection = map.get(peer_node_id).unwrap();
-        Ok(try!(connection.send_request(id, name, reader)))
+        Ok(try!(connection.send_request(id, name, None, deadline, trace_id, reader)))
+    }
+
+    pub fn abandon_request(&self, peer_node_id: &ID, request_id: u64) {
+        if let Some(connection) = self.map.read().unwrap().get(peer_node_id) {
+            connection.abandon_request(request_id);
+        }
     }
 
     pub fn send_response(&self,
                          peer_node_id: &ID,
-                         request_id: u32,
+                         request_id: u64,
                          service_result: service::Result)
                          -> io::Result<()> {
         let map = self.map.read().unwrap();
@@ -146,6 +237,54 @@ impl ConnectionMap {
         Ok(try!(connection.send_response(request_id, service_result)))
     }
 
+    fn peer_node_ids(&self) -> Vec<ID> {
+        self.map.read().unwrap().keys().cloned().collect()
+    }
+
+    fn announce<F>(&self, peer_node_ids: &[ID], f: F) -> Announcement
+        where F: Fn(&Connection) -> io::Result<()>
+    {
+        // the map isn't locked while the aknowledges are awaited, so connections can come and go.
+        let connections = {
+            let map = self.map.read().unwrap();
+            peer_node_ids.iter()
+                         .map(|peer_node_id| (*peer_node_id, map.get(peer_node_id).cloned()))
+                         .collect::<Vec<_>>()
+        };
+        let mut announcement = Announcement::new();
+        for (peer_node_id, connection) in connections {
+            let result = match connection {
+                Some(connection) => f(&connection),
+                None => {
+                    Err(io::Error::new(io::ErrorKind::ConnectionAborted, "connection aborted"))
+                }
+            };
+            match result {
+                Ok(()) => announcement.aknowledged.push(peer_node_id),
+                Err(error) => announcement.failed.push((peer_node_id, error)),
+            }
+        }
+        announcement
+    }
+
+    /// Says goodbye to all peers and returns the ones that couldn't be told.
+    pub fn send_goodbye(&self) -> Vec<(ID, io::Error)> {
+        let map = self.map.read().unwrap();
+        map.iter()
+           .filter_map(|(peer_node_id, connection)| {
+               connection.send_goodbye().err().map(|error| (*peer_node_id, error))
+           })
+           .collect()
+    }
+
+    /// Returns true if no requests are pending on any of the connections, neither sent nor
+    /// received ones.
+    pub fn is_drained(&self) -> bool {
+        self.map.read().unwrap().values().all(|connection| {
+            connection.pending_requests() == 0 && connection.pending_responses() == 0
+        })
+    }
+
     pub fn shutdown(&self) {
         let map = self.map.read().unwrap();
         for (_, connection) in map.iter() {
@@ -154,3 +293,9 @@ impl ConnectionMap {
         }
     }
 }
+
+fn notify(count_handler: &RwLock<Option<Box<Fn(usize) + Send + Sync>>>, count: usize) {
+    if let Some(ref handler) = *count_handler.read().unwrap() {
+        handler(count);
+    }
+}
@@ -14,17 +14,39 @@
 //
 
 use std::error::Error as StdError;
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 use std::iter;
-use std::net::{self, SocketAddr};
+use std::net::{self, SocketAddr, SocketAddrV6};
 use std::result;
 
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
 use protobuf::{self, Message};
+use time::{self, Duration, Timespec};
 
 use message;
 use node::{ID, id, response, service};
+use transport::ConnectionStats;
+use transport::direct::deadline;
 use util::{reader, writer};
 
+pub const PROTOCOL_VERSION: u32 = 11;
+pub const WIDE_REQUEST_IDS_VERSION: u32 = 2;
+pub const STATS_VERSION: u32 = 3;
+pub const HEARTBEAT_VERSION: u32 = 4;
+pub const FLOW_CONTROL_VERSION: u32 = 5;
+pub const IDLE_VERSION: u32 = 6;
+pub const DRAIN_VERSION: u32 = 7;
+pub const REDIRECT_VERSION: u32 = 8;
+pub const ROUTING_VERSION: u32 = 9;
+pub const NOTIFICATION_VERSION: u32 = 10;
+pub const GOODBYE_VERSION: u32 = 11;
+
+pub const MAX_CONTAINER_SIZE: usize = 64 * 1024 * 1024;
+// milliseconds of 9999-12-31T23:59:59.999Z
+const MAX_CLOCK: i64 = 253402300799999;
+
 pub struct Container {
     message: message::Container,
 }
@@ -36,6 +58,9 @@ pub enum Error {
     Id(id::Error),
     Protobuf(protobuf::ProtobufError),
     AddrParse(net::AddrParseError),
+    TooLarge(usize),
+    Decompress(io::ErrorKind),
+    InvalidValue(&'static str),
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -47,18 +72,50 @@ pub enum PacketType {
 impl Container {
     pub fn read<R>(reader: &mut R) -> io::Result<Self>
         where R: io::Read
+    {
+        Self::read_limited(reader, MAX_CONTAINER_SIZE)
+    }
+
+    /// Reads a container and fails before allocating anything, if the size prefix exceeds
+    /// `max_size`.
+    pub fn read_limited<R>(reader: &mut R, max_size: usize) -> io::Result<Self>
+        where R: io::Read
     {
         let size = try!(reader::read_size(reader));
+        if size > max_size {
+            return Err(io::Error::from(Error::TooLarge(size)));
+        }
 
         let mut bytes = iter::repeat(0u8).take(size).collect::<Vec<u8>>();
         try!(reader.read_exact(&mut bytes));
 
-        let message = match protobuf::parse_from_bytes::<message::Container>(&bytes) {
-            Ok(message) => message,
-            Err(error) => {
-                return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", error)))
+        Ok(try!(Self::parse_limited(&bytes, max_size)))
+    }
+
+    #[doc(hidden)]
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        Self::parse_limited(bytes, MAX_CONTAINER_SIZE)
+    }
+
+    fn parse_limited(bytes: &[u8], max_size: usize) -> Result<Self> {
+        if bytes.len() > max_size {
+            return Err(Error::TooLarge(bytes.len()));
+        }
+
+        let mut message = try!(protobuf::parse_from_bytes::<message::Container>(bytes));
+
+        if message.get_compressed() {
+            let mut payload = Vec::new();
+            try!(DeflateDecoder::new(message.get_payload())
+                     .take(max_size as u64 + 1)
+                     .read_to_end(&mut payload)
+                     .map_err(|error| Error::Decompress(error.kind())));
+            if payload.len() > max_size {
+                return Err(Error::TooLarge(payload.len()));
             }
-        };
+            message.set_payload(payload);
+            message.clear_compressed();
+        }
 
         Ok(Container { message: message })
     }
@@ -78,23 +135,72 @@ impl Container {
         Ok(total)
     }
 
+    /// Returns the size of the uncompressed container without its size prefix.
+    pub fn size(&self) -> usize {
+        self.message.compute_size() as usize
+    }
+
     pub fn get_kind(&self) -> message::Kind {
         self.message.get_kind()
     }
+
+    pub fn compress(&mut self, threshold: usize) -> io::Result<()> {
+        if self.message.get_compressed() || self.message.get_payload().len() < threshold {
+            return Ok(());
+        }
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::Default);
+        try!(encoder.write_all(self.message.get_payload()));
+        let payload = try!(encoder.finish());
+
+        if payload.len() < self.message.get_payload().len() {
+            self.message.set_payload(payload);
+            self.message.set_compressed(true);
+        }
+        Ok(())
+    }
 }
 
-pub fn pack_introduction(node_id: ID, public_address: SocketAddr) -> Container {
+pub fn pack_introduction(node_id: ID,
+                         public_address: SocketAddr,
+                         observed_address: Option<SocketAddr>,
+                         compression: bool,
+                         zone: Option<String>)
+                         -> Container {
     let mut introduction = message::Introduction::new();
     introduction.set_id(node_id.to_vec());
-    introduction.set_public_address(format!("{}", public_address));
+    introduction.set_public_address(format_address(public_address));
+    introduction.set_protocol_version(PROTOCOL_VERSION);
+    introduction.set_compression(compression);
+    if let Some(observed_address) = observed_address {
+        introduction.set_observed_address(format_address(observed_address));
+    }
+    if let Some(zone) = zone {
+        introduction.set_zone(zone);
+    }
     pack(message::Kind::IntroductionMessage, introduction)
 }
 
-pub fn unpack_introduction(container: Container) -> Result<(ID, SocketAddr)> {
+pub fn unpack_introduction(container: Container)
+                           -> Result<(ID, SocketAddr, u32, bool, Option<SocketAddr>,
+                                      Option<String>)> {
     let introduction_packet = try!(unpack::<message::Introduction>(&container));
+    let observed_address = if introduction_packet.has_observed_address() {
+        Some(try!(parse_address(introduction_packet.get_observed_address())))
+    } else {
+        None
+    };
+    let zone = if introduction_packet.has_zone() {
+        Some(introduction_packet.get_zone().to_string())
+    } else {
+        None
+    };
     Ok((try!(ID::from_vec(introduction_packet.get_id().to_vec())),
-        try!(introduction_packet.get_public_address()
-                                .parse::<SocketAddr>())))
+        try!(parse_address(introduction_packet.get_public_address())),
+        introduction_packet.get_protocol_version(),
+        introduction_packet.get_compression(),
+        observed_address,
+        zone))
 }
 
 pub fn pack_peers(peers: &[(ID, SocketAddr)]) -> Container {
@@ -103,30 +209,36 @@ pub fn pack_peers(peers: &[(ID, SocketAddr)]) -> Container {
         let (peer_node_id, peer_public_address) = *peer;
         let mut peer_packet = message::Peer::new();
         peer_packet.set_id(peer_node_id.to_vec());
-        peer_packet.set_public_address(format!("{}", peer_public_address));
+        peer_packet.set_public_address(format_address(peer_public_address));
         peers_packet.mut_peers().push(peer_packet);
     }
     pack(message::Kind::PeersMessage, peers_packet)
 }
 
 pub fn unpack_peers(container: Container) -> Result<Vec<(ID, SocketAddr)>> {
-    Ok(try!(unpack::<message::Peers>(&container))
-           .get_peers()
-           .iter()
-           .map(|peer_packet| {
-               (ID::from_vec(peer_packet.get_id().to_vec()).unwrap(),
-                peer_packet.get_public_address()
-                           .parse::<SocketAddr>()
-                           .unwrap())
-           })
-           .collect())
+    try!(unpack::<message::Peers>(&container))
+        .get_peers()
+        .iter()
+        .map(|peer_packet| {
+            Ok((try!(ID::from_vec(peer_packet.get_id().to_vec())),
+                try!(parse_address(peer_packet.get_public_address()))))
+        })
+        .collect()
 }
 
-pub fn pack_add_services(service_names: &[String]) -> Container {
+/// Packs local services together with their weight and metadata.
+pub fn pack_add_services(services: &[(String, u32, service::Metadata)]) -> Container {
     let mut services_packet = message::AddServices::new();
-    for service_name in service_names {
+    for &(ref service_name, weight, ref metadata) in services {
         let mut service_packet = message::Service::new();
-        service_packet.set_name((*service_name).to_string());
+        service_packet.set_name(service_name.to_string());
+        service_packet.set_weight(weight);
+        for (key, value) in metadata {
+            let mut tag_packet = message::Tag::new();
+            tag_packet.set_key(key.to_string());
+            tag_packet.set_value(value.to_string());
+            service_packet.mut_tags().push(tag_packet);
+        }
         services_packet.mut_services().push(service_packet);
     }
     pack(message::Kind::AddServicesMessage, services_packet)
@@ -141,6 +253,46 @@ pub fn unpack_add_services(container: Container) -> Result<Vec<String>> {
            .collect())
 }
 
+/// Packs services that are reachable over the sending node together with their hop count.
+pub fn pack_routes(routes: &[(String, u32)]) -> Container {
+    let mut services_packet = message::AddServices::new();
+    for &(ref service_name, hops) in routes {
+        let mut service_packet = message::Service::new();
+        service_packet.set_name(service_name.to_string());
+        service_packet.set_hops(hops);
+        services_packet.mut_services().push(service_packet);
+    }
+    pack(message::Kind::AddServicesMessage, services_packet)
+}
+
+/// Unpacks announced services together with their hop count, weight and metadata. Local
+/// services of the sender have no hop count and are returned with zero hops. Services without a
+/// weight get the default weight. Routes carry no metadata.
+pub fn unpack_routes(container: Container) -> Result<Vec<(String, u32, u32, service::Metadata)>> {
+    Ok(try!(unpack::<message::AddServices>(&container))
+           .get_services()
+           .iter()
+           .map(|service_packet| {
+               let weight = if service_packet.get_weight() > 0 {
+                   service_packet.get_weight()
+               } else {
+                   service::DEFAULT_WEIGHT
+               };
+               let metadata = service_packet.get_tags()
+                                            .iter()
+                                            .map(|tag_packet| {
+                                                (tag_packet.get_key().to_string(),
+                                                 tag_packet.get_value().to_string())
+                                            })
+                                            .collect();
+               (service_packet.get_name().to_string(),
+                service_packet.get_hops(),
+                weight,
+                metadata)
+           })
+           .collect())
+}
+
 pub fn pack_remove_services(service_names: &[String]) -> Container {
     let mut services_packet = message::RemoveServices::new();
     for service_name in service_names {
@@ -169,20 +321,124 @@ pub fn unpack_aknowledge(container: Container) -> Result<()> {
     Ok(())
 }
 
-pub fn pack_request(id: u32, name: &str) -> Container {
+pub fn pack_ping() -> Container {
+    pack(message::Kind::PingMessage, message::Ping::new())
+}
+
+pub fn unpack_ping(container: Container) -> Result<()> {
+    try!(unpack::<message::Ping>(&container));
+    Ok(())
+}
+
+pub fn pack_pong() -> Container {
+    pack(message::Kind::PongMessage, message::Pong::new())
+}
+
+pub fn unpack_pong(container: Container) -> Result<()> {
+    try!(unpack::<message::Pong>(&container));
+    Ok(())
+}
+
+pub fn pack_idle() -> Container {
+    pack(message::Kind::IdleMessage, message::Idle::new())
+}
+
+pub fn unpack_idle(container: Container) -> Result<()> {
+    try!(unpack::<message::Idle>(&container));
+    Ok(())
+}
+
+pub fn pack_goodbye() -> Container {
+    pack(message::Kind::GoodbyeMessage, message::Goodbye::new())
+}
+
+pub fn unpack_goodbye(container: Container) -> Result<()> {
+    try!(unpack::<message::Goodbye>(&container));
+    Ok(())
+}
+
+/// Packs a request. Forwarded requests carry the number of further hops they may take. The
+/// deadline is sent as milliseconds since the unix epoch.
+pub fn pack_request(id: u64,
+                    name: &str,
+                    ttl: Option<u32>,
+                    deadline: Option<Timespec>,
+                    trace_id: Option<&str>)
+                    -> Container {
     let mut request_packet = message::Request::new();
     request_packet.set_id(id);
     request_packet.set_name(name.to_string());
+    if let Some(ttl) = ttl {
+        request_packet.set_ttl(ttl);
+    }
+    if let Some(deadline) = deadline {
+        request_packet.set_deadline(deadline::to_millis(deadline));
+    }
+    if let Some(trace_id) = trace_id {
+        request_packet.set_trace_id(trace_id.to_string());
+    }
     pack(message::Kind::RequestMessage, request_packet)
 }
 
-pub fn unpack_request(container: Container) -> Result<(u32, String)> {
+pub fn unpack_request(container: Container)
+                      -> Result<(u64, String, Option<u32>, Option<Timespec>, Option<String>)> {
     let request_packet = try!(unpack::<message::Request>(&container));
     Ok((request_packet.get_id(),
-        request_packet.get_name().to_string()))
+        request_packet.get_name().to_string(),
+        if request_packet.has_ttl() {
+            Some(request_packet.get_ttl())
+        } else {
+            None
+        },
+        if request_packet.has_deadline() {
+            Some(deadline::from_millis(request_packet.get_deadline()))
+        } else {
+            None
+        },
+        if request_packet.has_trace_id() {
+            Some(request_packet.get_trace_id().to_string())
+        } else {
+            None
+        }))
 }
 
-pub fn pack_response(request_id: u32, response: &service::Result) -> Container {
+/// Packs a one-way notification. The payload is carried in the container itself, since no
+/// packets follow it.
+pub fn pack_notification(name: &str,
+                         payload: Vec<u8>,
+                         ttl: Option<u32>,
+                         trace_id: Option<&str>)
+                         -> Container {
+    let mut notification_packet = message::Notification::new();
+    notification_packet.set_name(name.to_string());
+    notification_packet.set_payload(payload);
+    if let Some(ttl) = ttl {
+        notification_packet.set_ttl(ttl);
+    }
+    if let Some(trace_id) = trace_id {
+        notification_packet.set_trace_id(trace_id.to_string());
+    }
+    pack(message::Kind::NotificationMessage, notification_packet)
+}
+
+pub fn unpack_notification(container: Container)
+                           -> Result<(String, Vec<u8>, Option<u32>, Option<String>)> {
+    let mut notification_packet = try!(unpack::<message::Notification>(&container));
+    Ok((notification_packet.take_name(),
+        notification_packet.take_payload(),
+        if notification_packet.has_ttl() {
+            Some(notification_packet.get_ttl())
+        } else {
+            None
+        },
+        if notification_packet.has_trace_id() {
+            Some(notification_packet.take_trace_id())
+        } else {
+            None
+        }))
+}
+
+pub fn pack_response(request_id: u64, response: &service::Result) -> Container {
     let mut response_packet = message::Response::new();
     response_packet.set_request_id(request_id);
     match *response {
@@ -199,13 +455,24 @@ pub fn pack_response(request_id: u32, response: &service::Result) -> Container {
             response_packet.set_kind(message::Response_Kind::Internal);
             response_packet.set_message(message.to_string());
         }
+        Err(service::Error::InvalidInput(ref message)) => {
+            response_packet.set_kind(message::Response_Kind::InvalidInput);
+            response_packet.set_message(message.to_string());
+        }
+        Err(service::Error::Redirect(ref name, ref node_id)) => {
+            response_packet.set_kind(message::Response_Kind::Redirect);
+            response_packet.set_redirect_service(name.to_string());
+            if let Some(ref node_id) = *node_id {
+                response_packet.set_redirect_node_id(node_id.to_vec());
+            }
+        }
     }
     pack(message::Kind::ResponseMessage, response_packet)
 }
 
 pub fn unpack_response(container: Container,
                        response_reader: Box<response::Reader>)
-                       -> Result<(u32, service::Result)> {
+                       -> Result<(u64, service::Result)> {
     let response_packet = try!(unpack::<message::Response>(&container));
     let result = match response_packet.get_kind() {
         message::Response_Kind::OK => Ok(response_reader),
@@ -214,12 +481,125 @@ pub fn unpack_response(container: Container,
         message::Response_Kind::Internal => {
             Err(service::Error::Internal(response_packet.get_message().to_string()))
         }
+        message::Response_Kind::InvalidInput => {
+            Err(service::Error::InvalidInput(response_packet.get_message().to_string()))
+        }
+        message::Response_Kind::Redirect => {
+            let node_id = if response_packet.has_redirect_node_id() {
+                Some(try!(ID::from_vec(response_packet.get_redirect_node_id().to_vec())))
+            } else {
+                None
+            };
+            Err(service::Error::Redirect(response_packet.get_redirect_service().to_string(),
+                                         node_id))
+        }
     };
     Ok((response_packet.get_request_id(), result))
 }
 
+pub fn pack_credit(pt: PacketType, request_id: u64, packets: u32) -> Container {
+    let mut credit_packet = message::Credit::new();
+    credit_packet.set_request_id(request_id);
+    credit_packet.set_packets(packets);
+    pack(match pt {
+             PacketType::Request => message::Kind::RequestCreditMessage,
+             PacketType::Response => message::Kind::ResponseCreditMessage,
+         },
+         credit_packet)
+}
+
+pub fn unpack_credit(container: Container) -> Result<(u64, u32)> {
+    let credit_packet = try!(unpack::<message::Credit>(&container));
+    Ok((credit_packet.get_request_id(), credit_packet.get_packets()))
+}
+
+pub fn pack_stats_request(request_id: u64) -> Container {
+    let mut stats_request_packet = message::StatsRequest::new();
+    stats_request_packet.set_request_id(request_id);
+    pack(message::Kind::StatsRequestMessage, stats_request_packet)
+}
+
+/// Returns the id of the request. Peers before the ids were introduced don't send one.
+pub fn unpack_stats_request(container: Container) -> Result<Option<u64>> {
+    let stats_request_packet = try!(unpack::<message::StatsRequest>(&container));
+    Ok(optional_request_id(stats_request_packet.has_request_id(),
+                           stats_request_packet.get_request_id()))
+}
+
+pub fn pack_drain_request(request_id: u64, acquire: bool) -> Container {
+    let mut drain_request_packet = message::DrainRequest::new();
+    drain_request_packet.set_acquire(acquire);
+    drain_request_packet.set_request_id(request_id);
+    pack(message::Kind::DrainRequestMessage, drain_request_packet)
+}
+
+pub fn unpack_drain_request(container: Container) -> Result<(Option<u64>, bool)> {
+    let drain_request_packet = try!(unpack::<message::DrainRequest>(&container));
+    Ok((optional_request_id(drain_request_packet.has_request_id(),
+                            drain_request_packet.get_request_id()),
+        drain_request_packet.get_acquire()))
+}
+
+pub fn pack_drain_response(request_id: Option<u64>, granted: bool, draining: usize) -> Container {
+    let mut drain_response_packet = message::DrainResponse::new();
+    drain_response_packet.set_granted(granted);
+    drain_response_packet.set_draining(draining as u32);
+    if let Some(request_id) = request_id {
+        drain_response_packet.set_request_id(request_id);
+    }
+    pack(message::Kind::DrainResponseMessage, drain_response_packet)
+}
+
+pub fn unpack_drain_response(container: Container) -> Result<(Option<u64>, (bool, usize))> {
+    let drain_response_packet = try!(unpack::<message::DrainResponse>(&container));
+    Ok((optional_request_id(drain_response_packet.has_request_id(),
+                            drain_response_packet.get_request_id()),
+        (drain_response_packet.get_granted(),
+         drain_response_packet.get_draining() as usize)))
+}
+
+pub fn pack_stats_response(request_id: Option<u64>, stats: &ConnectionStats) -> Container {
+    let clock = stats.clock.to_timespec();
+    let mut stats_packet = message::StatsResponse::new();
+    if let Some(request_id) = request_id {
+        stats_packet.set_request_id(request_id);
+    }
+    stats_packet.set_bytes_sent(stats.bytes_sent as u64);
+    stats_packet.set_bytes_received(stats.bytes_received as u64);
+    stats_packet.set_frames_sent(stats.frames_sent as u64);
+    stats_packet.set_frames_received(stats.frames_received as u64);
+    if let Some(ref last_error) = stats.last_error {
+        stats_packet.set_last_error(last_error.to_string());
+    }
+    stats_packet.set_clock(clock.sec * 1000 + clock.nsec as i64 / 1000000);
+    stats_packet.set_uptime(stats.uptime.num_milliseconds());
+    pack(message::Kind::StatsResponseMessage, stats_packet)
+}
+
+pub fn unpack_stats_response(container: Container) -> Result<(Option<u64>, ConnectionStats)> {
+    let stats_packet = try!(unpack::<message::StatsResponse>(&container));
+    let clock = stats_packet.get_clock();
+    if clock < 0 || clock > MAX_CLOCK {
+        return Err(Error::InvalidValue("clock"));
+    }
+    Ok((optional_request_id(stats_packet.has_request_id(), stats_packet.get_request_id()),
+        ConnectionStats {
+            bytes_sent: stats_packet.get_bytes_sent() as usize,
+            bytes_received: stats_packet.get_bytes_received() as usize,
+            frames_sent: stats_packet.get_frames_sent() as usize,
+            frames_received: stats_packet.get_frames_received() as usize,
+            last_error: if stats_packet.has_last_error() {
+                Some(stats_packet.get_last_error().to_string())
+            } else {
+                None
+            },
+            clock: time::at_utc(Timespec::new(clock / 1000, (clock % 1000 * 1000000) as i32)),
+            uptime: Duration::milliseconds(stats_packet.get_uptime()),
+        }))
+}
+
 pub fn pack_packet(pt: PacketType,
-                   request_id: u32,
+                   request_id: u64,
                    result: io::Result<usize>,
                    buffer: &[u8])
                    -> Container {
@@ -258,7 +638,7 @@ pub fn pack_packet(pt: PacketType,
     pack(message::Kind::from(pt), packet)
 }
 
-pub fn unpack_packet(container: Container) -> Result<(u32, io::Result<Vec<u8>>)> {
+pub fn unpack_packet(container: Container) -> Result<(u64, io::Result<Vec<u8>>)> {
     let mut packet = try!(unpack::<message::Packet>(&container));
     match packet.get_result() {
         message::Packet_Result::Ok => Ok((packet.get_request_id(), Ok(packet.take_payload()))),
@@ -290,6 +670,60 @@ pub fn unpack_packet(container: Container) -> Result<(u32, io::Result<Vec<u8>>)>
     }
 }
 
+/// Parses the bytes of a container and unpacks the message it carries, like a connection would
+/// do it with an inbound container. Meant to be driven by fuzzing harnesses.
+#[doc(hidden)]
+pub fn parse_and_unpack(bytes: &[u8]) -> Result<()> {
+    let container = try!(Container::parse(bytes));
+    match container.get_kind() {
+        message::Kind::IntroductionMessage => {
+            try!(unpack_introduction(container));
+        }
+        message::Kind::PeersMessage => {
+            try!(unpack_peers(container));
+        }
+        message::Kind::AddServicesMessage => {
+            try!(unpack_add_services(container));
+        }
+        message::Kind::RemoveServicesMessage => {
+            try!(unpack_remove_services(container));
+        }
+        message::Kind::AknowledgeMessage => try!(unpack_aknowledge(container)),
+        message::Kind::RequestMessage => {
+            try!(unpack_request(container));
+        }
+        message::Kind::RequestPacketMessage | message::Kind::ResponsePacketMessage => {
+            try!(unpack_packet(container));
+        }
+        message::Kind::ResponseMessage => {
+            try!(unpack_response(container, Box::new(io::Cursor::new(Vec::new()))));
+        }
+        message::Kind::RequestCreditMessage | message::Kind::ResponseCreditMessage => {
+            try!(unpack_credit(container));
+        }
+        message::Kind::StatsRequestMessage => {
+            try!(unpack_stats_request(container));
+        }
+        message::Kind::StatsResponseMessage => {
+            try!(unpack_stats_response(container));
+        }
+        message::Kind::PingMessage => try!(unpack_ping(container)),
+        message::Kind::PongMessage => try!(unpack_pong(container)),
+        message::Kind::IdleMessage => try!(unpack_idle(container)),
+        message::Kind::GoodbyeMessage => try!(unpack_goodbye(container)),
+        message::Kind::DrainRequestMessage => {
+            try!(unpack_drain_request(container));
+        }
+        message::Kind::DrainResponseMessage => {
+            try!(unpack_drain_response(container));
+        }
+        message::Kind::NotificationMessage => {
+            try!(unpack_notification(container));
+        }
+    }
+    Ok(())
+}
+
 fn pack<T>(kind: message::Kind, message: T) -> Container
     where T: protobuf::Message + protobuf::MessageStatic
 {
@@ -308,6 +742,14 @@ fn unpack<T>(container: &Container) -> Result<T>
     Ok(try!(protobuf::parse_from_bytes::<T>(container.message.get_payload())))
 }
 
+fn optional_request_id(has_request_id: bool, request_id: u64) -> Option<u64> {
+    if has_request_id {
+        Some(request_id)
+    } else {
+        None
+    }
+}
+
 impl From<PacketType> for message::Kind {
     fn from(pt: PacketType) -> Self {
         match pt {
@@ -340,3 +782,266 @@ impl From<Error> for io::Error {
         io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", error))
     }
 }
+
+fn format_address(address: SocketAddr) -> String {
+    match address {
+        SocketAddr::V6(address) if address.scope_id() != 0 => {
+            format!("[{}%{}]:{}", address.ip(), address.scope_id(), address.port())
+        }
+        address => format!("{}", address),
+    }
+}
+
+fn parse_address(value: &str) -> result::Result<SocketAddr, net::AddrParseError> {
+    if let (Some(start), Some(end)) = (value.find('%'), value.find(']')) {
+        if start < end {
+            if let Ok(scope_id) = value[start + 1..end].parse::<u32>() {
+                let address = try!(format!("{}{}", &value[..start], &value[end..])
+                                       .parse::<SocketAddr>());
+                if let SocketAddr::V6(address) = address {
+                    return Ok(SocketAddr::V6(SocketAddrV6::new(*address.ip(),
+                                                               address.port(),
+                                                               address.flowinfo(),
+                                                               scope_id)));
+                }
+            }
+        }
+    }
+    value.parse::<SocketAddr>()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io;
+    use std::net::SocketAddr;
+    use time::{self, Duration};
+    use node::{ID, service};
+    use transport::ConnectionStats;
+    use super::{Container, Error, PacketType, format_address, pack_add_services,
+                pack_drain_request, pack_drain_response, pack_notification, pack_packet,
+                pack_request, pack_response, pack_routes, pack_stats_response, parse_address,
+                parse_and_unpack, unpack_add_services, unpack_drain_request,
+                unpack_drain_response, unpack_notification, unpack_packet, unpack_request,
+                unpack_response, unpack_routes, unpack_stats_response};
+
+    #[test]
+    fn address_round_trip() {
+        for value in vec!["127.0.0.1:3001", "[::1]:3001", "[fe80::1%2]:3001"] {
+            let address = parse_address(value).unwrap();
+            assert_eq!(value, format_address(address));
+        }
+
+        match parse_address("[fe80::1%2]:3001").unwrap() {
+            SocketAddr::V6(address) => assert_eq!(2, address.scope_id()),
+            address => panic!("unexpected address {}", address),
+        }
+        assert!(parse_address("[fe80::1%x]:3001").is_err());
+    }
+
+    #[test]
+    fn redirect_response_round_trip() {
+        let node_id = ID::new_random();
+        for redirect in vec![service::Error::Redirect("echo-2".to_string(), Some(node_id)),
+                             service::Error::Redirect("echo-3".to_string(), None)] {
+            let container = pack_response(7, &Err(redirect.clone()));
+            match unpack_response(container, Box::new(io::Cursor::new(Vec::new()))).unwrap() {
+                (7, Err(error)) => assert_eq!(redirect, error),
+                (request_id, _) => panic!("unexpected response for request {}", request_id),
+            }
+        }
+    }
+
+    #[test]
+    fn routes_round_trip() {
+        let routes = vec![("echo".to_string(), 2), ("time".to_string(), 1)];
+        assert_eq!(vec![("echo".to_string(), 2, 1, service::Metadata::new()),
+                        ("time".to_string(), 1, 1, service::Metadata::new())],
+                   unpack_routes(pack_routes(&routes)).unwrap());
+
+        let mut metadata = service::Metadata::new();
+        metadata.insert("version".to_string(), "1.2".to_string());
+        metadata.insert("region".to_string(), "eu".to_string());
+        let services = vec![("echo".to_string(), 3, metadata.clone())];
+        assert_eq!(vec![("echo".to_string(), 0, 3, metadata)],
+                   unpack_routes(pack_add_services(&services)).unwrap());
+        assert_eq!(vec!["echo".to_string()],
+                   unpack_add_services(pack_routes(&[("echo".to_string(), 2)])).unwrap());
+    }
+
+    #[test]
+    fn request_ttl_round_trip() {
+        assert_eq!((7, "echo".to_string(), None, None, None),
+                   unpack_request(pack_request(7, "echo", None, None, None)).unwrap());
+        assert_eq!((7, "echo".to_string(), Some(0), None, None),
+                   unpack_request(pack_request(7, "echo", Some(0), None, None)).unwrap());
+    }
+
+    #[test]
+    fn request_deadline_round_trip() {
+        let deadline = time::Timespec::new(1444000000, 123000000);
+        assert_eq!((7, "echo".to_string(), None, Some(deadline), None),
+                   unpack_request(pack_request(7, "echo", None, Some(deadline), None)).unwrap());
+    }
+
+    #[test]
+    fn request_trace_id_round_trip() {
+        assert_eq!((7, "echo".to_string(), None, None, Some("0123456789abcdef".to_string())),
+                   unpack_request(pack_request(7, "echo", None, None, Some("0123456789abcdef")))
+                       .unwrap());
+    }
+
+    #[test]
+    fn notification_round_trip() {
+        assert_eq!(("events".to_string(), b"payload".to_vec(), Some(2), Some("trace".to_string())),
+                   unpack_notification(pack_notification("events",
+                                                         b"payload".to_vec(),
+                                                         Some(2),
+                                                         Some("trace")))
+                       .unwrap());
+    }
+
+    #[test]
+    fn drain_round_trip() {
+        assert_eq!((Some(3), true),
+                   unpack_drain_request(pack_drain_request(3, true)).unwrap());
+        assert_eq!((Some(3), (true, 2)),
+                   unpack_drain_response(pack_drain_response(Some(3), true, 2)).unwrap());
+        assert_eq!((None, (false, 0)),
+                   unpack_drain_response(pack_drain_response(None, false, 0)).unwrap());
+    }
+
+    #[test]
+    fn stats_response_round_trip() {
+        let stats = ConnectionStats {
+            bytes_sent: 1024,
+            bytes_received: 2048,
+            frames_sent: 3,
+            frames_received: 4,
+            last_error: Some("connection reset".to_string()),
+            clock: time::at_utc(time::Timespec::new(1444000000, 123000000)),
+            uptime: Duration::milliseconds(5000),
+        };
+
+        let (request_id, result) = unpack_stats_response(pack_stats_response(Some(7), &stats))
+                                       .unwrap();
+        assert_eq!(Some(7), request_id);
+        assert_eq!(1024, result.bytes_sent);
+        assert_eq!(2048, result.bytes_received);
+        assert_eq!(3, result.frames_sent);
+        assert_eq!(4, result.frames_received);
+        assert_eq!(Some("connection reset".to_string()), result.last_error);
+        assert_eq!(stats.clock, result.clock);
+        assert_eq!(stats.uptime, result.uptime);
+    }
+
+    #[test]
+    fn compressed_packet_round_trip() {
+        let payload = vec![b'a'; 4096];
+        let mut container = pack_packet(PacketType::Request, 1, Ok(payload.len()), &payload);
+        container.compress(1024).unwrap();
+
+        let mut bytes = Vec::new();
+        container.write(&mut bytes).unwrap();
+        assert!(bytes.len() < payload.len());
+
+        let container = Container::read(&mut io::Cursor::new(bytes)).unwrap();
+        let (request_id, result) = unpack_packet(container).unwrap();
+        assert_eq!(1, request_id);
+        assert_eq!(payload, result.unwrap());
+    }
+
+    #[test]
+    fn compress_skips_payload_below_threshold() {
+        let payload = vec![b'a'; 512];
+        let mut container = pack_packet(PacketType::Request, 1, Ok(payload.len()), &payload);
+        container.compress(1024).unwrap();
+
+        let mut bytes = Vec::new();
+        container.write(&mut bytes).unwrap();
+        assert!(bytes.len() > payload.len());
+    }
+
+    #[test]
+    fn parse_arbitrary_bytes() {
+        for bytes in vec![vec![0xff],
+                          vec![0x08, 0x63],
+                          vec![0x12, 0x80, 0x80, 0x80, 0x80, 0x10],
+                          vec![0x08, 0x02, 0x12, 0x04, 0x0a, 0x02, 0x0a, 0x00]] {
+            assert!(parse_and_unpack(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_packet_container() {
+        let mut container = pack_packet(PacketType::Response, 1, Ok(3), b"abc");
+        container.compress(0).unwrap();
+
+        let mut bytes = Vec::new();
+        container.write(&mut bytes).unwrap();
+        assert!(parse_and_unpack(&bytes[8..]).is_ok());
+    }
+
+    #[test]
+    fn reject_oversized_container() {
+        let mut bytes = Vec::new();
+        ::util::writer::write_size(&mut bytes, 1 << 40).unwrap();
+
+        let error = Container::read(&mut io::Cursor::new(bytes)).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+
+    #[test]
+    fn reject_container_beyond_limit() {
+        let payload = vec![b'a'; 1024];
+        let mut bytes = Vec::new();
+        pack_packet(PacketType::Request, 1, Ok(payload.len()), &payload).write(&mut bytes).unwrap();
+
+        assert!(Container::read_limited(&mut io::Cursor::new(bytes.clone()), 2048).is_ok());
+        let error = Container::read_limited(&mut io::Cursor::new(bytes), 512).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+
+    #[test]
+    fn reject_compressed_container_beyond_limit() {
+        let payload = vec![b'a'; 4096];
+        let mut container = pack_packet(PacketType::Request, 1, Ok(payload.len()), &payload);
+        container.compress(1024).unwrap();
+        let mut bytes = Vec::new();
+        container.write(&mut bytes).unwrap();
+        assert!(bytes.len() < 1024);
+
+        let error = Container::read_limited(&mut io::Cursor::new(bytes), 1024).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+
+    #[test]
+    fn reject_clock_out_of_range() {
+        let mut container = pack_stats_response(None, &ConnectionStats {
+            bytes_sent: 0,
+            bytes_received: 0,
+            frames_sent: 0,
+            frames_received: 0,
+            last_error: None,
+            clock: time::at_utc(time::Timespec::new(0, 0)),
+            uptime: Duration::milliseconds(0),
+        });
+        let mut bytes = Vec::new();
+        container.write(&mut bytes).unwrap();
+        container = Container::read(&mut io::Cursor::new(bytes)).unwrap();
+        assert!(unpack_stats_response(container).is_ok());
+
+        match unpack_stats_response(super::pack_stats_response(None, &ConnectionStats {
+            bytes_sent: 0,
+            bytes_received: 0,
+            frames_sent: 0,
+            frames_received: 0,
+            last_error: None,
+            clock: time::at_utc(time::Timespec::new(-1, 0)),
+            uptime: Duration::milliseconds(0),
+        })) {
+            Err(Error::InvalidValue("clock")) => {}
+            result => panic!("unexpected result {:?}", result.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::cell::Cell;
+use std::sync::{Arc, mpsc};
+
+use time::{self, Duration, Timespec};
+
+use logger::trace;
+use node::{Service, request, service};
+use util::clock::Clock;
+use util::thread::supervisor;
+
+thread_local!(static DEADLINE: Cell<Option<Timespec>> = Cell::new(None));
+
+/// Returns the deadline of the request that is handled by the current thread. Requests that are
+/// sent while it's set inherit it.
+pub fn current() -> Option<Timespec> {
+    DEADLINE.with(|deadline| deadline.get())
+}
+
+pub fn set(deadline: Option<Timespec>) {
+    DEADLINE.with(|current| current.set(deadline));
+}
+
+/// Returns the deadline for a request that is sent now and may take the given timeout, but not
+/// longer than the deadline of the current request.
+pub fn for_request(timeout: Option<Duration>) -> Option<Timespec> {
+    let deadline = timeout.map(|timeout| time::get_time() + timeout);
+    match (current(), deadline) {
+        (Some(current), Some(deadline)) if deadline < current => Some(deadline),
+        (Some(current), _) => Some(current),
+        (None, deadline) => deadline,
+    }
+}
+
+pub fn is_expired(deadline: Timespec) -> bool {
+    deadline <= time::get_time()
+}
+
+pub fn to_millis(deadline: Timespec) -> i64 {
+    deadline.sec * 1000 + deadline.nsec as i64 / 1000000
+}
+
+pub fn from_millis(millis: i64) -> Timespec {
+    Timespec::new(millis / 1000, ((millis % 1000) * 1000000) as i32)
+}
+
+/// Calls the handler with the deadline set and returns `Timeout` as soon as the deadline has
+/// passed. A handler that is still running at that point can't be stopped, but its result is
+/// dropped and the requests it sends fail right away.
+pub fn call(handler: Arc<Box<Service>>,
+            reader: Box<request::Reader>,
+            deadline: Timespec,
+            clock: &Arc<Clock>)
+            -> service::Result {
+    let remaining = deadline - time::get_time();
+    if remaining <= Duration::zero() {
+        return Err(service::Error::Timeout);
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let timeout_tx = tx.clone();
+    let trace_id = trace::trace_id();
+    supervisor().spawn("deadline request", move || {
+        set(Some(deadline));
+        trace::set_trace_id(trace_id);
+        let _ = tx.send(handler(reader));
+        trace::set_trace_id(None);
+        set(None);
+    });
+    let clock = clock.clone();
+    supervisor().spawn("deadline timeout", move || {
+        clock.sleep(remaining);
+        let _ = timeout_tx.send(Err(service::Error::Timeout));
+    });
+    rx.recv().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io;
+    use std::sync::{Arc, mpsc};
+    use std::thread;
+    use time::{self, Duration};
+
+    use node::{Service, request, service};
+    use util::clock::{Clock, Mock};
+    use super::{call, current, from_millis, to_millis};
+
+    #[test]
+    fn millis_round_trip() {
+        let deadline = time::Timespec::new(1444000000, 123000000);
+        assert_eq!(1444000000123, to_millis(deadline));
+        assert_eq!(deadline, from_millis(to_millis(deadline)));
+    }
+
+    #[test]
+    fn expired_deadline() {
+        let clock: Arc<Clock> = Arc::new(Mock::new());
+        let handler: Arc<Box<Service>> =
+            Arc::new(Box::new(|_: Box<request::Reader>| -> service::Result {
+                panic!("handler called after the deadline")
+            }));
+        assert_eq!(Some(service::Error::Timeout),
+                   call(handler,
+                        Box::new(io::empty()),
+                        time::get_time() - Duration::seconds(1),
+                        &clock)
+                       .err());
+    }
+
+    #[test]
+    fn timeout_slow_handler() {
+        let mock = Arc::new(Mock::new());
+        let clock: Arc<Clock> = mock.clone();
+        let (started_tx, started_rx) = mpsc::channel();
+        let handler: Arc<Box<Service>> =
+            Arc::new(Box::new(move |_: Box<request::Reader>| -> service::Result {
+                started_tx.send(current().is_some()).unwrap();
+                thread::sleep(::std::time::Duration::from_secs(60));
+                Ok(Box::new(io::empty()))
+            }));
+
+        let deadline = time::get_time() + Duration::seconds(5);
+        let join_handle = thread::spawn(move || {
+            call(handler, Box::new(io::empty()), deadline, &clock)
+        });
+        assert!(started_rx.recv().unwrap());
+        mock.wait_for_sleepers(1);
+        mock.advance(Duration::seconds(10));
+
+        assert_eq!(Some(service::Error::Timeout), join_handle.join().unwrap().err());
+    }
+
+}
@@ -13,60 +13,417 @@
 // limitations under the License.
 //
 
-use std::io;
+extern crate net2;
+
+use std::cmp;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{self, Read};
 use std::net::{self, SocketAddr};
 use std::sync::{Arc, Mutex, RwLock, mpsc};
 use std::thread;
-use time::Duration;
+use rand::{self, Rng};
+use time::{self, Duration, SteadyTime};
 
+use transport::Capture;
 use transport::cipher::{self, Cipher};
-use transport::{Result, Transport};
+use transport::{self, ConnectionStats, JoinEvent, JoinReport, PeerInfo, ReconnectState, Result,
+                Transport};
+use logger::{collector, trace};
 use metric::Metric;
-use node::{ID, Service, request, response};
-use super::{Connection, ConnectionMap, Handlers, Link, Tracker, ServiceMap, balancer};
+use node::{ID, Placement, Service, request, response, service, version};
+use util::clock::{self, Clock};
+use util::memory::Watermark;
+use util::net::Proxy;
+use util::time::to_std_duration;
+use super::{Config, Connection, ConnectionMap, Handlers, Link, Tracker, ServiceMap, balancer};
+use super::{connection, container, deadline};
+use super::drain::Permits;
+use super::packet;
+use super::public_address::PublicAddress;
+use super::reconnects::Reconnects;
+use super::replay::Replay;
+use super::retry;
+use super::router::Router;
 use super::tracker::Statistic;
+use util::thread::supervisor;
+use self::net2::TcpBuilder;
+
+const LISTEN_BACKLOG: i32 = 128;
+const MAX_REDIRECTS: usize = 4;
+const MAX_REDIRECT_BODY_SIZE: usize = 64 * 1024;
+const SHUTDOWN_POLL_INTERVAL_MS: i64 = 10;
+const DEFAULT_SHUTDOWN_TIMEOUT_S: i64 = 30;
 
 pub struct Direct {
-    join_handle: RwLock<Option<thread::JoinHandle<()>>>,
+    join_handles: RwLock<Vec<thread::JoinHandle<()>>>,
     running: Arc<RwLock<bool>>,
-    local_address: SocketAddr,
-    public_address: SocketAddr,
+    node_id: RwLock<Option<ID>>,
+    local_addresses: Vec<SocketAddr>,
+    public_address: Arc<PublicAddress>,
     cipher: Arc<Box<Cipher>>,
     connections: Arc<ConnectionMap>,
     services: Arc<ServiceMap>,
     tracker: Arc<Tracker<Mutex<Box<response::Handler>>, request::Result<()>>>,
+    options: connection::Options,
+    reconnect_backoff: Option<Duration>,
+    reconnect_max_backoff: Duration,
+    reconnects: Arc<Reconnects>,
+    abandoned: Arc<Mutex<HashSet<SocketAddr>>>,
+    idle_timeout: Option<Duration>,
+    idle: Arc<Mutex<HashMap<ID, SocketAddr>>>,
+    join_parallelism: usize,
+    join_dial_timeout: Option<Duration>,
+    permits: Arc<Permits>,
+    draining: Arc<RwLock<bool>>,
+    capture: Option<Arc<Capture>>,
+    router: Arc<Router>,
+    proxy: Option<Proxy>,
+    memory: Option<Arc<Watermark>>,
+    retry_policies: retry::Policies,
 }
 
 impl Direct {
     pub fn new(cipher: Box<Cipher>,
                mut balancer_factory: Box<balancer::Factory>,
                metric: Arc<Metric>,
-               local_address: SocketAddr,
-               public_address: Option<SocketAddr>,
-               request_timeout: Option<Duration>)
+               config: Config)
                -> Self {
 
-        let statistic = Arc::new(Statistic::new());
+        let clock: Arc<Clock> = Arc::new(clock::System);
+        let statistic = Arc::new(Statistic::with_clock(clock.clone()));
+        if let Some((consecutive_failures, duration)) = config.outlier_ejection {
+            statistic.set_ejection(consecutive_failures, duration);
+        }
         balancer_factory.set_statistic(statistic.clone());
 
+        let connections = Arc::new(ConnectionMap::new(metric.clone()));
+        let services = Arc::new(ServiceMap::with_clock(balancer_factory,
+                                                       metric.clone(),
+                                                       config.service_grace_period,
+                                                       config.max_services_per_peer,
+                                                       config.max_service_name_length,
+                                                       clock.clone()));
+        services.set_strict_names(config.strict_service_names);
+        if let Some((consecutive_failures, cool_down)) = config.breaker {
+            services.set_breaker(consecutive_failures, cool_down);
+        }
+        let tracker = Arc::new(Tracker::with_clock(statistic.clone(),
+                                                   config.request_timeout,
+                                                   config.wide_request_ids,
+                                                   clock.clone()));
+        let router = Arc::new(Router::new(config.max_hops,
+                                          connections.clone(),
+                                          services.clone(),
+                                          tracker.clone()));
+
         Direct {
-            join_handle: RwLock::new(None),
+            join_handles: RwLock::new(Vec::new()),
             running: Arc::new(RwLock::new(false)),
-            local_address: local_address,
-            public_address: public_address.unwrap_or(local_address),
+            node_id: RwLock::new(None),
+            public_address: Arc::new(PublicAddress::new(config.local_addresses[0],
+                                                        config.public_address)),
+            local_addresses: config.local_addresses,
             cipher: Arc::new(cipher),
-            connections: Arc::new(ConnectionMap::new(metric.clone())),
-            services: Arc::new(ServiceMap::new(balancer_factory, metric.clone())),
-            tracker: Arc::new(Tracker::new(statistic.clone(), request_timeout)),
+            connections: connections,
+            services: services,
+            tracker: tracker,
+            options: connection::Options {
+                zone: config.zone,
+                aknowledge_timeout: config.aknowledge_timeout,
+                coalesce_delay: config.coalesce_delay,
+                coalesce_size: config.coalesce_size,
+                heartbeat_interval: config.heartbeat_interval,
+                heartbeat_timeout: config.heartbeat_timeout,
+                compression_threshold: config.compression_threshold,
+                flow_control_window: config.flow_control_window,
+                upload_limit: config.upload_limit,
+                download_limit: config.download_limit,
+                max_message_size: config.max_message_size,
+                packet_pool: Arc::new(packet::Pool::new(config.packet_buffer_size)),
+                clock: clock.clone(),
+            },
+            reconnect_backoff: config.reconnect_backoff,
+            reconnect_max_backoff: config.reconnect_max_backoff,
+            reconnects: Arc::new(Reconnects::new(metric.clone())),
+            abandoned: Arc::new(Mutex::new(HashSet::new())),
+            idle_timeout: config.idle_timeout,
+            idle: Arc::new(Mutex::new(HashMap::new())),
+            join_parallelism: if config.join_parallelism > 0 {
+                config.join_parallelism
+            } else {
+                1
+            },
+            join_dial_timeout: config.join_dial_timeout,
+            permits: Arc::new(Permits::new(config.drain_limit, config.drain_lease_ttl, clock)),
+            draining: Arc::new(RwLock::new(false)),
+            capture: config.capture.map(Arc::new),
+            router: router,
+            proxy: config.proxy,
+            memory: config.memory.map(Arc::new),
+            retry_policies: config.retry_policies,
+        }
+    }
+
+    fn drain(&self, acquire: bool) -> Result<(bool, usize)> {
+        let node_id = match *self.node_id.read().unwrap() {
+            Some(node_id) => node_id,
+            None => {
+                return Err(transport::Error::Io(io::Error::new(io::ErrorKind::NotConnected,
+                                                               "transport is not bound")))
+            }
+        };
+        let (granted, draining) = try!(self.context(node_id).drain(acquire));
+        // the lease is renewed and re-registered with a new coordinator until it's released.
+        *self.draining.write().unwrap() = granted;
+        Ok((granted, draining))
+    }
+
+    fn select_service(&self,
+                      name: &str,
+                      key: Option<&[u8]>)
+                      -> request::Result<(Link, Option<Arc<Box<Service>>>)> {
+        let service_name = version::service_name(name);
+        let deadline = deadline::for_request(self.tracker.timeout());
+        loop {
+            let epoch = self.services.epoch();
+            match self.services.get_with_key(name, key) {
+                Err(request::Error::NoService) => {
+                    let grace_period = match self.services.remaining_grace_period(service_name) {
+                        Some(grace_period) => grace_period,
+                        None => return Err(request::Error::NoService),
+                    };
+                    let timeout = match deadline {
+                        Some(deadline) => {
+                            let remaining = deadline - time::get_time();
+                            if remaining <= Duration::zero() {
+                                return Err(request::Error::Timeout);
+                            }
+                            cmp::min(grace_period, remaining)
+                        }
+                        None => grace_period,
+                    };
+                    self.services.wait_for_change(epoch, timeout);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn select_service_on(&self,
+                         name: &str,
+                         node_id: ID)
+                         -> Option<(Link, Option<Arc<Box<Service>>>)> {
+        let link = if Some(node_id) == *self.node_id.read().unwrap() {
+            Link::Local
+        } else {
+            Link::Remote(node_id)
+        };
+        self.services.get_on(name, &link)
+    }
+
+    fn request_once(&self,
+                    name: &str,
+                    node_id: Option<ID>,
+                    key: Option<&[u8]>,
+                    reader: Box<request::Reader>,
+                    response_handler: Box<response::Handler>)
+                    -> request::Result<()> {
+
+        let (link, local_handler) = match node_id.and_then(|node_id| {
+            self.select_service_on(name, node_id)
+        }) {
+            Some(selection) => selection,
+            None => try!(self.select_service(name, key)),
+        };
+        self.request_selected(version::service_name(name),
+                              link,
+                              local_handler,
+                              reader,
+                              response_handler)
+    }
+
+    fn request_selected(&self,
+                        name: &str,
+                        link: Link,
+                        local_handler: Option<Arc<Box<Service>>>,
+                        reader: Box<request::Reader>,
+                        response_handler: Box<response::Handler>)
+                        -> request::Result<()> {
+        if link == Link::Local {
+            if let Some(node_id) = *self.node_id.read().unwrap() {
+                self.services.record_usage(name, node_id);
+            }
+        }
+
+        let result = self.request_over(name, link, local_handler, reader, response_handler);
+        self.services.record_result(name, &link, !is_failure(&result));
+        result
+    }
+
+    fn notify_selected(&self,
+                       name: &str,
+                       link: Link,
+                       local_handler: Option<Arc<Box<Service>>>,
+                       mut reader: Box<request::Reader>)
+                       -> request::Result<()> {
+        let trace_id = trace::trace_id();
+        match link {
+            Link::Local => {
+                if let Some(node_id) = *self.node_id.read().unwrap() {
+                    self.services.record_usage(name, node_id);
+                }
+                let local_handler = local_handler.unwrap();
+                let name = name.to_string();
+                supervisor().spawn("local notification", move || {
+                    trace::set_trace_id(trace_id);
+                    if let Err(error) = local_handler(reader) {
+                        debug!("notification for {} failed: {:?}", name, error);
+                    }
+                    trace::set_trace_id(None);
+                });
+            }
+            Link::Remote(peer_node_id) => {
+                let mut payload = Vec::new();
+                try!(reader.read_to_end(&mut payload));
+                try!(self.redial_idle(&peer_node_id));
+                match self.connections.select(&peer_node_id, |connection| {
+                    connection.send_notification(name,
+                                                 None,
+                                                 trace_id.as_ref().map(String::as_str),
+                                                 payload)
+                }) {
+                    Ok(result) => try!(result),
+                    Err(_) => return Err(request::Error::Service(service::Error::Unavailable)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn request_over(&self,
+                    name: &str,
+                    link: Link,
+                    local_handler: Option<Arc<Box<Service>>>,
+                    mut reader: Box<request::Reader>,
+                    response_handler: Box<response::Handler>)
+                    -> request::Result<()> {
+        match link {
+            Link::Local if !self.tracker.has_timeout() => {
+                let (request_id, response_rx) = self.tracker
+                                                    .begin(name,
+                                                           &Link::Local,
+                                                           Mutex::new(response_handler));
+                let service_result = local_handler.unwrap()(reader);
+                self.tracker.end(request_id,
+                                 |response_handler| respond(service_result, &response_handler));
+                try!(response_rx.recv().unwrap())
+            }
+            Link::Local => {
+                let (request_id, response_rx) = self.tracker
+                                                    .begin(name,
+                                                           &Link::Local,
+                                                           Mutex::new(response_handler));
+                let tracker_clone = self.tracker.clone();
+                let deadline = deadline::for_request(self.tracker.timeout());
+                let trace_id = trace::trace_id();
+                supervisor().spawn("local request", move || {
+                    deadline::set(deadline);
+                    trace::set_trace_id(trace_id);
+                    let service_result = local_handler.unwrap()(reader);
+                    trace::set_trace_id(None);
+                    deadline::set(None);
+
+                    let timed_out = !tracker_clone.end(request_id, |response_handler| {
+                        respond(service_result, &response_handler)
+                    });
+
+                    if timed_out {
+                        debug!("got response for request ({}) that already timed out",
+                               request_id);
+                    }
+                });
+                try!(response_rx.recv().unwrap())
+            }
+            Link::Remote(peer_node_id) => {
+                let deadline = deadline::for_request(self.tracker.timeout());
+                if deadline.map(deadline::is_expired).unwrap_or(false) {
+                    return Err(request::Error::Timeout);
+                }
+                try!(self.redial_idle(&peer_node_id));
+                let (request_id, response_rx) = self.tracker
+                                                    .begin(name,
+                                                           &Link::Remote(peer_node_id),
+                                                           Mutex::new(response_handler));
+                let trace_id = trace::trace_id();
+                try!(self.connections.send_request(&peer_node_id,
+                                                   request_id,
+                                                   name,
+                                                   deadline,
+                                                   trace_id.as_ref().map(String::as_str),
+                                                   &mut reader));
+                let result = response_rx.recv().unwrap();
+                if result.is_err() {
+                    self.connections.abandon_request(&peer_node_id, request_id);
+                }
+                try!(result)
+            }
+        }
+    }
+
+    fn redial_idle(&self, peer_node_id: &ID) -> request::Result<()> {
+        match *self.node_id.read().unwrap() {
+            Some(node_id) => self.context(node_id).redial_idle(peer_node_id),
+            None => Ok(()),
+        }
+    }
+
+    fn dial(&self,
+            node_id: ID,
+            address: SocketAddr,
+            tx: &mpsc::Sender<(SocketAddr, Result<(ID, Vec<(ID, SocketAddr)>)>)>) {
+        let tx = tx.clone();
+        let context = self.context(node_id);
+        supervisor().spawn("join dial", move || {
+            let result = context.connect(address);
+            let _ = tx.send((address, result));
+        });
+    }
+
+    fn context(&self, node_id: ID) -> Context {
+        Context {
+            node_id: node_id,
+            public_address: self.public_address.clone(),
+            options: self.options.clone(),
+            reconnect_backoff: self.reconnect_backoff,
+            reconnect_max_backoff: self.reconnect_max_backoff,
+            reconnects: self.reconnects.clone(),
+            capture: self.capture.clone(),
+            router: self.router.clone(),
+            proxy: self.proxy.clone(),
+            memory: self.memory.clone(),
+            idle: self.idle.clone(),
+            permits: self.permits.clone(),
+            draining: self.draining.clone(),
+            cipher: self.cipher.clone(),
+            connections: self.connections.clone(),
+            services: self.services.clone(),
+            tracker: self.tracker.clone(),
+            running: self.running.clone(),
+            abandoned: self.abandoned.clone(),
         }
     }
 
     fn unbind(&self) -> Result<()> {
         *self.running.write().unwrap() = false;
-        if let Some(join_handle) = self.join_handle.write().unwrap().take() {
-            // connect to local address to enable the thread to escape the accept loop.
-            try!(net::TcpStream::connect(self.local_address));
-            join_handle.join().unwrap();
+        let join_handles = self.join_handles.write().unwrap().drain(..).collect::<Vec<_>>();
+        if !join_handles.is_empty() {
+            // connect to local addresses to enable the threads to escape the accept loops.
+            for local_address in &self.local_addresses {
+                try!(net::TcpStream::connect(*local_address));
+            }
+            for join_handle in join_handles {
+                join_handle.join().unwrap();
+            }
         }
         Ok(())
     }
@@ -74,147 +431,447 @@ impl Direct {
 
 impl Transport for Direct {
     fn public_address(&self) -> SocketAddr {
-        self.public_address
+        self.public_address.get()
     }
 
     fn bind(&self, node_id: ID) -> Result<()> {
-        let tcp_listener = try!(net::TcpListener::bind(self.local_address));
+        // ipv6 listeners only take ipv6 connections, if ipv4 addresses are bound separately.
+        let only_v6 = self.local_addresses.iter().any(|address| {
+            match *address {
+                SocketAddr::V4(_) => true,
+                SocketAddr::V6(_) => false,
+            }
+        });
+        let mut tcp_listeners = Vec::new();
+        for local_address in &self.local_addresses {
+            tcp_listeners.push(try!(listen(*local_address, only_v6)));
+        }
 
         *self.running.write().unwrap() = true;
+        *self.node_id.write().unwrap() = Some(node_id);
 
-        let public_address = self.public_address;
-        let running_clone = self.running.clone();
-        let cipher_clone = self.cipher.clone();
-        let connections_clone = self.connections.clone();
-        let services_clone = self.services.clone();
-        let tracker_clone = self.tracker.clone();
-        *self.join_handle.write().unwrap() = Some(thread::spawn(move || {
-            for tcp_stream in tcp_listener.incoming() {
-                if !*running_clone.read().unwrap() {
-                    break;
-                }
+        let mut join_handles = self.join_handles.write().unwrap();
+        for tcp_listener in tcp_listeners {
+            let context = self.context(node_id);
+            join_handles.push(supervisor().spawn("accept loop", move || {
+                loop {
+                    // new connections are not accepted while the memory limit is exceeded.
+                    if let Some(ref memory) = context.memory {
+                        while memory.is_exceeded() && *context.running.read().unwrap() {
+                            thread::sleep(to_std_duration(Duration::milliseconds(100)));
+                        }
+                    }
+                    let tcp_stream = match tcp_listener.incoming().next() {
+                        Some(tcp_stream) => tcp_stream,
+                        None => break,
+                    };
+                    if !*context.running.read().unwrap() {
+                        break;
+                    }
 
-                let tcp_stream = tcp_stream.unwrap();
-                let stream = cipher::Stream::new(tcp_stream, cipher_clone.box_clone());
+                    let mut tcp_stream = tcp_stream.unwrap();
+                    let session = match context.cipher.handshake(&mut tcp_stream,
+                                                                 cipher::Role::Responder) {
+                        Ok(session) => session,
+                        Err(error) => {
+                            error!("error accepting connection: {:?}", error);
+                            continue;
+                        }
+                    };
+                    let mut stream = cipher::Stream::new(tcp_stream, session);
+                    if let Some(ref capture) = context.capture {
+                        stream.set_capture(capture.clone());
+                    }
+
+                    if let Err(error) = accept(stream, &context) {
+                        error!("error accepting connection: {:?}", error);
+                    }
+                }
+            }));
+        }
 
-                if let Err(error) = accept(stream,
-                                           node_id,
-                                           public_address,
-                                           &connections_clone,
-                                           &services_clone,
-                                           &tracker_clone) {
-                    error!("error accepting connection: {:?}", error);
+        if let Some(idle_timeout) = self.idle_timeout {
+            let running_clone = self.running.clone();
+            let connections_clone = self.connections.clone();
+            let idle_clone = self.idle.clone();
+            let clock_clone = self.options.clock.clone();
+            supervisor().spawn("idle reaper", move || {
+                while *running_clone.read().unwrap() {
+                    clock_clone.sleep(idle_timeout / 2);
+                    reap_idle_connections(node_id, idle_timeout, &connections_clone, &idle_clone);
                 }
+            });
+        }
+
+        let context = self.context(node_id);
+        supervisor().spawn("drain lease", move || {
+            while *context.running.read().unwrap() {
+                context.options.clock.sleep(context.permits.ttl() / 3);
+                context.renew_lease();
             }
-        }));
+        });
 
         Ok(())
     }
 
-    fn join(&self, address: SocketAddr, node_id: ID) -> Result<()> {
+    fn join(&self,
+            address: SocketAddr,
+            node_id: ID,
+            progress: &Fn(&JoinEvent))
+            -> Result<JoinReport> {
+        let mut report = JoinReport::new();
+        let mut discovered = HashSet::new();
+        let mut queue = VecDeque::new();
+        let mut dialing = HashMap::new();
         let (tx, rx) = mpsc::channel();
-        let mut pending_peers_count = 1;
-        tx.send(vec![(ID::new_random(), address)]).unwrap();
 
-        while pending_peers_count > 0 {
-            let peers = rx.recv().unwrap();
+        discovered.insert(address);
+        queue.push_back((None, address));
+        emit(progress, &mut report, JoinEvent::Discovered(discovered.len()));
+
+        while !queue.is_empty() || !dialing.is_empty() {
+            while dialing.len() < self.join_parallelism {
+                let (peer_node_id, peer_public_address) = match queue.pop_front() {
+                    Some(peer) => peer,
+                    None => break,
+                };
+                if let Some(ref peer_node_id) = peer_node_id {
+                    if self.connections.contains_key(peer_node_id) {
+                        continue;
+                    }
+                }
+
+                emit(progress,
+                     &mut report,
+                     JoinEvent::Connecting(peer_public_address));
+                dialing.insert(peer_public_address,
+                               self.join_dial_timeout.map(|timeout| SteadyTime::now() + timeout));
+                self.dial(node_id, peer_public_address, &tx);
+            }
 
-            for peer in peers {
-                let (peer_node_id, peer_public_address) = peer;
-                if self.connections.contains_key(&peer_node_id) {
-                    continue;
+            if dialing.is_empty() {
+                continue;
+            }
+
+            let next_timeout = dialing.iter()
+                                      .filter_map(|(address, deadline)| {
+                                          deadline.map(|deadline| (*address, deadline))
+                                      })
+                                      .min_by_key(|&(_, deadline)| deadline);
+            let (peer_public_address, result) = match next_timeout {
+                Some((address, deadline)) => {
+                    let remaining = cmp::max(deadline - SteadyTime::now(), Duration::zero());
+                    match rx.recv_timeout(to_std_duration(remaining)) {
+                        Ok(received) => received,
+                        Err(_) => {
+                            let error = io::Error::new(io::ErrorKind::TimedOut, "dial timed out");
+                            (address, Err(transport::Error::Io(error)))
+                        }
+                    }
+                }
+                None => rx.recv().unwrap(),
+            };
+            if dialing.remove(&peer_public_address).is_none() {
+                // result of a dial that already timed out
+                continue;
+            }
+
+            match result {
+                Ok((peer_node_id, peers)) => {
+                    emit(progress,
+                         &mut report,
+                         JoinEvent::Connected(peer_node_id, peer_public_address));
+                    for (peer_node_id, peer_public_address) in peers {
+                        if discovered.insert(peer_public_address) {
+                            queue.push_back((Some(peer_node_id), peer_public_address));
+                        }
+                    }
+                    emit(progress, &mut report, JoinEvent::Discovered(discovered.len()));
                 }
+                Err(error) => {
+                    emit(progress,
+                         &mut report,
+                         JoinEvent::Failed(peer_public_address, format!("{:?}", error)));
+                    if report.connected.is_empty() {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
 
-                pending_peers_count += 1;
-
-                let tcp_stream = try!(net::TcpStream::connect(peer_public_address));
-                let stream = cipher::Stream::new(tcp_stream, self.cipher.box_clone());
-                let handlers = build_handlers(&self.connections, &self.services, &self.tracker);
-                let (connection, peers) = try!(Connection::new_outbound(stream,
-                                                                        node_id,
-                                                                        self.public_address,
-                                                                        handlers));
-                let peer_node_id = connection.peer_node_id();
-                info!("{}: outbound {}", node_id, connection);
-                try!(self.connections.add(connection));
-
-                tx.send(peers).unwrap();
-
-                try!(try!(self.connections
-                              .select(&peer_node_id, |connection| -> io::Result<()> {
-                                  Ok(try!(connection.send_add_services(&self.services
-                                                                       .local_service_names())))
-                              })));
+    fn remove_peer(&self, public_address: SocketAddr) -> Result<()> {
+        for (peer_node_id, peer_public_address) in self.connections.id_public_address_pairs() {
+            if peer_public_address == public_address {
+                if self.reconnect_backoff.is_some() {
+                    self.abandoned.lock().unwrap().insert(public_address);
+                }
+                let names = self.services.remote_service_names(&peer_node_id);
+                self.services.remove_all_remotes(&peer_node_id);
+                self.router.announce(names);
+                try!(self.connections.select(&peer_node_id, |connection| connection.shutdown()));
             }
+        }
+
+        let mut idle = self.idle.lock().unwrap();
+        let idle_peer_node_ids = idle.iter()
+                                     .filter(|&(_, address)| *address == public_address)
+                                     .map(|(peer_node_id, _)| *peer_node_id)
+                                     .collect::<Vec<_>>();
+        for peer_node_id in idle_peer_node_ids {
+            idle.remove(&peer_node_id);
+            self.services.remove_all_remotes(&peer_node_id);
+        }
+        Ok(())
+    }
+
+    fn set_peer_count_handler(&self, handler: Box<Fn(usize) + Send + Sync>) {
+        self.connections.set_count_handler(handler);
+    }
+
+    fn connection_stats(&self, peer_node_id: ID) -> Result<(ConnectionStats, ConnectionStats)> {
+        let (local, remote) = try!(self.connections.select(&peer_node_id, |connection| {
+            (connection.stats(), connection.request_stats())
+        }));
+        Ok((local, try!(remote)))
+    }
+
+    fn acquire_drain_permit(&self) -> Result<bool> {
+        self.drain(true).map(|(granted, _)| granted)
+    }
 
-            pending_peers_count -= 1;
+    fn release_drain_permit(&self) -> Result<()> {
+        self.drain(false).map(|_| ())
+    }
+
+    fn leave(&self) -> Result<()> {
+        try!(self.unbind());
+        self.connections.shutdown();
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        try!(self.unbind());
+        for (peer_node_id, error) in self.connections.send_goodbye() {
+            debug!("saying goodbye to {} failed: {:?}", peer_node_id, error);
         }
 
+        // the peers don't send new requests anymore, but the pending ones may still finish.
+        let timeout = self.tracker
+                          .timeout()
+                          .unwrap_or(Duration::seconds(DEFAULT_SHUTDOWN_TIMEOUT_S));
+        let deadline = self.options.clock.now() + timeout;
+        while self.tracker.len() > 0 || !self.connections.is_drained() {
+            if self.options.clock.now() >= deadline {
+                warn!("shutting down with {} pending requests", self.tracker.len());
+                break;
+            }
+            self.options.clock.sleep(Duration::milliseconds(SHUTDOWN_POLL_INTERVAL_MS));
+        }
+
+        self.connections.shutdown();
         Ok(())
     }
 
-    fn register(&self, name: &str, f: Box<Service>) -> Result<()> {
-        try!(self.services.insert_local(name, f));
+    fn register(&self,
+                name: &str,
+                f: Box<Service>,
+                placement: Placement,
+                weight: u32,
+                metadata: service::Metadata)
+                -> Result<()> {
+        try!(self.services.insert_local_with_placement(name,
+                                                       f,
+                                                       placement,
+                                                       weight,
+                                                       metadata.clone()));
 
-        self.connections.send_add_services(&vec![name.to_string()]).unwrap();
+        let names = vec![(name.to_string(), weight, metadata)];
+        let peer_node_ids = self.connections
+                                .placed_peer_node_ids(self.options
+                                                          .zone
+                                                          .as_ref()
+                                                          .map(String::as_str),
+                                                      placement);
+        let announcement = self.connections.send_add_services_to(&peer_node_ids, &names);
+        if !announcement.is_complete() {
+            return Err(transport::Error::Announcement(announcement));
+        }
 
         Ok(())
     }
 
     fn deregister(&self, name: &str) -> Result<()> {
-        self.connections.send_remove_services(&vec![name.to_string()]).unwrap();
+        let announcement = self.connections.send_remove_services(&vec![name.to_string()]);
 
         try!(self.services.remove_local(name));
+        // the service may still be reachable over other nodes.
+        self.router.announce(vec![name.to_string()]);
+
+        // peers that missed the removal drop the service with the connection that just failed.
+        for &(ref peer_node_id, ref error) in &announcement.failed {
+            warn!("removal of service [{}] wasn't aknowledged by {}: {:?}",
+                  name,
+                  peer_node_id.short(),
+                  error);
+        }
 
         Ok(())
     }
 
+    fn has_service(&self, name: &str) -> bool {
+        self.services.contains(name)
+    }
+
+    fn service_usage(&self) -> Vec<(String, ID, usize)> {
+        self.services.usage()
+    }
+
+    fn reconnect_states(&self) -> Vec<ReconnectState> {
+        self.reconnects.states()
+    }
+
+    fn peers(&self) -> Vec<PeerInfo> {
+        let mut peers = self.connections.peer_infos();
+        for peer in &mut peers {
+            peer.services = self.services.remote_service_names(&peer.node_id).len();
+        }
+        peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        peers
+    }
+
+    fn providers(&self, name: &str) -> Vec<ID> {
+        let node_id = *self.node_id.read().unwrap();
+        self.services
+            .links(name)
+            .into_iter()
+            .filter_map(|link| {
+                match link {
+                    Link::Local => node_id,
+                    Link::Remote(peer_node_id) => Some(peer_node_id),
+                }
+            })
+            .collect()
+    }
+
     fn request(&self,
                name: &str,
-               mut reader: Box<request::Reader>,
+               key: Option<&[u8]>,
+               reader: Box<request::Reader>,
                response_handler: Box<response::Handler>)
                -> request::Result<()> {
 
-        let (link, local_handler) = try!(self.services.get(name));
+        // requests that aren't part of a trace yet start a new one. Forwarded log records stay
+        // outside of traces, otherwise they would be forwarded again.
+        let _trace = if name != collector::SERVICE_NAME {
+            Some(Trace::ensure())
+        } else {
+            None
+        };
 
-        match link {
-            Link::Local => {
-                let (request_id, response_rx) = self.tracker
-                                                    .begin(name,
-                                                           &Link::Local,
-                                                           Mutex::new(response_handler));
-                let tracker_clone = self.tracker.clone();
-                thread::spawn(move || {
-                    let service_result = local_handler.unwrap()(reader);
-
-                    let timed_out = !tracker_clone.end(request_id, |response_handler| {
-                        let service_result = service_result;
-                        match service_result {
-                            Ok(reader) => {
-                                (&mut **response_handler.lock()
-                                                        .unwrap())(reader);
-                                Ok(())
-                            }
-                            Err(error) => Err(request::Error::Service(error)),
+        // the body is recorded, so the request can follow redirects and be retried.
+        let replay = Replay::new(reader, MAX_REDIRECT_BODY_SIZE);
+        let response_handler = Arc::new(Mutex::new(response_handler));
+        let mut target = (name.to_string(), None);
+        let mut redirects = 0;
+        let mut attempt = 1;
+        let mut failure = None;
+        loop {
+            let reader = match replay.reader() {
+                Some(reader) if redirects <= MAX_REDIRECTS => reader,
+                _ => {
+                    // the body was too large to be sent again.
+                    if let Some(error) = failure {
+                        return Err(error);
+                    }
+                    let (name, node_id) = target;
+                    return Err(request::Error::Service(service::Error::Redirect(name, node_id)));
+                }
+            };
+            let response_handler = response_handler.clone();
+            let result = self.request_once(&target.0,
+                                           target.1,
+                                           key,
+                                           reader,
+                                           Box::new(move |reader| {
+                                               (&mut **response_handler.lock().unwrap())(reader)
+                                           }));
+            match result {
+                Err(request::Error::Service(service::Error::Redirect(name, node_id))) => {
+                    debug!("request for service [{}] redirected to [{}]", target.0, name);
+                    target = (name, node_id);
+                    redirects += 1;
+                    attempt = 1;
+                    failure = None;
+                }
+                Err(error) => {
+                    let backoff = match self.retry_policies
+                                            .get(version::service_name(&target.0)) {
+                        Some(policy) if target.1.is_none() => policy.next_backoff(attempt, &error),
+                        _ => None,
+                    };
+                    match backoff {
+                        Some(backoff) => {
+                            debug!("request for service [{}] failed with {:?} - retry {} in {} ms",
+                                   target.0,
+                                   error,
+                                   attempt,
+                                   backoff.num_milliseconds());
+                            self.options.clock.sleep(backoff);
+                            attempt += 1;
+                            failure = Some(error);
                         }
-                    });
-
-                    if timed_out {
-                        debug!("got response for request ({}) that already timed out",
-                               request_id);
+                        None => return Err(error),
                     }
-                });
-                try!(response_rx.recv().unwrap())
+                }
+                result => return result,
             }
-            Link::Remote(peer_node_id) => {
-                let (request_id, response_rx) = self.tracker
-                                                    .begin(name,
-                                                           &Link::Remote(peer_node_id),
-                                                           Mutex::new(response_handler));
-                try!(self.connections
-                         .send_request(&peer_node_id, request_id, name, &mut reader));
-                try!(response_rx.recv().unwrap())
+        }
+    }
+
+    fn request_tagged(&self,
+                      name: &str,
+                      tags: &service::Metadata,
+                      reader: Box<request::Reader>,
+                      response_handler: Box<response::Handler>)
+                      -> request::Result<()> {
+        let _trace = Trace::ensure();
+        let (link, local_handler) = try!(self.services.get_tagged(name, tags));
+        self.request_selected(name, link, local_handler, reader, response_handler)
+    }
+
+    fn notify(&self, name: &str, reader: Box<request::Reader>) -> request::Result<()> {
+        let _trace = Trace::ensure();
+        let (link, local_handler) = try!(self.select_service(name, None));
+        self.notify_selected(version::service_name(name), link, local_handler, reader)
+    }
+
+    fn notify_on(&self,
+                 name: &str,
+                 node_id: ID,
+                 reader: Box<request::Reader>)
+                 -> request::Result<()> {
+        let _trace = Trace::ensure();
+        match self.select_service_on(name, node_id) {
+            Some((link, local_handler)) => self.notify_selected(name, link, local_handler, reader),
+            None => Err(request::Error::NoService),
+        }
+    }
+
+    fn request_on(&self,
+                  name: &str,
+                  node_id: ID,
+                  reader: Box<request::Reader>,
+                  response_handler: Box<response::Handler>)
+                  -> request::Result<()> {
+        let _trace = Trace::ensure();
+        match self.select_service_on(name, node_id) {
+            Some((link, local_handler)) => {
+                self.request_selected(name, link, local_handler, reader, response_handler)
             }
+            None => Err(request::Error::NoService),
         }
     }
 }
@@ -226,36 +883,310 @@ impl Drop for Direct {
     }
 }
 
-fn accept(stream: cipher::Stream<net::TcpStream>,
-          node_id: ID,
-          public_address: SocketAddr,
-          connections: &Arc<ConnectionMap>,
-          services: &Arc<ServiceMap>,
-          tracker: &Arc<Tracker<Mutex<Box<response::Handler>>, request::Result<()>>>)
-          -> Result<()> {
+/// Sets a new trace id for the current thread if it has none and removes it again when dropped.
+struct Trace {
+    generated: bool,
+}
+
+impl Trace {
+    fn ensure() -> Trace {
+        let generated = trace::trace_id().is_none();
+        if generated {
+            trace::set_trace_id(Some(trace::generate()));
+        }
+        Trace { generated: generated }
+    }
+}
+
+impl Drop for Trace {
+    fn drop(&mut self) {
+        if self.generated {
+            trace::set_trace_id(None);
+        }
+    }
+}
+
+/// Returns true if the request failed in a way that counts against the link it was sent over.
+fn is_failure(result: &request::Result<()>) -> bool {
+    match *result {
+        Err(request::Error::Timeout) |
+        Err(request::Error::Io(..)) |
+        Err(request::Error::Service(service::Error::Unavailable)) |
+        Err(request::Error::Service(service::Error::Internal(_))) |
+        Err(request::Error::Service(service::Error::Timeout)) => true,
+        _ => false,
+    }
+}
+
+fn respond(service_result: service::Result,
+           response_handler: &Mutex<Box<response::Handler>>)
+           -> request::Result<()> {
+    match service_result {
+        Ok(reader) => {
+            (&mut **response_handler.lock().unwrap())(reader);
+            Ok(())
+        }
+        Err(error) => Err(request::Error::Service(error)),
+    }
+}
+
+fn emit(progress: &Fn(&JoinEvent), report: &mut JoinReport, event: JoinEvent) {
+    progress(&event);
+    report.push(&event);
+}
+
+/// The state that is shared by the connections of a transport. It's used to accept and to dial
+/// connections.
+#[derive(Clone)]
+struct Context {
+    node_id: ID,
+    public_address: Arc<PublicAddress>,
+    options: connection::Options,
+    reconnect_backoff: Option<Duration>,
+    reconnect_max_backoff: Duration,
+    reconnects: Arc<Reconnects>,
+    capture: Option<Arc<Capture>>,
+    router: Arc<Router>,
+    proxy: Option<Proxy>,
+    memory: Option<Arc<Watermark>>,
+    idle: Arc<Mutex<HashMap<ID, SocketAddr>>>,
+    permits: Arc<Permits>,
+    draining: Arc<RwLock<bool>>,
+    cipher: Arc<Box<Cipher>>,
+    connections: Arc<ConnectionMap>,
+    services: Arc<ServiceMap>,
+    tracker: Arc<Tracker<Mutex<Box<response::Handler>>, request::Result<()>>>,
+    running: Arc<RwLock<bool>>,
+    abandoned: Arc<Mutex<HashSet<SocketAddr>>>,
+}
+
+impl Context {
+    fn drain(&self, acquire: bool) -> Result<(bool, usize)> {
+        // the node with the lowest id hands out the permits.
+        let coordinator = self.connections
+                              .peer_node_ids_since(container::DRAIN_VERSION)
+                              .into_iter()
+                              .chain(self.idle.lock().unwrap().keys().cloned())
+                              .min();
+        match coordinator {
+            Some(peer_node_id) if peer_node_id < self.node_id => {
+                if let Err(error) = self.redial_idle(&peer_node_id) {
+                    return Err(transport::Error::Io(io::Error::new(io::ErrorKind::NotConnected,
+                                                                   format!("{:?}", error))));
+                }
+                let result = try!(self.connections.select(&peer_node_id, |connection| {
+                    connection.request_drain(acquire)
+                }));
+                Ok(try!(result))
+            }
+            _ if acquire => Ok(self.permits.acquire(self.node_id)),
+            _ => Ok((false, self.permits.release(&self.node_id))),
+        }
+    }
 
+    /// Renews the drain lease of this node with the current coordinator, which registers it with
+    /// a new coordinator after the previous one went away.
+    fn renew_lease(&self) {
+        if !*self.draining.read().unwrap() {
+            return;
+        }
+        match self.drain(true) {
+            Ok((true, _)) => {}
+            Ok((false, draining)) => {
+                warn!("{}: drain lease was not renewed, {} nodes are draining",
+                      self.node_id.short(),
+                      draining);
+            }
+            Err(error) => {
+                warn!("{}: renewing the drain lease failed: {:?}",
+                      self.node_id.short(),
+                      error);
+            }
+        }
+    }
+
+    fn redial_idle(&self, peer_node_id: &ID) -> request::Result<()> {
+        if self.connections.contains_key(peer_node_id) {
+            return Ok(());
+        }
+        let address = match self.idle.lock().unwrap().get(peer_node_id) {
+            Some(address) => *address,
+            None => return Ok(()),
+        };
+
+        debug!("{}: redial idle peer {} ({})",
+               self.node_id.short(),
+               peer_node_id.short(),
+               address);
+        match self.connect(address) {
+            Ok(_) => Ok(()),
+            // a concurrent request or the peer itself may have re-established the connection.
+            Err(_) if self.connections.contains_key(peer_node_id) => Ok(()),
+            Err(error) => {
+                self.idle.lock().unwrap().remove(peer_node_id);
+                self.services.remove_all_remotes(peer_node_id);
+                Err(request::Error::Io(io::ErrorKind::NotConnected,
+                                       format!("redial of idle peer {} failed: {:?}",
+                                               peer_node_id,
+                                               error)))
+            }
+        }
+    }
+
+    fn connect(&self, address: SocketAddr) -> Result<(ID, Vec<(ID, SocketAddr)>)> {
+        let mut tcp_stream = match self.proxy {
+            Some(ref proxy) => try!(proxy.connect(address)),
+            None => try!(net::TcpStream::connect(address)),
+        };
+        let session = try!(self.cipher
+                               .handshake(&mut tcp_stream, cipher::Role::Initiator)
+                               .map_err(io::Error::from));
+        let mut stream = cipher::Stream::new(tcp_stream, session);
+        if let Some(ref capture) = self.capture {
+            stream.set_capture(capture.clone());
+        }
+        let mut handlers = build_handlers(self);
+        if self.reconnect_backoff.is_some() {
+            let drop_handler = handlers.drop;
+            let context = self.clone();
+            handlers.drop = Box::new(move |peer_node_id| {
+                drop_handler(peer_node_id);
+                if !context.idle.lock().unwrap().contains_key(&peer_node_id) {
+                    context.reconnect(address, peer_node_id);
+                }
+            });
+        }
+        let (connection, peers) = try!(Connection::new_outbound(stream,
+                                                                self.node_id,
+                                                                self.public_address.get(),
+                                                                &self.options,
+                                                                handlers));
+        let peer_node_id = connection.peer_node_id();
+        if let Some(observed_address) = connection.observed_address() {
+            self.public_address.observe(peer_node_id, observed_address);
+        }
+        info!("{}: outbound {}", self.node_id.short(), connection);
+        try!(self.connections.add(connection));
+        self.abandoned.lock().unwrap().remove(&address);
+        self.idle.lock().unwrap().remove(&peer_node_id);
+
+        try!(try!(self.connections.select(&peer_node_id, |connection| -> io::Result<()> {
+            let names = self.services
+                            .local_services_for(self.options.zone.as_ref().map(String::as_str),
+                                                connection.peer_zone());
+            try!(connection.send_add_services(&names));
+            self.router.introduce(connection)
+        })));
+
+        Ok((peer_node_id, peers))
+    }
+
+    fn reconnect(&self, address: SocketAddr, peer_node_id: ID) {
+        let mut backoff = match self.reconnect_backoff {
+            Some(reconnect_backoff) => reconnect_backoff,
+            None => return,
+        };
+        if !*self.running.read().unwrap() {
+            return;
+        }
+
+        self.reconnects.start(peer_node_id, address, backoff);
+        let context = self.clone();
+        supervisor().spawn("reconnect", move || {
+            loop {
+                thread::sleep(to_std_duration(jitter(backoff)));
+
+                if !*context.running.read().unwrap() ||
+                   context.abandoned.lock().unwrap().remove(&address) ||
+                   context.connections.contains_key(&peer_node_id) {
+                    context.reconnects.stop(&peer_node_id);
+                    return;
+                }
+
+                match context.connect(address) {
+                    Ok(_) => {
+                        context.reconnects.succeed(&peer_node_id);
+                        info!("{}: reconnected to {} ({})",
+                              context.node_id.short(),
+                              peer_node_id.short(),
+                              address);
+                        return;
+                    }
+                    Err(error) => {
+                        backoff = cmp::min(backoff * 2, context.reconnect_max_backoff);
+                        context.reconnects.fail(&peer_node_id, backoff, format!("{:?}", error));
+                        debug!("{}: reconnect to {} ({}) failed - retrying in {} ms: {:?}",
+                               context.node_id.short(),
+                               peer_node_id.short(),
+                               address,
+                               backoff.num_milliseconds(),
+                               error);
+                    }
+                }
+            }
+        });
+    }
+}
+
+fn jitter(backoff: Duration) -> Duration {
+    let milliseconds = backoff.num_milliseconds();
+    backoff + Duration::milliseconds(rand::thread_rng().gen_range(0, milliseconds / 2 + 1))
+}
+
+fn listen(address: SocketAddr, only_v6: bool) -> io::Result<net::TcpListener> {
+    match address {
+        SocketAddr::V4(_) => net::TcpListener::bind(address),
+        SocketAddr::V6(_) => {
+            let builder = try!(TcpBuilder::new_v6());
+            try!(builder.only_v6(only_v6));
+            try!(builder.reuse_address(true));
+            try!(builder.bind(address));
+            builder.listen(LISTEN_BACKLOG)
+        }
+    }
+}
+
+fn accept(stream: cipher::Stream<net::TcpStream>, context: &Context) -> Result<()> {
+    let node_id = context.node_id;
+    let connections = &context.connections;
     let peers = &connections.id_public_address_pairs();
-    let handlers = build_handlers(connections, services, tracker);
+    let handlers = build_handlers(context);
     let connection = try!(Connection::new_inbound(stream,
                                                   node_id,
-                                                  public_address,
+                                                  context.public_address.get(),
                                                   peers,
+                                                  &context.options,
                                                   handlers));
     let peer_node_id = connection.peer_node_id();
-    info!("{}: inbound {}", node_id, connection);
+    if let Some(observed_address) = connection.observed_address() {
+        context.public_address.observe(peer_node_id, observed_address);
+    }
+    info!("{}: inbound {}", node_id.short(), connection);
     try!(connections.add(connection));
+    context.idle.lock().unwrap().remove(&peer_node_id);
 
     try!(try!(connections.select(&peer_node_id, |connection| -> io::Result<()> {
-        Ok(try!(connection.send_add_services(&services.local_service_names())))
+        let names = context.services
+                           .local_services_for(context.options.zone.as_ref().map(String::as_str),
+                                               connection.peer_zone());
+        try!(connection.send_add_services(&names));
+        context.router.introduce(connection)
     })));
 
     Ok(())
 }
 
-fn build_handlers(connections: &Arc<ConnectionMap>,
-                  services: &Arc<ServiceMap>,
-                  tracker: &Arc<Tracker<Mutex<Box<response::Handler>>, request::Result<()>>>)
-                  -> Handlers {
+fn build_handlers(context: &Context) -> Handlers {
+    let connections = &context.connections;
+    let services = &context.services;
+    let tracker = &context.tracker;
+    let idle = &context.idle;
+    let zone = &context.options.zone;
+    let permits = &context.permits;
+    let router = &context.router;
+    let memory = &context.memory;
+    let clock = &context.options.clock;
+    let abandoned = &context.abandoned;
 
     let connections_request_clone = connections.clone();
     let services_add_clone = services.clone();
@@ -264,21 +1195,101 @@ fn build_handlers(connections: &Arc<ConnectionMap>,
     let services_drop_clone = services.clone();
     let tracker_response_clone = tracker.clone();
     let tracker_drop_clone = tracker.clone();
+    let idle_clone = idle.clone();
+    let idle_drop_clone = idle.clone();
+    let idle_goodbye_clone = idle.clone();
+    let zone_clone = zone.clone();
+    let permits_drain_clone = permits.clone();
+    let context_drop_clone = context.clone();
+    let router_add_clone = router.clone();
+    let router_remove_clone = router.clone();
+    let router_request_clone = router.clone();
+    let router_drop_clone = router.clone();
+    let router_goodbye_clone = router.clone();
+    let services_goodbye_clone = services.clone();
+    let abandoned_clone = abandoned.clone();
+    let router_notification_clone = router.clone();
+    let services_notification_clone = services.clone();
+    let connections_notification_clone = connections.clone();
+    let zone_notification_clone = zone.clone();
+    let memory_clone = memory.clone();
+    let memory_notification_clone = memory.clone();
+    let clock_clone = clock.clone();
 
     Handlers {
-        add_services: Box::new(move |peer_node_id, services| {
-            services_add_clone.insert_remotes(&services, peer_node_id);
+        add_services: Box::new(move |peer_node_id, routes| {
+            if let Err(error) = services_add_clone.insert_routes(&routes, peer_node_id) {
+                warn!("rejected services announced by {}: {:?}", peer_node_id.short(), error);
+                return;
+            }
+            router_add_clone.announce(routes.into_iter().map(|(name, _, _, _)| name).collect());
         }),
         remove_services: Box::new(move |peer_node_id, services| {
             services_remove_clone.remove_remotes(&services, &peer_node_id);
+            router_remove_clone.announce(services);
         }),
-        request: Box::new(move |peer_node_id, request_id, name, reader| {
+        request: Box::new(move |peer_node_id, request_id, name, ttl, deadline, trace_id, reader| {
             let connections_clone = connections_request_clone.clone();
             let services_clone = services_request_clone.clone();
+            let zone = zone_clone.clone();
+            let router = router_request_clone.clone();
+            let memory = memory_clone.clone();
+            let clock = clock_clone.clone();
             let name = name.to_string();
-            thread::spawn(move || {
-                let handler = services_clone.get_local(&name).unwrap();
-                let service_result = handler(reader);
+            supervisor().spawn("remote request", move || {
+                trace::set_trace_id(trace_id.clone());
+                if memory.as_ref().map(|memory| memory.is_exceeded()).unwrap_or(false) {
+                    warn!("rejected request for {} from {} while the memory limit is exceeded",
+                          name,
+                          peer_node_id.short());
+                    let service_result = Err(service::Error::Unavailable);
+                    if let Err(error) = connections_clone.send_response(&peer_node_id,
+                                                                        request_id,
+                                                                        service_result) {
+                        error!("error while sending response: {:?}", error);
+                    }
+                    return;
+                }
+                let handler = match services_clone.get_local(&name) {
+                    Some(handler) => handler,
+                    None => {
+                        let service_result =
+                            router.forward(&name,
+                                           &peer_node_id,
+                                           ttl,
+                                           deadline,
+                                           trace_id.as_ref().map(String::as_str),
+                                           reader);
+                        if let Err(error) = connections_clone.send_response(&peer_node_id,
+                                                                            request_id,
+                                                                            service_result) {
+                            error!("error while sending forwarded response: {:?}", error);
+                        }
+                        return;
+                    }
+                };
+                let placed = match services_clone.local_placement(&name) {
+                    Some(placement) => {
+                        connections_clone.select(&peer_node_id, |connection| {
+                                             placement.allows(zone.as_ref().map(String::as_str),
+                                                              connection.peer_zone())
+                                         })
+                                         .unwrap_or(false)
+                    }
+                    None => true,
+                };
+                let service_result = if placed {
+                    services_clone.record_usage(&name, peer_node_id);
+                    match deadline {
+                        Some(deadline) => deadline::call(handler, reader, deadline, &clock),
+                        None => handler(reader),
+                    }
+                } else {
+                    warn!("rejected request for {} from {} outside of its placement",
+                          name,
+                          peer_node_id.short());
+                    Err(service::Error::Unavailable)
+                };
                 if let Err(error) = connections_clone.send_response(&peer_node_id,
                                                                     request_id,
                                                                     service_result) {
@@ -287,18 +1298,29 @@ fn build_handlers(connections: &Arc<ConnectionMap>,
             });
         }),
         response: Box::new(move |request_id, service_result| {
-            let success = tracker_response_clone.end(request_id, |response_handler| {
+            let failed = match service_result {
+                Err(service::Error::Unavailable) |
+                Err(service::Error::Internal(_)) |
+                Err(service::Error::Timeout) => true,
+                _ => false,
+            };
+            let finish = |response_handler: Mutex<Box<response::Handler>>| {
                 let service_result = service_result;
                 match service_result {
                     Ok(reader) => {
-                        thread::spawn(move || {
+                        supervisor().spawn("response handler", move || {
                             (&mut **response_handler.lock().unwrap())(reader);
                         });
                         Ok(())
                     }
                     Err(error) => Err(request::Error::Service(error)),
                 }
-            });
+            };
+            let success = if failed {
+                tracker_response_clone.end_failed(request_id, finish)
+            } else {
+                tracker_response_clone.end(request_id, finish)
+            };
 
             if !success {
                 debug!("got response for request ({}) that already timed out",
@@ -307,9 +1329,126 @@ fn build_handlers(connections: &Arc<ConnectionMap>,
 
             Ok(())
         }),
+        idle: Box::new(move |peer_node_id, peer_public_address| {
+            idle_clone.lock().unwrap().insert(peer_node_id, peer_public_address);
+        }),
+        goodbye: Box::new(move |peer_node_id, peer_public_address| {
+            info!("{} shuts down", peer_node_id.short());
+            // the connection is closed by the peer once its pending requests are done.
+            abandoned_clone.lock().unwrap().insert(peer_public_address);
+            idle_goodbye_clone.lock().unwrap().remove(&peer_node_id);
+            let names = services_goodbye_clone.remote_service_names(&peer_node_id);
+            services_goodbye_clone.remove_all_remotes(&peer_node_id);
+            router_goodbye_clone.announce(names);
+        }),
+        drain: Box::new(move |peer_node_id, acquire| {
+            if acquire {
+                permits_drain_clone.acquire(peer_node_id)
+            } else {
+                (false, permits_drain_clone.release(&peer_node_id))
+            }
+        }),
+        notification: Box::new(move |peer_node_id, name, ttl, trace_id, payload| {
+            if memory_notification_clone.as_ref()
+                                        .map(|memory| memory.is_exceeded())
+                                        .unwrap_or(false) {
+                warn!("dropped notification for {} from {} while the memory limit is exceeded",
+                      name,
+                      peer_node_id.short());
+                return;
+            }
+            let services = services_notification_clone.clone();
+            let connections = connections_notification_clone.clone();
+            let zone = zone_notification_clone.clone();
+            let router = router_notification_clone.clone();
+            let name = name.to_string();
+            supervisor().spawn("remote notification", move || {
+                trace::set_trace_id(trace_id.clone());
+                let placed = match services.local_placement(&name) {
+                    Some(placement) => {
+                        connections.select(&peer_node_id, |connection| {
+                                       placement.allows(zone.as_ref().map(String::as_str),
+                                                        connection.peer_zone())
+                                   })
+                                   .unwrap_or(false)
+                    }
+                    None => true,
+                };
+                match services.get_local(&name) {
+                    Some(_) if !placed => {
+                        warn!("dropped notification for {} from {} outside of its placement",
+                              name,
+                              peer_node_id.short());
+                    }
+                    Some(handler) => {
+                        services.record_usage(&name, peer_node_id);
+                        if let Err(error) = handler(Box::new(io::Cursor::new(payload))) {
+                            debug!("notification for {} from {} failed: {:?}",
+                                   name,
+                                   peer_node_id.short(),
+                                   error);
+                        }
+                    }
+                    None => {
+                        router.forward_notification(&name,
+                                                    &peer_node_id,
+                                                    ttl,
+                                                    trace_id.as_ref().map(String::as_str),
+                                                    payload)
+                    }
+                }
+            });
+        }),
         drop: Box::new(move |peer_node_id| {
             tracker_drop_clone.cancel(&peer_node_id);
-            services_drop_clone.remove_all_remotes(&peer_node_id);
+            // the services of idle peers stay, the connection is re-established on demand.
+            if !idle_drop_clone.lock().unwrap().contains_key(&peer_node_id) {
+                let names = services_drop_clone.remote_service_names(&peer_node_id);
+                services_drop_clone.suspect_all_remotes(&peer_node_id);
+                router_drop_clone.announce(names);
+            }
+            // the peer may have been the coordinator, so a held drain lease moves to the next one.
+            if *context_drop_clone.draining.read().unwrap() {
+                let context = context_drop_clone.clone();
+                supervisor().spawn("drain lease", move || context.renew_lease());
+            }
         }),
     }
 }
+
+fn reap_idle_connections(node_id: ID,
+                         idle_timeout: Duration,
+                         connections: &Arc<ConnectionMap>,
+                         idle: &Arc<Mutex<HashMap<ID, SocketAddr>>>) {
+    for (peer_node_id, peer_public_address) in
+        connections.idle_id_public_address_pairs(idle_timeout) {
+        info!("{}: close connection to {} after {} ms idle",
+              node_id.short(),
+              peer_node_id.short(),
+              idle_timeout.num_milliseconds());
+        idle.lock().unwrap().insert(peer_node_id, peer_public_address);
+        if let Ok(Err(error)) = connections.select(&peer_node_id,
+                                                   |connection| connection.send_idle()) {
+            debug!("sending idle message to {} failed: {:?}", peer_node_id, error);
+        }
+        connections.remove(&peer_node_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use time::Duration;
+    use super::jitter;
+
+    #[test]
+    fn jitter_within_half_of_backoff() {
+        let backoff = Duration::milliseconds(100);
+        for _ in 0..100 {
+            let delay = jitter(backoff);
+            assert!(delay >= backoff);
+            assert!(delay <= Duration::milliseconds(150));
+        }
+    }
+
+}
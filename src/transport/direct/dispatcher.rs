@@ -16,12 +16,13 @@
 use std::collections::HashMap;
 use std::io;
 use std::result;
-use std::sync::{Mutex, RwLock, mpsc};
+use std::sync::{Arc, Mutex, RwLock, mpsc};
 
 use super::packet;
 
 pub struct Dispatcher {
-    entries: RwLock<HashMap<u32, Mutex<mpsc::Sender<io::Result<Vec<u8>>>>>>,
+    entries: RwLock<HashMap<u64, Mutex<mpsc::Sender<io::Result<Vec<u8>>>>>>,
+    credit_handler: Option<Arc<Box<Fn(u64) + Send + Sync>>>,
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -31,20 +32,36 @@ pub enum Error { }
 
 impl Dispatcher {
     pub fn new() -> Self {
-        Dispatcher { entries: RwLock::new(HashMap::new()) }
+        Dispatcher {
+            entries: RwLock::new(HashMap::new()),
+            credit_handler: None,
+        }
+    }
+
+    pub fn with_credit_handler(credit_handler: Box<Fn(u64) + Send + Sync>) -> Self {
+        Dispatcher {
+            entries: RwLock::new(HashMap::new()),
+            credit_handler: Some(Arc::new(credit_handler)),
+        }
     }
 
-    pub fn begin(&self, id: u32) -> Box<io::Read + Send> {
+    pub fn begin(&self, id: u64) -> Box<io::Read + Send> {
         let mut entries = self.entries.write().unwrap();
 
-        let (tx, reader) = packet::Reader::new();
+        let (tx, reader) = match self.credit_handler {
+            Some(ref credit_handler) => {
+                let credit_handler = credit_handler.clone();
+                packet::Reader::with_credit_handler(Box::new(move || credit_handler(id)))
+            }
+            None => packet::Reader::new(),
+        };
 
         entries.insert(id, Mutex::new(tx));
 
         Box::new(reader)
     }
 
-    pub fn dispatch(&self, id: u32, result: io::Result<Vec<u8>>) -> Result<()> {
+    pub fn dispatch(&self, id: u64, result: io::Result<Vec<u8>>) -> Result<()> {
         let mut entries = self.entries.write().unwrap();
 
         let mut remove = match result {
@@ -53,9 +70,15 @@ impl Dispatcher {
             _ => false,
         };
 
-        if let Some(ref entry) = entries.get(&id) {
-            if let Err(_) = entry.lock().unwrap().send(result) {
-                remove = true;
+        let delivered = match entries.get(&id) {
+            Some(entry) => entry.lock().unwrap().send(result).is_ok(),
+            None => false,
+        };
+        if !delivered {
+            remove = true;
+            // the packet is dropped, so the sender gets its credit back.
+            if let Some(ref credit_handler) = self.credit_handler {
+                credit_handler(id);
             }
         }
 
@@ -78,6 +101,7 @@ mod tests {
     use std::error::Error;
     use std::io;
     use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::thread;
     use super::Dispatcher;
 
@@ -123,4 +147,21 @@ mod tests {
         assert_eq!(0, dispatcher.len());
     }
 
+    #[test]
+    fn credit_packets_of_a_dropped_reader() {
+        let credits = Arc::new(AtomicUsize::new(0));
+        let credits_clone = credits.clone();
+        let dispatcher = Dispatcher::with_credit_handler(Box::new(move |_| {
+            credits_clone.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        let reader = dispatcher.begin(1);
+        assert!(dispatcher.dispatch(1, Ok(b"one".to_vec())).is_ok());
+        drop(reader);
+        assert!(dispatcher.dispatch(1, Ok(b"two".to_vec())).is_ok());
+
+        assert_eq!(2, credits.load(Ordering::SeqCst));
+        assert_eq!(0, dispatcher.len());
+    }
+
 }
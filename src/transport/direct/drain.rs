@@ -0,0 +1,109 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use time::{Duration, SteadyTime};
+
+use node::ID;
+use util::clock::Clock;
+
+/// Drain permits handed out by the coordinating node. At most `limit` nodes can hold a permit
+/// at the same time. A permit is a lease that is held until it's released or its ttl passes
+/// without a renewal, so a node that is taken down keeps its permit while it's gone.
+pub struct Permits {
+    limit: usize,
+    ttl: Duration,
+    clock: Arc<Clock>,
+    holders: RwLock<HashMap<ID, SteadyTime>>,
+}
+
+impl Permits {
+    pub fn new(limit: usize, ttl: Duration, clock: Arc<Clock>) -> Self {
+        Permits {
+            limit: limit,
+            ttl: ttl,
+            clock: clock,
+            holders: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Grants or renews the lease of the node.
+    pub fn acquire(&self, node_id: ID) -> (bool, usize) {
+        let now = self.clock.now();
+        let mut holders = self.holders.write().unwrap();
+        holders.retain(|_, expires_at| *expires_at > now);
+        let granted = holders.contains_key(&node_id) || holders.len() < self.limit;
+        if granted {
+            holders.insert(node_id, now + self.ttl);
+        }
+        (granted, holders.len())
+    }
+
+    pub fn release(&self, node_id: &ID) -> usize {
+        let now = self.clock.now();
+        let mut holders = self.holders.write().unwrap();
+        holders.remove(node_id);
+        holders.retain(|_, expires_at| *expires_at > now);
+        holders.len()
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+    use time::Duration;
+    use node::ID;
+    use util::clock;
+    use super::Permits;
+
+    #[test]
+    fn acquire_up_to_limit() {
+        let permits = Permits::new(2, Duration::seconds(60), Arc::new(clock::System));
+        let (first, second, third) = (ID::new_random(), ID::new_random(), ID::new_random());
+
+        assert_eq!((true, 1), permits.acquire(first));
+        assert_eq!((true, 1), permits.acquire(first));
+        assert_eq!((true, 2), permits.acquire(second));
+        assert_eq!((false, 2), permits.acquire(third));
+
+        assert_eq!(1, permits.release(&first));
+        assert_eq!((true, 2), permits.acquire(third));
+    }
+
+    #[test]
+    fn expire_leases_without_renewal() {
+        let clock = Arc::new(clock::Mock::new());
+        let permits = Permits::new(1, Duration::seconds(60), clock.clone());
+        let (first, second) = (ID::new_random(), ID::new_random());
+
+        assert_eq!((true, 1), permits.acquire(first));
+        clock.advance(Duration::seconds(40));
+        assert_eq!((false, 1), permits.acquire(second));
+
+        // the renewal pushes the expiry another ttl ahead.
+        assert_eq!((true, 1), permits.acquire(first));
+        clock.advance(Duration::seconds(40));
+        assert_eq!((false, 1), permits.acquire(second));
+
+        clock.advance(Duration::seconds(30));
+        assert_eq!((true, 1), permits.acquire(second));
+    }
+}
@@ -14,17 +14,30 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+mod announcement;
 pub mod balancer;
+mod breaker;
+mod config;
 mod connection;
 mod connection_map;
 pub mod container;
+pub mod deadline;
 mod direct;
 mod dispatcher;
+mod drain;
 mod link;
 mod packet;
+mod public_address;
+mod reconnects;
+mod replay;
+pub mod retry;
+mod router;
 mod service_map;
 pub mod tracker;
+mod writer;
 
+pub use self::announcement::Announcement;
+pub use self::config::Config;
 pub use self::connection::{Connection, Handlers};
 pub use self::connection_map::ConnectionMap;
 pub use self::connection_map::Error as ConnectionMapError;
@@ -17,18 +17,26 @@ use std::io;
 
 use super::super::container;
 
-const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
-
 pub mod request {
 
     use std::io;
     use super::super::super::container;
 
-    pub fn copy<R: ?Sized, W>(request_id: u32, reader: &mut R, w: W) -> io::Result<usize>
+    pub fn copy<R: ?Sized, W>(request_id: u64,
+                              compression_threshold: Option<usize>,
+                              buffer: &mut [u8],
+                              reader: &mut R,
+                              w: W)
+                              -> io::Result<usize>
         where R: io::Read,
               W: FnMut(&[u8]) -> io::Result<usize>
     {
-        super::copy(container::PacketType::Request, request_id, reader, w)
+        super::copy(container::PacketType::Request,
+                    request_id,
+                    compression_threshold,
+                    buffer,
+                    reader,
+                    w)
     }
 
 }
@@ -38,28 +46,39 @@ pub mod response {
     use std::io;
     use super::super::super::container;
 
-    pub fn copy<R: ?Sized, W>(request_id: u32, reader: &mut R, w: W) -> io::Result<usize>
+    pub fn copy<R: ?Sized, W>(request_id: u64,
+                              compression_threshold: Option<usize>,
+                              buffer: &mut [u8],
+                              reader: &mut R,
+                              w: W)
+                              -> io::Result<usize>
         where R: io::Read,
               W: FnMut(&[u8]) -> io::Result<usize>
     {
-        super::copy(container::PacketType::Response, request_id, reader, w)
+        super::copy(container::PacketType::Response,
+                    request_id,
+                    compression_threshold,
+                    buffer,
+                    reader,
+                    w)
     }
 
 }
 
 fn copy<R: ?Sized, W>(pt: container::PacketType,
-                      request_id: u32,
+                      request_id: u64,
+                      compression_threshold: Option<usize>,
+                      buffer: &mut [u8],
                       reader: &mut R,
                       mut w: W)
                       -> io::Result<usize>
     where R: io::Read,
           W: FnMut(&[u8]) -> io::Result<usize>
 {
-    let mut buffer = [0; DEFAULT_BUFFER_SIZE];
     let mut total = 0;
     let mut reading = true;
     while reading {
-        let result = reader.read(&mut buffer);
+        let result = reader.read(buffer);
 
         match result {
             Ok(ref size) => {
@@ -75,8 +94,13 @@ fn copy<R: ?Sized, W>(pt: container::PacketType,
             }
         }
 
+        let mut container = container::pack_packet(pt, request_id, result, buffer);
+        if let Some(compression_threshold) = compression_threshold {
+            try!(container.compress(compression_threshold));
+        }
+
         let mut bytes = Vec::new();
-        try!(container::pack_packet(pt, request_id, result, &buffer).write(&mut bytes));
+        try!(container.write(&mut bytes));
         try!(w(&bytes));
     }
     Ok(total)
@@ -93,7 +117,7 @@ mod tests {
     fn copy_request_packets_while_reader_has_no_errors() {
         let mut reader = io::Cursor::new(b"test message".to_vec());
         let mut output = Vec::new();
-        assert!(request::copy(1, &mut reader, |buffer| output.write(buffer)).is_ok());
+        assert!(request::copy(1, None, &mut [0; 16], &mut reader, |b| output.write(b)).is_ok());
         assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 22, 8, 7, 18, 18, 8, 1, 16, 1, 34, 12, 116, 101,
                         115, 116, 32, 109, 101, 115, 115, 97, 103, 101, 0, 0, 0, 0, 0, 0, 0, 10,
                         8, 7, 18, 6, 8, 1, 16, 1, 34, 0],
@@ -106,7 +130,7 @@ mod tests {
                                                                                     .to_vec()),
                                                                 4);
         let mut output = Vec::new();
-        assert!(request::copy(1, &mut reader, |buffer| output.write(buffer)).is_ok());
+        assert!(request::copy(1, None, &mut [0; 16], &mut reader, |b| output.write(b)).is_ok());
         assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 18, 8, 7, 18, 14, 8, 1, 16, 1, 34, 8, 116, 101, 115,
                         116, 32, 109, 101, 115, 0, 0, 0, 0, 0, 0, 0, 24, 8, 7, 18, 20, 8, 1, 16,
                         19, 26, 14, 117, 110, 101, 120, 112, 101, 99, 116, 101, 100, 32, 69, 79,
@@ -118,7 +142,7 @@ mod tests {
     fn copy_response_packets_while_reader_has_no_errors() {
         let mut reader = io::Cursor::new(b"test message".to_vec());
         let mut output = Vec::new();
-        assert!(response::copy(1, &mut reader, |buffer| output.write(buffer)).is_ok());
+        assert!(response::copy(1, None, &mut [0; 16], &mut reader, |b| output.write(b)).is_ok());
         assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 22, 8, 9, 18, 18, 8, 1, 16, 1, 34, 12, 116, 101,
                         115, 116, 32, 109, 101, 115, 115, 97, 103, 101, 0, 0, 0, 0, 0, 0, 0, 10,
                         8, 9, 18, 6, 8, 1, 16, 1, 34, 0],
@@ -131,7 +155,7 @@ mod tests {
                                                                                     .to_vec()),
                                                                 4);
         let mut output = Vec::new();
-        assert!(response::copy(1, &mut reader, |buffer| output.write(buffer)).is_ok());
+        assert!(response::copy(1, None, &mut [0; 16], &mut reader, |b| output.write(b)).is_ok());
         assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 18, 8, 9, 18, 14, 8, 1, 16, 1, 34, 8, 116, 101, 115,
                         116, 32, 109, 101, 115, 0, 0, 0, 0, 0, 0, 0, 24, 8, 9, 18, 20, 8, 1, 16,
                         19, 26, 14, 117, 110, 101, 120, 112, 101, 99, 116, 101, 100, 32, 69, 79,
@@ -15,7 +15,11 @@ limitations under the License.
 */
 
 mod copy;
+mod pool;
 mod reader;
+mod window;
 
 pub use self::copy::{request, response};
+pub use self::pool::Pool;
 pub use self::reader::Reader;
+pub use self::window::Window;
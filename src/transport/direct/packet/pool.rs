@@ -0,0 +1,111 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+const MAX_IDLE_BUFFERS: usize = 64;
+
+pub struct Pool {
+    buffer_size: usize,
+    idle: Mutex<Vec<Vec<u8>>>,
+}
+
+pub struct Buffer<'a> {
+    pool: &'a Pool,
+    data: Option<Vec<u8>>,
+}
+
+impl Pool {
+    pub fn new(buffer_size: usize) -> Self {
+        Pool {
+            buffer_size: buffer_size,
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn get(&self) -> Buffer {
+        let data = self.idle.lock().unwrap().pop().unwrap_or_else(|| vec![0; self.buffer_size]);
+        Buffer {
+            pool: self,
+            data: Some(data),
+        }
+    }
+
+    fn put(&self, data: Vec<u8>) {
+        let mut idle = self.idle.lock().unwrap();
+        if idle.len() < MAX_IDLE_BUFFERS {
+            idle.push(data);
+        }
+    }
+
+    pub fn idle(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+}
+
+impl<'a> Deref for Buffer<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.data.as_ref().unwrap()
+    }
+}
+
+impl<'a> DerefMut for Buffer<'a> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.data.as_mut().unwrap()
+    }
+}
+
+impl<'a> Drop for Buffer<'a> {
+    fn drop(&mut self) {
+        if let Some(data) = self.data.take() {
+            self.pool.put(data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{MAX_IDLE_BUFFERS, Pool};
+
+    #[test]
+    fn reuse_released_buffer() {
+        let pool = Pool::new(16);
+        {
+            let mut buffer = pool.get();
+            assert_eq!(16, buffer.len());
+            buffer[0] = 1;
+        }
+        assert_eq!(1, pool.idle());
+
+        let buffer = pool.get();
+        assert_eq!(1, buffer[0]);
+        assert_eq!(0, pool.idle());
+    }
+
+    #[test]
+    fn drop_buffers_beyond_capacity() {
+        let pool = Pool::new(16);
+        {
+            let buffers = (0..MAX_IDLE_BUFFERS + 1).map(|_| pool.get()).collect::<Vec<_>>();
+            assert_eq!(MAX_IDLE_BUFFERS + 1, buffers.len());
+        }
+        assert_eq!(MAX_IDLE_BUFFERS, pool.idle());
+    }
+
+}
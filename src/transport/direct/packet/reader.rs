@@ -19,6 +19,7 @@ use std::sync::mpsc;
 pub struct Reader {
     rx: mpsc::Receiver<io::Result<Vec<u8>>>,
     buffer: Box<io::Read + Send + 'static>,
+    credit_handler: Option<Box<Fn() + Send>>,
 }
 
 impl Reader {
@@ -28,8 +29,16 @@ impl Reader {
          Reader {
             rx: rx,
             buffer: Box::new(io::Cursor::new(Vec::new())),
+            credit_handler: None,
         })
     }
+
+    pub fn with_credit_handler(credit_handler: Box<Fn() + Send>)
+                               -> (mpsc::Sender<io::Result<Vec<u8>>>, Self) {
+        let (tx, mut reader) = Self::new();
+        reader.credit_handler = Some(credit_handler);
+        (tx, reader)
+    }
 }
 
 impl io::Read for Reader {
@@ -46,6 +55,9 @@ impl io::Read for Reader {
             result = match received {
                 Ok(payload) => {
                     if payload.len() > 0 {
+                        if let Some(ref credit_handler) = self.credit_handler {
+                            credit_handler();
+                        }
                         self.buffer = Box::new(io::Cursor::new(payload));
                         self.buffer.read(buffer)
                     } else {
@@ -59,13 +71,29 @@ impl io::Read for Reader {
     }
 }
 
+impl Drop for Reader {
+    fn drop(&mut self) {
+        if let Some(ref credit_handler) = self.credit_handler {
+            // return the credits of the packets that are never read.
+            while let Ok(result) = self.rx.try_recv() {
+                if let Ok(ref payload) = result {
+                    if payload.len() > 0 {
+                        credit_handler();
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use std::error::Error;
     use std::io;
     use std::thread;
-    use std::sync::mpsc;
+    use std::sync::{Arc, mpsc};
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use super::Reader;
 
     #[test]
@@ -108,6 +136,24 @@ mod tests {
         assert_eq!("unexpected EOF", error.description());
     }
 
+    #[test]
+    fn read_from_with_credit_for_every_packet() {
+        let credits = Arc::new(AtomicUsize::new(0));
+        let credits_clone = credits.clone();
+        let (tx, mut reader) = Reader::with_credit_handler(Box::new(move || {
+            credits_clone.fetch_add(1, Ordering::SeqCst);
+        }));
+        thread::spawn(move || {
+            send_bytes(&tx, b"test ");
+            send_bytes(&tx, b"message");
+            send_bytes(&tx, b"");
+        });
+
+        let mut output = Vec::new();
+        assert_eq!(Some(12), io::copy(&mut reader, &mut output).ok());
+        assert_eq!(2, credits.load(Ordering::SeqCst));
+    }
+
     fn send_bytes(tx: &mpsc::Sender<io::Result<Vec<u8>>>, message: &[u8]) {
         let _ = tx.send(Ok(message.to_vec()));
     }
@@ -0,0 +1,136 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Condvar, Mutex};
+
+pub struct Window {
+    size: usize,
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+struct State {
+    credits: HashMap<u64, usize>,
+    closed: bool,
+}
+
+impl Window {
+    pub fn new(size: usize) -> Self {
+        Window {
+            size: size,
+            state: Mutex::new(State {
+                credits: HashMap::new(),
+                closed: false,
+            }),
+            condvar: Condvar::new(),
+        }
+    }
+
+    pub fn acquire(&self, request_id: u64) -> io::Result<()> {
+        if self.size == 0 {
+            return Ok(());
+        }
+
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if state.closed {
+                return Err(io::Error::new(io::ErrorKind::ConnectionAborted, "connection aborted"));
+            }
+            {
+                let credits = state.credits.entry(request_id).or_insert(self.size);
+                if *credits > 0 {
+                    *credits -= 1;
+                    return Ok(());
+                }
+            }
+            state = self.condvar.wait(state).unwrap();
+        }
+    }
+
+    pub fn release(&self, request_id: u64, packets: usize) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(credits) = state.credits.get_mut(&request_id) {
+            *credits += packets;
+            self.condvar.notify_all();
+        }
+    }
+
+    pub fn finish(&self, request_id: u64) {
+        self.state.lock().unwrap().credits.remove(&request_id);
+    }
+
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.condvar.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io;
+    use std::sync::{Arc, mpsc};
+    use std::thread;
+    use time::Duration;
+    use util::time::to_std_duration;
+    use super::Window;
+
+    #[test]
+    fn block_until_credit_is_released() {
+        let window = Arc::new(Window::new(2));
+        window.acquire(1).unwrap();
+        window.acquire(1).unwrap();
+        window.acquire(2).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let window_clone = window.clone();
+        thread::spawn(move || {
+            window_clone.acquire(1).unwrap();
+            tx.send(()).unwrap();
+        });
+
+        thread::sleep(to_std_duration(Duration::milliseconds(50)));
+        assert!(rx.try_recv().is_err());
+
+        window.release(1, 1);
+        rx.recv().unwrap();
+    }
+
+    #[test]
+    fn abort_on_close() {
+        let window = Arc::new(Window::new(1));
+        window.acquire(1).unwrap();
+
+        let window_clone = window.clone();
+        thread::spawn(move || {
+            thread::sleep(to_std_duration(Duration::milliseconds(50)));
+            window_clone.close();
+        });
+
+        assert_eq!(io::ErrorKind::ConnectionAborted,
+                   window.acquire(1).unwrap_err().kind());
+    }
+
+    #[test]
+    fn unlimited_without_size() {
+        let window = Window::new(0);
+        for _ in 0..100 {
+            window.acquire(1).unwrap();
+        }
+    }
+
+}
@@ -0,0 +1,115 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Mutex, RwLock};
+
+use node::ID;
+
+pub struct PublicAddress {
+    local_address: SocketAddr,
+    configured: bool,
+    current: RwLock<SocketAddr>,
+    observations: Mutex<HashMap<ID, IpAddr>>,
+}
+
+impl PublicAddress {
+    pub fn new(local_address: SocketAddr, public_address: Option<SocketAddr>) -> Self {
+        PublicAddress {
+            local_address: local_address,
+            configured: public_address.is_some(),
+            current: RwLock::new(public_address.unwrap_or(local_address)),
+            observations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self) -> SocketAddr {
+        *self.current.read().unwrap()
+    }
+
+    pub fn observe(&self, peer_node_id: ID, observed_address: SocketAddr) {
+        if self.configured ||
+           observed_address.ip().is_loopback() && !self.local_address.ip().is_loopback() {
+            return;
+        }
+
+        let mut observations = self.observations.lock().unwrap();
+        observations.insert(peer_node_id, observed_address.ip());
+
+        let mut counts = HashMap::new();
+        for ip in observations.values() {
+            *counts.entry(*ip).or_insert(0) += 1;
+        }
+        let (ip, count) = counts.into_iter().max_by_key(|&(_, count)| count).unwrap();
+        if count * 2 <= observations.len() {
+            return;
+        }
+
+        let mut current = self.current.write().unwrap();
+        if current.ip() == ip {
+            return;
+        }
+
+        *current = SocketAddr::new(ip, self.local_address.port());
+        if ip == self.local_address.ip() {
+            info!("adopted public address {} observed by {} of {} peers",
+                  *current,
+                  count,
+                  observations.len());
+        } else {
+            info!("adopted public address {} observed by {} of {} peers (bound to {})",
+                  *current,
+                  count,
+                  observations.len(),
+                  self.local_address);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::SocketAddr;
+    use node::ID;
+    use super::PublicAddress;
+
+    #[test]
+    fn adopt_majority_observed_address() {
+        let local_address = "0.0.0.0:3001".parse::<SocketAddr>().unwrap();
+        let public_address = PublicAddress::new(local_address, None);
+        assert_eq!(local_address, public_address.get());
+
+        public_address.observe(ID::new_random(), "10.0.0.1:41000".parse().unwrap());
+        assert_eq!("10.0.0.1:3001".parse::<SocketAddr>().unwrap(),
+                   public_address.get());
+
+        public_address.observe(ID::new_random(), "192.168.0.1:42000".parse().unwrap());
+        public_address.observe(ID::new_random(), "192.168.0.1:43000".parse().unwrap());
+        assert_eq!("192.168.0.1:3001".parse::<SocketAddr>().unwrap(),
+                   public_address.get());
+    }
+
+    #[test]
+    fn keep_configured_address() {
+        let local_address = "0.0.0.0:3001".parse::<SocketAddr>().unwrap();
+        let configured_address = "10.0.0.1:4001".parse::<SocketAddr>().unwrap();
+        let public_address = PublicAddress::new(local_address, Some(configured_address));
+
+        public_address.observe(ID::new_random(), "192.168.0.1:42000".parse().unwrap());
+        assert_eq!(configured_address, public_address.get());
+    }
+
+}
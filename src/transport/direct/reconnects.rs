@@ -0,0 +1,141 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use time::Duration;
+
+use metric::Metric;
+use node::ID;
+use transport::ReconnectState;
+
+/// Keeps track of the reconnects per peer, so flapping peers are visible in the metrics and the
+/// admin api. Every peer exports the `peer.<id>.reconnect_attempts` counter and the
+/// `peer.<id>.reconnect_backoff_ms` gauge.
+pub struct Reconnects {
+    metric: Arc<Metric>,
+    states: Mutex<HashMap<ID, ReconnectState>>,
+}
+
+impl Reconnects {
+    pub fn new(metric: Arc<Metric>) -> Reconnects {
+        Reconnects {
+            metric: metric,
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn start(&self, peer_node_id: ID, address: SocketAddr, backoff: Duration) {
+        let mut states = self.states.lock().unwrap();
+        let state = states.entry(peer_node_id).or_insert(ReconnectState {
+            peer_node_id: peer_node_id,
+            address: address,
+            reconnecting: false,
+            attempts: 0,
+            reconnects: 0,
+            backoff: Duration::zero(),
+            last_error: None,
+        });
+        state.address = address;
+        state.reconnecting = true;
+        state.backoff = backoff;
+        self.set_backoff(&peer_node_id, backoff);
+    }
+
+    pub fn fail(&self, peer_node_id: &ID, backoff: Duration, error: String) {
+        if let Some(state) = self.states.lock().unwrap().get_mut(peer_node_id) {
+            state.attempts += 1;
+            state.backoff = backoff;
+            state.last_error = Some(error);
+        }
+        self.metric.counter(&format!("peer.{}.reconnect_attempts", peer_node_id)).increment();
+        self.set_backoff(peer_node_id, backoff);
+    }
+
+    pub fn succeed(&self, peer_node_id: &ID) {
+        if let Some(state) = self.states.lock().unwrap().get_mut(peer_node_id) {
+            state.attempts += 1;
+            state.reconnects += 1;
+            state.reconnecting = false;
+            state.backoff = Duration::zero();
+        }
+        self.metric.counter(&format!("peer.{}.reconnect_attempts", peer_node_id)).increment();
+        self.set_backoff(peer_node_id, Duration::zero());
+    }
+
+    /// Ends the reconnect without a connection, e.g. because the peer has been removed.
+    pub fn stop(&self, peer_node_id: &ID) {
+        if let Some(state) = self.states.lock().unwrap().get_mut(peer_node_id) {
+            state.reconnecting = false;
+            state.backoff = Duration::zero();
+        }
+        self.set_backoff(peer_node_id, Duration::zero());
+    }
+
+    /// Returns the reconnect states ordered by peer.
+    pub fn states(&self) -> Vec<ReconnectState> {
+        let mut states = self.states.lock().unwrap().values().cloned().collect::<Vec<_>>();
+        states.sort_by(|a, b| a.peer_node_id.cmp(&b.peer_node_id));
+        states
+    }
+
+    fn set_backoff(&self, peer_node_id: &ID, backoff: Duration) {
+        self.metric
+            .gauge(&format!("peer.{}.reconnect_backoff_ms", peer_node_id))
+            .set(backoff.num_milliseconds() as isize);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+    use time::Duration;
+
+    use super::Reconnects;
+    use metric::{self, Metric};
+    use node::ID;
+
+    #[test]
+    fn track_attempts() {
+        let metric = Arc::new(metric::Memory::new());
+        let reconnects = Reconnects::new(metric.clone());
+        let peer_node_id = ID::new_random();
+        let address = "127.0.0.1:3001".parse().unwrap();
+
+        reconnects.start(peer_node_id, address, Duration::milliseconds(100));
+        reconnects.fail(&peer_node_id, Duration::milliseconds(200), "refused".to_string());
+
+        let states = reconnects.states();
+        assert_eq!(1, states.len());
+        assert!(states[0].reconnecting);
+        assert_eq!(1, states[0].attempts);
+        assert_eq!(Duration::milliseconds(200), states[0].backoff);
+        assert_eq!(Some("refused".to_string()), states[0].last_error);
+        assert_eq!(Some(metric::Value::Gauge(200)),
+                   metric.get(&format!("peer.{}.reconnect_backoff_ms", peer_node_id)));
+
+        reconnects.succeed(&peer_node_id);
+
+        let states = reconnects.states();
+        assert!(!states[0].reconnecting);
+        assert_eq!(2, states[0].attempts);
+        assert_eq!(1, states[0].reconnects);
+        assert_eq!(Some(metric::Value::Counter(2)),
+                   metric.get(&format!("peer.{}.reconnect_attempts", peer_node_id)));
+    }
+
+}
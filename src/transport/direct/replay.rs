@@ -0,0 +1,118 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use node::request;
+
+/// Records a request body while it's read, so the request can be sent again after a redirect.
+/// Once more than `limit` bytes have been read, the body can't be replayed anymore.
+pub struct Replay {
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    reader: Box<request::Reader>,
+    recorded: Vec<u8>,
+    limit: usize,
+    exceeded: bool,
+}
+
+struct Reader {
+    state: Arc<Mutex<State>>,
+    position: usize,
+}
+
+impl Replay {
+    pub fn new(reader: Box<request::Reader>, limit: usize) -> Replay {
+        Replay {
+            state: Arc::new(Mutex::new(State {
+                reader: reader,
+                recorded: Vec::new(),
+                limit: limit,
+                exceeded: false,
+            })),
+        }
+    }
+
+    /// Returns a reader that starts at the beginning of the body or `None` if the body exceeded
+    /// the limit.
+    pub fn reader(&self) -> Option<Box<request::Reader>> {
+        if self.state.lock().unwrap().exceeded {
+            return None;
+        }
+        Some(Box::new(Reader {
+            state: self.state.clone(),
+            position: 0,
+        }))
+    }
+}
+
+impl io::Read for Reader {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let mut state = self.state.lock().unwrap();
+        if self.position < state.recorded.len() {
+            let size = cmp::min(buffer.len(), state.recorded.len() - self.position);
+            buffer[..size].copy_from_slice(&state.recorded[self.position..self.position + size]);
+            self.position += size;
+            return Ok(size);
+        }
+
+        let size = try!(state.reader.read(buffer));
+        if !state.exceeded {
+            if state.recorded.len() + size > state.limit {
+                state.exceeded = true;
+                state.recorded = Vec::new();
+            } else {
+                state.recorded.extend_from_slice(&buffer[..size]);
+            }
+        }
+        self.position += size;
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{self, Read};
+    use super::Replay;
+
+    #[test]
+    fn replay_body() {
+        let replay = Replay::new(Box::new(io::Cursor::new(b"test message".to_vec())), 16);
+
+        let mut first = replay.reader().unwrap();
+        let mut buffer = [0u8; 4];
+        first.read_exact(&mut buffer).unwrap();
+        assert_eq!(b"test", &buffer);
+
+        let mut output = String::new();
+        replay.reader().unwrap().read_to_string(&mut output).unwrap();
+        assert_eq!("test message", output);
+    }
+
+    #[test]
+    fn replay_body_beyond_limit() {
+        let replay = Replay::new(Box::new(io::Cursor::new(b"test message".to_vec())), 8);
+
+        let mut output = String::new();
+        replay.reader().unwrap().read_to_string(&mut output).unwrap();
+        assert_eq!("test message", output);
+        assert!(replay.reader().is_none());
+    }
+
+}
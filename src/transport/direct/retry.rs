@@ -0,0 +1,133 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::cmp;
+use std::collections::HashMap;
+use time::Duration;
+
+use node::{request, service};
+
+/// The kinds of service errors a request can be retried on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorKind {
+    Unavailable,
+    Timeout,
+    Internal,
+}
+
+/// Tells how often a failed request is sent again and how long to wait in between. The wait
+/// doubles with every attempt.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Policy {
+    pub max_attempts: usize,
+    pub backoff: Duration,
+    pub retry_on: Vec<ErrorKind>,
+}
+
+/// The retry policies of the services. Services without their own policy use the default one.
+#[derive(Clone, Debug, Default)]
+pub struct Policies {
+    pub default: Option<Policy>,
+    pub services: HashMap<String, Policy>,
+}
+
+impl ErrorKind {
+    pub fn parse(text: &str) -> Option<ErrorKind> {
+        match text {
+            "unavailable" => Some(ErrorKind::Unavailable),
+            "timeout" => Some(ErrorKind::Timeout),
+            "internal" => Some(ErrorKind::Internal),
+            _ => None,
+        }
+    }
+
+    fn of(error: &request::Error) -> Option<ErrorKind> {
+        match *error {
+            request::Error::Timeout |
+            request::Error::Service(service::Error::Timeout) => Some(ErrorKind::Timeout),
+            request::Error::Service(service::Error::Unavailable) => Some(ErrorKind::Unavailable),
+            request::Error::Service(service::Error::Internal(_)) => Some(ErrorKind::Internal),
+            _ => None,
+        }
+    }
+}
+
+impl Policy {
+    pub fn new(max_attempts: usize, backoff: Duration, retry_on: Vec<ErrorKind>) -> Policy {
+        Policy {
+            max_attempts: max_attempts,
+            backoff: backoff,
+            retry_on: retry_on,
+        }
+    }
+
+    /// Returns the time to wait before the next attempt, if the error of the given attempt
+    /// (starting at 1) should be retried.
+    pub fn next_backoff(&self, attempt: usize, error: &request::Error) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        match ErrorKind::of(error) {
+            Some(kind) if self.retry_on.contains(&kind) => {
+                Some(self.backoff * (1 << cmp::min(attempt - 1, 16)) as i32)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Policies {
+    pub fn new() -> Policies {
+        Policies::default()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Policy> {
+        self.services.get(name).or(self.default.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use time::Duration;
+
+    use super::{ErrorKind, Policy};
+    use node::{request, service};
+
+    #[test]
+    fn retry_on_given_errors() {
+        let policy = Policy::new(3,
+                                 Duration::milliseconds(10),
+                                 vec![ErrorKind::Unavailable, ErrorKind::Timeout]);
+        let unavailable = request::Error::Service(service::Error::Unavailable);
+
+        assert_eq!(Some(Duration::milliseconds(10)),
+                   policy.next_backoff(1, &unavailable));
+        assert_eq!(Some(Duration::milliseconds(20)),
+                   policy.next_backoff(2, &request::Error::Timeout));
+        assert_eq!(None, policy.next_backoff(3, &unavailable));
+
+        let internal = request::Error::Service(service::Error::Internal("test".to_string()));
+        assert_eq!(None, policy.next_backoff(1, &internal));
+        assert_eq!(None, policy.next_backoff(1, &request::Error::NoService));
+    }
+
+    #[test]
+    fn parse_error_kind() {
+        assert_eq!(Some(ErrorKind::Internal), ErrorKind::parse("internal"));
+        assert_eq!(None, ErrorKind::parse("redirect"));
+    }
+
+}
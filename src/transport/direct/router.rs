@@ -0,0 +1,207 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::io;
+use std::sync::{Arc, Mutex, mpsc};
+
+use time::Timespec;
+
+use node::{ID, request, response, service};
+use util::thread::supervisor;
+use super::{Connection, ConnectionMap, Link, ServiceMap, Tracker};
+use super::{container, deadline};
+
+/// Propagates the services that are reachable over this node to its peers and forwards
+/// requests for them, so nodes that aren't connected directly can still reach each other's
+/// services. Routes are announced up to `max_hops` intermediate nodes.
+pub struct Router {
+    max_hops: u32,
+    connections: Arc<ConnectionMap>,
+    services: Arc<ServiceMap>,
+    tracker: Arc<Tracker<Mutex<Box<response::Handler>>, request::Result<()>>>,
+    announcing: Arc<Mutex<()>>,
+}
+
+impl Router {
+    pub fn new(max_hops: usize,
+               connections: Arc<ConnectionMap>,
+               services: Arc<ServiceMap>,
+               tracker: Arc<Tracker<Mutex<Box<response::Handler>>, request::Result<()>>>)
+               -> Router {
+        Router {
+            max_hops: max_hops as u32,
+            connections: connections,
+            services: services,
+            tracker: tracker,
+            announcing: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_hops > 0
+    }
+
+    /// Sends the known routes to a newly connected peer.
+    pub fn introduce(&self, connection: &Connection) -> io::Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let (routes, _) = self.services.routes_for(&self.services.service_names(),
+                                                   &connection.peer_node_id(),
+                                                   self.max_hops);
+        connection.send_add_routes(&routes)
+    }
+
+    /// Announces the current routes of the given services to all peers. The announcements are
+    /// sent in the background, one after another, so they can be triggered from within the
+    /// connection handlers.
+    pub fn announce(&self, names: Vec<String>) {
+        if !self.is_enabled() || names.is_empty() {
+            return;
+        }
+        let max_hops = self.max_hops;
+        let connections = self.connections.clone();
+        let services = self.services.clone();
+        let announcing = self.announcing.clone();
+        supervisor().spawn("route announcement", move || {
+            let _announcing = announcing.lock().unwrap();
+            for peer_node_id in connections.peer_node_ids_since(container::ROUTING_VERSION) {
+                let (routes, withdrawn) = services.routes_for(&names, &peer_node_id, max_hops);
+                let result = connections.select(&peer_node_id, |connection| {
+                    if !withdrawn.is_empty() {
+                        try!(connection.send_remove_services(&withdrawn));
+                    }
+                    connection.send_add_routes(&routes)
+                });
+                if let Ok(Err(error)) = result {
+                    debug!("announcing routes to {} failed: {:?}", peer_node_id, error);
+                }
+            }
+        });
+    }
+
+    /// Forwards a request that arrived from the given peer to the next node on the way to the
+    /// service and returns its response. The first node on the way sets the request's TTL to
+    /// `max_hops`, every further one decrements it and requests that arrive with a TTL of zero
+    /// expire, so a routing loop can't keep a request alive. The deadline and the trace id are
+    /// passed on unchanged.
+    pub fn forward(&self,
+                   name: &str,
+                   from_peer_node_id: &ID,
+                   ttl: Option<u32>,
+                   deadline: Option<Timespec>,
+                   trace_id: Option<&str>,
+                   mut reader: Box<request::Reader>)
+                   -> service::Result {
+        if deadline.map(deadline::is_expired).unwrap_or(false) {
+            debug!("request for {} from {} passed its deadline", name, from_peer_node_id);
+            return Err(service::Error::Timeout);
+        }
+        let ttl = match ttl {
+            Some(0) => {
+                debug!("request for {} from {} expired", name, from_peer_node_id);
+                return Err(service::Error::Timeout);
+            }
+            Some(ttl) => ttl - 1,
+            None => self.max_hops.saturating_sub(1),
+        };
+
+        let peer_node_id = match self.services.next_hop(name, from_peer_node_id) {
+            Some(peer_node_id) => peer_node_id,
+            None => return Err(service::Error::Unavailable),
+        };
+
+        let (reader_tx, reader_rx) = mpsc::channel();
+        let response_handler: Box<response::Handler> =
+            Box::new(move |reader: Box<response::Reader>| {
+                let _ = reader_tx.send(reader);
+            });
+        let (request_id, response_rx) = self.tracker.begin(name,
+                                                           &Link::Remote(peer_node_id),
+                                                           Mutex::new(response_handler));
+        match self.connections.select(&peer_node_id, |connection| {
+            connection.send_request(request_id, name, Some(ttl), deadline, trace_id, &mut reader)
+        }) {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => {
+                return Err(service::Error::Internal(format!("forwarding to {} failed: {}",
+                                                            peer_node_id,
+                                                            error)))
+            }
+            Err(_) => return Err(service::Error::Unavailable),
+        }
+
+        let result = match response_rx.recv().unwrap() {
+            Ok(result) => result,
+            Err(error) => {
+                self.connections.abandon_request(&peer_node_id, request_id);
+                Err(request::Error::from(error))
+            }
+        };
+        match result {
+            Ok(()) => {
+                reader_rx.recv().map_err(|_| {
+                    service::Error::Internal("forwarded response got lost".to_string())
+                })
+            }
+            Err(request::Error::Service(error)) => Err(error),
+            Err(request::Error::Timeout) => Err(service::Error::Timeout),
+            Err(request::Error::NoService) => Err(service::Error::Unavailable),
+            Err(request::Error::Io(_, message)) => Err(service::Error::Internal(message)),
+        }
+    }
+
+    /// Forwards a notification that arrived from the given peer to the next node on the way to
+    /// the service. Its TTL is handled like the one of a request, but since nobody waits for a
+    /// notification, it's just dropped if it can't be passed on.
+    pub fn forward_notification(&self,
+                                name: &str,
+                                from_peer_node_id: &ID,
+                                ttl: Option<u32>,
+                                trace_id: Option<&str>,
+                                payload: Vec<u8>) {
+        let ttl = match ttl {
+            Some(0) => {
+                debug!("notification for {} from {} expired", name, from_peer_node_id);
+                return;
+            }
+            Some(ttl) => ttl - 1,
+            None => self.max_hops.saturating_sub(1),
+        };
+
+        let peer_node_id = match self.services.next_hop(name, from_peer_node_id) {
+            Some(peer_node_id) => peer_node_id,
+            None => {
+                debug!("dropped notification for {} from {} without a route",
+                       name,
+                       from_peer_node_id);
+                return;
+            }
+        };
+
+        match self.connections.select(&peer_node_id, |connection| {
+            connection.send_notification(name, Some(ttl), trace_id, payload)
+        }) {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => {
+                debug!("forwarding notification for {} to {} failed: {:?}",
+                       name,
+                       peer_node_id,
+                       error)
+            }
+            Err(_) => debug!("dropped notification for {} to vanished {}", name, peer_node_id),
+        }
+    }
+}
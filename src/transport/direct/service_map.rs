@@ -13,21 +13,38 @@
 // limitations under the License.
 //
 
+use std::cmp;
 use std::collections::HashMap;
 use std::result;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use rand::{self, Rng};
+use time::{Duration, SteadyTime};
 
 use metric::{self, Metric};
-use node::{ID, Service, request};
+use node::{ID, Placement, Service, request, service, version};
 use transport::direct::{self, Link};
 use transport::direct::balancer::{self, Balancer};
+use transport::direct::breaker::Breaker;
+use util::clock::{self, Clock};
+use util::time::to_std_duration;
 
 pub struct ServiceMap {
     balancer_factory: Box<balancer::Factory>,
     entries: RwLock<HashMap<String, Entry>>,
+    snapshot: RwLock<Arc<Snapshot>>,
     metric: Arc<Metric>,
     services_gauge: metric::item::Gauge,
     endpoints_gauge: metric::item::Gauge,
+    grace_period: Option<Duration>,
+    max_services_per_peer: Option<usize>,
+    max_service_name_length: Option<usize>,
+    strict_names: AtomicBool,
+    usage: Mutex<HashMap<(String, ID), usize>>,
+    breaker: Breaker,
+    clock: Arc<Clock>,
+    published_epoch: Mutex<u64>,
+    published: Condvar,
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -36,21 +53,88 @@ pub type Result<T> = result::Result<T, Error>;
 pub enum Error {
     ServiceAlreadyExists,
     ServiceDoesNotExists,
+    TooManyServices(ID, usize),
+    InvalidServiceName(String),
     ConnectionMap(direct::ConnectionMapError),
 }
 
 impl ServiceMap {
-    pub fn new(balancer_factory: Box<balancer::Factory>, metric: Arc<Metric>) -> Self {
+    pub fn new(balancer_factory: Box<balancer::Factory>,
+               metric: Arc<Metric>,
+               grace_period: Option<Duration>,
+               max_services_per_peer: Option<usize>,
+               max_service_name_length: Option<usize>)
+               -> Self {
+        Self::with_clock(balancer_factory,
+                         metric,
+                         grace_period,
+                         max_services_per_peer,
+                         max_service_name_length,
+                         Arc::new(clock::System))
+    }
+
+    pub fn with_clock(balancer_factory: Box<balancer::Factory>,
+                      metric: Arc<Metric>,
+                      grace_period: Option<Duration>,
+                      max_services_per_peer: Option<usize>,
+                      max_service_name_length: Option<usize>,
+                      clock: Arc<Clock>)
+                      -> Self {
         ServiceMap {
             balancer_factory: balancer_factory,
             entries: RwLock::new(HashMap::default()),
+            snapshot: RwLock::new(Arc::new(Snapshot::new(0, &HashMap::default()))),
             metric: metric.clone(),
             services_gauge: metric.gauge("services"),
             endpoints_gauge: metric.gauge("endpoints"),
+            grace_period: grace_period,
+            max_services_per_peer: max_services_per_peer,
+            max_service_name_length: max_service_name_length,
+            strict_names: AtomicBool::new(false),
+            usage: Mutex::new(HashMap::new()),
+            breaker: Breaker::new(metric.clone()),
+            clock: clock,
+            published_epoch: Mutex::new(0),
+            published: Condvar::new(),
+        }
+    }
+
+    /// Opens the breaker of a link after the given number of consecutive failures. While it's
+    /// open, requests over the link fail fast with `Unavailable`.
+    pub fn set_breaker(&self, consecutive_failures: usize, cool_down: Duration) {
+        self.breaker.enable(consecutive_failures, cool_down);
+    }
+
+    /// Only accepts service names of alphanumeric characters, `-`, `_` and `.` from now on. By
+    /// default, any non-empty name within the length limit is accepted.
+    pub fn set_strict_names(&self, strict_names: bool) {
+        self.strict_names.store(strict_names, Ordering::SeqCst);
+    }
+
+    /// Feeds the outcome of a request over the link into its breaker.
+    pub fn record_result(&self, name: &str, link: &Link, success: bool) {
+        if success {
+            self.breaker.succeed(name, link);
+        } else {
+            self.breaker.fail(name, link);
         }
     }
 
     pub fn insert_local(&self, name: &str, f: Box<Service>) -> Result<()> {
+        self.insert_local_with_placement(name,
+                                         f,
+                                         Placement::Any,
+                                         service::DEFAULT_WEIGHT,
+                                         service::Metadata::new())
+    }
+
+    pub fn insert_local_with_placement(&self,
+                                       name: &str,
+                                       f: Box<Service>,
+                                       placement: Placement,
+                                       weight: u32,
+                                       metadata: service::Metadata)
+                                       -> Result<()> {
         let mut entries = self.entries.write().unwrap();
 
         if !entries.contains_key(name) {
@@ -66,9 +150,10 @@ impl ServiceMap {
             return Err(Error::ServiceAlreadyExists);
         }
 
-        entry.add_local_link(Arc::new(f));
+        entry.add_local_link(Arc::new(f), placement, weight, metadata);
         self.endpoints_gauge.change(1);
 
+        self.publish(&entries);
         Ok(())
     }
 
@@ -88,16 +173,64 @@ impl ServiceMap {
             return Err(Error::ServiceAlreadyExists);
         }
 
-        entry.add_remote_link(peer_node_id);
+        entry.suspects.remove(&peer_node_id);
+        entry.add_remote_link(peer_node_id,
+                              0,
+                              service::DEFAULT_WEIGHT,
+                              service::Metadata::new());
         self.endpoints_gauge.change(1);
 
+        self.publish(&entries);
         Ok(())
     }
 
-    pub fn insert_remotes(&self, names: &[String], peer_node_id: ID) {
+    pub fn insert_remotes(&self, names: &[String], peer_node_id: ID) -> Result<()> {
+        self.insert_routes(&names.iter()
+                                 .map(|name| {
+                                     (name.to_string(),
+                                      0,
+                                      service::DEFAULT_WEIGHT,
+                                      service::Metadata::new())
+                                 })
+                                 .collect::<Vec<_>>(),
+                           peer_node_id)
+    }
+
+    /// Inserts services that are reachable over the given peer. The hop count is the number of
+    /// nodes between the peer and the service. Only the links with the lowest hop count are
+    /// used to balance requests. The weight is handed to the balancer as a capacity hint and the
+    /// metadata is kept for requests that ask for tagged providers.
+    pub fn insert_routes(&self,
+                         routes: &[(String, u32, u32, service::Metadata)],
+                         peer_node_id: ID)
+                         -> Result<()> {
         let mut entries = self.entries.write().unwrap();
 
-        for name in names {
+        for &(ref name, _, _, _) in routes {
+            if !self.is_valid_name(name) {
+                return Err(Error::InvalidServiceName(name.to_string()));
+            }
+        }
+
+        if let Some(max_services_per_peer) = self.max_services_per_peer {
+            let count = entries.iter()
+                               .filter(|&(name, entry)| {
+                                   entry.links
+                                        .iter()
+                                        .any(|link| Link::is_remote(link, &peer_node_id)) ||
+                                   routes.iter()
+                                         .any(|&(ref route_name, _, _, _)| route_name == name)
+                               })
+                               .count() +
+                        routes.iter()
+                              .filter(|&&(ref name, _, _, _)| !entries.contains_key(name))
+                              .count();
+            if count > max_services_per_peer {
+                return Err(Error::TooManyServices(peer_node_id, count));
+            }
+        }
+
+        for &(ref name, hops, weight, ref metadata) in routes {
             if !entries.contains_key(name) {
                 entries.insert(name.to_string(),
                                Entry::new(name,
@@ -108,30 +241,191 @@ impl ServiceMap {
             let mut entry = entries.get_mut(name).unwrap();
 
             if let None = entry.links.iter().find(|&link| Link::is_remote(link, &peer_node_id)) {
-                entry.add_remote_link(peer_node_id);
+                entry.suspects.remove(&peer_node_id);
+                entry.add_remote_link(peer_node_id, hops, weight, metadata.clone());
                 self.endpoints_gauge.change(1);
+            } else {
+                entry.metadata.insert(peer_node_id, metadata.clone());
+                if entry.hops.get(&peer_node_id) != Some(&hops) ||
+                   entry.weights.get(&peer_node_id) != Some(&weight) {
+                    entry.hops.insert(peer_node_id, hops);
+                    entry.weights.insert(peer_node_id, weight);
+                    entry.update_balancer();
+                }
             }
         }
+
+        self.publish(&entries);
+        Ok(())
     }
 
     pub fn get(&self, name: &str) -> request::Result<(Link, Option<Arc<Box<Service>>>)> {
-        let mut entries = self.entries.write().unwrap();
+        self.get_with_key(name, None)
+    }
 
-        let mut entry = match entries.get_mut(name) {
-            Some(entry) => entry,
-            None => return Err(request::Error::NoService),
-        };
+    /// Selects a link for the service. The key is passed to the balancer, so balancers with key
+    /// affinity can select the same link for the same key. A name with a version requirement like
+    /// `echo@^1.2` selects one of the links whose version is compatible at random.
+    pub fn get_with_key(&self,
+                        name: &str,
+                        key: Option<&[u8]>)
+                        -> request::Result<(Link, Option<Arc<Box<Service>>>)> {
+        if let (name, Some(requirement)) = version::split(name) {
+            let requirement = match version::Requirement::parse(requirement) {
+                Some(requirement) => requirement,
+                None => {
+                    return Err(request::Error::Service(service::Error::InvalidInput(
+                        format!("invalid version requirement [{}]", requirement))));
+                }
+            };
+            let links = self.matching_links(name, |entry, link| {
+                entry.has_version(link, &requirement)
+            });
+            return self.get_any(name, links);
+        }
+
+        let mut epoch = None;
+        loop {
+            let snapshot = self.snapshot();
+            // the balancer could have been updated before the snapshot got published. if the
+            // selected link is unknown to the snapshot, retry with the next one.
+            if epoch == Some(snapshot.epoch) {
+                return Err(request::Error::NoService);
+            }
+            epoch = Some(snapshot.epoch);
+
+            let selection = match snapshot.entries.get(name) {
+                Some(selection) => selection,
+                None => return Err(request::Error::NoService),
+            };
+
+            if !selection.has_links {
+                return Err(request::Error::NoService);
+            }
+
+            if let Some(link) = selection.select_link(key) {
+                if !self.breaker.allow(name, &link) {
+                    return Err(request::Error::Service(service::Error::Unavailable));
+                }
+                return Ok((link,
+                           selection.local_handler.as_ref().map(|handler| handler.clone())));
+            }
+        }
+    }
+
+    /// Selects the given link for the service, if the service is available there.
+    pub fn get_on(&self, name: &str, link: &Link) -> Option<(Link, Option<Arc<Box<Service>>>)> {
+        self.snapshot().entries.get(name).and_then(|selection| {
+            selection.select_given_link(link).map(|link| {
+                (link, selection.local_handler.as_ref().map(|handler| handler.clone()))
+            })
+        })
+    }
+
+    /// Selects one of the links that carry all the given tags. The balancer doesn't know about
+    /// tags, so the link is picked at random.
+    pub fn get_tagged(&self,
+                      name: &str,
+                      tags: &service::Metadata)
+                      -> request::Result<(Link, Option<Arc<Box<Service>>>)> {
+        let links = self.tagged_links(name, tags);
+        self.get_any(name, links)
+    }
+
+    /// Returns all links the service is balanced over, which are the ones with the lowest hop
+    /// count.
+    pub fn links(&self, name: &str) -> Vec<Link> {
+        self.tagged_links(name, &service::Metadata::new())
+    }
+
+    /// Returns the links the service is balanced over that carry all the given tags.
+    pub fn tagged_links(&self, name: &str, tags: &service::Metadata) -> Vec<Link> {
+        self.matching_links(name, |entry, link| entry.has_tags(link, tags))
+    }
+
+    fn matching_links<F>(&self, name: &str, f: F) -> Vec<Link>
+        where F: Fn(&Entry, &Link) -> bool
+    {
+        match self.entries.read().unwrap().get(name) {
+            Some(entry) => {
+                let min_hops = entry.links.iter().map(|link| entry.hops_of(link)).min();
+                entry.links
+                     .iter()
+                     .filter(|link| Some(entry.hops_of(link)) == min_hops)
+                     .filter(|link| f(entry, link))
+                     .cloned()
+                     .collect()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn get_any(&self,
+               name: &str,
+               mut links: Vec<Link>)
+               -> request::Result<(Link, Option<Arc<Box<Service>>>)> {
+        if links.is_empty() {
+            return Err(request::Error::NoService);
+        }
+        rand::thread_rng().shuffle(&mut links);
+        links.iter()
+             .filter(|link| self.breaker.allow(name, link))
+             .filter_map(|link| self.get_on(name, link))
+             .next()
+             .ok_or(request::Error::Service(service::Error::Unavailable))
+    }
 
-        let link = entry.select_link();
+    pub fn is_suspect(&self, name: &str) -> bool {
+        self.remaining_grace_period(name).is_some()
+    }
 
-        Ok((link,
-            entry.local_handler.as_ref().map(|handler| handler.clone())))
+    /// Returns how long the suspect providers of the service may still come back.
+    pub fn remaining_grace_period(&self, name: &str) -> Option<Duration> {
+        self.snapshot()
+            .entries
+            .get(name)
+            .and_then(|selection| selection.suspects_expire_at)
+            .map(|expires_at| expires_at - self.clock.now())
+            .and_then(|remaining| if remaining > Duration::zero() {
+                Some(remaining)
+            } else {
+                None
+            })
+    }
+
+    /// Blocks until the services change after the given epoch or the timeout elapses.
+    pub fn wait_for_change(&self, epoch: u64, timeout: Duration) {
+        let deadline = SteadyTime::now() + timeout;
+        let mut published_epoch = self.published_epoch.lock().unwrap();
+        while *published_epoch == epoch {
+            let remaining = deadline - SteadyTime::now();
+            if remaining <= Duration::zero() {
+                break;
+            }
+            published_epoch = self.published
+                                  .wait_timeout(published_epoch, to_std_duration(remaining))
+                                  .unwrap()
+                                  .0;
+        }
     }
 
     pub fn get_local(&self, name: &str) -> Option<Arc<Box<Service>>> {
-        let entries = self.entries.read().unwrap();
-        entries.get(name)
-               .and_then(|entry| entry.select_local_link())
+        self.snapshot()
+            .entries
+            .get(name)
+            .and_then(|selection| selection.select_local_link())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.snapshot()
+            .entries
+            .get(name)
+            .map(|selection| selection.has_links)
+            .unwrap_or(false)
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.snapshot.read().unwrap().epoch
     }
 
     pub fn local_service_names(&self) -> Vec<String> {
@@ -145,6 +439,125 @@ impl ServiceMap {
             .collect()
     }
 
+    /// Returns the names, weights and metadata of the local services that may be announced to a
+    /// peer in the given zone.
+    pub fn local_services_for(&self,
+                              zone: Option<&str>,
+                              peer_zone: Option<&str>)
+                              -> Vec<(String, u32, service::Metadata)> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|&(_, entry)| {
+                entry.local_handler.is_some() && entry.local_placement.allows(zone, peer_zone)
+            })
+            .map(|(name, entry)| {
+                (name.to_string(), entry.local_weight, entry.local_metadata.clone())
+            })
+            .collect()
+    }
+
+    pub fn local_placement(&self, name: &str) -> Option<Placement> {
+        self.entries
+            .read()
+            .unwrap()
+            .get(name)
+            .and_then(|entry| entry.local_handler.as_ref().and(Some(entry.local_placement)))
+    }
+
+    /// Counts a request that a local service handles for the given caller.
+    pub fn record_usage(&self, name: &str, caller_node_id: ID) {
+        *self.usage
+             .lock()
+             .unwrap()
+             .entry((name.to_string(), caller_node_id))
+             .or_insert(0) += 1;
+    }
+
+    /// Returns the number of handled requests per service and caller, ordered by service and
+    /// the busiest caller first.
+    pub fn usage(&self) -> Vec<(String, ID, usize)> {
+        let mut usage = self.usage
+                            .lock()
+                            .unwrap()
+                            .iter()
+                            .map(|(&(ref name, caller_node_id), &count)| {
+                                (name.to_string(), caller_node_id, count)
+                            })
+                            .collect::<Vec<_>>();
+        usage.sort_by(|a, b| {
+            match a.0.cmp(&b.0) {
+                cmp::Ordering::Equal => b.2.cmp(&a.2),
+                ordering => ordering,
+            }
+        });
+        usage
+    }
+
+    pub fn service_names(&self) -> Vec<String> {
+        self.entries.read().unwrap().keys().cloned().collect()
+    }
+
+    pub fn remote_service_names(&self, peer_node_id: &ID) -> Vec<String> {
+        self.entries
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|&(_, entry)| {
+                entry.links.iter().any(|link| Link::is_remote(link, peer_node_id))
+            })
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+
+    /// Returns the routes of the given services to announce to the peer and the services to
+    /// withdraw from it. Local services are announced separately and routes that have been
+    /// learned from the peer itself are never sent back.
+    pub fn routes_for(&self,
+                      names: &[String],
+                      peer_node_id: &ID,
+                      max_hops: u32)
+                      -> (Vec<(String, u32)>, Vec<String>) {
+        let entries = self.entries.read().unwrap();
+        let mut routes = Vec::new();
+        let mut withdrawn = Vec::new();
+        for name in names {
+            let hops = match entries.get(name) {
+                Some(entry) if entry.local_handler.is_some() => continue,
+                Some(entry) => entry.min_hops_except(peer_node_id).map(|hops| hops + 1),
+                None => None,
+            };
+            match hops {
+                Some(hops) if hops <= max_hops => routes.push((name.to_string(), hops)),
+                _ => withdrawn.push(name.to_string()),
+            }
+        }
+        (routes, withdrawn)
+    }
+
+    /// Selects the peer to forward a request for the service to, avoiding the peer the
+    /// request came from.
+    pub fn next_hop(&self, name: &str, from_peer_node_id: &ID) -> Option<ID> {
+        let entries = self.entries.read().unwrap();
+        let entry = match entries.get(name) {
+            Some(entry) => entry,
+            None => return None,
+        };
+        entry.links
+             .iter()
+             .filter_map(|link| {
+                 match *link {
+                     Link::Remote(peer_node_id) if peer_node_id != *from_peer_node_id => {
+                         Some((entry.hops_of(link), peer_node_id))
+                     }
+                     _ => None,
+                 }
+             })
+             .min_by_key(|&(hops, _)| hops)
+             .map(|(_, peer_node_id)| peer_node_id)
+    }
+
     pub fn len(&self) -> usize {
         self.entries.read().unwrap().len()
     }
@@ -158,12 +571,13 @@ impl ServiceMap {
             };
             entry.remove_local_link();
             self.endpoints_gauge.change(-1);
-            !entry.has_links()
+            entry.is_empty()
         };
         if remove {
             entries.remove(name);
             self.services_gauge.change(-1);
         }
+        self.publish(&entries);
         Ok(())
     }
 
@@ -176,12 +590,13 @@ impl ServiceMap {
             };
             entry.remove_remote_link(peer_node_id);
             self.endpoints_gauge.change(-1);
-            !entry.has_links()
+            entry.is_empty()
         };
         if remove {
             entries.remove(name);
             self.services_gauge.change(-1);
         }
+        self.publish(&entries);
         Ok(())
     }
 
@@ -193,15 +608,38 @@ impl ServiceMap {
                     Some(entry) => entry,
                     None => continue,
                 };
+                // withdrawn routes may have never been announced to this node.
+                if !entry.links.iter().any(|link| Link::is_remote(link, peer_node_id)) {
+                    continue;
+                }
                 entry.remove_remote_link(peer_node_id);
                 self.endpoints_gauge.change(-1);
-                !entry.has_links()
+                entry.is_empty()
             };
             if remove {
                 entries.remove(name);
                 self.services_gauge.change(-1);
             }
         }
+        self.publish(&entries);
+    }
+
+    pub fn suspect_all_remotes(&self, peer_node_id: &ID) {
+        let grace_period = match self.grace_period {
+            Some(grace_period) => grace_period,
+            None => return self.remove_all_remotes(peer_node_id),
+        };
+        let expires_at = self.clock.now() + grace_period;
+
+        let mut entries = self.entries.write().unwrap();
+        for (_, entry) in entries.iter_mut() {
+            if entry.links.iter().any(|link| Link::is_remote(link, peer_node_id)) {
+                entry.remove_remote_link(peer_node_id);
+                entry.suspects.insert(*peer_node_id, expires_at);
+                self.endpoints_gauge.change(-1);
+            }
+        }
+        self.publish(&entries);
     }
 
     pub fn remove_all_remotes(&self, peer_node_id: &ID) {
@@ -210,7 +648,67 @@ impl ServiceMap {
         for (name, entry) in entries.iter_mut() {
             entry.remove_remote_link(peer_node_id);
             self.endpoints_gauge.change(-1);
-            if !entry.has_links() {
+            if entry.is_empty() {
+                names.push(name.to_string());
+            }
+        }
+        for name in names {
+            entries.remove(&name);
+            self.services_gauge.change(-1);
+        }
+        self.publish(&entries);
+    }
+
+    fn is_valid_name(&self, name: &str) -> bool {
+        if name.is_empty() {
+            return false;
+        }
+        if let Some(max_service_name_length) = self.max_service_name_length {
+            if name.len() > max_service_name_length {
+                return false;
+            }
+        }
+        !self.strict_names.load(Ordering::SeqCst) ||
+        name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+    }
+
+    fn snapshot(&self) -> Arc<Snapshot> {
+        let snapshot = self.snapshot.read().unwrap().clone();
+        match snapshot.expires_at {
+            Some(expires_at) if expires_at <= self.clock.now() => {
+                let mut entries = self.entries.write().unwrap();
+                self.expire_suspects(&mut entries);
+                self.publish(&entries);
+                let snapshot = self.snapshot.read().unwrap().clone();
+                snapshot
+            }
+            _ => snapshot,
+        }
+    }
+
+    fn publish(&self, entries: &HashMap<String, Entry>) {
+        let epoch = {
+            let mut snapshot = self.snapshot.write().unwrap();
+            *snapshot = Arc::new(Snapshot::new(snapshot.epoch + 1, entries));
+            snapshot.epoch
+        };
+        *self.published_epoch.lock().unwrap() = epoch;
+        self.published.notify_all();
+    }
+
+    fn expire_suspects(&self, entries: &mut HashMap<String, Entry>) {
+        let now = self.clock.now();
+        let mut names = Vec::new();
+        for (name, entry) in entries.iter_mut() {
+            let expired = entry.suspects
+                               .iter()
+                               .filter(|&(_, expires_at)| *expires_at <= now)
+                               .map(|(peer_node_id, _)| *peer_node_id)
+                               .collect::<Vec<_>>();
+            for peer_node_id in expired {
+                entry.suspects.remove(&peer_node_id);
+            }
+            if entry.is_empty() {
                 names.push(name.to_string());
             }
         }
@@ -221,97 +719,266 @@ impl ServiceMap {
     }
 }
 
+struct Snapshot {
+    epoch: u64,
+    entries: HashMap<String, Selection>,
+    expires_at: Option<SteadyTime>,
+}
+
+impl Snapshot {
+    fn new(epoch: u64, entries: &HashMap<String, Entry>) -> Snapshot {
+        Snapshot {
+            epoch: epoch,
+            entries: entries.iter()
+                            .map(|(name, entry)| (name.to_string(), Selection::new(entry)))
+                            .collect(),
+            expires_at: entries.values()
+                               .flat_map(|entry| entry.suspects.values())
+                               .min()
+                               .map(|expires_at| *expires_at),
+        }
+    }
+}
+
+struct Selection {
+    balancer: Arc<Mutex<Box<Balancer<Item = Link>>>>,
+    has_links: bool,
+    suspects_expire_at: Option<SteadyTime>,
+    local_handler: Option<Arc<Box<Service>>>,
+    local_inbound_counter: Option<Arc<metric::item::Counter>>,
+    local_outbound_counter: Option<Arc<metric::item::Counter>>,
+    remote_outbound_counters: HashMap<ID, Arc<metric::item::Counter>>,
+}
+
+impl Selection {
+    fn new(entry: &Entry) -> Selection {
+        Selection {
+            balancer: entry.balancer.clone(),
+            has_links: entry.has_links(),
+            suspects_expire_at: entry.suspects.values().max().cloned(),
+            local_handler: entry.local_handler.clone(),
+            local_inbound_counter: entry.local_inbound_counter.clone(),
+            local_outbound_counter: entry.local_outbound_counter.clone(),
+            remote_outbound_counters: entry.remote_outbound_counters.clone(),
+        }
+    }
+
+    fn select_link(&self, key: Option<&[u8]>) -> Option<Link> {
+        let link = match self.balancer.lock().unwrap().select(key) {
+            Some(link) => link,
+            None => return None,
+        };
+        match link {
+            Link::Local => {
+                match self.local_outbound_counter {
+                    Some(ref counter) => counter.increment(),
+                    None => return None,
+                }
+            }
+            Link::Remote(ref peer_node_id) => {
+                match self.remote_outbound_counters.get(peer_node_id) {
+                    Some(counter) => counter.increment(),
+                    None => return None,
+                }
+            }
+        }
+        Some(link)
+    }
+
+    fn select_given_link(&self, link: &Link) -> Option<Link> {
+        let counter = match *link {
+            Link::Local => self.local_outbound_counter.as_ref(),
+            Link::Remote(ref peer_node_id) => self.remote_outbound_counters.get(peer_node_id),
+        };
+        counter.map(|counter| {
+            counter.increment();
+            *link
+        })
+    }
+
+    fn select_local_link(&self) -> Option<Arc<Box<Service>>> {
+        match self.local_handler {
+            Some(ref local_handler) => {
+                self.local_inbound_counter.as_ref().unwrap().increment();
+                Some(local_handler.clone())
+            }
+            None => None,
+        }
+    }
+}
+
 struct Entry {
     name: String,
-    balancer: Box<Balancer<Item = Link>>,
+    balancer: Arc<Mutex<Box<Balancer<Item = Link>>>>,
     metric: Arc<Metric>,
     local_handler: Option<Arc<Box<Service>>>,
+    local_placement: Placement,
+    local_weight: u32,
+    local_metadata: service::Metadata,
     links: Vec<Link>,
-    local_inbound_counter: Option<metric::item::Counter>,
-    local_outbound_counter: Option<metric::item::Counter>,
-    remote_outbound_counters: HashMap<ID, metric::item::Counter>,
+    hops: HashMap<ID, u32>,
+    weights: HashMap<ID, u32>,
+    metadata: HashMap<ID, service::Metadata>,
+    suspects: HashMap<ID, SteadyTime>,
+    local_inbound_counter: Option<Arc<metric::item::Counter>>,
+    local_outbound_counter: Option<Arc<metric::item::Counter>>,
+    remote_outbound_counters: HashMap<ID, Arc<metric::item::Counter>>,
 }
 
 impl Entry {
     fn new(name: &str, balancer: Box<Balancer<Item = Link>>, metric: Arc<Metric>) -> Entry {
         Entry {
             name: name.to_string(),
-            balancer: balancer,
+            balancer: Arc::new(Mutex::new(balancer)),
             metric: metric,
             local_handler: None,
+            local_placement: Placement::Any,
+            local_weight: service::DEFAULT_WEIGHT,
+            local_metadata: service::Metadata::new(),
             links: Vec::new(),
+            hops: HashMap::default(),
+            weights: HashMap::default(),
+            metadata: HashMap::default(),
+            suspects: HashMap::default(),
             local_inbound_counter: None,
             local_outbound_counter: None,
             remote_outbound_counters: HashMap::default(),
         }
     }
 
-    fn add_local_link(&mut self, local_handler: Arc<Box<Service>>) {
+    fn add_local_link(&mut self,
+                      local_handler: Arc<Box<Service>>,
+                      placement: Placement,
+                      weight: u32,
+                      metadata: service::Metadata) {
         self.local_handler = Some(local_handler);
-        self.local_inbound_counter = Some(self.metric.counter(&format!("service.{}.inbound.\
-                                                                        local.selected",
-                                                                       self.name)));
-        self.local_outbound_counter = Some(self.metric
-                                               .counter(&format!("service.{}.outbound.local.\
-                                                                  selected",
-                                                                 self.name)));
+        self.local_placement = placement;
+        self.local_weight = weight;
+        self.local_metadata = metadata;
+        self.local_inbound_counter = Some(Arc::new(self.metric
+                                                       .counter(&format!("service.{}.inbound.\
+                                                                          local.selected",
+                                                                         self.name))));
+        self.local_outbound_counter = Some(Arc::new(self.metric
+                                                        .counter(&format!("service.{}.\
+                                                                           outbound.local.\
+                                                                           selected",
+                                                                          self.name))));
 
         self.links.push(Link::Local);
-        self.balancer.set_links(&self.links);
+        self.update_balancer();
     }
 
     fn remove_local_link(&mut self) {
         self.local_handler = None;
+        self.local_metadata = service::Metadata::new();
         self.local_inbound_counter = None;
         self.local_outbound_counter = None;
 
         self.links.retain(|link| !Link::is_local(link));
-        self.balancer.set_links(&self.links);
+        self.update_balancer();
     }
 
-    fn add_remote_link(&mut self, peer_node_id: ID) {
+    fn add_remote_link(&mut self,
+                       peer_node_id: ID,
+                       hops: u32,
+                       weight: u32,
+                       metadata: service::Metadata) {
         self.remote_outbound_counters.insert(peer_node_id,
-                                             self.metric
-                                                 .counter(&format!("service.{}.outbound.{}.\
-                                                                    selected",
-                                                                   self.name,
-                                                                   peer_node_id)));
+                                             Arc::new(self.metric
+                                                          .counter(&format!("service.{}.\
+                                                                             outbound.{}.\
+                                                                             selected",
+                                                                            self.name,
+                                                                            peer_node_id))));
 
         self.links.push(Link::Remote(peer_node_id));
-        self.balancer.set_links(&self.links);
+        self.hops.insert(peer_node_id, hops);
+        self.weights.insert(peer_node_id, weight);
+        self.metadata.insert(peer_node_id, metadata);
+        self.update_balancer();
     }
 
     fn remove_remote_link(&mut self, peer_node_id: &ID) {
         self.remote_outbound_counters.remove(peer_node_id);
+        self.hops.remove(peer_node_id);
+        self.weights.remove(peer_node_id);
+        self.metadata.remove(peer_node_id);
 
         self.links.retain(|link| !Link::is_remote(link, peer_node_id));
-        self.balancer.set_links(&self.links);
+        self.update_balancer();
     }
 
-    fn select_link(&mut self) -> Link {
-        let link = self.balancer.next().expect("balancer did not produce any link");
-        match link {
-            Link::Local => self.local_outbound_counter.as_ref().unwrap().increment(),
+    fn hops_of(&self, link: &Link) -> u32 {
+        match *link {
+            Link::Local => 0,
+            Link::Remote(ref peer_node_id) => self.hops.get(peer_node_id).cloned().unwrap_or(0),
+        }
+    }
+
+    fn weight_of(&self, link: &Link) -> u32 {
+        match *link {
+            Link::Local => self.local_weight,
             Link::Remote(ref peer_node_id) => {
-                self.remote_outbound_counters.get(peer_node_id).unwrap().increment()
+                self.weights.get(peer_node_id).cloned().unwrap_or(service::DEFAULT_WEIGHT)
             }
         }
-        link
     }
 
-    fn select_local_link(&self) -> Option<Arc<Box<Service>>> {
-        match self.local_handler {
-            Some(ref local_handler) => {
-                self.local_inbound_counter.as_ref().unwrap().increment();
-                Some(local_handler.clone())
-            }
-            None => None,
+    fn metadata_of(&self, link: &Link) -> Option<&service::Metadata> {
+        match *link {
+            Link::Local => Some(&self.local_metadata),
+            Link::Remote(ref peer_node_id) => self.metadata.get(peer_node_id),
         }
     }
 
+    fn has_tags(&self, link: &Link, tags: &service::Metadata) -> bool {
+        let metadata = self.metadata_of(link);
+        tags.iter().all(|(key, value)| {
+            metadata.and_then(|metadata| metadata.get(key)) == Some(value)
+        })
+    }
+
+    fn has_version(&self, link: &Link, requirement: &version::Requirement) -> bool {
+        self.metadata_of(link)
+            .and_then(|metadata| metadata.get(version::METADATA_KEY))
+            .and_then(|version| version::Version::parse(version))
+            .map(|version| requirement.matches(&version))
+            .unwrap_or(false)
+    }
+
+    fn min_hops_except(&self, peer_node_id: &ID) -> Option<u32> {
+        self.links
+            .iter()
+            .filter(|link| !Link::is_remote(link, peer_node_id))
+            .map(|link| self.hops_of(link))
+            .min()
+    }
+
+    fn update_balancer(&self) {
+        let min_hops = self.links.iter().map(|link| self.hops_of(link)).min();
+        let links = self.links
+                        .iter()
+                        .filter(|link| Some(self.hops_of(link)) == min_hops)
+                        .cloned()
+                        .collect::<Vec<_>>();
+        let weights = links.iter().map(|link| (*link, self.weight_of(link))).collect::<Vec<_>>();
+        let mut balancer = self.balancer.lock().unwrap();
+        balancer.set_links(&links);
+        balancer.set_weights(&weights);
+    }
+
     fn has_links(&self) -> bool {
         !self.links.is_empty()
     }
+
+    fn has_suspects(&self) -> bool {
+        !self.suspects.is_empty()
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.has_links() && !self.has_suspects()
+    }
 }
 
 impl From<direct::ConnectionMapError> for Error {
@@ -324,11 +991,30 @@ impl From<direct::ConnectionMapError> for Error {
 mod tests {
 
     use std::sync::Arc;
+    use time::Duration;
     use metric;
-    use node::ID;
-    use super::ServiceMap;
+    use node::{ID, Placement, request, service};
+    use super::{Error, ServiceMap};
+    use super::super::Link;
     use super::super::balancer::{self, Factory};
     use super::super::tracker::Statistic;
+    use util::clock::{self, Clock};
+
+    #[test]
+    fn usage_per_caller() {
+        let service_map = build_service_map();
+        let (first, second) = (ID::new_random(), ID::new_random());
+
+        service_map.record_usage("echo", first);
+        service_map.record_usage("time", first);
+        service_map.record_usage("echo", second);
+        service_map.record_usage("echo", second);
+
+        assert_eq!(vec![("echo".to_string(), second, 2),
+                        ("echo".to_string(), first, 1),
+                        ("time".to_string(), first, 1)],
+                   service_map.usage());
+    }
 
     #[test]
     fn insert_local() {
@@ -342,6 +1028,99 @@ mod tests {
         assert_eq!(vec!["test"], service_map.local_service_names());
     }
 
+    #[test]
+    fn get_on_link() {
+        let service_map = build_service_map();
+        let peer_node_id = ID::new_random();
+
+        assert!(service_map.insert_local("echo", Box::new(|request| Ok(request))).is_ok());
+        assert!(service_map.insert_remote("echo", peer_node_id).is_ok());
+
+        let (link, local_handler) = service_map.get_on("echo", &Link::Remote(peer_node_id))
+                                               .unwrap();
+        assert_eq!(Link::Remote(peer_node_id), link);
+        assert!(local_handler.is_some());
+        assert_eq!(Some(Link::Local),
+                   service_map.get_on("echo", &Link::Local).map(|(link, _)| link));
+        assert!(service_map.get_on("echo", &Link::Remote(ID::new_random())).is_none());
+        assert!(service_map.get_on("missing", &Link::Local).is_none());
+    }
+
+    #[test]
+    fn links_with_fewest_hops() {
+        let service_map = build_service_map();
+        let near = ID::new_random();
+        let far = ID::new_random();
+        service_map.insert_local("echo", Box::new(|request| Ok(request))).unwrap();
+        service_map.insert_routes(&[route("echo", 0)], near).unwrap();
+        service_map.insert_routes(&[route("echo", 1)], far).unwrap();
+
+        assert_eq!(vec![Link::Local, Link::Remote(near)], service_map.links("echo"));
+        assert!(service_map.links("missing").is_empty());
+    }
+
+    #[test]
+    fn get_tagged() {
+        let service_map = build_service_map();
+        let eu = ID::new_random();
+        let us = ID::new_random();
+        service_map.insert_local("echo", Box::new(|request| Ok(request))).unwrap();
+        service_map.insert_routes(&[tagged_route("echo", "region", "eu")], eu).unwrap();
+        service_map.insert_routes(&[tagged_route("echo", "region", "us")], us).unwrap();
+
+        let mut tags = service::Metadata::new();
+        tags.insert("region".to_string(), "eu".to_string());
+        assert_eq!(vec![Link::Remote(eu)], service_map.tagged_links("echo", &tags));
+        for _ in 0..4 {
+            assert_eq!(Link::Remote(eu), service_map.get_tagged("echo", &tags).unwrap().0);
+        }
+        assert_eq!(3, service_map.links("echo").len());
+
+        tags.insert("region".to_string(), "asia".to_string());
+        assert_eq!(Some(request::Error::NoService),
+                   service_map.get_tagged("echo", &tags).err());
+    }
+
+    #[test]
+    fn get_compatible_version() {
+        let service_map = build_service_map();
+        let old = ID::new_random();
+        let new = ID::new_random();
+        service_map.insert_routes(&[tagged_route("echo", "version", "1.1.0")], old).unwrap();
+        service_map.insert_routes(&[tagged_route("echo", "version", "1.2.3")], new).unwrap();
+
+        for _ in 0..4 {
+            assert_eq!(Link::Remote(new), service_map.get("echo@^1.2").unwrap().0);
+        }
+        assert_eq!(Some(request::Error::NoService),
+                   service_map.get("echo@^2").err());
+        assert!(service_map.get("echo@latest").is_err());
+    }
+
+    #[test]
+    fn insert_local_with_placement() {
+        let service_map = build_service_map();
+
+        assert!(service_map.insert_local("any", Box::new(|request| Ok(request))).is_ok());
+        assert!(service_map.insert_local_with_placement("zoned",
+                                                        Box::new(|request| Ok(request)),
+                                                        Placement::SameZone,
+                                                        2,
+                                                        service::Metadata::new())
+                           .is_ok());
+        assert!(service_map.insert_remote("remote", ID::new_random()).is_ok());
+
+        assert_eq!(Some(Placement::SameZone), service_map.local_placement("zoned"));
+        assert_eq!(None, service_map.local_placement("remote"));
+        assert_eq!(vec![("any".to_string(), 1, service::Metadata::new())],
+                   service_map.local_services_for(Some("a"), Some("b")));
+        let mut services = service_map.local_services_for(Some("a"), Some("a"));
+        services.sort();
+        assert_eq!(vec![("any".to_string(), 1, service::Metadata::new()),
+                        ("zoned".to_string(), 2, service::Metadata::new())],
+                   services);
+    }
+
     #[test]
     fn insert_remote() {
         let service_map = build_service_map();
@@ -423,11 +1202,212 @@ mod tests {
         assert_eq!(0, service_map.len());
     }
 
+    #[test]
+    fn suspect_all_remotes() {
+        let clock = Arc::new(clock::Mock::new());
+        let service_map = build(Some(Duration::milliseconds(50)), None, None, clock.clone());
+        let node_id = ID::new_random();
+        service_map.insert_remote("test", node_id).unwrap();
+
+        service_map.suspect_all_remotes(&node_id);
+
+        assert_eq!(1, service_map.len());
+        assert!(service_map.is_suspect("test"));
+        assert_eq!(Some(Duration::milliseconds(50)),
+                   service_map.remaining_grace_period("test"));
+        assert_eq!(Some(request::Error::NoService), service_map.get("test").err());
+
+        clock.advance(Duration::milliseconds(20));
+        assert_eq!(Some(Duration::milliseconds(30)),
+                   service_map.remaining_grace_period("test"));
+
+        clock.advance(Duration::milliseconds(30));
+
+        assert!(!service_map.is_suspect("test"));
+        assert_eq!(0, service_map.len());
+    }
+
+    #[test]
+    fn suspect_all_remotes_and_restore() {
+        let service_map = build_service_map_with_grace_period(Some(Duration::milliseconds(50)));
+        let node_id = ID::new_random();
+        service_map.insert_remote("test", node_id).unwrap();
+        service_map.suspect_all_remotes(&node_id);
+
+        service_map.insert_remotes(&["test".to_string()], node_id).unwrap();
+
+        assert!(!service_map.is_suspect("test"));
+        assert!(service_map.get("test").is_ok());
+    }
+
+    #[test]
+    fn suspect_all_remotes_without_grace_period() {
+        let service_map = build_service_map();
+        let node_id = ID::new_random();
+        service_map.insert_remote("test", node_id).unwrap();
+
+        service_map.suspect_all_remotes(&node_id);
+
+        assert!(!service_map.is_suspect("test"));
+        assert_eq!(0, service_map.len());
+    }
+
+    #[test]
+    fn get_without_changing_the_epoch() {
+        let service_map = build_service_map();
+        let epoch = service_map.epoch();
+        service_map.insert_local("test", Box::new(|request| Ok(request))).unwrap();
+        assert!(service_map.epoch() > epoch);
+
+        let epoch = service_map.epoch();
+        assert!(service_map.get("test").is_ok());
+        assert!(service_map.get_local("test").is_some());
+        assert!(service_map.get("unknown").is_err());
+        assert_eq!(epoch, service_map.epoch());
+    }
+
+    #[test]
+    fn get_after_remove() {
+        let service_map = build_service_map();
+        let node_id = ID::new_random();
+        service_map.insert_remote("test", node_id).unwrap();
+        assert_eq!(request::Error::NoService,
+                   service_map.get("other").err().unwrap());
+        assert!(service_map.get("test").is_ok());
+
+        service_map.remove_remote("test", &node_id).unwrap();
+
+        assert_eq!(Some(request::Error::NoService), service_map.get("test").err());
+    }
+
+    #[test]
+    fn insert_remotes_with_invalid_name() {
+        let service_map = build_service_map_with_limits(None, Some(8));
+        let node_id = ID::new_random();
+        assert!(service_map.insert_remotes(&["test one".to_string()], node_id).is_ok());
+
+        service_map.set_strict_names(true);
+        match service_map.insert_remotes(&["test service".to_string()], node_id) {
+            Err(Error::InvalidServiceName(ref name)) if name == "test service" => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match service_map.insert_remotes(&["test-service".to_string()], node_id) {
+            Err(Error::InvalidServiceName(ref name)) if name == "test-service" => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert!(service_map.insert_remotes(&["test.one".to_string()], node_id).is_ok());
+
+        assert_eq!(2, service_map.len());
+    }
+
+    #[test]
+    fn insert_remotes_with_too_many_services() {
+        let service_map = build_service_map_with_limits(Some(2), None);
+        let node_id = ID::new_random();
+        service_map.insert_remotes(&["one".to_string(), "two".to_string()], node_id).unwrap();
+
+        match service_map.insert_remotes(&["three".to_string()], node_id) {
+            Err(Error::TooManyServices(ref id, 3)) if *id == node_id => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert!(service_map.insert_remotes(&["two".to_string()], node_id).is_ok());
+        assert!(service_map.insert_remotes(&["three".to_string()], ID::new_random()).is_ok());
+
+        assert_eq!(3, service_map.len());
+    }
+
+    #[test]
+    fn insert_routes_prefers_fewest_hops() {
+        let service_map = build_service_map();
+        let near = ID::new_random();
+        let far = ID::new_random();
+        service_map.insert_routes(&[route("test", 2)], far).unwrap();
+        service_map.insert_routes(&[route("test", 1)], near).unwrap();
+
+        for _ in 0..4 {
+            assert_eq!(Link::Remote(near), service_map.get("test").unwrap().0);
+        }
+
+        service_map.insert_routes(&[route("test", 0)], far).unwrap();
+        assert_eq!(Link::Remote(far), service_map.get("test").unwrap().0);
+        assert_eq!(vec!["test"], service_map.remote_service_names(&far));
+    }
+
+    #[test]
+    fn routes_for_peer() {
+        let service_map = build_service_map();
+        let one = ID::new_random();
+        let two = ID::new_random();
+        service_map.insert_local("local", Box::new(|request| Ok(request))).unwrap();
+        service_map.insert_routes(&[route("near", 0), route("far", 1)], one).unwrap();
+
+        let names = vec!["local".to_string(),
+                         "near".to_string(),
+                         "far".to_string(),
+                         "gone".to_string()];
+        assert_eq!((vec![("near".to_string(), 1)], vec!["far".to_string(), "gone".to_string()]),
+                   service_map.routes_for(&names, &two, 1));
+        assert_eq!((vec![("near".to_string(), 1), ("far".to_string(), 2)],
+                    vec!["gone".to_string()]),
+                   service_map.routes_for(&names, &two, 2));
+        assert_eq!((Vec::<(String, u32)>::new(),
+                    vec!["near".to_string(), "far".to_string(), "gone".to_string()]),
+                   service_map.routes_for(&names, &one, 2));
+    }
+
+    #[test]
+    fn next_hop_avoids_origin() {
+        let service_map = build_service_map();
+        let one = ID::new_random();
+        let two = ID::new_random();
+        service_map.insert_routes(&[route("test", 1)], one).unwrap();
+
+        assert_eq!(Some(one), service_map.next_hop("test", &two));
+        assert_eq!(None, service_map.next_hop("test", &one));
+        assert_eq!(None, service_map.next_hop("missing", &two));
+    }
+
+    fn route(name: &str, hops: u32) -> (String, u32, u32, service::Metadata) {
+        (name.to_string(), hops, 1, service::Metadata::new())
+    }
+
+    fn tagged_route(name: &str, key: &str, value: &str) -> (String, u32, u32, service::Metadata) {
+        let (name, hops, weight, mut metadata) = route(name, 0);
+        metadata.insert(key.to_string(), value.to_string());
+        (name, hops, weight, metadata)
+    }
+
     fn build_service_map() -> ServiceMap {
+        build_service_map_with_grace_period(None)
+    }
+
+    fn build_service_map_with_grace_period(grace_period: Option<Duration>) -> ServiceMap {
+        build(grace_period, None, None, Arc::new(clock::System))
+    }
+
+    fn build_service_map_with_limits(max_services_per_peer: Option<usize>,
+                                     max_service_name_length: Option<usize>)
+                                     -> ServiceMap {
+        build(None,
+              max_services_per_peer,
+              max_service_name_length,
+              Arc::new(clock::System))
+    }
+
+    fn build(grace_period: Option<Duration>,
+             max_services_per_peer: Option<usize>,
+             max_service_name_length: Option<usize>,
+             clock: Arc<Clock>)
+             -> ServiceMap {
         let mut balancer_factory = Box::new(balancer::DynamicRoundRobinFactory::new());
         balancer_factory.set_statistic(Arc::new(Statistic::new()));
         let metric = Arc::new(metric::Memory::new());
-        ServiceMap::new(balancer_factory, metric)
+        ServiceMap::with_clock(balancer_factory,
+                               metric,
+                               grace_period,
+                               max_services_per_peer,
+                               max_service_name_length,
+                               clock)
     }
 
 }
@@ -15,26 +15,50 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, RwLock};
-use time::{self, Duration};
+use time::{Duration, SteadyTime};
 
 use transport::direct::Link;
+use util::clock::{self, Clock};
 use super::{Subject, store};
 
 const MAXIMAL_SIZE: usize = 20;
 
 pub struct Statistic {
+    clock: Arc<Clock>,
     query: RwLock<Option<Arc<store::Query>>>,
     entries: RwLock<HashMap<Subject, VecDeque<Duration>>>,
+    ejection: RwLock<Option<(usize, Duration)>>,
+    failures: RwLock<HashMap<Subject, Failures>>,
+}
+
+struct Failures {
+    consecutive: usize,
+    ejected_until: Option<SteadyTime>,
 }
 
 impl Statistic {
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(clock::System))
+    }
+
+    pub fn with_clock(clock: Arc<Clock>) -> Self {
         Statistic {
+            clock: clock,
             query: RwLock::new(None),
             entries: RwLock::new(HashMap::new()),
+            ejection: RwLock::new(None),
+            failures: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Enables the ejection of remote links after the given number of consecutive failures.
+    /// An ejected link is left out of the rotation for the given duration, then a single request
+    /// is let through as a probe. A successful probe readmits the link, otherwise it stays
+    /// ejected for another period.
+    pub fn set_ejection(&self, consecutive_failures: usize, duration: Duration) {
+        *self.ejection.write().unwrap() = Some((consecutive_failures, duration));
+    }
+
     pub fn assign_query(&self, query: Arc<store::Query>) {
         *self.query.write().unwrap() = Some(query);
     }
@@ -51,6 +75,58 @@ impl Statistic {
             durations.pop_front();
         }
         durations.push_back(duration);
+
+        self.failures.write().unwrap().remove(&subject);
+    }
+
+    /// Counts a failed request, e.g. a timeout or a service error.
+    pub fn push_failure(&self, subject: Subject) {
+        let (threshold, duration) = match *self.ejection.read().unwrap() {
+            Some(ejection) => ejection,
+            None => return,
+        };
+        if let Subject::Local(_) = subject {
+            return;
+        }
+
+        let mut failures = self.failures.write().unwrap();
+        let entry = failures.entry(subject.clone()).or_insert(Failures {
+            consecutive: 0,
+            ejected_until: None,
+        });
+        entry.consecutive += 1;
+        if entry.consecutive >= threshold && entry.ejected_until.is_none() {
+            if let Subject::Remote(ref name, ref peer_node_id) = subject {
+                warn!("ejected {} of service {} after {} consecutive failures",
+                      peer_node_id.short(),
+                      name,
+                      entry.consecutive);
+            }
+            entry.ejected_until = Some(self.clock.now() + duration);
+        }
+    }
+
+    /// Returns true if the link is ejected. Once the ejection period is over, a single call
+    /// returns false to let a probe through and starts the next period.
+    pub fn is_ejected(&self, name: &str, link: &Link) -> bool {
+        let duration = match *self.ejection.read().unwrap() {
+            Some((_, duration)) => duration,
+            None => return false,
+        };
+        let subject = Subject::from_name_and_link(name, link);
+        let mut failures = self.failures.write().unwrap();
+        let ejected_until = match failures.get_mut(&subject) {
+            Some(&mut Failures { ejected_until: Some(ref mut ejected_until), .. }) => {
+                ejected_until
+            }
+            _ => return false,
+        };
+        let now = self.clock.now();
+        if now < *ejected_until {
+            return true;
+        }
+        *ejected_until = now + duration;
+        false
     }
 
     pub fn average(&self, name: &str, link: &Link) -> Duration {
@@ -67,7 +143,7 @@ impl Statistic {
         if let Some(ref query) = *self.query.read().unwrap() {
             let times = query.started_ats_with_subject(&subject);
 
-            let now = time::now_utc();
+            let now = self.clock.now();
             sum = sum +
                   times.iter().fold(Duration::zero(),
                                     |sum, &started_at| sum + (now - started_at));
@@ -83,11 +159,35 @@ mod tests {
 
     use std::thread;
     use std::sync::Arc;
-    use time::{self, Duration};
+    use time::{Duration, SteadyTime};
+    use node::ID;
+    use util::clock::Mock;
     use super::Statistic;
     use super::super::{Subject, Store};
     use super::super::super::Link;
 
+    #[test]
+    fn eject_and_probe() {
+        let clock = Arc::new(Mock::new());
+        let statistic = Statistic::with_clock(clock.clone());
+        statistic.set_ejection(2, Duration::seconds(10));
+        let peer_node_id = ID::new_random();
+        let link = Link::Remote(peer_node_id);
+
+        statistic.push_failure(Subject::remote("test", peer_node_id));
+        assert!(!statistic.is_ejected("test", &link));
+        statistic.push_failure(Subject::remote("test", peer_node_id));
+        assert!(statistic.is_ejected("test", &link));
+
+        clock.advance(Duration::seconds(10));
+        assert!(!statistic.is_ejected("test", &link));
+        assert!(statistic.is_ejected("test", &link));
+
+        statistic.push(Subject::remote("test", peer_node_id), Duration::milliseconds(10));
+        assert!(!statistic.is_ejected("test", &link));
+        assert!(!statistic.is_ejected("test", &Link::Local));
+    }
+
     #[test]
     fn add() {
         let statistic = Statistic::new();
@@ -121,7 +221,7 @@ mod tests {
 
         statistic.push(subject.clone(), Duration::milliseconds(1000));
 
-        store.insert(10, subject.clone(), time::now_utc(), "test entry")
+        store.insert(10, subject.clone(), SteadyTime::now(), "test entry")
              .unwrap();
         thread::sleep(::std::time::Duration::from_millis(10));
 
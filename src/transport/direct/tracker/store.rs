@@ -20,14 +20,14 @@ use std::sync::RwLock;
 use node::ID;
 use super::Subject;
 
-use time;
+use time::SteadyTime;
 
 pub struct Store<T> {
-    entries: RwLock<HashMap<u32, (Subject, time::Tm, T)>>,
+    entries: RwLock<HashMap<u64, (Subject, SteadyTime, T)>>,
 }
 
 pub trait Query : Send + Sync {
-    fn started_ats_with_subject(&self, &Subject) -> Vec<time::Tm>;
+    fn started_ats_with_subject(&self, &Subject) -> Vec<SteadyTime>;
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -44,9 +44,9 @@ impl<T> Store<T> {
     }
 
     pub fn insert(&self,
-                  id: u32,
+                  id: u64,
                   subject: Subject,
-                  started_at: time::Tm,
+                  started_at: SteadyTime,
                   entry: T)
                   -> Result<bool> {
 
@@ -58,14 +58,32 @@ impl<T> Store<T> {
         Ok(entries.len() == 1)
     }
 
-    pub fn get_mut<F: FnMut(&mut T)>(&self, id: &u32, mut f: F) {
+    pub fn insert_with_free_id<F>(&self,
+                                  mut next_id: F,
+                                  subject: Subject,
+                                  started_at: SteadyTime,
+                                  entry: T)
+                                  -> (u64, bool)
+        where F: FnMut() -> u64
+    {
+        let mut entries = self.entries.write().unwrap();
+        let mut id = next_id();
+        while entries.contains_key(&id) {
+            warn!("request id {} is still in use - skipping it", id);
+            id = next_id();
+        }
+        entries.insert(id, (subject, started_at, entry));
+        (id, entries.len() == 1)
+    }
+
+    pub fn get_mut<F: FnMut(&mut T)>(&self, id: &u64, mut f: F) {
         let mut entries = self.entries.write().unwrap();
         if let Some(ref mut entry) = entries.get_mut(id).map(|value| &mut value.2) {
             f(entry);
         }
     }
 
-    pub fn remove(&self, id: &u32) -> Result<(Subject, time::Tm, T)> {
+    pub fn remove(&self, id: &u64) -> Result<(Subject, SteadyTime, T)> {
         let mut entries = self.entries.write().unwrap();
         if !entries.contains_key(&id) {
             return Err(Error::IdDoesNotExists);
@@ -97,8 +115,8 @@ impl<T> Store<T> {
     }
 
     pub fn remove_all_started_before(&self,
-                                     threshold: time::Tm)
-                                     -> (Vec<(u32, T)>, Option<time::Tm>) {
+                                     threshold: SteadyTime)
+                                     -> (Vec<(u64, Subject, T)>, Option<SteadyTime>) {
 
         let mut entries = self.entries.write().unwrap();
 
@@ -118,8 +136,8 @@ impl<T> Store<T> {
 
         let mut result = Vec::new();
         for id in to_remove {
-            let (_, _, entry) = entries.remove(&id).unwrap();
-            result.push((id, entry));
+            let (subject, _, entry) = entries.remove(&id).unwrap();
+            result.push((id, subject, entry));
         }
         (result, next_at)
     }
@@ -131,7 +149,7 @@ impl<T> Store<T> {
 
 impl<T> Query for Store<T> where T: Send + Sync
 {
-    fn started_ats_with_subject(&self, subject: &Subject) -> Vec<time::Tm> {
+    fn started_ats_with_subject(&self, subject: &Subject) -> Vec<SteadyTime> {
         let entries = self.entries.read().unwrap();
         entries.iter()
                .filter_map(|(_, &(ref entry_subject, ref started_at, _))| {
@@ -141,43 +159,64 @@ impl<T> Query for Store<T> where T: Send + Sync
                        None
                    }
                })
-               .collect::<Vec<time::Tm>>()
+               .collect::<Vec<SteadyTime>>()
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use time;
+    use time::{Duration, SteadyTime};
     use node::ID;
     use super::{Error, Store};
     use super::super::Subject;
     use super::super::store::Query;
 
+    #[test]
+    fn insert_with_free_id() {
+        let base = SteadyTime::now();
+        let store = Store::new();
+        store.insert(1, Subject::local("test"), build_time(base, 100), "test entry").unwrap();
+
+        let mut ids = vec![0, 1, 2].into_iter();
+        assert_eq!((0, false),
+                   store.insert_with_free_id(|| ids.next().unwrap(),
+                                             Subject::local("test"),
+                                             build_time(base, 100),
+                                             "test entry"));
+        assert_eq!((2, false),
+                   store.insert_with_free_id(|| ids.next().unwrap(),
+                                             Subject::local("test"),
+                                             build_time(base, 100),
+                                             "test entry"));
+    }
+
     #[test]
     fn insert() {
+        let base = SteadyTime::now();
         let store = Store::new();
 
-        let started_at = build_time(100);
+        let started_at = build_time(base, 100);
         store.insert(0, Subject::local("test"), started_at, "test entry")
              .unwrap();
 
         assert_eq!(1, store.len());
 
         assert_eq!(Err(Error::IdAlreadyExists),
-                   store.insert(0, Subject::local("test"), time::now_utc(), "test entry"));
+                   store.insert(0, Subject::local("test"), SteadyTime::now(), "test entry"));
     }
 
     #[test]
     fn remove() {
+        let base = SteadyTime::now();
         let store = Store::new();
-        store.insert(0, Subject::local("test"), build_time(100), "test entry")
+        store.insert(0, Subject::local("test"), build_time(base, 100), "test entry")
              .unwrap();
 
         let (removed_subject, removed_started_at, removed_entry) = store.remove(&0).unwrap();
 
         assert_eq!(Subject::local("test"), removed_subject);
-        assert_eq!(build_time(100), removed_started_at);
+        assert_eq!(build_time(base, 100), removed_started_at);
         assert_eq!("test entry", removed_entry);
         assert_eq!(0, store.len());
         assert_eq!(Some(Error::IdDoesNotExists), store.remove(&0).err());
@@ -185,11 +224,12 @@ mod tests {
 
     #[test]
     fn remove_all_from_remote() {
+        let base = SteadyTime::now();
         let store = Store::new();
         let id = ID::new_random();
-        store.insert(0, Subject::remote("one", id), build_time(100), "test entry")
+        store.insert(0, Subject::remote("one", id), build_time(base, 100), "test entry")
              .unwrap();
-        store.insert(1, Subject::remote("two", id), build_time(100), "test entry")
+        store.insert(1, Subject::remote("two", id), build_time(base, 100), "test entry")
              .unwrap();
 
         let removed_entries = store.remove_all_from_remote(&id);
@@ -202,22 +242,23 @@ mod tests {
 
     #[test]
     fn remove_all_started_before() {
+        let base = SteadyTime::now();
         let store = Store::new();
-        store.insert(10, Subject::local("test"), build_time(200), "test entry")
+        store.insert(10, Subject::local("test"), build_time(base, 200), "test entry")
              .unwrap();
-        store.insert(20, Subject::local("test"), build_time(100), "test entry")
+        store.insert(20, Subject::local("test"), build_time(base, 100), "test entry")
              .unwrap();
 
-        let (removed, next_at) = store.remove_all_started_before(build_time(150));
+        let (removed, next_at) = store.remove_all_started_before(build_time(base, 150));
         assert_eq!(1, removed.len());
         assert_eq!(20, removed[0].0);
-        assert_eq!(Some(build_time(200)), next_at);
+        assert_eq!(Some(build_time(base, 200)), next_at);
 
-        let (removed, next_at) = store.remove_all_started_before(build_time(150));
+        let (removed, next_at) = store.remove_all_started_before(build_time(base, 150));
         assert_eq!(0, removed.len());
-        assert_eq!(Some(build_time(200)), next_at);
+        assert_eq!(Some(build_time(base, 200)), next_at);
 
-        let (removed, next_at) = store.remove_all_started_before(build_time(250));
+        let (removed, next_at) = store.remove_all_started_before(build_time(base, 250));
         assert_eq!(1, removed.len());
         assert_eq!(10, removed[0].0);
         assert_eq!(None, next_at);
@@ -227,19 +268,20 @@ mod tests {
 
     #[test]
     fn started_ats_with_subject() {
+        let base = SteadyTime::now();
         let store = Store::new();
-        store.insert(10, Subject::local("one"), build_time(200), "test entry")
+        store.insert(10, Subject::local("one"), build_time(base, 200), "test entry")
              .unwrap();
-        store.insert(20, Subject::local("two"), build_time(100), "test entry")
+        store.insert(20, Subject::local("two"), build_time(base, 100), "test entry")
              .unwrap();
 
         let started_ats = store.started_ats_with_subject(&Subject::local("two"));
         assert_eq!(1, started_ats.len());
-        assert_eq!(build_time(100), started_ats[0]);
+        assert_eq!(build_time(base, 100), started_ats[0]);
     }
 
-    fn build_time(seconds: i64) -> time::Tm {
-        time::at(time::Timespec::new(seconds, 0))
+    fn build_time(base: SteadyTime, seconds: i64) -> SteadyTime {
+        base + Duration::seconds(seconds)
     }
 
 }
@@ -15,21 +15,27 @@
 
 use std::io;
 use std::result;
-use std::sync::{Arc, Mutex, atomic, mpsc};
+use std::sync::{Arc, Mutex, mpsc};
 use std::thread;
+use std::{u32, u64};
 
-use time::{self, Duration};
+use time::Duration;
 
 use node::{ID, request};
 use transport::direct::Link;
 use transport::direct::tracker::{Statistic, Store, Subject};
+use util::clock::{self, Clock};
+use util::thread::supervisor;
 
 const TIMEOUT_TOLERANCE_MS: i64 = 2;
 
 pub struct Tracker<P, R> {
     store: Arc<Store<(P, Mutex<mpsc::Sender<Result<R>>>)>>,
     statistic: Arc<Statistic>,
-    current_id: atomic::AtomicUsize,
+    clock: Arc<Clock>,
+    timeout: Option<Duration>,
+    next_id: Mutex<u64>,
+    max_id: u64,
     join_handle_and_running_tx: Option<(thread::JoinHandle<()>, Mutex<mpsc::Sender<bool>>)>,
 }
 
@@ -46,20 +52,38 @@ impl<P, R> Tracker<P, R>
           R: Send + 'static
 {
     pub fn new(statistic: Arc<Statistic>, timeout: Option<Duration>) -> Self {
+        Self::with_wide_ids(statistic, timeout, false)
+    }
+
+    pub fn with_wide_ids(statistic: Arc<Statistic>,
+                         timeout: Option<Duration>,
+                         wide_ids: bool)
+                         -> Self {
+        Self::with_clock(statistic, timeout, wide_ids, Arc::new(clock::System))
+    }
+
+    pub fn with_clock(statistic: Arc<Statistic>,
+                      timeout: Option<Duration>,
+                      wide_ids: bool,
+                      clock: Arc<Clock>)
+                      -> Self {
         let store: Arc<Store<(P, Mutex<mpsc::Sender<Result<R>>>)>> = Arc::new(Store::new());
         statistic.assign_query(store.clone());
 
         let store_clone = store.clone();
+        let statistic_clone = statistic.clone();
+        let clock_clone = clock.clone();
         let join_handle_and_running_tx = timeout.map(|timeout| {
             let (running_tx, running_rx) = mpsc::channel();
-            (thread::spawn(move || {
+            (supervisor().spawn("request timeout", move || {
                 while running_rx.recv().unwrap() {
                     loop {
-                        let now = time::now_utc();
+                        let now = clock_clone.now();
 
                         let (removed, next_at) = store_clone.remove_all_started_before(now -
                                                                                        timeout);
-                        for (_, (_, result_tx)) in removed {
+                        for (_, subject, (_, result_tx)) in removed {
+                            statistic_clone.push_failure(subject);
                             result_tx.lock().unwrap().send(Err(Error::Timeout)).unwrap();
                         }
 
@@ -68,7 +92,7 @@ impl<P, R> Tracker<P, R>
                         }
                         let wait_for = next_at.unwrap() - (now - timeout) +
                                        Duration::milliseconds(TIMEOUT_TOLERANCE_MS);
-                        thread::sleep(::std::time::Duration::from_millis(wait_for.num_milliseconds() as u64));
+                        clock_clone.sleep(wait_for);
                     }
                 }
             }),
@@ -78,20 +102,33 @@ impl<P, R> Tracker<P, R>
         Tracker {
             store: store,
             statistic: statistic,
-            current_id: atomic::AtomicUsize::new(0),
+            clock: clock,
+            timeout: timeout,
+            next_id: Mutex::new(0),
+            max_id: if wide_ids {
+                u64::MAX
+            } else {
+                u32::MAX as u64
+            },
             join_handle_and_running_tx: join_handle_and_running_tx,
         }
     }
 
-    pub fn begin(&self, name: &str, link: &Link, payload: P) -> (u32, mpsc::Receiver<Result<R>>) {
+    pub fn begin(&self, name: &str, link: &Link, payload: P) -> (u64, mpsc::Receiver<Result<R>>) {
         let (result_tx, result_rx) = mpsc::channel();
-        let id = self.current_id.fetch_add(1, atomic::Ordering::SeqCst) as u32;
         let subject = Subject::from_name_and_link(name, link);
-        let started_at = time::now_utc();
+        let started_at = self.clock.now();
+
+        let (id, is_first) = {
+            let mut next_id = self.next_id.lock().unwrap();
+            let max_id = self.max_id;
+            self.store.insert_with_free_id(|| advance(&mut next_id, max_id),
+                                           subject,
+                                           started_at,
+                                           (payload, Mutex::new(result_tx)))
+        };
 
-        if self.store
-               .insert(id, subject, started_at, (payload, Mutex::new(result_tx)))
-               .unwrap() {
+        if is_first {
             if let Some((_, ref running_tx)) = self.join_handle_and_running_tx {
                 running_tx.lock().unwrap().send(true).unwrap();
             }
@@ -110,7 +147,20 @@ impl<P, R> Tracker<P, R>
         }
     }
 
-    pub fn end<F>(&self, id: u32, f: F) -> bool
+    pub fn end<F>(&self, id: u64, f: F) -> bool
+        where F: FnOnce(P) -> R
+    {
+        self.finish(id, false, f)
+    }
+
+    /// Ends the request like `end`, but counts it as a failure of the link it was sent to.
+    pub fn end_failed<F>(&self, id: u64, f: F) -> bool
+        where F: FnOnce(P) -> R
+    {
+        self.finish(id, true, f)
+    }
+
+    fn finish<F>(&self, id: u64, failed: bool, f: F) -> bool
         where F: FnOnce(P) -> R
     {
         let (subject, started_at, (payload, result_tx)) = match self.store.remove(&id) {
@@ -121,11 +171,23 @@ impl<P, R> Tracker<P, R>
         // ignore error cause receiver could be gone already (request timed out before)
         let _ = result_tx.lock().unwrap().send(Ok(f(payload)));
 
-        self.statistic.push(subject, time::now_utc() - started_at);
+        if failed {
+            self.statistic.push_failure(subject);
+        } else {
+            self.statistic.push(subject, self.clock.now() - started_at);
+        }
 
         true
     }
 
+    pub fn has_timeout(&self) -> bool {
+        self.join_handle_and_running_tx.is_some()
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     pub fn len(&self) -> usize {
         self.store.len()
     }
@@ -140,6 +202,16 @@ impl<P, R> Drop for Tracker<P, R> {
     }
 }
 
+fn advance(next_id: &mut u64, max_id: u64) -> u64 {
+    let id = *next_id;
+    *next_id = if id >= max_id {
+        0
+    } else {
+        id + 1
+    };
+    id
+}
+
 impl From<Error> for request::Error {
     fn from(error: Error) -> Self {
         match error {
@@ -157,9 +229,11 @@ mod tests {
 
     use std::thread;
     use std::sync::Arc;
+    use std::{u32, u64};
     use time::Duration;
     use node::ID;
-    use super::{Error, Tracker};
+    use util::clock::Mock;
+    use super::{Error, Tracker, advance};
     use super::super::Statistic;
     use super::super::super::Link;
 
@@ -202,6 +276,18 @@ mod tests {
         assert_eq!(0, tracker.len());
     }
 
+    #[test]
+    fn request_id_wrap_around() {
+        let mut next_id = u32::MAX as u64;
+        assert_eq!(u32::MAX as u64, advance(&mut next_id, u32::MAX as u64));
+        assert_eq!(0, advance(&mut next_id, u32::MAX as u64));
+        assert_eq!(1, next_id);
+
+        let mut next_id = u32::MAX as u64;
+        advance(&mut next_id, u64::MAX);
+        assert_eq!(u32::MAX as u64 + 1, next_id);
+    }
+
     #[test]
     fn request_cancel() {
         let tracker: Tracker<&'static str, &'static str> = Tracker::new(Arc::new(Statistic::new()),
@@ -241,6 +327,25 @@ mod tests {
         assert_eq!(0, tracker.len());
     }
 
+    #[test]
+    fn request_timeout_on_mock_clock() {
+        let clock = Arc::new(Mock::new());
+        let tracker = Tracker::with_clock(Arc::new(Statistic::new()),
+                                          Some(Duration::milliseconds(50)),
+                                          false,
+                                          clock.clone());
+
+        let (_, result_rx) = tracker.begin("test", &Link::Local, "test payload");
+
+        clock.wait_for_sleepers(1);
+        assert!(result_rx.try_recv().is_err());
+
+        clock.advance(Duration::milliseconds(60));
+
+        assert_eq!(Err(Error::Timeout), result_rx.recv().unwrap());
+        assert_eq!(0, tracker.len());
+    }
+
     #[test]
     fn request_end_after_timeout() {
         let tracker = Tracker::new(Arc::new(Statistic::new()), Some(Duration::milliseconds(50)));
@@ -0,0 +1,219 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::net;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, mpsc};
+
+use super::container::Container;
+use super::super::cipher;
+use util::thread::supervisor;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Priority {
+    /// Acknowledges, credits, heartbeats and service announcements.
+    Control,
+    /// Requests, responses and their packets.
+    Data,
+}
+
+/// Writes the outbound frames of a connection on a dedicated thread. Queued control frames are
+/// written before queued data frames, so they don't wait behind large request or response
+/// bodies.
+pub struct Writer {
+    stream: Arc<Mutex<cipher::Stream<net::TcpStream>>>,
+    socket: net::TcpStream,
+    queue: Arc<Queue>,
+}
+
+struct Queue {
+    jobs: Mutex<Jobs>,
+    available: Condvar,
+}
+
+struct Jobs {
+    control: VecDeque<Job>,
+    data: VecDeque<Job>,
+    closed: bool,
+}
+
+struct Job {
+    frame: Frame,
+    flush: bool,
+    result_tx: mpsc::Sender<io::Result<()>>,
+}
+
+enum Frame {
+    Container(Container),
+    Bytes(Vec<u8>),
+    Flush,
+}
+
+impl Writer {
+    pub fn new(stream: cipher::Stream<net::TcpStream>) -> io::Result<Writer> {
+        let socket = try!(stream.get_ref().try_clone());
+        let stream = Arc::new(Mutex::new(stream));
+        let queue = Arc::new(Queue::new());
+
+        let stream_clone = stream.clone();
+        let queue_clone = queue.clone();
+        supervisor().spawn("connection writer", move || {
+            while let Some(job) = queue_clone.pop() {
+                let mut stream = stream_clone.lock().unwrap();
+                let result = match job.frame {
+                    Frame::Container(ref container) => container.write(&mut *stream).map(|_| ()),
+                    Frame::Bytes(ref bytes) => stream.write_all(bytes),
+                    Frame::Flush => Ok(()),
+                };
+                let result = result.and_then(|_| {
+                    if job.flush {
+                        stream.flush()
+                    } else {
+                        Ok(())
+                    }
+                });
+                // the sender might not wait for the result.
+                let _ = job.result_tx.send(result);
+            }
+        });
+
+        Ok(Writer {
+            stream: stream,
+            socket: socket,
+            queue: queue,
+        })
+    }
+
+    /// Gives direct access to the stream, e.g. for the handshake or to read its counters.
+    pub fn stream(&self) -> MutexGuard<cipher::Stream<net::TcpStream>> {
+        self.stream.lock().unwrap()
+    }
+
+    pub fn socket(&self) -> &net::TcpStream {
+        &self.socket
+    }
+
+    /// Writes the container and waits until it's written.
+    pub fn send(&self, priority: Priority, container: Container, flush: bool) -> io::Result<()> {
+        wait(self.queue.push(priority, Frame::Container(container), flush))
+    }
+
+    /// Queues the container without waiting. Write errors show up at the reading side of the
+    /// connection.
+    pub fn post(&self, priority: Priority, container: Container, flush: bool) {
+        self.queue.push(priority, Frame::Container(container), flush);
+    }
+
+    pub fn send_bytes(&self, bytes: &[u8], flush: bool) -> io::Result<()> {
+        wait(self.queue.push(Priority::Data, Frame::Bytes(bytes.to_vec()), flush))
+    }
+
+    pub fn flush(&self) -> io::Result<()> {
+        wait(self.queue.push(Priority::Data, Frame::Flush, true))
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        self.queue.close();
+    }
+}
+
+impl Queue {
+    fn new() -> Queue {
+        Queue {
+            jobs: Mutex::new(Jobs {
+                control: VecDeque::new(),
+                data: VecDeque::new(),
+                closed: false,
+            }),
+            available: Condvar::new(),
+        }
+    }
+
+    fn push(&self,
+            priority: Priority,
+            frame: Frame,
+            flush: bool)
+            -> mpsc::Receiver<io::Result<()>> {
+        let (result_tx, result_rx) = mpsc::channel();
+        let job = Job {
+            frame: frame,
+            flush: flush,
+            result_tx: result_tx,
+        };
+        let mut jobs = self.jobs.lock().unwrap();
+        match priority {
+            Priority::Control => jobs.control.push_back(job),
+            Priority::Data => jobs.data.push_back(job),
+        }
+        self.available.notify_one();
+        result_rx
+    }
+
+    /// Returns the next job or `None` if the queue has been closed and is empty.
+    fn pop(&self) -> Option<Job> {
+        let mut jobs = self.jobs.lock().unwrap();
+        loop {
+            if let Some(job) = jobs.control.pop_front() {
+                return Some(job);
+            }
+            if let Some(job) = jobs.data.pop_front() {
+                return Some(job);
+            }
+            if jobs.closed {
+                return None;
+            }
+            jobs = self.available.wait(jobs).unwrap();
+        }
+    }
+
+    fn close(&self) {
+        self.jobs.lock().unwrap().closed = true;
+        self.available.notify_one();
+    }
+}
+
+fn wait(result_rx: mpsc::Receiver<io::Result<()>>) -> io::Result<()> {
+    match result_rx.recv() {
+        Ok(result) => result,
+        Err(_) => Err(io::Error::new(io::ErrorKind::ConnectionAborted, "connection aborted")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Frame, Priority, Queue};
+
+    #[test]
+    fn control_frames_go_first() {
+        let queue = Queue::new();
+        queue.push(Priority::Data, Frame::Bytes(vec![1]), false);
+        queue.push(Priority::Control, Frame::Bytes(vec![2]), false);
+        queue.push(Priority::Data, Frame::Bytes(vec![3]), false);
+        queue.push(Priority::Control, Frame::Bytes(vec![4]), false);
+        queue.close();
+
+        let mut order = Vec::new();
+        while let Some(job) = queue.pop() {
+            if let Frame::Bytes(bytes) = job.frame {
+                order.push(bytes[0]);
+            }
+        }
+        assert_eq!(vec![2, 4, 1, 3], order);
+    }
+
+}
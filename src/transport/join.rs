@@ -0,0 +1,110 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::fmt;
+use std::net::SocketAddr;
+
+use node::ID;
+
+#[derive(Debug)]
+pub enum JoinEvent {
+    Discovered(usize),
+    Connecting(SocketAddr),
+    Connected(ID, SocketAddr),
+    Failed(SocketAddr, String),
+}
+
+#[derive(Debug)]
+pub struct JoinReport {
+    pub discovered: usize,
+    pub connected: Vec<(ID, SocketAddr)>,
+    pub failed: Vec<(SocketAddr, String)>,
+}
+
+impl JoinReport {
+    pub fn new() -> Self {
+        JoinReport {
+            discovered: 0,
+            connected: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, event: &JoinEvent) {
+        match *event {
+            JoinEvent::Discovered(count) => self.discovered = count,
+            JoinEvent::Connecting(_) => {}
+            JoinEvent::Connected(peer_node_id, address) => {
+                self.connected.push((peer_node_id, address))
+            }
+            JoinEvent::Failed(address, ref message) => {
+                self.failed.push((address, message.to_string()))
+            }
+        }
+    }
+}
+
+impl fmt::Display for JoinEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JoinEvent::Discovered(count) => write!(f, "discovered {} peer(s)", count),
+            JoinEvent::Connecting(address) => write!(f, "connecting to {}", address),
+            JoinEvent::Connected(peer_node_id, address) => {
+                write!(f, "connected to {} at {}", peer_node_id, address)
+            }
+            JoinEvent::Failed(address, ref message) => {
+                write!(f, "failed to connect to {}: {}", address, message)
+            }
+        }
+    }
+}
+
+impl fmt::Display for JoinReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "{} peer(s) discovered, {} connected, {} failed",
+               self.discovered,
+               self.connected.len(),
+               self.failed.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::net::SocketAddr;
+    use std::str::FromStr;
+    use node::ID;
+    use super::{JoinEvent, JoinReport};
+
+    #[test]
+    fn push() {
+        let address = SocketAddr::from_str("127.0.0.1:3001").unwrap();
+        let peer_node_id = ID::new_random();
+
+        let mut report = JoinReport::new();
+        report.push(&JoinEvent::Discovered(2));
+        report.push(&JoinEvent::Connecting(address));
+        report.push(&JoinEvent::Connected(peer_node_id, address));
+        report.push(&JoinEvent::Failed(address, "test".to_string()));
+
+        assert_eq!(2, report.discovered);
+        assert_eq!(vec![(peer_node_id, address)], report.connected);
+        assert_eq!(vec![(address, "test".to_string())], report.failed);
+        assert_eq!("2 peer(s) discovered, 1 connected, 1 failed",
+                   format!("{}", report));
+    }
+
+}
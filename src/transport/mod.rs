@@ -12,9 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod capture;
 pub mod cipher;
 pub mod transport;
 pub mod direct;
+mod join;
+mod stats;
 
+pub use self::capture::Capture;
+pub use self::join::{JoinEvent, JoinReport};
+pub use self::stats::{ConnectionStats, Direction, PeerInfo, ReconnectState};
 pub use self::transport::*;
 pub use self::direct::Direct;
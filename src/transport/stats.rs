@@ -0,0 +1,59 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::net::SocketAddr;
+use time::{self, Duration};
+
+use node::ID;
+
+#[derive(Clone, Debug)]
+pub struct ConnectionStats {
+    pub bytes_sent: usize,
+    pub bytes_received: usize,
+    pub frames_sent: usize,
+    pub frames_received: usize,
+    pub last_error: Option<String>,
+    pub clock: time::Tm,
+    pub uptime: Duration,
+}
+
+/// Tells which side opened the connection to a peer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+/// A connected peer together with the number of services that are reachable over it.
+#[derive(Clone, Debug)]
+pub struct PeerInfo {
+    pub node_id: ID,
+    pub public_address: SocketAddr,
+    pub direction: Direction,
+    pub connected_since: time::Tm,
+    pub services: usize,
+}
+
+/// The reconnect history of a peer whose connection dropped.
+#[derive(Clone, Debug)]
+pub struct ReconnectState {
+    pub peer_node_id: ID,
+    pub address: SocketAddr,
+    pub reconnecting: bool,
+    pub attempts: usize,
+    pub reconnects: usize,
+    pub backoff: Duration,
+    pub last_error: Option<String>,
+}
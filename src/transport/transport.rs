@@ -17,18 +17,49 @@ use std::net::SocketAddr;
 use std::io;
 use std::result;
 
-use node::{ID, Service, request, response};
-use transport::direct;
+use node::{ID, Metadata, Placement, Service, request, response};
+use transport::{ConnectionStats, JoinEvent, JoinReport, PeerInfo, ReconnectState, direct};
 
 pub trait Transport : Send + Sync {
     fn public_address(&self) -> SocketAddr;
     fn bind(&self, ID) -> Result<()>;
-    fn join(&self, SocketAddr, ID) -> Result<()>;
+    fn join(&self, SocketAddr, ID, &Fn(&JoinEvent)) -> Result<JoinReport>;
+    fn remove_peer(&self, SocketAddr) -> Result<()>;
+    fn set_peer_count_handler(&self, Box<Fn(usize) + Send + Sync>);
+    fn connection_stats(&self, ID) -> Result<(ConnectionStats, ConnectionStats)>;
+    fn reconnect_states(&self) -> Vec<ReconnectState>;
+    fn peers(&self) -> Vec<PeerInfo>;
+    fn acquire_drain_permit(&self) -> Result<bool>;
+    fn release_drain_permit(&self) -> Result<()>;
+    fn leave(&self) -> Result<()>;
+    fn shutdown(&self) -> Result<()>;
 
-    fn register(&self, &str, Box<Service>) -> Result<()>;
+    fn register(&self, &str, Box<Service>, Placement, u32, Metadata) -> Result<()>;
     fn deregister(&self, &str) -> Result<()>;
+    fn has_service(&self, &str) -> bool;
+    fn service_usage(&self) -> Vec<(String, ID, usize)>;
+    fn providers(&self, &str) -> Vec<ID>;
 
-    fn request(&self, &str, Box<request::Reader>, Box<response::Handler>) -> request::Result<()>;
+    fn request(&self,
+               &str,
+               Option<&[u8]>,
+               Box<request::Reader>,
+               Box<response::Handler>)
+               -> request::Result<()>;
+    fn request_tagged(&self,
+                      &str,
+                      &Metadata,
+                      Box<request::Reader>,
+                      Box<response::Handler>)
+                      -> request::Result<()>;
+    fn notify(&self, &str, Box<request::Reader>) -> request::Result<()>;
+    fn notify_on(&self, &str, ID, Box<request::Reader>) -> request::Result<()>;
+    fn request_on(&self,
+                  &str,
+                  ID,
+                  Box<request::Reader>,
+                  Box<response::Handler>)
+                  -> request::Result<()>;
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -39,6 +70,7 @@ pub enum Error {
     Io(io::Error),
     ConnectionMap(direct::ConnectionMapError),
     ServiceMap(direct::ServiceMapError),
+    Announcement(direct::Announcement),
 }
 
 impl From<io::Error> for Error {
@@ -0,0 +1,148 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use std::sync::{Condvar, Mutex};
+use std::thread;
+use time::{self, Duration, SteadyTime, Tm};
+
+use util::time::to_std_duration;
+
+pub trait Clock : Send + Sync {
+    fn now(&self) -> SteadyTime;
+    fn sleep(&self, duration: Duration);
+
+    /// Returns the wall clock time, e.g. to show it to peers.
+    fn utc(&self) -> Tm {
+        time::now_utc()
+    }
+}
+
+pub struct System;
+
+impl Clock for System {
+    fn now(&self) -> SteadyTime {
+        SteadyTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        if duration > Duration::zero() {
+            thread::sleep(to_std_duration(duration));
+        }
+    }
+}
+
+pub struct Mock {
+    start: SteadyTime,
+    start_utc: Tm,
+    state: Mutex<MockState>,
+    condvar: Condvar,
+}
+
+struct MockState {
+    elapsed: Duration,
+    sleepers: usize,
+}
+
+impl Mock {
+    pub fn new() -> Self {
+        Mock {
+            start: SteadyTime::now(),
+            start_utc: time::now_utc(),
+            state: Mutex::new(MockState {
+                elapsed: Duration::zero(),
+                sleepers: 0,
+            }),
+            condvar: Condvar::new(),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.elapsed = state.elapsed + duration;
+        }
+        self.condvar.notify_all();
+    }
+
+    pub fn sleepers(&self) -> usize {
+        self.state.lock().unwrap().sleepers
+    }
+
+    pub fn wait_for_sleepers(&self, count: usize) {
+        while self.sleepers() < count {
+            thread::yield_now();
+        }
+    }
+}
+
+impl Clock for Mock {
+    fn now(&self) -> SteadyTime {
+        self.start + self.state.lock().unwrap().elapsed
+    }
+
+    fn sleep(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        let wake_at = state.elapsed + duration;
+        state.sleepers += 1;
+        while state.elapsed < wake_at {
+            state = self.condvar.wait(state).unwrap();
+        }
+        state.sleepers -= 1;
+    }
+
+    fn utc(&self) -> Tm {
+        self.start_utc + self.state.lock().unwrap().elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+    use std::thread;
+    use time::Duration;
+    use super::{Clock, Mock};
+
+    #[test]
+    fn mock_sleeps_until_advanced() {
+        let clock = Arc::new(Mock::new());
+        let start = clock.now();
+
+        let clock_clone = clock.clone();
+        let handle = thread::spawn(move || {
+            clock_clone.sleep(Duration::milliseconds(100));
+            clock_clone.now()
+        });
+
+        clock.wait_for_sleepers(1);
+        clock.advance(Duration::milliseconds(50));
+        assert_eq!(1, clock.sleepers());
+        clock.advance(Duration::milliseconds(50));
+
+        assert_eq!(Duration::milliseconds(100), handle.join().unwrap() - start);
+        assert_eq!(0, clock.sleepers());
+    }
+
+    #[test]
+    fn mock_advances_wall_clock() {
+        let clock = Mock::new();
+        let start = clock.utc();
+
+        clock.advance(Duration::seconds(5));
+
+        assert_eq!(Duration::seconds(5), clock.utc() - start);
+    }
+
+}
@@ -0,0 +1,133 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate libc;
+
+use std::fs;
+use std::io::{self, Read};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use time::Duration;
+
+use util::thread::supervisor;
+use util::time::to_std_duration;
+
+pub type Probe = Box<Fn() -> io::Result<usize> + Send>;
+
+/// Watches the memory usage of the process and flags when it's above the limit.
+pub struct Watermark {
+    exceeded: Arc<AtomicBool>,
+    running: Arc<AtomicBool>,
+}
+
+impl Watermark {
+    pub fn start(limit: usize, interval: Duration) -> Watermark {
+        Self::with_probe(limit, interval, Box::new(resident_size))
+    }
+
+    pub fn with_probe(limit: usize, interval: Duration, probe: Probe) -> Watermark {
+        let exceeded = Arc::new(AtomicBool::new(false));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let exceeded_clone = exceeded.clone();
+        let running_clone = running.clone();
+        supervisor().spawn("memory watermark", move || {
+            while running_clone.load(Ordering::SeqCst) {
+                match probe() {
+                    Ok(size) => {
+                        let above = size > limit;
+                        if exceeded_clone.swap(above, Ordering::SeqCst) != above {
+                            if above {
+                                warn!("memory usage of {} bytes exceeds the limit of {} bytes - \
+                                       shedding load",
+                                      size,
+                                      limit);
+                            } else {
+                                info!("memory usage of {} bytes is below the limit again", size);
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        error!("error while reading memory usage: {:?}", error);
+                        return;
+                    }
+                }
+                thread::sleep(to_std_duration(interval));
+            }
+        });
+
+        Watermark {
+            exceeded: exceeded,
+            running: running,
+        }
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.exceeded.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for Watermark {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Returns the resident set size of the process in bytes. Only Linux is supported.
+pub fn resident_size() -> io::Result<usize> {
+    let mut content = String::new();
+    try!(fs::File::open("/proc/self/statm").and_then(|mut file| file.read_to_string(&mut content)));
+    let pages = try!(content.split_whitespace()
+                            .nth(1)
+                            .and_then(|value| value.parse::<usize>().ok())
+                            .ok_or(io::Error::new(io::ErrorKind::InvalidData,
+                                                  "invalid /proc/self/statm")));
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Ok(pages * page_size as usize)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time;
+    use time::Duration;
+    use super::Watermark;
+
+    #[test]
+    fn flag_exceeded_limit() {
+        let size = Arc::new(AtomicUsize::new(10));
+        let size_clone = size.clone();
+        let watermark = Watermark::with_probe(100,
+                                              Duration::milliseconds(5),
+                                              Box::new(move || {
+                                                  Ok(size_clone.load(Ordering::SeqCst))
+                                              }));
+        thread::sleep(time::Duration::from_millis(20));
+        assert!(!watermark.is_exceeded());
+
+        size.store(200, Ordering::SeqCst);
+        thread::sleep(time::Duration::from_millis(20));
+        assert!(watermark.is_exceeded());
+
+        size.store(50, Ordering::SeqCst);
+        thread::sleep(time::Duration::from_millis(20));
+        assert!(!watermark.is_exceeded());
+    }
+
+}
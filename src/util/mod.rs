@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod clock;
+pub mod memory;
 pub mod net;
+pub mod rate;
 pub mod reader;
 pub mod writer;
 pub mod resolve;
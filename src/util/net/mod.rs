@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod proxy;
 mod tcp_server;
 
+pub use self::proxy::Proxy;
 pub use self::tcp_server::TcpServer;
@@ -30,7 +30,7 @@ impl TcpServer {
         let tcp_listener = try!(net::TcpListener::bind(address));
         let local_address = tcp_listener.local_addr().unwrap();
 
-        let thread = thread::Bound::spawn(move |running| {
+        let thread = thread::Bound::spawn("tcp server", move |running| {
             let mut threads = Vec::new();
             for stream in tcp_listener.incoming() {
                 if !*running.read().unwrap() {
@@ -38,7 +38,7 @@ impl TcpServer {
                 }
 
                 let mut handler = handler_factory(stream.unwrap());
-                threads.push(thread::Bound::spawn(move |_| {
+                threads.push(thread::Bound::spawn("tcp server connection", move |_| {
                     handler();
                 }));
             }
@@ -0,0 +1,95 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::{Arc, Mutex};
+use time::{Duration, SteadyTime};
+
+use util::clock::Clock;
+
+/// Limits the throughput to a number of bytes per second. Bursts of up to one second worth of
+/// bytes pass without delay.
+pub struct Limiter {
+    bytes_per_second: usize,
+    clock: Arc<Clock>,
+    available_at: Mutex<Option<SteadyTime>>,
+}
+
+impl Limiter {
+    pub fn with_clock(bytes_per_second: usize, clock: Arc<Clock>) -> Limiter {
+        Limiter {
+            bytes_per_second: if bytes_per_second > 0 {
+                bytes_per_second
+            } else {
+                1
+            },
+            clock: clock,
+            available_at: Mutex::new(None),
+        }
+    }
+
+    pub fn bytes_per_second(&self) -> usize {
+        self.bytes_per_second
+    }
+
+    /// Blocks until `size` bytes can pass without exceeding the limit.
+    pub fn consume(&self, size: usize) {
+        let delay = {
+            let now = self.clock.now();
+            let burst_start = now - Duration::seconds(1);
+            let mut available_at = self.available_at.lock().unwrap();
+            let start = match *available_at {
+                Some(available_at) if available_at > burst_start => available_at,
+                _ => burst_start,
+            };
+            let next = start +
+                       Duration::microseconds(size as i64 * 1_000_000 /
+                                              self.bytes_per_second as i64);
+            *available_at = Some(next);
+            next - now
+        };
+        self.clock.sleep(delay);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Arc;
+    use std::thread;
+    use time::Duration;
+    use super::Limiter;
+    use util::clock::{Clock, Mock};
+
+    #[test]
+    fn consume_delays_beyond_burst() {
+        let clock = Arc::new(Mock::new());
+        let limiter = Arc::new(Limiter::with_clock(1000, clock.clone()));
+        let start = clock.now();
+
+        limiter.consume(1000);
+        assert_eq!(start, clock.now());
+
+        let limiter_clone = limiter.clone();
+        let clock_clone = clock.clone();
+        let handle = thread::spawn(move || {
+            limiter_clone.consume(500);
+            clock_clone.now()
+        });
+
+        clock.wait_for_sleepers(1);
+        clock.advance(Duration::milliseconds(500));
+        assert_eq!(Duration::milliseconds(500), handle.join().unwrap() - start);
+    }
+
+}
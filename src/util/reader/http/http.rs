@@ -14,13 +14,17 @@
 //
 
 use std::io::{self, BufRead, Read};
+use std::str;
+
+use message::http;
 use super::ChunkedBody;
 
 pub struct Http<R>
     where R: Send
 {
     reader: Option<R>,
-    computed_reader: Option<Box<io::Read + Send>>,
+    header: Option<io::Cursor<Vec<u8>>>,
+    body: Option<Box<io::Read + Send>>,
 }
 
 impl<R> Http<R>
@@ -29,13 +33,24 @@ impl<R> Http<R>
     pub fn new(reader: R) -> Http<R> {
         Http {
             reader: Some(reader),
-            computed_reader: None,
+            header: None,
+            body: None,
         }
     }
 
     pub fn get_ref(&self) -> Option<&R> {
         self.reader.as_ref()
     }
+
+    /// Returns the header including the request line, once it has been read.
+    pub fn header(&self) -> Option<&str> {
+        self.header.as_ref().and_then(|header| str::from_utf8(header.get_ref()).ok())
+    }
+
+    /// Replaces the header that is passed on in front of the body.
+    pub fn set_header(&mut self, header: String) {
+        self.header = Some(io::Cursor::new(header.into_bytes()));
+    }
 }
 
 impl<R> Http<R>
@@ -55,10 +70,9 @@ impl<R> Http<R>
                 break;
             }
 
-            let parts = line.split(':').collect::<Vec<_>>();
-            if parts.len() == 2 {
-                let key = parts[0].to_lowercase().trim().to_string();
-                let value = parts[1].to_string().trim().to_string();
+            if let Some((key, value)) = http::split_header_line(&line) {
+                let key = key.to_lowercase();
+                let value = value.to_string();
 
                 f(&key, &value);
 
@@ -74,15 +88,10 @@ impl<R> Http<R>
             }
         }
 
-        self.computed_reader = Some(match content_length {
-            Some(size) => {
-                Box::new(io::Cursor::new(buffer.into_bytes())
-                             .chain(buf_reader.take(size))) as Box<io::Read + Send>
-            }
-            None => {
-                Box::new(io::Cursor::new(buffer.into_bytes())
-                             .chain(ChunkedBody::new(buf_reader))) as Box<io::Read + Send>
-            }
+        self.header = Some(io::Cursor::new(buffer.into_bytes()));
+        self.body = Some(match content_length {
+            Some(size) => Box::new(buf_reader.take(size)) as Box<io::Read + Send>,
+            None => Box::new(ChunkedBody::new(buf_reader)) as Box<io::Read + Send>,
         });
 
         Ok(total)
@@ -93,12 +102,18 @@ impl<R> io::Read for Http<R>
     where R: io::Read + Send + 'static
 {
     fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
-        if let None = self.computed_reader {
+        if let None = self.body {
             try!(self.read_header(|_, _| {
             }));
         }
 
-        self.computed_reader.as_mut().unwrap().read(buffer)
+        if let Some(ref mut header) = self.header {
+            let size = try!(header.read(buffer));
+            if size > 0 {
+                return Ok(size);
+            }
+        }
+        self.body.as_mut().unwrap().read(buffer)
     }
 }
 
@@ -145,4 +160,22 @@ mod tests {
                    String::from_utf8_lossy(&output));
     }
 
+    #[test]
+    fn read_request_with_replaced_header() {
+        let stream = b"GET / HTTP/1.1\r\n\
+                       Content-Length: 12\r\n\
+                       \r\n\
+                       test message";
+        let mut http_reader = Http::new(io::Cursor::new(stream.to_vec()));
+        http_reader.read_header(|_, _| {}).unwrap();
+        assert_eq!(Some("GET / HTTP/1.1\r\nContent-Length: 12\r\n\r\n"),
+                   http_reader.header());
+
+        http_reader.set_header("GET /test HTTP/1.1\r\nContent-Length: 12\r\n\r\n".to_string());
+
+        let mut output = String::new();
+        http_reader.read_to_string(&mut output).unwrap();
+        assert_eq!("GET /test HTTP/1.1\r\nContent-Length: 12\r\n\r\ntest message", output);
+    }
+
 }
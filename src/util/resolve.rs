@@ -14,6 +14,18 @@
 
 use std::io;
 use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::Mutex;
+
+use time::{Duration, SteadyTime};
+
+const PENDING_RETRY_INTERVAL_MS: i64 = 5000;
+
+/// Host names that couldn't be resolved yet, e.g. because the DNS isn't ready while the node
+/// boots. They are retried at most every few seconds.
+pub struct Pending {
+    names: Mutex<Vec<String>>,
+    last_attempt: Mutex<Option<SteadyTime>>,
+}
 
 pub fn socket_address(address: &str) -> io::Result<SocketAddr> {
     Ok(try!(try!(address.to_socket_addrs())
@@ -29,3 +41,102 @@ pub fn socket_addresses(addresses: &[String]) -> io::Result<Vec<SocketAddr>> {
     }
     Ok(result)
 }
+
+/// Resolves the given addresses and returns the names that couldn't be resolved separately.
+/// Malformed addresses are still an error.
+pub fn partition(addresses: &[String]) -> io::Result<(Vec<SocketAddr>, Vec<String>)> {
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+    for address in addresses {
+        match address.to_socket_addrs() {
+            Ok(addresses) => resolved.extend(addresses),
+            Err(ref error) if error.kind() == io::ErrorKind::InvalidInput => {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                          format!("invalid address [{}]: {}", address, error)))
+            }
+            Err(_) => unresolved.push(address.to_string()),
+        }
+    }
+    Ok((resolved, unresolved))
+}
+
+impl Pending {
+    pub fn new() -> Pending {
+        Pending {
+            names: Mutex::new(Vec::new()),
+            last_attempt: Mutex::new(None),
+        }
+    }
+
+    pub fn add(&self, names: Vec<String>) {
+        self.names.lock().unwrap().extend(names);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.lock().unwrap().is_empty()
+    }
+
+    /// Tries to resolve the pending names and returns the addresses of the ones that succeeded.
+    pub fn resolve(&self) -> Vec<SocketAddr> {
+        let mut names = self.names.lock().unwrap();
+        if names.is_empty() {
+            return Vec::new();
+        }
+
+        {
+            let now = SteadyTime::now();
+            let mut last_attempt = self.last_attempt.lock().unwrap();
+            if let Some(last_attempt) = *last_attempt {
+                if now - last_attempt < Duration::milliseconds(PENDING_RETRY_INTERVAL_MS) {
+                    return Vec::new();
+                }
+            }
+            *last_attempt = Some(now);
+        }
+
+        let mut resolved = Vec::new();
+        names.retain(|name| {
+            match name.to_socket_addrs() {
+                Ok(addresses) => {
+                    let addresses = addresses.collect::<Vec<_>>();
+                    info!("resolved {} to {:?}", name, addresses);
+                    resolved.extend(addresses);
+                    false
+                }
+                Err(_) => true,
+            }
+        });
+        resolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io;
+    use super::{Pending, partition};
+
+    #[test]
+    fn partition_unresolvable_names() {
+        let (resolved, unresolved) = partition(&["127.0.0.1:3001".to_string(),
+                                                 "unknown.invalid:3002".to_string()])
+                                         .unwrap();
+        assert_eq!(vec!["127.0.0.1:3001".parse().unwrap()], resolved);
+        assert_eq!(vec!["unknown.invalid:3002".to_string()], unresolved);
+
+        assert_eq!(io::ErrorKind::InvalidInput,
+                   partition(&["127.0.0.1".to_string()]).unwrap_err().kind());
+    }
+
+    #[test]
+    fn resolve_pending() {
+        let pending = Pending::new();
+        pending.add(vec!["127.0.0.1:3001".to_string(), "unknown.invalid:3002".to_string()]);
+
+        assert_eq!(vec!["127.0.0.1:3001".parse().unwrap()], pending.resolve());
+        assert!(!pending.is_empty());
+        // the next attempt is delayed.
+        assert_eq!(Vec::<::std::net::SocketAddr>::new(), pending.resolve());
+    }
+
+}
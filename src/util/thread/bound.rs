@@ -15,19 +15,21 @@
 use std::thread;
 use std::sync::{Arc, RwLock};
 
+use super::supervisor;
+
 pub struct Bound {
     running: Arc<RwLock<bool>>,
     join_handle: Option<thread::JoinHandle<()>>,
 }
 
 impl Bound {
-    pub fn spawn<F>(f: F) -> Self
+    pub fn spawn<F>(name: &str, f: F) -> Self
         where F: FnOnce(Arc<RwLock<bool>>) + Send + 'static
     {
         let running = Arc::new(RwLock::new(true));
         let running_clone = running.clone();
 
-        let join_handle = thread::spawn(move || {
+        let join_handle = supervisor().spawn(name, move || {
             f(running_clone);
         });
 
@@ -63,7 +65,7 @@ mod tests {
     fn drop() {
         let (tx, rx) = mpsc::channel();
         {
-            Bound::spawn(move |running| {
+            Bound::spawn("test", move |running| {
                 while *running.read().unwrap() {
                     thread::sleep(Duration::from_millis(50));
                 }
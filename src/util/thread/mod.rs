@@ -13,5 +13,7 @@
 // limitations under the License.
 
 mod bound;
+pub mod supervisor;
 
 pub use self::bound::Bound;
+pub use self::supervisor::{Supervisor, supervisor};
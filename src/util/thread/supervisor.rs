@@ -0,0 +1,247 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use time::{self, Duration};
+
+use util::time::to_std_duration;
+
+/// The number of panicked tasks that are kept for inspection. Older ones are pruned.
+const MAX_PANICKED_TASKS: usize = 32;
+
+lazy_static! {
+    static ref SUPERVISOR: Supervisor = Supervisor::new();
+}
+
+/// Returns the supervisor of the process. Tests that inspect the tasks should use their own
+/// `Supervisor::new()` instead.
+pub fn supervisor() -> &'static Supervisor {
+    &SUPERVISOR
+}
+
+pub struct Supervisor {
+    tasks: Arc<RwLock<HashMap<usize, Task>>>,
+    next_id: AtomicUsize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum State {
+    Running,
+    Restarting,
+    Panicked,
+}
+
+#[derive(Clone, Debug)]
+pub struct Task {
+    pub name: String,
+    pub state: State,
+    pub restarts: usize,
+    pub started_at: time::Tm,
+}
+
+struct Guard {
+    id: usize,
+    tasks: Arc<RwLock<HashMap<usize, Task>>>,
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Supervisor {
+            tasks: Arc::new(RwLock::new(HashMap::new())),
+            next_id: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn spawn<F, T>(&self, name: &str, f: F) -> thread::JoinHandle<T>
+        where F: FnOnce() -> T + Send + 'static,
+              T: Send + 'static
+    {
+        let guard = self.register(name);
+        thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                let _guard = guard;
+                f()
+            })
+            .unwrap()
+    }
+
+    pub fn spawn_restartable<F>(&self,
+                                name: &str,
+                                restart_delay: Duration,
+                                f: F)
+                                -> thread::JoinHandle<()>
+        where F: Fn() + Send + Sync + 'static
+    {
+        let guard = self.register(name);
+        let name = name.to_string();
+        let f = Arc::new(f);
+        thread::Builder::new()
+            .name(name.clone())
+            .spawn(move || {
+                loop {
+                    let f = f.clone();
+                    let inner = thread::Builder::new()
+                                    .name(name.clone())
+                                    .spawn(move || f())
+                                    .unwrap();
+                    match inner.join() {
+                        Ok(()) => break,
+                        Err(_) => {
+                            error!("thread {} panicked - restarting in {} ms",
+                                   name,
+                                   restart_delay.num_milliseconds());
+                            guard.update(|task| {
+                                task.state = State::Restarting;
+                                task.restarts += 1;
+                            });
+                            thread::sleep(to_std_duration(restart_delay));
+                            guard.update(|task| {
+                                task.state = State::Running;
+                                task.started_at = time::now_utc();
+                            });
+                        }
+                    }
+                }
+            })
+            .unwrap()
+    }
+
+    pub fn tasks(&self) -> Vec<Task> {
+        let mut tasks = self.tasks.read().unwrap().values().cloned().collect::<Vec<_>>();
+        tasks.sort_by(|a, b| a.name.cmp(&b.name));
+        tasks
+    }
+
+    fn register(&self, name: &str) -> Guard {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.tasks.write().unwrap().insert(id,
+                                           Task {
+                                               name: name.to_string(),
+                                               state: State::Running,
+                                               restarts: 0,
+                                               started_at: time::now_utc(),
+                                           });
+        Guard {
+            id: id,
+            tasks: self.tasks.clone(),
+        }
+    }
+}
+
+impl Guard {
+    fn update<F>(&self, f: F)
+        where F: FnOnce(&mut Task)
+    {
+        if let Some(task) = self.tasks.write().unwrap().get_mut(&self.id) {
+            f(task);
+        }
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let mut tasks = self.tasks.write().unwrap();
+        if !thread::panicking() {
+            tasks.remove(&self.id);
+            return;
+        }
+
+        if let Some(task) = tasks.get_mut(&self.id) {
+            task.state = State::Panicked;
+        }
+        let mut panicked = tasks.iter()
+                                .filter(|&(_, task)| task.state == State::Panicked)
+                                .map(|(id, _)| *id)
+                                .collect::<Vec<_>>();
+        if panicked.len() > MAX_PANICKED_TASKS {
+            panicked.sort();
+            for id in &panicked[..panicked.len() - MAX_PANICKED_TASKS] {
+                tasks.remove(id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::{Arc, Mutex, mpsc};
+    use time::Duration;
+    use super::{MAX_PANICKED_TASKS, State, Supervisor};
+
+    #[test]
+    fn finished_task_is_removed() {
+        let supervisor = Supervisor::new();
+        let (tx, rx) = mpsc::channel();
+        let join_handle = supervisor.spawn("test", move || rx.recv().unwrap());
+
+        assert_eq!(vec![("test".to_string(), State::Running)],
+                   supervisor.tasks()
+                             .into_iter()
+                             .map(|task| (task.name, task.state))
+                             .collect::<Vec<_>>());
+
+        tx.send(()).unwrap();
+        join_handle.join().unwrap();
+
+        assert!(supervisor.tasks().is_empty());
+    }
+
+    #[test]
+    fn panicked_task_is_reported() {
+        let supervisor = Supervisor::new();
+        assert!(supervisor.spawn("test", || panic!("test panic")).join().is_err());
+
+        let tasks = supervisor.tasks();
+        assert_eq!(1, tasks.len());
+        assert_eq!(State::Panicked, tasks[0].state);
+    }
+
+    #[test]
+    fn panicked_tasks_are_pruned() {
+        let supervisor = Supervisor::new();
+        for _ in 0..MAX_PANICKED_TASKS + 2 {
+            assert!(supervisor.spawn("test", || panic!("test panic")).join().is_err());
+        }
+
+        let tasks = supervisor.tasks();
+        assert_eq!(MAX_PANICKED_TASKS, tasks.len());
+        assert!(tasks.iter().all(|task| task.state == State::Panicked));
+    }
+
+    #[test]
+    fn restartable_task_is_restarted() {
+        let supervisor = Supervisor::new();
+        let runs = Arc::new(Mutex::new(0));
+        let runs_clone = runs.clone();
+        supervisor.spawn_restartable("test", Duration::milliseconds(10), move || {
+                      let mut runs = runs_clone.lock().unwrap();
+                      *runs += 1;
+                      if *runs < 3 {
+                          drop(runs);
+                          panic!("test panic");
+                      }
+                  })
+                  .join()
+                  .unwrap();
+
+        assert_eq!(3, *runs.lock().unwrap());
+        assert!(supervisor.tasks().is_empty());
+    }
+}
@@ -15,10 +15,11 @@
 
 extern crate delix;
 
-mod helper;
+use delix::testing as helper;
 
 use std::error::Error;
 use std::io;
+use delix::transport::Direction;
 use delix::util::reader;
 
 #[test]
@@ -72,7 +73,7 @@ fn loose_while_transmitting_request() {
     helper::wait_for_services(&[&metric_one, &metric_two], 1);
 
     let request = Box::new(reader::ErrorAfter::new_unexpected_eof(io::Cursor::new(b"test message".to_vec()), 4));
-    assert!(node_one.request("echo", request, Box::new(move |mut reader| {
+    assert!(node_one.request("echo", None, request, Box::new(move |mut reader| {
         let mut response = Vec::new();
         assert!(io::copy(&mut reader, &mut response).is_ok());
         assert_eq!("test message", String::from_utf8_lossy(&response));
@@ -93,9 +94,48 @@ fn loose_while_transmitting_response() {
     helper::wait_for_services(&[&metric_one, &metric_two], 1);
 
     let request = Box::new(io::Cursor::new(b"test message".to_vec()));
-    assert!(node_one.request("echo", request, Box::new(move |mut reader| {
+    assert!(node_one.request("echo", None, request, Box::new(move |mut reader| {
         let result = io::copy(&mut reader, &mut io::sink()).unwrap_err();
         assert_eq!(io::ErrorKind::UnexpectedEof, result.kind());
         assert_eq!("unexpected EOF", result.description());
     })).is_ok());
 }
+
+#[test]
+fn stats() {
+    helper::set_up();
+
+    let (node_one, metric_one) = helper::build_node("localhost:3081", &[], None);
+    let (node_two, metric_two) = helper::build_node("localhost:3082", &["localhost:3081"], None);
+    helper::wait_for_joined(&[&metric_one, &metric_two]);
+
+    let (local, remote) = node_one.connection_stats(node_two.id).unwrap();
+    assert!(local.frames_sent > 0);
+    assert!(remote.frames_received > 0);
+    assert_eq!(None, remote.last_error);
+
+    assert!(node_one.connection_stats(node_one.id).is_err());
+}
+
+#[test]
+fn peers() {
+    helper::set_up();
+
+    let (node_one, metric_one) = helper::build_node("localhost:3201", &[], None);
+    node_one.register("echo", Box::new(|request| Ok(request)))
+            .unwrap();
+    let (node_two, metric_two) = helper::build_node("localhost:3202", &["localhost:3201"], None);
+    helper::wait_for_joined(&[&metric_one, &metric_two]);
+    helper::wait_for_services(&[&metric_one, &metric_two], 1);
+
+    let peers = node_two.peers();
+    assert_eq!(1, peers.len());
+    assert_eq!(node_one.id, peers[0].node_id);
+    assert_eq!(3201, peers[0].public_address.port());
+    assert_eq!(Direction::Outbound, peers[0].direction);
+    assert_eq!(1, peers[0].services);
+
+    let peers = node_one.peers();
+    assert_eq!(Direction::Inbound, peers[0].direction);
+    assert_eq!(0, peers[0].services);
+}
@@ -15,7 +15,7 @@
 
 extern crate delix;
 
-mod helper;
+use delix::testing as helper;
 
 #[test]
 #[allow(unused_variables)]
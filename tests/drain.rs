@@ -0,0 +1,51 @@
+// Copyright 2015 The Delix Project Authors. See the AUTHORS file at the top level directory.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+extern crate delix;
+
+use delix::metric::{self, Query};
+use delix::testing as helper;
+
+#[test]
+fn one_node_drains_at_a_time() {
+    helper::set_up();
+
+    let (node_one, metric_one) = helper::build_node("localhost:3301", &[], None);
+    let (node_two, metric_two) = helper::build_node("localhost:3302", &["localhost:3301"], None);
+    helper::wait_for_joined(&[&metric_one, &metric_two]);
+    {
+        let (node_three, metric_three) = helper::build_node("localhost:3303",
+                                                            &["localhost:3301"],
+                                                            None);
+        helper::wait_for_joined(&[&metric_one, &metric_two, &metric_three]);
+
+        assert!(node_three.acquire_drain_permit().unwrap());
+        assert!(node_three.acquire_drain_permit().unwrap());
+        assert!(!node_one.acquire_drain_permit().unwrap());
+        assert!(!node_two.acquire_drain_permit().unwrap());
+
+        node_three.release_drain_permit().unwrap();
+        assert!(node_one.acquire_drain_permit().unwrap());
+        assert!(!node_two.acquire_drain_permit().unwrap());
+        node_one.release_drain_permit().unwrap();
+
+        assert!(node_three.acquire_drain_permit().unwrap());
+    }
+
+    for query in &[&metric_one, &metric_two] {
+        query.watch("connections", |_, value| *value != metric::Value::Gauge(1));
+    }
+    // the lease of the node that went away is kept until it expires.
+    assert!(!node_two.acquire_drain_permit().unwrap());
+}
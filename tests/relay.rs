@@ -18,7 +18,7 @@ extern crate delix;
 extern crate hyper;
 extern crate tempdir;
 
-mod helper;
+use delix::testing as helper;
 
 use std::fs;
 use std::error::Error;
@@ -246,3 +246,46 @@ fn http_api_delete_service() {
     assert_eq!(Some(metric::Value::Gauge(0)), metric.get("services"));
     assert!(!file_name.exists());
 }
+
+#[test]
+#[allow(unused_variables)]
+fn http_api_metrics() {
+    helper::set_up();
+
+    let (node, metric) = helper::build_node("localhost:3311", &[], None);
+    let relay = helper::build_http_relay(&node, None, Some("localhost:4190"), None);
+
+    let mut response = Client::new()
+                           .get("http://localhost:4190/metrics")
+                           .send()
+                           .unwrap();
+    let mut content = String::new();
+    response.read_to_string(&mut content).unwrap();
+    assert_eq!(StatusCode::Ok, response.status);
+    assert!(content.starts_with("{\"metrics\":{"));
+    assert!(content.contains("\"services\":{\"type\":\"gauge\",\"value\":0}"));
+
+    let mut response = Client::new()
+                           .get("http://localhost:4190/metrics/prometheus")
+                           .send()
+                           .unwrap();
+    let mut content = String::new();
+    response.read_to_string(&mut content).unwrap();
+    assert_eq!(StatusCode::Ok, response.status);
+    assert!(content.contains("# TYPE services gauge\nservices 0\n"));
+}
+
+#[test]
+fn http_shutdown_on_node_leave() {
+    helper::set_up();
+
+    let (node, _) = helper::build_node("localhost:3081", &[], None);
+    let relay = helper::build_http_relay(&node, Some("localhost:4080"), None, None);
+    node.add_relay(relay.clone());
+
+    assert!(net::TcpStream::connect("localhost:4080").is_ok());
+
+    node.leave();
+
+    assert!(net::TcpStream::connect("localhost:4080").is_err());
+}
@@ -14,15 +14,16 @@
 //
 
 extern crate delix;
+extern crate time;
 
-mod helper;
+use delix::testing as helper;
 
-use std::io;
+use std::io::{self, Read, Write};
 use std::iter;
 use std::sync::{Arc, Mutex, RwLock, mpsc};
 use std::thread;
 
-use delix::node::request;
+use delix::node::{self, service};
 
 #[test]
 fn single_echo_from_local_without_timeout() {
@@ -34,7 +35,7 @@ fn single_echo_from_local_without_timeout() {
 
     helper::wait_for_services(&[&metric], 1);
 
-    assert_eq!("test message", String::from_utf8_lossy(&node.request_bytes("echo", b"test message").unwrap()));
+    assert_eq!("test message", String::from_utf8_lossy(&node.request_bytes("echo", b"test message", None).unwrap()));
 }
 
 #[test]
@@ -49,7 +50,7 @@ fn single_large_echo_from_local_without_timeout() {
 
     let request_bytes = iter::repeat(0u8).take(70000).collect::<Vec<_>>();
     let request = Box::new(io::Cursor::new(request_bytes.clone()));
-    node.request("echo", request, Box::new(move |mut reader| {
+    node.request("echo", None, request, Box::new(move |mut reader| {
         assert_eq!(Some(70000), io::copy(&mut reader, &mut io::sink()).ok());
     })).unwrap();
 }
@@ -66,7 +67,25 @@ fn single_echo_from_local_with_timeout() {
 
     helper::wait_for_services(&[&metric], 1);
 
-    assert_eq!(Err(request::Error::Timeout), node.request_bytes("echo", b""));
+    assert_eq!(Err(node::Error::Timeout), node.request_bytes("echo", b"", None));
+}
+
+#[test]
+fn single_echo_from_local_with_call_timeout() {
+    helper::set_up();
+
+    let (node, metric) = helper::build_node("localhost:3141", &[], None);
+    node.register("echo", Box::new(|request| {
+        thread::sleep(::std::time::Duration::from_millis(100));
+        Ok(request)
+    })).unwrap();
+
+    helper::wait_for_services(&[&metric], 1);
+
+    assert_eq!(Err(node::Error::Timeout),
+               node.request_bytes("echo", b"", Some(time::Duration::milliseconds(10))));
+    assert_eq!(b"test".to_vec(),
+               node.request_bytes("echo", b"test", Some(time::Duration::seconds(5))).unwrap());
 }
 
 #[test]
@@ -82,7 +101,7 @@ fn single_echo_from_remote_without_timeout() {
     helper::wait_for_joined(&[&metric_one, &metric_two]);
     helper::wait_for_services(&[&metric_one, &metric_two], 1);
 
-    assert_eq!("test message", String::from_utf8_lossy(&node_two.request_bytes("echo", b"test message").unwrap()));
+    assert_eq!("test message", String::from_utf8_lossy(&node_two.request_bytes("echo", b"test message", None).unwrap()));
 }
 
 #[test]
@@ -100,7 +119,7 @@ fn single_echo_from_remote_with_timeout() {
     helper::wait_for_joined(&[&metric_one, &metric_two]);
     helper::wait_for_services(&[&metric_one, &metric_two], 1);
 
-    assert_eq!(Err(request::Error::Timeout), node_two.request_bytes("echo", b""));
+    assert_eq!(Err(node::Error::Timeout), node_two.request_bytes("echo", b"", None));
 }
 
 #[test]
@@ -116,8 +135,8 @@ fn multiple_echos_from_remote() {
     helper::wait_for_joined(&[&metric_one, &metric_two]);
     helper::wait_for_services(&[&metric_one, &metric_two], 1);
 
-    assert_eq!(b"test message one".to_vec(), node_two.request_bytes("echo", b"test message one").unwrap());
-    assert_eq!(b"test message two".to_vec(), node_two.request_bytes("echo", b"test message two").unwrap());
+    assert_eq!(b"test message one".to_vec(), node_two.request_bytes("echo", b"test message one", None).unwrap());
+    assert_eq!(b"test message two".to_vec(), node_two.request_bytes("echo", b"test message two", None).unwrap());
 }
 
 #[test]
@@ -145,15 +164,15 @@ fn balanced_echos_from_two_remotes() {
     helper::wait_for_joined(&[&metric_one, &metric_two, &metric_three]);
     helper::wait_for_services(&[&metric_one, &metric_two, &metric_three], 1);
 
-    assert_eq!("test", String::from_utf8_lossy(&node_one.request_bytes("echo", b"test").unwrap()));
-    assert_eq!("test", String::from_utf8_lossy(&node_one.request_bytes("echo", b"test").unwrap()));
+    assert_eq!("test", String::from_utf8_lossy(&node_one.request_bytes("echo", b"test", None).unwrap()));
+    assert_eq!("test", String::from_utf8_lossy(&node_one.request_bytes("echo", b"test", None).unwrap()));
 
     helper::assert_contains_all(&["two", "three"], &helper::recv_all(&rx));
 
     node_three.deregister("echo").unwrap();
 
-    assert_eq!("test", String::from_utf8_lossy(&node_one.request_bytes("echo", b"test").unwrap()));
-    assert_eq!("test", String::from_utf8_lossy(&node_one.request_bytes("echo", b"test").unwrap()));
+    assert_eq!("test", String::from_utf8_lossy(&node_one.request_bytes("echo", b"test", None).unwrap()));
+    assert_eq!("test", String::from_utf8_lossy(&node_one.request_bytes("echo", b"test", None).unwrap()));
 
     helper::assert_contains_all(&["two", "two"], &helper::recv_all(&rx));
 }
@@ -173,7 +192,7 @@ fn parallel_requests_while_a_node_joins_and_leaves() {
     let running_clone = running.clone();
     let jh_one = thread::spawn(move || {
         while *running_clone.read().unwrap() {
-            match node_one_clone.request_bytes("echo", b"test") {
+            match node_one_clone.request_bytes("echo", b"test", None) {
                 Ok(response) => assert_eq!("test", String::from_utf8_lossy(&response)),
                 Err(error) => println!("got error: {:?}", error),
             }
@@ -185,7 +204,7 @@ fn parallel_requests_while_a_node_joins_and_leaves() {
     let running_clone = running.clone();
     let jh_two = thread::spawn(move || {
         while *running_clone.read().unwrap() {
-            match node_one_clone.request_bytes("echo", b"test") {
+            match node_one_clone.request_bytes("echo", b"test", None) {
                 Ok(response) => assert_eq!("test", String::from_utf8_lossy(&response)),
                 Err(error) => println!("got error: {:?}", error),
             }
@@ -214,3 +233,239 @@ fn parallel_requests_while_a_node_joins_and_leaves() {
     jh_one.join().unwrap();
     jh_two.join().unwrap();
 }
+
+#[test]
+fn follow_redirect_to_remote_service() {
+    helper::set_up();
+
+    let (node_one, metric_one) = helper::build_node("localhost:3101", &[], None);
+    node_one.register("echo", Box::new(|_| {
+        Err(service::Error::Redirect("echo-two".to_string(), None))
+    })).unwrap();
+    node_one.register("loop", Box::new(|_| {
+        Err(service::Error::Redirect("loop".to_string(), None))
+    })).unwrap();
+
+    let (node_two, metric_two) = helper::build_node("localhost:3102", &["localhost:3101"], None);
+    node_two.register("echo-two", Box::new(|request| Ok(request)))
+            .unwrap();
+
+    helper::wait_for_joined(&[&metric_one, &metric_two]);
+    helper::wait_for_services(&[&metric_one, &metric_two], 3);
+
+    assert_eq!("test message", String::from_utf8_lossy(&node_two.request_bytes("echo", b"test message", None).unwrap()));
+    assert_eq!(Err(node::Error::Unavailable("loop (redirected to loop)".to_string())),
+               node_one.request_bytes("loop", b"", None));
+}
+
+#[test]
+fn routed_echo_through_intermediate_node() {
+    helper::set_up();
+
+    let (_node_two, metric_two) = helper::build_routing_node("localhost:3121", None, &[], 2);
+
+    // the public address of node three is not reachable, so node one can only use node two
+    let (node_three, metric_three) = helper::build_routing_node("localhost:3122",
+                                                                Some("localhost:3129"),
+                                                                &["localhost:3121"],
+                                                                2);
+    node_three.register("echo", Box::new(|request| Ok(request)))
+              .unwrap();
+
+    helper::wait_for_services(&[&metric_two, &metric_three], 1);
+
+    let (node_one, metric_one) = helper::build_routing_node("localhost:3120",
+                                                            None,
+                                                            &["localhost:3121"],
+                                                            2);
+
+    helper::wait_for_services(&[&metric_one], 1);
+
+    assert_eq!("test message", String::from_utf8_lossy(&node_one.request_bytes("echo", b"test message", None).unwrap()));
+}
+
+#[test]
+fn streamed_response_from_remote() {
+    helper::set_up();
+
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+    let release_rx = Mutex::new(release_rx);
+    let (node_one, metric_one) = helper::build_node("localhost:3161", &[], None);
+    node_one.register_streaming("ticker", Box::new(move |_: Box<node::request::Reader>,
+                                                         writer: &mut node::stream::Writer|
+                                                         -> Result<(), service::Error> {
+        writer.write_all(b"first").unwrap();
+        writer.flush().unwrap();
+        // the second part is only written after the first one has been received.
+        release_rx.lock().unwrap().recv().unwrap();
+        writer.write_all(b" second").unwrap();
+        Ok(())
+    })).unwrap();
+
+    let (node_two, metric_two) = helper::build_node("localhost:3162", &["localhost:3161"], None);
+
+    helper::wait_for_joined(&[&metric_one, &metric_two]);
+    helper::wait_for_services(&[&metric_one, &metric_two], 1);
+
+    let (content_tx, content_rx) = mpsc::channel();
+    node_two.request("ticker", None, Box::new(io::empty()), Box::new(move |mut reader| {
+        let mut content = vec![0u8; 5];
+        reader.read_exact(&mut content).unwrap();
+        release_tx.send(()).unwrap();
+        reader.read_to_end(&mut content).unwrap();
+        content_tx.send(content).unwrap();
+    })).unwrap();
+
+    assert_eq!("first second", String::from_utf8_lossy(&content_rx.recv().unwrap()));
+}
+
+#[test]
+fn broadcast_to_all_providers() {
+    helper::set_up();
+
+    let (node_one, metric_one) = helper::build_node("localhost:3171", &[], None);
+    node_one.register("name", Box::new(|_| Ok(Box::new(io::Cursor::new(b"one".to_vec())))))
+            .unwrap();
+
+    let (node_two, metric_two) = helper::build_node("localhost:3172", &["localhost:3171"], None);
+    node_two.register("name", Box::new(|_| Err(service::Error::Unavailable)))
+            .unwrap();
+
+    let (node_three, metric_three) = helper::build_node("localhost:3173", &["localhost:3171"], None);
+
+    helper::wait_for_joined(&[&metric_one, &metric_two, &metric_three]);
+    helper::wait_for_services(&[&metric_one, &metric_two, &metric_three], 1);
+
+    let mut expected = vec![(node_one.id, Ok(b"one".to_vec())),
+                            (node_two.id, Err(node::Error::Unavailable("name".to_string())))];
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(expected,
+               node_three.broadcast("name", Box::new(io::empty())).unwrap());
+    assert_eq!(Err(node::Error::NotFound("missing".to_string())),
+               node_three.broadcast("missing", Box::new(io::empty())));
+}
+
+#[test]
+fn notification_to_remote() {
+    helper::set_up();
+
+    let (tx, rx) = mpsc::channel();
+    let tx = Mutex::new(tx);
+    let (node_one, metric_one) = helper::build_node("localhost:3181", &[], None);
+    node_one.register("events", Box::new(move |mut request| {
+        let mut event = String::new();
+        request.read_to_string(&mut event).unwrap();
+        tx.lock().unwrap().send(event).unwrap();
+        Ok(request)
+    })).unwrap();
+
+    let (node_two, metric_two) = helper::build_node("localhost:3182", &["localhost:3181"], None);
+
+    helper::wait_for_joined(&[&metric_one, &metric_two]);
+    helper::wait_for_services(&[&metric_one, &metric_two], 1);
+
+    node_two.notify("events", Box::new(io::Cursor::new(b"started".to_vec()))).unwrap();
+    assert_eq!("started", rx.recv().unwrap());
+
+    assert_eq!(Err(node::Error::NotFound("missing".to_string())),
+               node_two.notify("missing", Box::new(io::empty())));
+}
+
+#[test]
+fn tagged_request_to_matching_provider() {
+    helper::set_up();
+
+    let (node_one, metric_one) = helper::build_node("localhost:3191", &[], None);
+    node_one.register_with_metadata("echo",
+                                    Box::new(|_| Ok(Box::new(io::Cursor::new(b"eu".to_vec())))),
+                                    node::Placement::Any,
+                                    service::DEFAULT_WEIGHT,
+                                    metadata("region", "eu"))
+            .unwrap();
+
+    let (node_two, metric_two) = helper::build_node("localhost:3192", &["localhost:3191"], None);
+    node_two.register_with_metadata("echo",
+                                    Box::new(|_| Ok(Box::new(io::Cursor::new(b"us".to_vec())))),
+                                    node::Placement::Any,
+                                    service::DEFAULT_WEIGHT,
+                                    metadata("region", "us"))
+            .unwrap();
+
+    let (node_three, metric_three) = helper::build_node("localhost:3193", &["localhost:3191"], None);
+
+    helper::wait_for_joined(&[&metric_one, &metric_two, &metric_three]);
+    helper::wait_for_services(&[&metric_one, &metric_two, &metric_three], 1);
+
+    for _ in 0..4 {
+        let (tx, rx) = mpsc::channel();
+        node_three.request_tagged("echo",
+                                  &metadata("region", "us"),
+                                  Box::new(io::empty()),
+                                  Box::new(move |mut reader| {
+                                      let mut response = String::new();
+                                      reader.read_to_string(&mut response).unwrap();
+                                      tx.send(response).unwrap();
+                                  }))
+                  .unwrap();
+        assert_eq!("us", rx.recv().unwrap());
+    }
+
+    assert_eq!(Err(node::Error::NotFound("echo".to_string())),
+               node_three.request_tagged("echo",
+                                         &metadata("region", "asia"),
+                                         Box::new(io::empty()),
+                                         Box::new(|_| {})));
+}
+
+fn metadata(key: &str, value: &str) -> service::Metadata {
+    let mut metadata = service::Metadata::new();
+    metadata.insert(key.to_string(), value.to_string());
+    metadata
+}
+
+#[test]
+fn publish_to_subscribers() {
+    helper::set_up();
+
+    let (tx, rx) = mpsc::channel();
+    let (node_one, metric_one) = helper::build_node("localhost:3211", &[], None);
+    for name in vec!["first", "second"] {
+        let tx = Mutex::new(tx.clone());
+        node_one.subscribe_topic("events", Box::new(move |message: &[u8]| {
+            tx.lock().unwrap().send((name, message.to_vec())).unwrap();
+        })).unwrap();
+    }
+
+    let (node_two, metric_two) = helper::build_node("localhost:3212", &["localhost:3211"], None);
+    let tx = Mutex::new(tx);
+    node_two.subscribe_topic("events", Box::new(move |message: &[u8]| {
+        tx.lock().unwrap().send(("third", message.to_vec())).unwrap();
+    })).unwrap();
+
+    let (node_three, metric_three) = helper::build_node("localhost:3213", &["localhost:3211"], None);
+
+    helper::wait_for_joined(&[&metric_one, &metric_two, &metric_three]);
+    helper::wait_for_endpoints(&[&metric_three], 2);
+
+    assert_eq!(Ok(2), node_three.publish("events", b"started"));
+    let mut received = rx.iter().take(3).collect::<Vec<_>>();
+    received.sort();
+    assert_eq!(vec![("first", b"started".to_vec()),
+                    ("second", b"started".to_vec()),
+                    ("third", b"started".to_vec())],
+               received);
+
+    assert_eq!(Ok(0), node_three.publish("missing", b"started"));
+}
+
+#[test]
+#[allow(unused_variables)]
+fn topic_service_names_are_reserved() {
+    helper::set_up();
+
+    let (node, metric) = helper::build_node("localhost:3221", &[], None);
+    assert_eq!(Err(node::Error::InvalidInput("service name [topic.events] is reserved for topics"
+                                                 .to_string())),
+               node.register("topic.events", Box::new(|request| Ok(request))));
+    assert!(!node.has_service("topic.events"));
+}
@@ -14,10 +14,15 @@
 //
 
 extern crate delix;
+extern crate time;
 
-mod helper;
+use delix::testing as helper;
 
-use delix::node::request;
+use std::sync::{Arc, Mutex, mpsc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use delix::node::{self, health};
 
 #[test]
 #[allow(unused_variables)]
@@ -94,5 +99,115 @@ fn deregistration_in_joined_network() {
     node_one.deregister("echo").unwrap();
     helper::wait_for_services(&[&metric_one, &metric_two], 0);
 
-    assert_eq!(Err(request::Error::NoService), node_two.request_bytes("echo", b"test"));
+    assert_eq!(Err(node::Error::NotFound("echo".to_string())),
+               node_two.request_bytes("echo", b"test", None));
+}
+
+#[test]
+#[allow(unused_variables)]
+fn registration_restricted_to_same_zone() {
+    helper::set_up();
+
+    let (node_one, metric_one) = helper::build_node_in_zone("localhost:3091",
+                                                            &[],
+                                                            None,
+                                                            Some("a"));
+    node_one.register_with_placement("echo",
+                                     Box::new(|request| Ok(request)),
+                                     node::Placement::SameZone)
+            .unwrap();
+
+    let (node_two, metric_two) = helper::build_node_in_zone("localhost:3092",
+                                                            &["localhost:3091"],
+                                                            None,
+                                                            Some("a"));
+    let (node_three, metric_three) = helper::build_node_in_zone("localhost:3093",
+                                                                &["localhost:3091"],
+                                                                None,
+                                                                Some("b"));
+
+    helper::wait_for_joined(&[&metric_one, &metric_two, &metric_three]);
+    helper::wait_for_services(&[&metric_one, &metric_two], 1);
+
+    assert_eq!(b"test".to_vec(), node_two.request_bytes("echo", b"test", None).unwrap());
+    assert_eq!(Err(node::Error::NotFound("echo".to_string())),
+               node_three.request_bytes("echo", b"test", None));
+}
+
+#[test]
+#[allow(unused_variables)]
+fn only_exposed_services_are_announced() {
+    helper::set_up();
+
+    let (node_one, metric_one) = helper::build_node("localhost:3151", &[], None);
+    node_one.set_exposed_services(&["echo".to_string()]);
+    node_one.register("echo", Box::new(|request| Ok(request)))
+            .unwrap();
+    node_one.register("internal", Box::new(|request| Ok(request)))
+            .unwrap();
+
+    let (node_two, metric_two) = helper::build_node("localhost:3152", &["localhost:3151"], None);
+
+    helper::wait_for_joined(&[&metric_one, &metric_two]);
+    helper::wait_for_services(&[&metric_one], 2);
+    helper::wait_for_services(&[&metric_two], 1);
+
+    assert_eq!(b"test".to_vec(), node_two.request_bytes("echo", b"test", None).unwrap());
+    assert_eq!(Err(node::Error::NotFound("internal".to_string())),
+               node_two.request_bytes("internal", b"test", None));
+    assert_eq!(b"test".to_vec(), node_one.request_bytes("internal", b"test", None).unwrap());
+}
+
+#[test]
+fn shutdown_finishes_pending_requests() {
+    helper::set_up();
+
+    let (started_tx, started_rx) = mpsc::channel();
+    let started_tx = Mutex::new(started_tx);
+    let (node_one, metric_one) = helper::build_node("localhost:3161", &[], None);
+    node_one.register("echo", Box::new(move |request| {
+        started_tx.lock().unwrap().send(()).unwrap();
+        thread::sleep(::std::time::Duration::from_millis(100));
+        Ok(request)
+    })).unwrap();
+
+    let (node_two, metric_two) = helper::build_node("localhost:3162", &["localhost:3161"], None);
+
+    helper::wait_for_joined(&[&metric_one, &metric_two]);
+    helper::wait_for_services(&[&metric_one, &metric_two], 1);
+
+    let request = thread::spawn(move || node_two.request_bytes("echo", b"test", None));
+    started_rx.recv().unwrap();
+    node_one.shutdown();
+
+    assert_eq!(Ok(b"test".to_vec()), request.join().unwrap());
+    helper::wait_for_services(&[&metric_two], 0);
+}
+
+#[test]
+fn unhealthy_service_is_deregistered() {
+    helper::set_up();
+
+    let healthy = Arc::new(AtomicBool::new(true));
+    let healthy_clone = healthy.clone();
+    let (node_one, metric_one) = helper::build_node("localhost:3171", &[], None);
+    node_one.register_with_health_check("echo",
+                                        Box::new(|request| Ok(request)),
+                                        Box::new(move || healthy_clone.load(Ordering::SeqCst)),
+                                        health::Policy::new(time::Duration::milliseconds(10), 2))
+            .unwrap();
+
+    let (node_two, metric_two) = helper::build_node("localhost:3172", &["localhost:3171"], None);
+
+    helper::wait_for_joined(&[&metric_one, &metric_two]);
+    helper::wait_for_services(&[&metric_one, &metric_two], 1);
+
+    healthy.store(false, Ordering::SeqCst);
+    helper::wait_for_services(&[&metric_one, &metric_two], 0);
+    assert_eq!(Err(node::Error::NotFound("echo".to_string())),
+               node_two.request_bytes("echo", b"test", None));
+
+    healthy.store(true, Ordering::SeqCst);
+    helper::wait_for_services(&[&metric_one, &metric_two], 1);
+    assert_eq!(b"test".to_vec(), node_two.request_bytes("echo", b"test", None).unwrap());
 }